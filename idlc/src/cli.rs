@@ -1,6 +1,8 @@
 // Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
 // SPDX-License-Identifier: BSD-3-Clause
 
+use std::path::PathBuf;
+
 use idlc_mir::NamedVersion;
 
 fn long_version() -> &'static str {
@@ -20,20 +22,55 @@ fn long_version() -> &'static str {
 
 #[derive(clap::Parser)]
 #[command(author, version, long_version=long_version(), about = None, long_about)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
+#[command(group(clap::ArgGroup::new("lang").multiple(true)))]
 /// Compile Mink IDL files into a header to be used by one of the supported language
 pub struct Cli {
-    /// Input IDL file
-    pub idl_file: std::path::PathBuf,
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    #[arg(required = true, value_name = "IDL_FILE")]
+    /// Input IDL files, `@FILE` reads whitespace separated inputs from FILE
+    /// and glob patterns are expanded. More than one input requires `--out-dir`.
+    pub idl_files: Vec<std::path::PathBuf>,
 
-    #[arg(short, value_name = "FILE or PATH")]
+    #[arg(short, value_name = "FILE or PATH", conflicts_with = "out_dir")]
     /// Output file (C/C++) or path (Rust/Java)
     pub output: Option<std::path::PathBuf>,
 
-    #[arg(long, conflicts_with_all = ["java", "rust"])]
+    #[arg(long, value_name = "DIR", conflicts_with_all = ["dump", "plugin", "spec", "depfile"])]
+    /// Generate every selected language for each input into DIR, as
+    /// `c/<stem>.h`, `cpp/<stem>.hpp`, `java/`, `rust/` and `doc/`.
+    ///
+    /// Skeletons are named `<stem>_invoke.h` and `<stem>_invoke.hpp`, C++
+    /// mocks `<stem>_mock.hpp`.
+    pub out_dir: Option<std::path::PathBuf>,
+
+    #[arg(short, long, value_name = "N", requires = "out_dir")]
+    /// Number of inputs compiled in parallel, defaults to the number of CPUs.
+    pub jobs: Option<std::num::NonZeroUsize>,
+
+    #[arg(long, conflicts_with_all = ["java", "rust", "doc"])]
     /// Generate skeleton header (instead of stub header).
     pub skel: bool,
 
-    #[arg(long, group = "lang", default_value_t = true)]
+    #[arg(long, requires = "lang", conflicts_with_all = ["skel", "c", "java", "doc", "plugin"])]
+    /// Generate mocks of the interfaces, for C++ and Rust.
+    ///
+    /// C++ gets a header of GoogleMock classes deriving from the skeletons,
+    /// instead of the stubs. Rust gets a `Mock<interface>` implementing the
+    /// trait of each interface along with the bindings.
+    pub mock: bool,
+
+    #[arg(long, requires = "lang", conflicts_with_all = ["skel", "c", "java", "doc", "plugin"])]
+    /// Derive `serde::Serialize` and `serde::Deserialize` on the Rust structs,
+    /// and print the C++ structs as JSON with `to_json` and `operator<<`.
+    ///
+    /// Structs containing objects are skipped. The Rust bindings need the
+    /// `serde` feature of `mink_object`.
+    pub serde: bool,
+
+    #[arg(long, group = "lang")]
     /// Generate c header. This is the default language.
     pub c: bool,
 
@@ -50,6 +87,26 @@ pub struct Cli {
     /// Generate Rust
     pub rust: bool,
 
+    #[arg(long, value_name = "FORMAT", group = "lang")]
+    /// Generate an API reference in the output path: a page for the input
+    /// file and one per interface, with op-codes, parameter placements, error
+    /// values and struct layouts.
+    ///
+    /// Links to declarations of the includes resolve once their pages are
+    /// generated in the same directory.
+    pub doc: Option<DocFormat>,
+
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["skel", "cpp", "java", "rust", "doc"])]
+    /// Generate the output with an external code generator.
+    ///
+    /// The plugin receives the MIR as JSON on stdin and replies with the files
+    /// to write in the output directory on stdout.
+    pub plugin: Option<std::path::PathBuf>,
+
+    #[arg(long, value_name = "OPT", requires = "plugin")]
+    /// Repeatable: parameter forwarded to the plugin.
+    pub plugin_opt: Vec<String>,
+
     #[arg(short = 'I', long = "include", value_name = "DIR")]
     /// Add DIR to include path. Can be passed multiple times.
     pub include_paths: Option<Vec<std::path::PathBuf>>,
@@ -58,6 +115,27 @@ pub struct Cli {
     /// Dump various phases of the compiler and exit.
     pub dump: Option<Dumpable>,
 
+    #[arg(long, requires = "dump", default_value = "debug")]
+    /// Format of the dump. `json` and `yaml` follow a versioned schema and are
+    /// only available for the AST and the MIR.
+    pub format: DumpFormat,
+
+    #[arg(long = "MD", conflicts_with = "dump")]
+    /// Also write a Make/Ninja depfile listing the IDL files the output was
+    /// generated from, also spelled `-MD`.
+    ///
+    /// The depfile is written to `<FILE>.d` for C/C++ and to
+    /// `<PATH>/<input stem>.d` for directory outputs, unless `-MF` is given.
+    pub write_deps: bool,
+
+    #[arg(long = "MF", value_name = "FILE", conflicts_with = "dump")]
+    /// Write the depfile to FILE, also spelled `-MF`. Implies `-MD`.
+    pub depfile: Option<std::path::PathBuf>,
+
+    #[arg(long, conflicts_with_all = ["dump", "write_deps", "depfile"])]
+    /// Print the depfile on stdout instead of generating the output.
+    pub print_deps: bool,
+
     #[arg(long)]
     /// Adding marking on top of the generated file
     pub marking: Option<std::path::PathBuf>,
@@ -67,6 +145,27 @@ pub struct Cli {
     /// This option does NOT affect any other codegen backends.
     pub no_typed_objects: bool,
 
+    #[arg(long, value_name = "PATH", requires = "rust", value_parser = rust_path)]
+    /// Path of the runtime crate the Rust bindings refer to, e.g.
+    /// `::mink_object`. Defaults to a `crate::object` module.
+    pub rust_runtime_path: Option<String>,
+
+    #[arg(long, requires = "rust", default_value_t = false)]
+    /// Generate Rust that only depends on `core` and `alloc`, for `#![no_std]`
+    /// crates declaring `extern crate alloc`.
+    ///
+    /// `std::error::Error` is implemented with the `std` feature of the crate.
+    pub rust_no_std: bool,
+
+    #[arg(long, requires = "rust", default_value_t = false)]
+    /// Also generate `_async` proxy methods and an `AsyncI<interface>` trait
+    /// for async implementations in Rust.
+    ///
+    /// Both go through the executor set with
+    /// `mink_object::asynchronous::set_executor`, the synchronous API is left
+    /// as is.
+    pub rust_async: bool,
+
     #[arg(long, default_value_t = false)]
     /// `idlc` by default is pedantic about integer widths overflowing.
     ///
@@ -93,6 +192,138 @@ pub struct Cli {
     pub spec: Vec<NamedVersion>,
 }
 
+impl Cli {
+    /// Parses the arguments of the process, accepting the `-MD` and `-MF`
+    /// spellings of build systems used to C compilers.
+    pub fn parse_args() -> Self {
+        use clap::Parser;
+
+        Self::parse_from(std::env::args_os().map(|arg| {
+            if arg == "-MD" || arg == "-MF" {
+                format!("-{}", arg.to_string_lossy()).into()
+            } else {
+                arg
+            }
+        }))
+    }
+}
+
+/// Parses a Rust path such as `::mink_object` or `crate::object`.
+fn rust_path(path: &str) -> Result<String, String> {
+    let is_ident = |segment: &str| {
+        segment.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+            && segment
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_')
+            && segment != "_"
+    };
+    if path
+        .strip_prefix("::")
+        .unwrap_or(path)
+        .split("::")
+        .all(is_ident)
+    {
+        Ok(path.to_string())
+    } else {
+        Err(format!("`{path}` is not a Rust path"))
+    }
+}
+
+/// Expands the `@FILE` response files and glob patterns of `inputs`.
+pub fn expand_inputs(inputs: Vec<PathBuf>) -> Result<Vec<PathBuf>, String> {
+    let mut expanded = Vec::new();
+    for input in inputs {
+        let arg = input.to_string_lossy();
+        if let Some(file) = arg.strip_prefix('@') {
+            let content = std::fs::read_to_string(file)
+                .map_err(|e| format!("couldn't read response file `{file}`: {e}"))?;
+            for input in content.split_whitespace() {
+                expand_glob(input, &mut expanded)?;
+            }
+        } else {
+            expand_glob(&arg, &mut expanded)?;
+        }
+    }
+    Ok(expanded)
+}
+
+fn expand_glob(pattern: &str, expanded: &mut Vec<PathBuf>) -> Result<(), String> {
+    if !pattern.contains(['*', '?', '[']) {
+        expanded.push(pattern.into());
+        return Ok(());
+    }
+    let paths = glob::glob(pattern).map_err(|e| format!("invalid pattern `{pattern}`: {e}"))?;
+    let len = expanded.len();
+    for path in paths {
+        expanded.push(path.map_err(|e| e.to_string())?);
+    }
+    if expanded.len() == len {
+        return Err(format!("`{pattern}` didn't match any file"));
+    }
+    // Matches are already sorted alphabetically.
+    Ok(())
+}
+
+#[derive(clap::Subcommand)]
+pub enum Command {
+    /// Report ABI changes between two revisions of an IDL file.
+    ///
+    /// Exits with a non-zero status if any of the changes is breaking.
+    Compat {
+        /// Old revision of the IDL file
+        old: std::path::PathBuf,
+
+        /// New revision of the IDL file
+        new: std::path::PathBuf,
+
+        #[arg(short = 'I', long = "include", value_name = "DIR")]
+        /// Add DIR to include path. Can be passed multiple times.
+        include_paths: Vec<std::path::PathBuf>,
+
+        #[arg(long, default_value_t = false)]
+        /// Allow integer widths to overflow, see `idlc --help`.
+        allow_undefined_behavior: bool,
+    },
+    /// Format IDL files in place.
+    ///
+    /// Comments are kept, struct fields are aligned and methods wider than 100
+    /// columns get one parameter per line.
+    Fmt {
+        /// IDL files to format
+        #[arg(required = true)]
+        files: Vec<std::path::PathBuf>,
+
+        #[arg(long, default_value_t = false)]
+        /// Don't write the files, print a diff of the changes instead and exit
+        /// with a non-zero status if any file isn't formatted.
+        check: bool,
+    },
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, clap::ValueEnum)]
+pub enum DocFormat {
+    Markdown,
+    Html,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, clap::ValueEnum)]
+pub enum DumpFormat {
+    /// Debug representation, subject to change between releases
+    Debug,
+    Json,
+    Yaml,
+}
+
+impl From<DumpFormat> for idlc::dump::Format {
+    fn from(format: DumpFormat) -> Self {
+        match format {
+            DumpFormat::Debug => Self::Debug,
+            DumpFormat::Json => Self::Json,
+            DumpFormat::Yaml => Self::Yaml,
+        }
+    }
+}
+
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, clap::ValueEnum)]
 pub enum Dumpable {
     /// Parse Syntax Tree
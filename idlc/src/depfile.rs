@@ -0,0 +1,77 @@
+// Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Make-style dependency files, as emitted by `cc -MD`. Ninja reads the same
+//! format through `depfile = ...`.
+
+use std::path::{Path, PathBuf};
+
+/// What to do with the dependencies of the generated files.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Mode {
+    /// Write the depfile next to the generated files.
+    Write(PathBuf),
+    /// Print the depfile on stdout instead of writing any file.
+    Print,
+}
+
+/// Default location of the depfile of `output`, `<output>.d` for files and
+/// `<output>/<input stem>.d` for directories.
+#[must_use]
+pub fn default_path(output: &Path, input: &Path, directory: bool) -> PathBuf {
+    let mut path = if directory {
+        output
+            .join(input.file_stem().expect("input is a file"))
+            .into_os_string()
+    } else {
+        output.to_path_buf().into_os_string()
+    };
+    path.push(".d");
+    path.into()
+}
+
+/// Renders a single rule listing `deps` as the prerequisites of `targets`.
+#[must_use]
+pub fn render(targets: &[PathBuf], deps: &[PathBuf]) -> String {
+    let mut rule = targets
+        .iter()
+        .map(|target| escape(target))
+        .collect::<Vec<_>>()
+        .join(" ");
+    rule.push(':');
+    for dep in deps {
+        rule.push_str(" \\\n  ");
+        rule.push_str(&escape(dep));
+    }
+    rule.push('\n');
+    rule
+}
+
+fn escape(path: &Path) -> String {
+    let mut escaped = String::new();
+    for c in path.display().to_string().chars() {
+        match c {
+            ' ' | '#' => escaped.push('\\'),
+            '$' => escaped.push('$'),
+            _ => {}
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escaping() {
+        assert_eq!(
+            render(
+                &["out dir/a.h".into()],
+                &["/src/a.idl".into(), "/src/$b#.idl".into()]
+            ),
+            "out\\ dir/a.h: \\\n  /src/a.idl \\\n  /src/$$b\\#.idl\n"
+        );
+    }
+}
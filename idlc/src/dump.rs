@@ -0,0 +1,513 @@
+// Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Machine readable dumps of the [`Ast`] and the [`Mir`].
+//!
+//! The documents are decoupled from the compiler's data structures so renaming
+//! a field in the compiler doesn't change the output, any change to the layout
+//! of the documents has to bump [`SCHEMA_VERSION`].
+
+use std::path::Path;
+
+use serde::Serialize;
+
+use idlc_ast::Ast;
+use idlc_codegen::counts::Counter;
+use idlc_codegen::serialization::PackedPrimitives;
+use idlc_mir::Mir;
+
+/// Version of the layout of the dumped documents.
+///
+/// 1. Initial layout.
+/// 2. `typedef` nodes.
+/// 3. Array sizes and constant values as written, naming constants.
+/// 4. `max_len` of bounded parameters.
+/// 5. `string` type of parameters.
+pub const SCHEMA_VERSION: u32 = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// Debug representation of the compiler's data structures, unstable.
+    Debug,
+    Json,
+    Yaml,
+}
+
+#[derive(Serialize)]
+struct Document<N> {
+    schema: u32,
+    stage: &'static str,
+    file: String,
+    nodes: Vec<N>,
+}
+
+impl<N: Serialize> Document<N> {
+    fn new(stage: &'static str, file: &Path, nodes: Vec<N>) -> Self {
+        Self {
+            schema: SCHEMA_VERSION,
+            stage,
+            file: file.display().to_string(),
+            nodes,
+        }
+    }
+
+    fn render(&self, format: Format) -> String {
+        match format {
+            Format::Json => serde_json::to_string_pretty(self).unwrap() + "\n",
+            Format::Yaml => serde_yaml::to_string(self).unwrap(),
+            Format::Debug => unreachable!("Debug dumps don't go through the schema"),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(tag = "kind", content = "name", rename_all = "snake_case")]
+enum Type {
+    Buffer,
+    /// Bounds are kept as the maximum length of the parameter.
+    String,
+    Primitive(String),
+    Struct(String),
+    Enum(String),
+    Custom(String),
+    /// Untyped interfaces don't have a name.
+    Interface(Option<String>),
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+enum Direction {
+    In,
+    Out,
+}
+
+/// Number of elements, the AST keeps constants naming array sizes as written.
+#[derive(Serialize)]
+#[serde(untagged)]
+enum Count {
+    Value(u16),
+    Const(String),
+}
+
+impl From<&idlc_ast::ArraySize> for Count {
+    fn from(size: &idlc_ast::ArraySize) -> Self {
+        match size {
+            idlc_ast::ArraySize::Count(count) => Self::Value(count.get()),
+            idlc_ast::ArraySize::Const(ident) => Self::Const(ident.to_string()),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Param {
+    name: String,
+    direction: Direction,
+    r#type: Type,
+    /// Whether the parameter is an array.
+    array: bool,
+    /// Maximum number of elements of bounded arrays.
+    count: Option<Count>,
+    /// Maximum number of elements of input buffers and unbounded arrays.
+    max_len: Option<Count>,
+}
+
+#[derive(Serialize)]
+struct Const {
+    name: String,
+    r#type: String,
+    /// Evaluated value in the MIR, expression as written in the AST.
+    value: String,
+}
+
+#[derive(Serialize)]
+struct EnumVariant {
+    name: String,
+    /// Resolved value in the MIR, value as written in the AST.
+    value: Option<String>,
+}
+
+#[derive(Serialize)]
+struct Enum {
+    name: String,
+    r#type: String,
+    variants: Vec<EnumVariant>,
+}
+
+#[derive(Serialize)]
+struct StructField {
+    name: String,
+    r#type: Type,
+    count: Count,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    offset: Option<usize>,
+}
+
+#[derive(Serialize)]
+struct Struct {
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    size: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    align: Option<usize>,
+    fields: Vec<StructField>,
+}
+
+#[derive(Serialize)]
+struct Typedef {
+    name: String,
+    r#type: Type,
+    count: Count,
+}
+
+#[derive(Serialize)]
+struct Counts {
+    input_buffers: u8,
+    input_objects: u8,
+    output_buffers: u8,
+    output_objects: u8,
+}
+
+/// Primitives and small structs sent as a single buffer, only done when a
+/// method has more than one of them in the same direction.
+#[derive(Serialize)]
+struct Bundle {
+    size: usize,
+    /// Bundled parameters in the order they're packed.
+    params: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct Bundles {
+    input: Option<Bundle>,
+    output: Option<Bundle>,
+}
+
+#[derive(Serialize)]
+struct Method {
+    name: String,
+    doc: Option<String>,
+    version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    op_code: Option<u32>,
+    params: Vec<Param>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    counts: Option<Counts>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bundles: Option<Bundles>,
+}
+
+#[derive(Serialize)]
+struct Error {
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    value: Option<i32>,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum InterfaceNode {
+    Const(Const),
+    Error(Error),
+    Enum(Enum),
+    Method(Method),
+}
+
+#[derive(Serialize)]
+struct Interface {
+    name: String,
+    base: Option<String>,
+    nodes: Vec<InterfaceNode>,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum Node {
+    Include { path: String },
+    Const(Const),
+    Struct(Struct),
+    Enum(Enum),
+    Typedef(Typedef),
+    Interface(Interface),
+}
+
+pub fn ast(ast: &Ast, format: Format) -> String {
+    let nodes = ast.nodes.iter().map(|node| ast_node(node)).collect();
+    Document::new("ast", &ast.tag, nodes).render(format)
+}
+
+pub fn mir(mir: &Mir, format: Format) -> String {
+    let nodes = mir.nodes.iter().map(mir_node).collect();
+    Document::new("mir", &mir.tag, nodes).render(format)
+}
+
+fn ast_node(node: &idlc_ast::Node) -> Node {
+    match node {
+        idlc_ast::Node::Include(path) => Node::Include {
+            path: path.display().to_string(),
+        },
+        idlc_ast::Node::Const(c) => Node::Const(ast_const(c)),
+        idlc_ast::Node::Struct(s) => Node::Struct(Struct {
+            name: s.ident.to_string(),
+            size: None,
+            align: None,
+            fields: s
+                .fields
+                .iter()
+                .map(|field| StructField {
+                    name: field.ident.to_string(),
+                    r#type: ast_type(&field.val.0),
+                    count: Count::from(&field.val.1),
+                    offset: None,
+                })
+                .collect(),
+        }),
+        idlc_ast::Node::Enum(e) => Node::Enum(ast_enum(e)),
+        idlc_ast::Node::Typedef(t) => Node::Typedef(Typedef {
+            name: t.ident.to_string(),
+            r#type: ast_type(&t.val.0),
+            count: Count::from(&t.val.1),
+        }),
+        idlc_ast::Node::Interface(i) => Node::Interface(Interface {
+            name: i.ident.to_string(),
+            base: i.base.as_ref().map(ToString::to_string),
+            nodes: i
+                .nodes
+                .iter()
+                .map(|node| match node {
+                    idlc_ast::InterfaceNode::Const(c) => InterfaceNode::Const(ast_const(c)),
+                    idlc_ast::InterfaceNode::Error(e) => InterfaceNode::Error(Error {
+                        name: e.ident.to_string(),
+                        value: e.value,
+                    }),
+                    idlc_ast::InterfaceNode::Enum(e) => InterfaceNode::Enum(ast_enum(e)),
+                    idlc_ast::InterfaceNode::Function(f) => InterfaceNode::Method(Method {
+                        name: f.ident.to_string(),
+                        doc: f.doc.as_ref().map(|d| d.0.clone()),
+                        version: f.attributes.iter().find_map(|attr| match attr {
+                            idlc_ast::FunctionAttribute::Version(v) => Some(v.to_string()),
+                            _ => None,
+                        }),
+                        op_code: f.attributes.iter().find_map(|attr| match attr {
+                            idlc_ast::FunctionAttribute::OpCode(op_code) => Some(*op_code),
+                            _ => None,
+                        }),
+                        params: f.params.iter().map(ast_param).collect(),
+                        counts: None,
+                        bundles: None,
+                    }),
+                })
+                .collect(),
+        }),
+    }
+}
+
+fn ast_const(c: &idlc_ast::Const) -> Const {
+    Const {
+        name: c.ident.to_string(),
+        r#type: c.r#type.to_string(),
+        value: c.value.to_string(),
+    }
+}
+
+fn ast_enum(e: &idlc_ast::Enum) -> Enum {
+    Enum {
+        name: e.ident.to_string(),
+        r#type: e.r#type.to_string(),
+        variants: e
+            .variants
+            .iter()
+            .map(|v| EnumVariant {
+                name: v.ident.to_string(),
+                value: v.value.clone(),
+            })
+            .collect(),
+    }
+}
+
+fn ast_param(param: &idlc_ast::Param) -> Param {
+    let (direction, (ty, array, count)) = match param {
+        idlc_ast::Param::In { r#type, .. } => (
+            Direction::In,
+            match r#type {
+                idlc_ast::ParamTypeIn::Array(ty, cnt) => (ty, true, cnt.as_ref()),
+                idlc_ast::ParamTypeIn::Value(ty) => (ty, false, None),
+            },
+        ),
+        idlc_ast::Param::Out { r#type, .. } => (
+            Direction::Out,
+            match r#type {
+                idlc_ast::ParamTypeOut::Array(ty, cnt) => (ty, true, cnt.as_ref()),
+                idlc_ast::ParamTypeOut::Reference(ty) => (ty, false, None),
+            },
+        ),
+    };
+    Param {
+        name: param.ident().to_string(),
+        direction,
+        r#type: ast_type(ty),
+        array,
+        count: count.map(Count::from),
+        max_len: match ty {
+            idlc_ast::Type::String(bound) => bound.as_ref(),
+            _ => param.max_len(),
+        }
+        .map(Count::from),
+    }
+}
+
+fn ast_type(ty: &idlc_ast::Type) -> Type {
+    match ty {
+        idlc_ast::Type::UntypedBuffer => Type::Buffer,
+        idlc_ast::Type::String(_) => Type::String,
+        idlc_ast::Type::Primitive(p) => Type::Primitive(p.to_string()),
+        idlc_ast::Type::Interface => Type::Interface(None),
+        idlc_ast::Type::Custom(c) => Type::Custom(c.to_string()),
+    }
+}
+
+fn mir_node(node: &idlc_mir::Node) -> Node {
+    match node {
+        idlc_mir::Node::Include(path) => Node::Include {
+            path: path.display().to_string(),
+        },
+        idlc_mir::Node::Const(c) => Node::Const(mir_const(c)),
+        idlc_mir::Node::Struct(s) => Node::Struct(mir_struct(s.as_ref())),
+        idlc_mir::Node::Enum(e) => Node::Enum(mir_enum(e)),
+        idlc_mir::Node::Typedef(t) => Node::Typedef(Typedef {
+            name: t.ident.to_string(),
+            r#type: mir_type(&t.r#type),
+            count: Count::Value(t.count.get()),
+        }),
+        idlc_mir::Node::Interface(i) => Node::Interface(Interface {
+            name: i.ident.to_string(),
+            base: i.base.as_ref().map(|b| b.ident.to_string()),
+            nodes: i
+                .nodes
+                .iter()
+                .map(|node| match node {
+                    idlc_mir::InterfaceNode::Const(c) => InterfaceNode::Const(mir_const(c)),
+                    idlc_mir::InterfaceNode::Error(e) => InterfaceNode::Error(Error {
+                        name: e.ident.to_string(),
+                        value: Some(e.value),
+                    }),
+                    idlc_mir::InterfaceNode::Enum(e) => InterfaceNode::Enum(mir_enum(e)),
+                    idlc_mir::InterfaceNode::Function(f) => InterfaceNode::Method(mir_method(f)),
+                })
+                .collect(),
+        }),
+    }
+}
+
+fn mir_const(c: &idlc_mir::Const) -> Const {
+    Const {
+        name: c.ident.to_string(),
+        r#type: c.r#type.to_string(),
+        value: c.value.clone(),
+    }
+}
+
+fn mir_enum(e: &idlc_mir::Enum) -> Enum {
+    Enum {
+        name: e.ident.to_string(),
+        r#type: e.r#type.to_string(),
+        variants: e
+            .variants
+            .iter()
+            .map(|v| EnumVariant {
+                name: v.ident.to_string(),
+                value: Some(v.value.to_string()),
+            })
+            .collect(),
+    }
+}
+
+fn mir_struct(s: &idlc_mir::StructInner) -> Struct {
+    let mut offset = 0;
+    Struct {
+        name: s.ident.to_string(),
+        size: Some(s.size()),
+        align: Some(s.align()),
+        fields: s
+            .fields
+            .iter()
+            .map(|field| {
+                let dumped = StructField {
+                    name: field.ident.to_string(),
+                    r#type: mir_type(&field.val.0),
+                    count: Count::Value(field.val.1.get()),
+                    offset: Some(offset),
+                };
+                offset += field.size();
+                dumped
+            })
+            .collect(),
+    }
+}
+
+fn mir_method(f: &idlc_mir::Function) -> Method {
+    let counter = Counter::new(f);
+    let packed = PackedPrimitives::new(f);
+    Method {
+        name: f.ident.to_string(),
+        doc: f.doc.clone(),
+        version: f.get_version().map(ToString::to_string),
+        op_code: Some(f.id),
+        params: f.params.iter().map(mir_param).collect(),
+        counts: Some(Counts {
+            input_buffers: counter.input_buffers,
+            input_objects: counter.input_objects,
+            output_buffers: counter.output_buffers,
+            output_objects: counter.output_objects,
+        }),
+        bundles: Some(Bundles {
+            input: (packed.n_inputs() > 1).then(|| Bundle {
+                size: packed.packed_input_size(),
+                params: packed.input_idents().map(ToString::to_string).collect(),
+            }),
+            output: (packed.n_outputs() > 1).then(|| Bundle {
+                size: packed.packed_output_size(),
+                params: packed.output_idents().map(ToString::to_string).collect(),
+            }),
+        }),
+    }
+}
+
+fn mir_param(param: &idlc_mir::Param) -> Param {
+    let count = match param {
+        idlc_mir::Param::In {
+            r#type: idlc_mir::ParamTypeIn::Array(_, cnt),
+            ..
+        }
+        | idlc_mir::Param::Out {
+            r#type: idlc_mir::ParamTypeOut::Array(_, cnt),
+            ..
+        } => *cnt,
+        _ => None,
+    };
+    Param {
+        name: param.ident().to_string(),
+        direction: if param.is_input() {
+            Direction::In
+        } else {
+            Direction::Out
+        },
+        r#type: mir_type(param.r#type()),
+        array: param.is_array(),
+        count: count.map(|count| Count::Value(count.get())),
+        max_len: param.max_len().map(|max_len| Count::Value(max_len.get())),
+    }
+}
+
+fn mir_type(ty: &idlc_mir::Type) -> Type {
+    match ty {
+        idlc_mir::Type::UntypedBuffer => Type::Buffer,
+        idlc_mir::Type::String(_) => Type::String,
+        idlc_mir::Type::Primitive(p) => Type::Primitive(p.to_string()),
+        idlc_mir::Type::Struct(s) => Type::Struct(s.as_ref().ident.to_string()),
+        idlc_mir::Type::Enum(e) => Type::Enum(match &e.scope {
+            Some(scope) => format!("{scope}::{}", e.ident),
+            None => e.ident.to_string(),
+        }),
+        idlc_mir::Type::Interface(i) => Type::Interface(i.clone()),
+    }
+}
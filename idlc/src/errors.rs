@@ -1,12 +1,26 @@
 // Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
 // SPDX-License-Identifier: BSD-3-Clause
 
+use idlc_errors::{codes, Diagnostic};
+
+/// Emits `e` as a diagnostic and stops the compilation if the pass failed.
 #[inline]
-pub fn check<T, E: std::fmt::Display>(r: Result<T, E>) -> T {
-    match r {
-        Ok(t) => t,
-        Err(e) => {
-            idlc_errors::unrecoverable!("{e}");
-        }
+pub fn check<T, E: std::fmt::Display>(r: Result<T, E>) -> Result<T, Vec<Diagnostic>> {
+    r.map_err(|e| failed(Diagnostic::error(codes::PASS_FAILURE, e.to_string())))
+}
+
+/// Stops the compilation with all the diagnostics collected so far if any of
+/// them is an error.
+pub fn stop_on_errors() -> Result<(), Vec<Diagnostic>> {
+    if idlc_errors::has_errors() {
+        Err(idlc_errors::take_diagnostics())
+    } else {
+        Ok(())
     }
 }
+
+/// Emits `diagnostic` and returns all the diagnostics collected so far.
+pub fn failed(diagnostic: Diagnostic) -> Vec<Diagnostic> {
+    idlc_errors::emit(diagnostic);
+    idlc_errors::take_diagnostics()
+}
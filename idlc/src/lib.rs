@@ -10,18 +10,24 @@
 //!    Cross interface resolution and hierarchy is also resolved here.
 //! 4. MIR is consumed by [`idlc_codegen`] and it's derivatives to create the output file.
 
-use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
+pub mod depfile;
+pub mod dump;
 mod errors;
 mod timer;
-use errors::check;
+use errors::{check, failed, stop_on_errors};
 
 use idlc_ast::Ast;
-use idlc_ast_passes::{cycles, idl_store::IDLStore, struct_verifier, CompilerPass};
-use idlc_codegen::{Generator, SplitInvokeGenerator};
-use idlc_mir::{Mir, NamedVersion};
+pub use idlc_ast_passes::idl_store::AstCache;
+use idlc_ast_passes::idl_store::{IDLStore, Sources};
+use idlc_ast_passes::{cycles, struct_verifier, CompilerPass};
+use idlc_codegen::marking::{Marking, MarkingStyle};
+use idlc_codegen::{Descriptor, Generator, SplitInvokeGenerator};
+use idlc_errors::{codes, Diagnostic};
+use idlc_mir::{compat, Mir, NamedVersion};
 use idlc_mir_passes::{interface_verifier, MirCompilerPass};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -30,6 +36,66 @@ pub enum Language {
     CPP,
     Java,
     Rust,
+    /// API reference as Markdown pages.
+    Markdown,
+    /// API reference as HTML pages.
+    Html,
+}
+
+/// Runs `compile` on each of `inputs` from up to `jobs` threads.
+///
+/// ASTs aren't thread-safe, so every thread shares its own [`AstCache`] across
+/// the inputs it picks up. A failing input aborts the whole process.
+pub fn compile_all(inputs: &[PathBuf], jobs: usize, compile: impl Fn(&Path, AstCache) + Sync) {
+    let next = AtomicUsize::new(0);
+    std::thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, inputs.len().max(1)) {
+            scope.spawn(|| {
+                let cache = AstCache::default();
+                while let Some(input) = inputs.get(next.fetch_add(1, Ordering::Relaxed)) {
+                    compile(input, cache.clone());
+                }
+            });
+        }
+    });
+}
+
+/// A file produced by a code generator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GeneratedFile {
+    /// Path of the file, relative to the output directory.
+    pub path: PathBuf,
+    pub contents: String,
+}
+
+/// Options shared by the code generators.
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    /// Legal marking added on top of the generated files.
+    pub legal_marking: String,
+    /// Generate the skeleton (invoke) side of C and C++ instead of the stubs.
+    pub skeleton: bool,
+    /// Emit `Object` instead of the typed objects in C.
+    pub no_typed_objects: bool,
+    /// Restricts interfaces to the methods up to these versions.
+    pub specs: Vec<NamedVersion>,
+    /// Path of the runtime crate the Rust bindings refer to, a `crate::object`
+    /// module by default.
+    pub rust_runtime_path: Option<String>,
+    /// Only depend on `core` and `alloc` in the Rust bindings.
+    pub rust_no_std: bool,
+    /// Add the async flavor of the proxies and of the implementation traits
+    /// to the Rust bindings.
+    pub rust_async: bool,
+    /// Generate mocks of the interfaces: GoogleMock classes instead of the
+    /// stubs in C++, along with the bindings in Rust.
+    pub mock: bool,
+    /// Derive serde on the Rust structs and print the C++ structs as JSON,
+    /// for structs without objects.
+    pub serde: bool,
+    /// Order bundled parameters by size rather than by alignment, like the
+    /// headers generated by older compilers.
+    pub bundle_params_by_size: bool,
 }
 
 pub struct Compiler {
@@ -38,10 +104,16 @@ pub struct Compiler {
     includes: Vec<PathBuf>,
     lang: Language,
     allow_undefined_behavior: bool,
-    raw_idl: String,
+    cache: AstCache,
+    sources: Sources,
 }
 
 impl Compiler {
+    /// Creates a compiler reading its input and includes from the disk.
+    ///
+    /// Nothing is read until the compiler runs, and logging is left to the
+    /// host, see [`idlc_errors::init`].
+    #[must_use]
     pub fn new(
         file: PathBuf,
         out: PathBuf,
@@ -49,195 +121,469 @@ impl Compiler {
         lang: Language,
         allow_undefined_behavior: bool,
     ) -> Self {
-        idlc_errors::init();
-        match lang {
-            Language::C | Language::CPP => {
-                if out.is_dir() {
-                    idlc_errors::unrecoverable!("Codegen language expects output file.")
-                }
-            }
-            Language::Java | Language::Rust => {
-                if out.is_file() {
-                    idlc_errors::unrecoverable!("Codegen language expects output directory.")
-                }
-            }
-        }
-        let content = std::fs::read_to_string(&file).unwrap();
         Self {
             input: file,
             output: out,
             includes,
             lang,
             allow_undefined_behavior,
-            raw_idl: content,
+            cache: AstCache::default(),
+            sources: Sources::Disk,
+        }
+    }
+
+    /// Creates a compiler which never touches the disk, `input` and its
+    /// includes are looked up in `files`.
+    ///
+    /// Like the CLI, includes are also searched in the directory of `input`.
+    /// Use [`Self::compile`] to get the generated files.
+    #[must_use]
+    pub fn in_memory(
+        input: PathBuf,
+        files: impl IntoIterator<Item = (PathBuf, String)>,
+        mut includes: Vec<PathBuf>,
+        allow_undefined_behavior: bool,
+    ) -> Self {
+        if let Some(dir) = input.parent() {
+            includes.push(dir.to_path_buf());
+        }
+        Self {
+            input,
+            output: PathBuf::new(),
+            includes,
+            lang: Language::C,
+            allow_undefined_behavior,
+            cache: AstCache::default(),
+            sources: Sources::memory(files),
         }
     }
 
-    fn parse_to_ast(&self) -> (Rc<Ast>, IDLStore) {
-        let mut idl_store = IDLStore::with_includes(&self.includes, self.allow_undefined_behavior);
+    /// Reads `files` instead of their copy on the disk, e.g. unsaved editor
+    /// buffers. Their paths have to be canonical.
+    #[must_use]
+    pub fn with_overlay(mut self, files: impl IntoIterator<Item = (PathBuf, String)>) -> Self {
+        self.sources = Sources::Overlay(Rc::new(files.into_iter().collect()));
+        self
+    }
+
+    /// Shares the parsed ASTs with the other compilers using `cache`.
+    #[must_use]
+    pub fn with_cache(mut self, cache: AstCache) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    /// Output of `lang` for `input` in a directory of outputs of several
+    /// inputs, laid out as `c/<stem>.h`, `cpp/<stem>.hpp` (`_invoke` suffixed
+    /// for skeletons, `_mock` for mocks), `java/`, `rust/` and `doc/`.
+    ///
+    /// Headers can't be named after an `input` without a file name.
+    pub fn output_in(
+        dir: &Path,
+        input: &Path,
+        lang: Language,
+        options: &Options,
+    ) -> Result<PathBuf, Vec<Diagnostic>> {
+        Ok(match lang {
+            Language::C => dir.join("c").join(header(input, lang, options)?),
+            Language::CPP => dir.join("cpp").join(header(input, lang, options)?),
+            Language::Java => dir.join("java"),
+            Language::Rust => dir.join("rust"),
+            Language::Markdown | Language::Html => dir.join("doc"),
+        })
+    }
+
+    fn parse_to_ast(&self) -> Result<(Rc<Ast>, IDLStore), Vec<Diagnostic>> {
+        let mut idl_store = IDLStore::with_cache(
+            &self.includes,
+            self.allow_undefined_behavior,
+            self.cache.clone(),
+        )
+        .with_sources(self.sources.clone());
         let ast = idl_store.get_or_insert(&self.input);
 
-        timer::time!(check(idl_store.run_pass(&ast)), "`IncludeChecker` pass");
+        timer::time!(check(idl_store.run_pass(&ast))?, "`IncludeChecker` pass");
 
         timer::time!(
-            check(idlc_ast_passes::functions::Functions::new().run_pass(&ast)),
+            check(idlc_ast_passes::functions::Functions::new().run_pass(&ast))?,
             "`FunctionDuplicateParam` pass"
         );
 
+        // Constants of the includes are evaluated into this file as well.
+        timer::time!(
+            for ast in idl_store.asts() {
+                check(idlc_ast_passes::consts::Consts::new(&idl_store).run_pass(&ast))?;
+            },
+            "`Consts` pass"
+        );
+
+        // Typedefs of the includes are resolved into this file as well.
+        timer::time!(
+            for ast in idl_store.asts() {
+                check(idlc_ast_passes::typedefs::Typedefs::new(&idl_store).run_pass(&ast))?;
+            },
+            "`Typedefs` pass"
+        );
+
         let struct_ordering = timer::time!(
-            check(cycles::Cycles::new(&idl_store).run_pass(&ast)),
+            check(cycles::Cycles::new(&idl_store).run_pass(&ast))?,
             "`CycleCheck` pass"
         );
 
+        // Enums of the includes are verified as well since they're resolved
+        // into the MIR of this file.
+        timer::time!(
+            for ast in idl_store.asts() {
+                check(idlc_ast_passes::enums::Enums::new().run_pass(&ast))?;
+            },
+            "`Enums` pass"
+        );
+
+        // Everything past this point expects all the symbols to be resolved.
+        stop_on_errors()?;
+
         timer::time!(
-            check(struct_verifier::StructVerifier::run_pass(
-                &idl_store,
-                &struct_ordering,
-            )),
+            struct_verifier::StructVerifier::run_pass(&idl_store, &struct_ordering),
             "`StructVerifier` pass"
         );
+        stop_on_errors()?;
+
+        timer::time!(
+            check(idlc_ast_passes::deprecated::Deprecations::new(&idl_store).run_pass(&ast))?,
+            "`Deprecations` pass"
+        );
 
-        (ast, idl_store)
+        Ok((ast, idl_store))
     }
 
-    fn parse_to_mir(&self) -> Mir {
-        let (ast, mut idl_store) = self.parse_to_ast();
+    /// Runs every pass and returns the verified MIR of the input.
+    pub fn mir(&self) -> Result<Mir, Vec<Diagnostic>> {
+        Ok(self.lower()?.0)
+    }
+
+    /// Returns the verified MIR along with every file it was parsed from.
+    fn lower(&self) -> Result<(Mir, Vec<PathBuf>), Vec<Diagnostic>> {
+        let (ast, mut idl_store) = self.parse_to_ast()?;
         let mir = timer::time!(idlc_mir::parse_to_mir(&ast, &mut idl_store), "Mir");
 
         idlc_errors::trace!("Verifying interfaces");
         interface_verifier::InterfaceVerifier::new(&mir).run_pass();
-        mir
+        stop_on_errors()?;
+        Ok((mir, idl_store.files()))
     }
 
-    pub fn generate(
+    /// Generates `lang` in memory.
+    ///
+    /// C and C++ headers are named after the input like in [`Self::output_in`],
+    /// Java, Rust and documentation files are named by their generators.
+    /// Warnings are left in the [`idlc_errors`] sink.
+    pub fn compile(
         &self,
-        legal_marking: String,
-        skeleton: bool,
-        no_typed_objects: bool,
-        specs: Vec<NamedVersion>,
-    ) {
-        let mut mir = self.parse_to_mir();
+        lang: Language,
+        options: &Options,
+    ) -> Result<Vec<GeneratedFile>, Vec<Diagnostic>> {
+        Ok(self.compile_with_dependencies(lang, options)?.0)
+    }
+
+    /// Same as [`Self::compile`], also returning the canonical path of the
+    /// input and of every file it includes, directly or not.
+    pub fn compile_with_dependencies(
+        &self,
+        lang: Language,
+        options: &Options,
+    ) -> Result<(Vec<GeneratedFile>, Vec<PathBuf>), Vec<Diagnostic>> {
+        let (mut mir, deps) = self.lower()?;
 
         // Prune the MIR to specs passed through the CLI, if any. Because the same
         // mir tree is parsed in multiple places after this, it is easier to modify
         // the tree itself rather than instruct all code generators to ignore the
         // same methods.
-        mir.prune(specs);
+        mir.prune(options.specs.clone());
 
-        match self.lang {
-            Language::C => {
-                let c_gen = idlc_codegen_c::Generator::new(no_typed_objects);
-                let content = if skeleton {
-                    timer::time!(c_gen.generate_invoke(&mir), "C invoke codegen")
-                } else {
-                    timer::time!(
-                        c_gen.generate_implementation(&mir),
-                        "C implementation codegen"
+        let generate = || -> Result<_, Vec<Diagnostic>> {
+            Ok(match lang {
+                Language::C => {
+                    let c_gen = idlc_codegen_c::Generator::new(options.no_typed_objects);
+                    let content = if options.skeleton {
+                        timer::time!(c_gen.generate_invoke(&mir), "C invoke codegen")
+                    } else {
+                        timer::time!(
+                            c_gen.generate_implementation(&mir),
+                            "C implementation codegen"
+                        )
+                    };
+                    (
+                        vec![(header(&self.input, lang, options)?, content)],
+                        MarkingStyle::C,
+                    )
+                }
+                Language::CPP => {
+                    let content = if options.mock {
+                        timer::time!(
+                            idlc_codegen_cpp::Generator::new(options.serde).generate_mock(&mir),
+                            "C++ mock codegen"
+                        )
+                    } else if options.skeleton {
+                        timer::time!(
+                            idlc_codegen_cpp::Generator::new(options.serde).generate_invoke(&mir),
+                            "C++ invoke codegen"
+                        )
+                    } else {
+                        timer::time!(
+                            idlc_codegen_cpp::Generator::new(options.serde)
+                                .generate_implementation(&mir),
+                            "C++ implementation codegen"
+                        )
+                    };
+                    (
+                        vec![(header(&self.input, lang, options)?, content)],
+                        MarkingStyle::C,
                     )
-                };
-                let mut file = std::fs::OpenOptions::new()
-                    .create(true)
-                    .write(true)
-                    .truncate(true)
-                    .open(&self.output)
-                    .unwrap();
-                let marking = idlc_codegen::marking::Marking::new(
-                    &legal_marking,
-                    idlc_codegen::marking::MarkingStyle::C,
+                }
+                Language::Java => {
+                    idlc_errors::warn!(
+                        "Note: JavaGen is untested but guaranteed to generate same output as the previous versions.",
                 );
-                file.write_all(marking.as_bytes()).unwrap();
-                file.write_all(content.as_bytes()).unwrap();
-            }
-            Language::CPP => {
-                let content = if skeleton {
-                    timer::time!(
-                        idlc_codegen_cpp::Generator.generate_invoke(&mir),
-                        "C++ invoke codegen"
+                    (
+                        timer::time!(idlc_codegen_java::Generator::generate(&mir), "Java codegen"),
+                        MarkingStyle::Java,
                     )
-                } else {
-                    timer::time!(
-                        idlc_codegen_cpp::Generator.generate_implementation(&mir),
-                        "C++ implementation codegen"
+                }
+                Language::Rust => {
+                    let rust_gen = idlc_codegen_rust::Generator::new(
+                        options
+                            .rust_runtime_path
+                            .as_deref()
+                            .unwrap_or(idlc_codegen_rust::DEFAULT_RUNTIME_PATH),
+                        options.rust_no_std,
                     )
-                };
-                let mut file = std::fs::OpenOptions::new()
-                    .create(true)
-                    .write(true)
-                    .truncate(true)
-                    .open(&self.output)
-                    .unwrap();
-                let marking = idlc_codegen::marking::Marking::new(
-                    &legal_marking,
-                    idlc_codegen::marking::MarkingStyle::C,
-                );
-                file.write_all(marking.as_bytes()).unwrap();
-                file.write_all(content.as_bytes()).unwrap();
+                    .with_mocks(options.mock)
+                    .with_async(options.rust_async)
+                    .with_serde(options.serde);
+                    (
+                        timer::time!(rust_gen.generate(&mir), "Rust codegen"),
+                        MarkingStyle::Rust,
+                    )
+                }
+                Language::Markdown => (
+                    timer::time!(
+                        idlc_codegen_doc::Markdown::generate(&mir),
+                        "Markdown codegen"
+                    ),
+                    MarkingStyle::Html,
+                ),
+                Language::Html => (
+                    timer::time!(idlc_codegen_doc::Html::generate(&mir), "HTML codegen"),
+                    MarkingStyle::Html,
+                ),
+            })
+        };
+        let (files, style): (Descriptor, _) =
+            idlc_codegen::serialization::with_bundling(options.bundle_params_by_size, generate)?;
+        let marking = Marking::new(&options.legal_marking, style);
+        let files = files
+            .into_iter()
+            .map(|(path, content)| GeneratedFile {
+                path,
+                contents: marking.to_string() + &content,
+            })
+            .collect();
+        Ok((files, deps))
+    }
+
+    /// Generates the output on the disk.
+    pub fn generate(
+        &self,
+        options: &Options,
+        depfile: Option<depfile::Mode>,
+    ) -> Result<(), Vec<Diagnostic>> {
+        // Dumps don't write to the output, so it's only checked when generating.
+        match self.lang {
+            Language::C | Language::CPP => {
+                if self.output.is_dir() {
+                    return Err(failed(Diagnostic::error(
+                        codes::INVALID_OUTPUT,
+                        "Codegen language expects output file.",
+                    )));
+                }
             }
-            Language::Java => {
-                idlc_errors::warn!(
-                        "Note: JavaGen is untested but guaranteed to generate same output as the previous versions.",
-                );
-                let marking = idlc_codegen::marking::Marking::new(
-                    &legal_marking,
-                    idlc_codegen::marking::MarkingStyle::Java,
-                );
-                for (name, content) in
-                    timer::time!(idlc_codegen_java::Generator::generate(&mir), "Java codegen")
-                {
-                    let mut file = std::fs::OpenOptions::new()
-                        .create(true)
-                        .write(true)
-                        .truncate(true)
-                        .open(self.output.join(name))
-                        .unwrap();
-                    file.write_all(marking.as_bytes()).unwrap();
-                    file.write_all(content.as_bytes()).unwrap();
+            Language::Java | Language::Rust | Language::Markdown | Language::Html => {
+                if self.output.is_file() {
+                    return Err(failed(Diagnostic::error(
+                        codes::INVALID_OUTPUT,
+                        "Codegen language expects output directory.",
+                    )));
                 }
             }
-            Language::Rust => {
-                let marking = idlc_codegen::marking::Marking::new(
-                    &legal_marking,
-                    idlc_codegen::marking::MarkingStyle::Rust,
-                );
-                for (name, content) in
-                    timer::time!(idlc_codegen_rust::Generator::generate(&mir), "Rust codegen")
-                {
-                    let mut file = std::fs::OpenOptions::new()
-                        .create(true)
-                        .write(true)
-                        .truncate(true)
-                        .open(self.output.join(name))
-                        .unwrap();
-                    file.write_all(marking.as_bytes()).unwrap();
-                    file.write_all(content.as_bytes()).unwrap();
+        }
+        let (files, deps) = self.compile_with_dependencies(self.lang, options)?;
+        let files = files
+            .into_iter()
+            .map(|file| match self.lang {
+                Language::C | Language::CPP => (self.output.clone(), file.contents),
+                Language::Java | Language::Rust | Language::Markdown | Language::Html => {
+                    (self.output.join(file.path), file.contents)
                 }
+            })
+            .collect();
+        Self::emit(files, &deps, depfile)
+    }
+
+    /// Generates the output with an out-of-process code generator, see
+    /// [`idlc_plugin`] for the protocol.
+    ///
+    /// Only the `specs` and `bundle_params_by_size` of `options` apply.
+    pub fn generate_with_plugin(
+        &self,
+        plugin: &Path,
+        parameters: Vec<String>,
+        options: &Options,
+        depfile: Option<depfile::Mode>,
+    ) -> Result<(), Vec<Diagnostic>> {
+        if self.output.is_file() {
+            return Err(failed(Diagnostic::error(
+                codes::INVALID_OUTPUT,
+                "Plugins expect output directory.",
+            )));
+        }
+        let (mut mir, deps) = self.lower()?;
+        mir.prune(options.specs.clone());
+
+        let request = idlc_plugin::Request::new(mir, parameters)
+            .with_bundle_params_by_size(options.bundle_params_by_size);
+        let files =
+            timer::time!(idlc_plugin::invoke(plugin, &request), "Plugin codegen").map_err(|e| {
+                failed(Diagnostic::error(
+                    codes::PLUGIN_FAILURE,
+                    format!("`{}`: {e}", plugin.display()),
+                ))
+            })?;
+        let files = files
+            .into_iter()
+            .map(|(name, content)| (self.output.join(name), content))
+            .collect();
+        Self::emit(files, &deps, depfile)
+    }
+
+    /// Writes the generated `files` and their depfile, unless the dependencies
+    /// are only printed.
+    fn emit(
+        files: Descriptor,
+        deps: &[PathBuf],
+        depfile: Option<depfile::Mode>,
+    ) -> Result<(), Vec<Diagnostic>> {
+        let write = |path: &Path, content: &str| {
+            path.parent()
+                .map_or(Ok(()), std::fs::create_dir_all)
+                .and_then(|()| std::fs::write(path, content))
+                .map_err(|e| {
+                    failed(Diagnostic::error(
+                        codes::INVALID_OUTPUT,
+                        format!("Couldn't write `{}`: {e}", path.display()),
+                    ))
+                })
+        };
+
+        let mut targets: Vec<_> = files.iter().map(|(path, _)| path.clone()).collect();
+        // Some generators emit their files in hash order.
+        targets.sort();
+        if depfile == Some(depfile::Mode::Print) {
+            print!("{}", depfile::render(&targets, deps));
+            return Ok(());
+        }
+        for (path, content) in &files {
+            write(path, content)?;
+        }
+        if let Some(depfile::Mode::Write(path)) = depfile {
+            write(&path, &depfile::render(&targets, deps))?;
+        }
+        Ok(())
+    }
+
+    /// Lowers both revisions of an IDL and reports the changes between them.
+    ///
+    /// Each revision is resolved against `includes` and its own directory.
+    pub fn compat(
+        old: PathBuf,
+        new: PathBuf,
+        includes: &[PathBuf],
+        allow_undefined_behavior: bool,
+    ) -> Result<Vec<compat::Change>, Vec<Diagnostic>> {
+        let lower = |file: PathBuf| {
+            let mut includes = includes.to_vec();
+            if let Some(dir) = file.parent() {
+                includes.push(dir.to_path_buf());
             }
+            Self::new(
+                file,
+                PathBuf::new(),
+                includes,
+                Language::C,
+                allow_undefined_behavior,
+            )
+            .mir()
         };
+        let old = lower(old)?;
+        let new = lower(new)?;
+        Ok(compat::compare(&old, &new))
     }
 
-    pub fn dump_pst(&self) {
+    pub fn dump_pst(&self) -> Result<(), Vec<Diagnostic>> {
         use std::time::Instant;
+        let raw_idl = self
+            .sources
+            .read(&self.input)
+            .map_err(|e| failed(e.to_diagnostic(&self.input)))?;
         let now = Instant::now();
-        let pst = idlc_ast::pst::parse_to_pst(self.raw_idl.as_ref());
+        let pst = idlc_ast::pst::parse_to_pst(&raw_idl);
         let duration = now.elapsed();
         match pst {
             Ok(pst) => println!("{pst:#?}"),
             Err(e) => eprintln!("Parsing failed:\n{e}\n"),
         }
         eprintln!("'dump_pst' completed in {duration:?}");
+        Ok(())
     }
 
-    pub fn dump_ast(&self) {
+    pub fn dump_ast(&self, format: dump::Format) -> Result<(), Vec<Diagnostic>> {
         use std::time::Instant;
         let now = Instant::now();
-        let ast = self.parse_to_ast();
+        let ast = self.parse_to_ast()?;
         let duration = now.elapsed();
-        println!("{ast:#?}");
+        match format {
+            dump::Format::Debug => println!("{ast:#?}"),
+            _ => print!("{}", dump::ast(&ast.0, format)),
+        }
         eprintln!("'dump_ast' completed in {duration:?}");
+        Ok(())
     }
 
-    pub fn dump_mir(&self) {
-        let mir = self.parse_to_mir();
-        println!("{mir:#?}");
+    pub fn dump_mir(&self, format: dump::Format) -> Result<(), Vec<Diagnostic>> {
+        let mir = self.mir()?;
+        match format {
+            dump::Format::Debug => println!("{mir:#?}"),
+            _ => print!("{}", dump::mir(&mir, format)),
+        }
+        Ok(())
     }
 }
+
+/// Name of the C or C++ header generated from `input`.
+fn header(input: &Path, lang: Language, options: &Options) -> Result<PathBuf, Vec<Diagnostic>> {
+    let Some(stem) = input.file_stem() else {
+        return Err(failed(Diagnostic::error(
+            codes::INVALID_INPUT,
+            format!("`{}` doesn't name a file.", input.display()),
+        )));
+    };
+    let stem = stem.to_string_lossy();
+    let suffix = if options.mock && lang == Language::CPP {
+        "_mock"
+    } else if options.skeleton {
+        "_invoke"
+    } else {
+        ""
+    };
+    let extension = if lang == Language::CPP { "hpp" } else { "h" };
+    Ok(format!("{stem}{suffix}.{extension}").into())
+}
@@ -3,25 +3,132 @@
 
 mod cli;
 
-use clap::Parser;
+use clap::CommandFactory;
 use idlc::Language;
 
 fn main() {
-    let args = cli::Cli::parse();
+    let mut args = cli::Cli::parse_args();
+    idlc_errors::init();
 
-    // Init vars for serialization
-    idlc_codegen::serialization::init(args.bundle_params_by_size);
+    if let Some(cli::Command::Compat {
+        old,
+        new,
+        include_paths,
+        allow_undefined_behavior,
+    }) = args.command
+    {
+        let canonicalize = |p: std::path::PathBuf| p.canonicalize().expect("Invalid input file.");
+        let changes = idlc::Compiler::compat(
+            canonicalize(old),
+            canonicalize(new),
+            &include_paths,
+            allow_undefined_behavior,
+        )
+        .unwrap_or_else(|diagnostics| abort(diagnostics));
+        let breaking = changes.iter().filter(|c| c.is_breaking()).count();
+        for change in &changes {
+            println!("{change}");
+        }
+        println!("{} change(s), {breaking} breaking", changes.len());
+        std::process::exit(i32::from(breaking > 0));
+    }
+
+    if let Some(cli::Command::Fmt { files, check }) = args.command {
+        let mut unformatted = false;
+        for file in files {
+            let source = std::fs::read_to_string(&file).unwrap_or_else(|e| {
+                cli::Cli::command()
+                    .error(
+                        clap::error::ErrorKind::Io,
+                        format!("Couldn't read `{}`: {e}", file.display()),
+                    )
+                    .exit()
+            });
+            idlc_errors::register_source(&file, &source);
+            let formatted = idlc_ast::fmt::format(&source)
+                .unwrap_or_else(|e| abort(vec![e.to_diagnostic(&file)]));
+            if formatted == source {
+                continue;
+            }
+            if check {
+                unformatted = true;
+                print!(
+                    "{}",
+                    similar::TextDiff::from_lines(&source, &formatted)
+                        .unified_diff()
+                        .header(&file.display().to_string(), &file.display().to_string())
+                );
+            } else {
+                std::fs::write(&file, formatted).unwrap_or_else(|e| {
+                    cli::Cli::command()
+                        .error(
+                            clap::error::ErrorKind::Io,
+                            format!("Couldn't write `{}`: {e}", file.display()),
+                        )
+                        .exit()
+                });
+            }
+        }
+        std::process::exit(i32::from(unformatted));
+    }
 
-    let lang = match (args.c, args.cpp, args.java, args.rust) {
-        (true, false, false, false) => Language::C,
-        (true, true, false, false) => Language::CPP,
-        (true, false, true, false) => Language::Java,
-        (true, false, false, true) => Language::Rust,
-        _ => unreachable!(),
+    let inputs = cli::expand_inputs(std::mem::take(&mut args.idl_files)).unwrap_or_else(|e| {
+        cli::Cli::command()
+            .error(clap::error::ErrorKind::ValueValidation, e)
+            .exit()
+    });
+    let mut languages: Vec<_> = [
+        (args.c, Language::C),
+        (args.cpp, Language::CPP),
+        (args.java, Language::Java),
+        (args.rust, Language::Rust),
+        (
+            args.doc == Some(cli::DocFormat::Markdown),
+            Language::Markdown,
+        ),
+        (args.doc == Some(cli::DocFormat::Html), Language::Html),
+    ]
+    .into_iter()
+    .filter_map(|(selected, lang)| selected.then_some(lang))
+    .collect();
+    if languages.is_empty() {
+        languages.push(Language::C);
+    }
+
+    let marking = match args.marking.take() {
+        Some(m_file) => std::fs::read_to_string(m_file).expect("Failed to read marking file"),
+        _ => "".to_string(),
+    };
+
+    let options = idlc::Options {
+        legal_marking: marking,
+        skeleton: args.skel,
+        no_typed_objects: args.no_typed_objects,
+        specs: std::mem::take(&mut args.spec),
+        rust_runtime_path: args.rust_runtime_path.take(),
+        rust_no_std: args.rust_no_std,
+        rust_async: args.rust_async,
+        mock: args.mock,
+        serde: args.serde,
+        bundle_params_by_size: args.bundle_params_by_size,
     };
 
+    if let Some(out_dir) = args.out_dir.take() {
+        generate_all(&args, &inputs, &out_dir, &languages, &options);
+        return;
+    }
+    if inputs.len() > 1 || languages.len() > 1 {
+        cli::Cli::command()
+            .error(
+                clap::error::ErrorKind::ArgumentConflict,
+                "compiling several inputs or languages at once requires `--out-dir`",
+            )
+            .exit();
+    }
+    let lang = languages[0];
+
     // Change current dir based on the location of the input file.
-    let input_file = args.idl_file.canonicalize().expect("Invalid input file.");
+    let input_file = inputs[0].canonicalize().expect("Invalid input file.");
     let dir_path = input_file
         .parent()
         .expect("Failed to find the location of the input file");
@@ -33,9 +140,19 @@ fn main() {
         .output
         .unwrap_or_else(|| std::env::current_dir().unwrap());
 
-    let marking = match args.marking {
-        Some(m_file) => std::fs::read_to_string(m_file).expect("Failed to read marking file"),
-        _ => "".to_string(),
+    let depfile = if args.print_deps {
+        Some(idlc::depfile::Mode::Print)
+    } else if let Some(depfile) = args.depfile {
+        Some(idlc::depfile::Mode::Write(depfile))
+    } else if args.write_deps {
+        let directory = args.plugin.is_some() || !matches!(lang, Language::C | Language::CPP);
+        Some(idlc::depfile::Mode::Write(idlc::depfile::default_path(
+            &output,
+            &input_file,
+            directory,
+        )))
+    } else {
+        None
     };
 
     let compiler = idlc::Compiler::new(
@@ -46,10 +163,112 @@ fn main() {
         args.allow_undefined_behavior,
     );
 
-    match args.dump {
+    let result = match args.dump {
+        Some(cli::Dumpable::Pst) if args.format != cli::DumpFormat::Debug => {
+            cli::Cli::command()
+                .error(
+                    clap::error::ErrorKind::ArgumentConflict,
+                    "the parse syntax tree can only be dumped with `--format debug`",
+                )
+                .exit();
+        }
         Some(cli::Dumpable::Pst) => compiler.dump_pst(),
-        Some(cli::Dumpable::Ast) => compiler.dump_ast(),
-        Some(cli::Dumpable::Mir) => compiler.dump_mir(),
-        _ => compiler.generate(marking, args.skel, args.no_typed_objects, args.spec),
+        Some(cli::Dumpable::Ast) => compiler.dump_ast(args.format.into()),
+        Some(cli::Dumpable::Mir) => {
+            idlc_codegen::serialization::with_bundling(options.bundle_params_by_size, || {
+                compiler.dump_mir(args.format.into())
+            })
+        }
+        None => match args.plugin {
+            Some(plugin) => {
+                compiler.generate_with_plugin(&plugin, args.plugin_opt, &options, depfile)
+            }
+            None => compiler.generate(&options, depfile),
+        },
+    };
+    result.unwrap_or_else(|diagnostics| abort(diagnostics));
+    // Only warnings are left at this point.
+    idlc_errors::report();
+}
+
+/// Reports `diagnostics` and exits with an error.
+fn abort(diagnostics: Vec<idlc_errors::Diagnostic>) -> ! {
+    for diagnostic in diagnostics {
+        idlc_errors::emit(diagnostic);
+    }
+    idlc_errors::report();
+    std::process::exit(1);
+}
+
+/// Generates every language of `languages` for each of `inputs` into `out_dir`.
+fn generate_all(
+    args: &cli::Cli,
+    inputs: &[std::path::PathBuf],
+    out_dir: &std::path::Path,
+    languages: &[Language],
+    options: &idlc::Options,
+) {
+    let inputs: Vec<_> = inputs
+        .iter()
+        .map(|input| input.canonicalize().expect("Invalid input file."))
+        .collect();
+    let mut stems = std::collections::HashMap::new();
+    for input in &inputs {
+        if let Some(other) = stems.insert(input.file_stem(), input) {
+            cli::Cli::command()
+                .error(
+                    clap::error::ErrorKind::ArgumentConflict,
+                    format!(
+                        "`{}` and `{}` would generate the same outputs",
+                        other.display(),
+                        input.display()
+                    ),
+                )
+                .exit();
+        }
     }
+
+    let jobs = args.jobs.map_or_else(
+        || std::thread::available_parallelism().map_or(1, std::num::NonZeroUsize::get),
+        std::num::NonZeroUsize::get,
+    );
+    idlc::compile_all(&inputs, jobs, |input, cache| {
+        let mut include_paths = args.include_paths.clone().unwrap_or_default();
+        include_paths.push(input.parent().expect("input is a file").to_path_buf());
+
+        for &lang in languages {
+            let output = idlc::Compiler::output_in(out_dir, input, lang, options)
+                .unwrap_or_else(|diagnostics| abort(diagnostics));
+            let directory = !matches!(lang, Language::C | Language::CPP);
+            if !args.print_deps {
+                std::fs::create_dir_all(if directory {
+                    &output
+                } else {
+                    output.parent().expect("output is in a directory")
+                })
+                .expect("Failed to create the output directory");
+            }
+
+            let depfile = if args.print_deps {
+                Some(idlc::depfile::Mode::Print)
+            } else if args.write_deps {
+                Some(idlc::depfile::Mode::Write(idlc::depfile::default_path(
+                    &output, input, directory,
+                )))
+            } else {
+                None
+            };
+            idlc::Compiler::new(
+                input.to_path_buf(),
+                output,
+                include_paths.clone(),
+                lang,
+                args.allow_undefined_behavior,
+            )
+            .with_cache(cache.clone())
+            .generate(options, depfile)
+            .unwrap_or_else(|diagnostics| abort(diagnostics));
+            idlc_errors::report();
+        }
+    });
 }
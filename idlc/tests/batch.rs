@@ -0,0 +1,106 @@
+// Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+fn temp_dir() -> PathBuf {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .expect("clock should be monotonic since epoch")
+        .as_nanos();
+    let dir = std::env::temp_dir().join(format!("idlc-batch-{}-{nanos}", std::process::id()));
+    std::fs::create_dir_all(dir.join("idl")).expect("temp dir should be creatable");
+    std::fs::write(dir.join("idl/common.idl"), "struct S { uint32 a; };")
+        .expect("include should be writable");
+    for name in ["IFoo", "IBar"] {
+        std::fs::write(
+            dir.join(format!("idl/{name}.idl")),
+            format!("include \"common.idl\"\ninterface {name} {{ method a(in S s); }};"),
+        )
+        .expect("input idl should be writable");
+    }
+    dir
+}
+
+fn idlc(dir: &Path, args: &[&str]) -> Output {
+    Command::new(PathBuf::from(env!("CARGO_BIN_EXE_idlc")))
+        .current_dir(dir)
+        .args(args)
+        .output()
+        .expect("idlc should execute")
+}
+
+fn success(output: &Output) {
+    assert!(
+        output.status.success(),
+        "stderr:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+fn read(path: PathBuf) -> String {
+    std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("{}: {e}", path.display()))
+}
+
+#[test]
+fn matches_single_file_outputs() {
+    let dir = temp_dir();
+    success(&idlc(
+        &dir,
+        &[
+            "idl/IFoo.idl",
+            "idl/IBar.idl",
+            "--out-dir",
+            "gen",
+            "--c",
+            "--cpp",
+            "--rust",
+            "-j",
+            "2",
+        ],
+    ));
+    success(&idlc(&dir, &["idl/IFoo.idl", "-o", "IFoo.h"]));
+    success(&idlc(&dir, &["idl/IBar.idl", "--cpp", "-o", "IBar.hpp"]));
+
+    assert_eq!(read(dir.join("gen/c/IFoo.h")), read(dir.join("IFoo.h")));
+    assert_eq!(
+        read(dir.join("gen/cpp/IBar.hpp")),
+        read(dir.join("IBar.hpp"))
+    );
+    assert!(dir.join("gen/c/IBar.h").is_file());
+    assert!(dir.join("gen/cpp/IFoo.hpp").is_file());
+    assert!(dir.join("gen/rust/ifoo.rs").is_file());
+    assert!(dir.join("gen/rust/ibar.rs").is_file());
+}
+
+#[test]
+fn response_file_and_glob() {
+    let dir = temp_dir();
+    std::fs::write(dir.join("inputs.rsp"), "idl/IFoo.idl\n").expect("rsp should be writable");
+    success(&idlc(
+        &dir,
+        &["@inputs.rsp", "--out-dir", "rsp", "--skel", "-MD"],
+    ));
+    assert!(dir.join("rsp/c/IFoo_invoke.h").is_file());
+    assert!(dir.join("rsp/c/IFoo_invoke.h.d").is_file());
+    assert!(!dir.join("rsp/c/IBar_invoke.h").exists());
+
+    let output = idlc(&dir, &["idl/I*.idl", "--out-dir", "glob", "--print-deps"]);
+    success(&output);
+    let deps = String::from_utf8(output.stdout).expect("depfile should be utf-8");
+    assert!(deps.contains("glob/c/IBar.h:"), "{deps}");
+    assert!(deps.contains("glob/c/IFoo.h:"), "{deps}");
+    assert!(!dir.join("glob").exists());
+}
+
+#[test]
+fn requires_out_dir() {
+    let dir = temp_dir();
+    let output = idlc(&dir, &["idl/IFoo.idl", "idl/IBar.idl"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("requires `--out-dir`"));
+
+    let output = idlc(&dir, &["idl/IFoo.idl", "--c", "--rust", "-o", "out"]);
+    assert!(!output.status.success());
+}
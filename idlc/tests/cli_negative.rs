@@ -141,3 +141,139 @@ fn rejects_file_output_for_rust_codegen() {
         stderr_string(&output)
     );
 }
+
+#[test]
+fn reports_every_interface_error_with_location() {
+    let dir = unique_temp_dir("multi-error");
+    let input = dir.join("multi_error.idl");
+    let output_file = dir.join("out.h");
+
+    std::fs::write(
+        &input,
+        r#"interface ITest {
+  const int32 A = 1;
+  const int32 A = 2;
+  method api_version();
+};
+"#,
+    )
+    .expect("input idl should be writable");
+
+    let output = run_idlc(&[
+        input.to_str().expect("utf-8 input path"),
+        "-o",
+        output_file.to_str().expect("utf-8 output path"),
+    ]);
+    let stderr = stderr_string(&output);
+
+    assert_eq!(output.status.code(), Some(1), "stderr:\n{stderr}");
+    assert!(!stderr.contains("panicked"), "stderr:\n{stderr}");
+    assert!(
+        stderr.contains("error[E0004]") && stderr.contains("multi_error.idl:3:15"),
+        "expected collision with location in stderr, got:\n{stderr}"
+    );
+    assert!(
+        stderr.contains("error[E0005]") && stderr.contains("multi_error.idl:4:10"),
+        "expected reserved word with location in stderr, got:\n{stderr}"
+    );
+    assert!(
+        stderr.contains("aborting due to 2 previous errors"),
+        "stderr:\n{stderr}"
+    );
+}
+
+#[test]
+fn reports_pinned_values_out_of_range() {
+    let dir = unique_temp_dir("pinned");
+    let output_file = dir.join("out.h");
+
+    for (source, code, location) in [
+        (
+            "interface ITest {\n  #[opcode = 99999999999]\n  method a();\n};\n",
+            "error[E0020]",
+            "pinned.idl:2:14",
+        ),
+        (
+            "interface ITest {\n  error ERROR_A = 0x80000000;\n};\n",
+            "error[E0030]",
+            "pinned.idl:2:19",
+        ),
+    ] {
+        let input = dir.join("pinned.idl");
+        std::fs::write(&input, source).expect("input idl should be writable");
+
+        let output = run_idlc(&[
+            input.to_str().expect("utf-8 input path"),
+            "-o",
+            output_file.to_str().expect("utf-8 output path"),
+        ]);
+        let stderr = stderr_string(&output);
+
+        assert_eq!(output.status.code(), Some(1), "stderr:\n{stderr}");
+        assert!(!stderr.contains("panicked"), "stderr:\n{stderr}");
+        assert!(
+            stderr.contains(code) && stderr.contains(location),
+            "expected {code} at {location} in stderr, got:\n{stderr}"
+        );
+    }
+}
+
+#[test]
+fn compat_fails_on_breaking_change() {
+    let dir = unique_temp_dir("compat");
+    let old = dir.join("old.idl");
+    let new = dir.join("new.idl");
+
+    std::fs::write(
+        &old,
+        "interface ITest {\n  method a();\n  method b();\n};\n",
+    )
+    .expect("old idl should be writable");
+    std::fs::write(
+        &new,
+        "interface ITest {\n  method b();\n  method a();\n};\n",
+    )
+    .expect("new idl should be writable");
+
+    let output = run_idlc(&[
+        "compat",
+        old.to_str().expect("utf-8 old path"),
+        new.to_str().expect("utf-8 new path"),
+    ]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert_eq!(output.status.code(), Some(1), "stdout:\n{stdout}");
+    assert!(
+        stdout.contains("breaking: method `ITest::a` op-code changed from 0 to 1"),
+        "expected op-code change in stdout, got:\n{stdout}"
+    );
+
+    let output = run_idlc(&[
+        "compat",
+        old.to_str().expect("utf-8 old path"),
+        old.to_str().expect("utf-8 old path"),
+    ]);
+    assert!(output.status.success(), "expected no changes");
+}
+
+#[test]
+fn rejects_invalid_rust_runtime_path() {
+    let output_dir = unique_temp_dir("runtime");
+    let input = fixture("tests/idl/ITest.idl");
+
+    let output = run_idlc(&[
+        input.to_str().expect("utf-8 input path"),
+        "--rust",
+        "--rust-runtime-path",
+        "mink-object",
+        "-o",
+        output_dir.to_str().expect("utf-8 output path"),
+    ]);
+
+    assert!(!output.status.success(), "expected clap to reject args");
+    assert!(
+        stderr_string(&output).contains("`mink-object` is not a Rust path"),
+        "stderr: {}",
+        stderr_string(&output)
+    );
+}
@@ -0,0 +1,79 @@
+// Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+fn temp_dir() -> PathBuf {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .expect("clock should be monotonic since epoch")
+        .as_nanos();
+    let dir = std::env::temp_dir().join(format!("idlc-depfile-{}-{nanos}", std::process::id()));
+    std::fs::create_dir_all(dir.join("inc")).expect("temp dir should be creatable");
+    std::fs::write(dir.join("inc/base.idl"), "struct S { uint32 a; };")
+        .expect("include should be writable");
+    std::fs::write(
+        dir.join("main.idl"),
+        "include \"base.idl\"\ninterface IMain { method a(in S s); };",
+    )
+    .expect("input idl should be writable");
+    dir.canonicalize()
+        .expect("temp dir should be canonicalizable")
+}
+
+fn idlc(dir: &Path, args: &[&str]) -> Output {
+    let output = Command::new(PathBuf::from(env!("CARGO_BIN_EXE_idlc")))
+        .current_dir(dir)
+        .args(["main.idl", "-I", "inc"])
+        .args(args)
+        .output()
+        .expect("idlc should execute");
+    assert!(
+        output.status.success(),
+        "stderr:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    output
+}
+
+fn deps(dir: &Path) -> String {
+    format!(
+        " \\\n  {} \\\n  {}\n",
+        dir.join("inc/base.idl").display(),
+        dir.join("main.idl").display()
+    )
+}
+
+#[test]
+fn header() {
+    let dir = temp_dir();
+    idlc(&dir, &["-o", "main.h", "-MD"]);
+    assert!(dir.join("main.h").is_file());
+    assert_eq!(
+        std::fs::read_to_string(dir.join("main.h.d")).expect("depfile should be written"),
+        format!("main.h:{}", deps(&dir))
+    );
+}
+
+#[test]
+fn directory_targets() {
+    let dir = temp_dir();
+    std::fs::create_dir(dir.join("out")).expect("output dir should be creatable");
+    idlc(&dir, &["--java", "-o", "out", "-MF", "java.d"]);
+    assert_eq!(
+        std::fs::read_to_string(dir.join("java.d")).expect("depfile should be written"),
+        format!("out/IMain.java out/main.java:{}", deps(&dir))
+    );
+}
+
+#[test]
+fn print_only() {
+    let dir = temp_dir();
+    let output = idlc(&dir, &["--rust", "-o", "out", "--print-deps"]);
+    assert_eq!(
+        String::from_utf8(output.stdout).expect("depfile should be utf-8"),
+        format!("out/imain.rs:{}", deps(&dir))
+    );
+    assert!(!dir.join("out").exists());
+}
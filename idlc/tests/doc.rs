@@ -0,0 +1,127 @@
+// Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::path::{Path, PathBuf};
+
+use idlc::{Compiler, GeneratedFile, Language, Options};
+
+const COMMON: &str = "struct Pair {
+  uint32 a;
+  uint16 b;
+  uint16 c;
+};
+";
+
+const IDL: &str = "include \"common.idl\"
+
+interface IBase {
+  error ERROR_BASE;
+  method ping();
+};
+
+/**
+ * Sums pairs.
+ */
+interface IFoo : IBase {
+  error ERROR_FOO;
+  /**
+   * Adds `p` to the total.
+   */
+  method add(in Pair p, in uint32 scale, out uint64 total);
+};
+";
+
+fn compile(input: &str, language: Language) -> Vec<GeneratedFile> {
+    Compiler::in_memory(
+        PathBuf::from(input),
+        [
+            (PathBuf::from("IFoo.idl"), IDL.to_string()),
+            (PathBuf::from("common.idl"), COMMON.to_string()),
+        ],
+        Vec::new(),
+        false,
+    )
+    .compile(language, &Options::default())
+    .expect("input should compile")
+}
+
+/// Pages of both files, as generated in the same output directory.
+fn compile_all(language: Language) -> Vec<GeneratedFile> {
+    let mut files = compile("IFoo.idl", language);
+    files.extend(compile("common.idl", language));
+    files
+}
+
+fn page<'a>(files: &'a [GeneratedFile], path: &str) -> &'a str {
+    files
+        .iter()
+        .find(|file| file.path == Path::new(path))
+        .map(|file| file.contents.as_str())
+        .unwrap_or_else(|| panic!("{path} should be generated"))
+}
+
+/// Checks that every link of `files`, starting with `link`, leads to a
+/// generated page and to an anchor of it declared as `anchor="<id>"`.
+fn assert_links_resolve(files: &[GeneratedFile], link: &str, anchor: &str) {
+    let mut links = 0;
+    for file in files {
+        for (start, _) in file.contents.match_indices(link) {
+            let rest = &file.contents[start + link.len()..];
+            let target = &rest[..rest.find(['"', ')']).expect("link should end")];
+            let (path, id) = target.split_once('#').unwrap_or((target, ""));
+            assert!(
+                id.is_empty() || page(files, path).contains(&format!("{anchor}\"{id}\"")),
+                "{}: `{target}` has no anchor",
+                file.path.display()
+            );
+            links += 1;
+        }
+    }
+    assert!(links > 0, "expected links");
+}
+
+#[test]
+fn markdown() {
+    let files = compile("IFoo.idl", Language::Markdown);
+    assert_eq!(files.len(), 3);
+    assert_links_resolve(&compile_all(Language::Markdown), "](", "<a id=");
+
+    let file = page(&files, "IFoo.idl.md");
+    assert!(file.contains("- [`common.idl`](common.idl.md)"));
+    assert!(file.contains("- [`IFoo`](IFoo.md): Sums pairs."));
+
+    let foo = page(&files, "IFoo.md");
+    assert!(foo.contains("Inheritance: `IFoo` → [`IBase`](IBase.md) → `Object`"));
+    // Errors of the bases come first, after the generic ones.
+    assert!(foo.contains("| `ERROR_BASE` | `10` | [`IBase`](IBase.md) |"));
+    assert!(foo.contains("| `ERROR_FOO` | `11` | [`IFoo`](IFoo.md) |"));
+    assert!(foo.contains("| `1` | [`add`](IFoo.md#method-add) |  | Adds `p` to the total. |"));
+    assert!(foo.contains(
+        "| `p` | in | [`Pair`](common.idl.md#struct-Pair) | input buffer 0 (bundled at offset 0) |"
+    ));
+    assert!(foo.contains("| `scale` | in | `uint32` | input buffer 0 (bundled at offset 8) |"));
+    assert!(foo.contains("| `total` | out | `uint64` | output buffer 0 |"));
+    // Methods of the bases are only detailed on their own page.
+    assert!(!foo.contains("<a id=\"method-ping\"></a>"));
+}
+
+#[test]
+fn struct_layout() {
+    let files = compile("common.idl", Language::Markdown);
+    let common = page(&files, "common.idl.md");
+    assert!(common.contains("<a id=\"struct-Pair\"></a>"));
+    assert!(common.contains("Size: 8 bytes, alignment: 4 bytes."));
+    assert!(common.contains("| `0` | `a` | `uint32` | 4 | 0 |  |"));
+    assert!(common.contains("| `6` | `c` | `uint16` | 2 | 0 |  |"));
+}
+
+#[test]
+fn html() {
+    let files = compile("IFoo.idl", Language::Html);
+    assert_links_resolve(&compile_all(Language::Html), "href=\"", "id=");
+    let foo = page(&files, "IFoo.html");
+    assert!(foo.contains("<h3 id=\"method-add\">Method <code>add</code></h3>"));
+    assert!(foo.contains("<a href=\"IBase.html\"><code>IBase</code></a>"));
+    assert!(foo.contains("<a href=\"common.idl.html#struct-Pair\"><code>Pair</code></a>"));
+    assert!(page(&files, "IBase.html").contains("<h3 id=\"method-ping\">"));
+}
@@ -0,0 +1,71 @@
+// Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::path::PathBuf;
+
+use idlc::{Compiler, Language, Options};
+
+const IDL: &str = "/**
+ * Largest count.
+ */
+const uint32 LIMIT = 4;
+
+/**
+ * A point.
+ */
+struct Point {
+  /**
+   * Abscissa.
+   */
+  uint32 x;
+  uint32 y;
+};
+
+/**
+ * Draws points.
+ */
+interface IFoo {
+  /**
+   * Nothing to draw.
+   */
+  error ERROR_EMPTY;
+  /**
+   * Draws `p`.
+   */
+  method draw(in Point p);
+};
+";
+
+#[test]
+fn emitted_by_every_backend() {
+    let compiler = Compiler::in_memory(
+        PathBuf::from("IFoo.idl"),
+        [(PathBuf::from("IFoo.idl"), IDL.to_string())],
+        Vec::new(),
+        false,
+    );
+    for (language, style) in [
+        (Language::C, "*  "),
+        (Language::CPP, "*  "),
+        (Language::Java, "*  "),
+        (Language::Rust, "///  "),
+    ] {
+        let files = compiler
+            .compile(language, &Options::default())
+            .expect("input should compile");
+        let contents: String = files.iter().map(|file| file.contents.as_str()).collect();
+        for doc in [
+            "Largest count.",
+            "A point.",
+            "Abscissa.",
+            "Draws points.",
+            "Nothing to draw.",
+            "Draws `p`.",
+        ] {
+            assert!(
+                contents.contains(&format!("{style}{doc}")),
+                "{language:?} is missing `{doc}`:\n{contents}"
+            );
+        }
+    }
+}
@@ -0,0 +1,96 @@
+// Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::path::PathBuf;
+use std::process::Command;
+
+fn dump(idl: &str, args: &[&str]) -> String {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .expect("clock should be monotonic since epoch")
+        .as_nanos();
+    let dir = std::env::temp_dir().join(format!("idlc-dump-{}-{nanos}", std::process::id()));
+    std::fs::create_dir_all(&dir).expect("temp dir should be creatable");
+    let input = dir.join("dump.idl");
+    std::fs::write(&input, idl).expect("input idl should be writable");
+
+    let output = Command::new(PathBuf::from(env!("CARGO_BIN_EXE_idlc")))
+        .arg(&input)
+        .args(args)
+        .output()
+        .expect("idlc should execute");
+    assert!(
+        output.status.success(),
+        "stderr:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).expect("dump should be utf-8")
+}
+
+const IDL: &str = r"
+struct S {
+  uint64 a;
+  uint32 b;
+  uint32 c;
+};
+
+interface IBase {
+  error E_BASE;
+  method base();
+};
+
+interface ITest : IBase {
+  error E_TEST;
+  method bundled(in uint32 x, in uint16 y, out S s);
+};
+
+typedef uint32[2] Pair;
+";
+
+#[test]
+fn mir_json() {
+    let mir: serde_json::Value =
+        serde_json::from_str(&dump(IDL, &["--dump", "mir", "--format", "json"]))
+            .expect("dump should be valid json");
+
+    assert_eq!(mir["schema"], 5);
+    assert_eq!(mir["stage"], "mir");
+
+    let s = &mir["nodes"][0];
+    assert_eq!(s["kind"], "struct");
+    assert_eq!(s["size"], 16);
+    assert_eq!(s["align"], 8);
+    assert_eq!(s["fields"][1]["offset"], 8);
+
+    let iface = &mir["nodes"][2];
+    assert_eq!(iface["name"], "ITest");
+    assert_eq!(iface["base"], "IBase");
+    assert_eq!(iface["nodes"][0]["value"], 11);
+
+    let method = &iface["nodes"][1];
+    assert_eq!(method["op_code"], 1);
+    assert_eq!(method["params"][0]["type"]["kind"], "primitive");
+    assert_eq!(method["params"][0]["type"]["name"], "uint32");
+    assert_eq!(method["counts"]["input_buffers"], 1);
+    assert_eq!(method["counts"]["output_buffers"], 1);
+    assert_eq!(method["bundles"]["input"]["size"], 6);
+    assert_eq!(
+        method["bundles"]["input"]["params"],
+        serde_json::json!(["x", "y"])
+    );
+    assert!(method["bundles"]["output"].is_null());
+
+    let typedef = &mir["nodes"][3];
+    assert_eq!(typedef["kind"], "typedef");
+    assert_eq!(typedef["name"], "Pair");
+    assert_eq!(typedef["type"]["name"], "uint32");
+    assert_eq!(typedef["count"], 2);
+}
+
+#[test]
+fn ast_yaml() {
+    let ast = dump(IDL, &["--dump", "ast", "--format", "yaml"]);
+    assert!(ast.starts_with("schema: 5\nstage: ast\n"), "{ast}");
+    assert!(ast.contains("kind: custom\n        name: S\n"), "{ast}");
+    assert!(!ast.contains("op_code"), "{ast}");
+}
@@ -0,0 +1,67 @@
+// Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+const UNFORMATTED: &str = "struct S { uint32 a; uint8[2] b; };\n";
+const FORMATTED: &str = "struct S {\n  uint32   a;\n  uint8[2] b;\n};\n";
+
+fn temp_dir() -> PathBuf {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .expect("clock should be monotonic since epoch")
+        .as_nanos();
+    let dir = std::env::temp_dir().join(format!("idlc-fmt-{}-{nanos}", std::process::id()));
+    std::fs::create_dir_all(&dir).expect("temp dir should be creatable");
+    std::fs::write(dir.join("S.idl"), UNFORMATTED).expect("input idl should be writable");
+    dir
+}
+
+fn idlc(dir: &Path, args: &[&str]) -> Output {
+    Command::new(PathBuf::from(env!("CARGO_BIN_EXE_idlc")))
+        .current_dir(dir)
+        .arg("fmt")
+        .args(args)
+        .output()
+        .expect("idlc should execute")
+}
+
+fn read(dir: &Path) -> String {
+    std::fs::read_to_string(dir.join("S.idl")).expect("input idl should be readable")
+}
+
+#[test]
+fn check_prints_diff() {
+    let dir = temp_dir();
+    let output = idlc(&dir, &["--check", "S.idl"]);
+    assert_eq!(output.status.code(), Some(1));
+    let diff = String::from_utf8(output.stdout).expect("diff should be utf-8");
+    assert!(
+        diff.contains("-struct S { uint32 a; uint8[2] b; };"),
+        "{diff}"
+    );
+    assert!(diff.contains("+  uint8[2] b;"), "{diff}");
+    assert_eq!(read(&dir), UNFORMATTED);
+}
+
+#[test]
+fn formats_in_place() {
+    let dir = temp_dir();
+    let output = idlc(&dir, &["S.idl"]);
+    assert!(output.status.success());
+    assert_eq!(read(&dir), FORMATTED);
+
+    let output = idlc(&dir, &["--check", "S.idl"]);
+    assert!(output.status.success());
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn reports_parse_errors() {
+    let dir = temp_dir();
+    std::fs::write(dir.join("S.idl"), "struct S {};").expect("input idl should be writable");
+    let output = idlc(&dir, &["S.idl"]);
+    assert!(!output.status.success());
+    assert!(!output.stderr.is_empty());
+}
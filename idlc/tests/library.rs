@@ -0,0 +1,1046 @@
+// Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::path::PathBuf;
+
+use idlc::{Compiler, GeneratedFile, Language, Options};
+use idlc_errors::codes;
+
+fn compiler(files: &[(&str, &str)]) -> Compiler {
+    Compiler::in_memory(
+        PathBuf::from("virtual/IFoo.idl"),
+        files
+            .iter()
+            .map(|(path, source)| (PathBuf::from(path), (*source).to_string())),
+        vec![PathBuf::from("shared")],
+        false,
+    )
+}
+
+#[test]
+fn generates_in_memory() {
+    let compiler = compiler(&[
+        (
+            "virtual/IFoo.idl",
+            "include \"common.idl\"\ninclude \"../shared/IBase.idl\"\ninterface IFoo : IBase { method foo(in S s); };",
+        ),
+        ("shared/common.idl", "struct S { uint32 a; };"),
+        ("shared/IBase.idl", "interface IBase { method base(); };"),
+    ]);
+
+    let options = Options {
+        skeleton: true,
+        ..Options::default()
+    };
+    let files = compiler
+        .compile(Language::C, &options)
+        .expect("input should compile");
+    assert_eq!(files.len(), 1);
+    assert_eq!(files[0].path, PathBuf::from("IFoo_invoke.h"));
+    assert!(files[0].contents.contains("IFoo_OP_foo"));
+
+    let files = compiler
+        .compile(Language::Rust, &Options::default())
+        .expect("input should compile");
+    assert!(files
+        .iter()
+        .map(|GeneratedFile { path, .. }| path)
+        .any(|path| path == &PathBuf::from("ifoo.rs")));
+    assert!(!PathBuf::from("virtual").exists());
+}
+
+#[test]
+fn returns_diagnostics() {
+    let diagnostics = compiler(&[("virtual/IFoo.idl", "include \"missing.idl\"")])
+        .compile(Language::C, &Options::default())
+        .expect_err("include should be missing");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].code, codes::INCLUDE_NOT_FOUND);
+
+    let diagnostics = compiler(&[(
+        "virtual/IFoo.idl",
+        "interface IFoo { method a(in uint32 x, in uint32 x); };",
+    )])
+    .mir()
+    .expect_err("parameters should be duplicated");
+    assert!(diagnostics.iter().any(|d| d.code == codes::DUPLICATE_PARAM));
+    assert!(!idlc_errors::has_errors());
+}
+
+#[test]
+fn rejects_inputs_without_a_name() {
+    let diagnostics = Compiler::output_in(
+        &PathBuf::from("out"),
+        &PathBuf::from("/"),
+        Language::C,
+        &Options::default(),
+    )
+    .expect_err("`/` doesn't name a header");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].code, codes::INVALID_INPUT);
+    assert_eq!(
+        Compiler::output_in(
+            &PathBuf::from("out"),
+            &PathBuf::from("/"),
+            Language::Rust,
+            &Options::default(),
+        ),
+        Ok(PathBuf::from("out/rust"))
+    );
+}
+
+#[test]
+fn honors_pinned_values() {
+    let files = compiler(&[
+        (
+            "virtual/IFoo.idl",
+            "include \"IBase.idl\"
+interface IFoo : IBase {
+  error ERROR_B = 20;
+  error ERROR_C;
+  #[opcode = 5]
+  method c();
+  method d();
+};",
+        ),
+        (
+            "shared/IBase.idl",
+            "interface IBase { error ERROR_A; method a(); method b(); };",
+        ),
+    ])
+    .compile(Language::C, &Options::default())
+    .expect("input should compile");
+    for define in [
+        "#define IFoo_ERROR_A INT32_C(10)",
+        "#define IFoo_ERROR_B INT32_C(20)",
+        "#define IFoo_ERROR_C INT32_C(21)",
+        "#define IFoo_OP_c 5",
+        "#define IFoo_OP_d 6",
+    ] {
+        assert!(files[0].contents.contains(define), "missing `{define}`");
+    }
+}
+
+#[test]
+fn rejects_invalid_pinned_values() {
+    for (source, code) in [
+        (
+            "interface IFoo { method a(); #[opcode = 0] method b(); };",
+            codes::OP_CODE_COLLISION,
+        ),
+        (
+            "interface IFoo { #[opcode = 1] method a(); method b(); #[opcode = 2] method c(); };",
+            codes::OP_CODE_COLLISION,
+        ),
+        (
+            "interface IFoo { #[opcode = 0x4000] method a(); };",
+            codes::OP_CODE_OUT_OF_RANGE,
+        ),
+        (
+            "interface IFoo { #[opcode = 1] #[opcode = 2] method a(); };",
+            codes::DUPLICATE_OP_CODE,
+        ),
+        (
+            "interface IFoo { error ERROR_A; error ERROR_B = 10; };",
+            codes::ERROR_VALUE_COLLISION,
+        ),
+        (
+            "interface IFoo { error ERROR_A = 9; };",
+            codes::ERROR_VALUE_RESERVED,
+        ),
+        (
+            "interface IFoo { #[opcode = 99999999999] method a(); };",
+            codes::OP_CODE_OUT_OF_RANGE,
+        ),
+        (
+            "interface IFoo { error ERROR_A = 0x80000000; };",
+            codes::ERROR_VALUE_OUT_OF_RANGE,
+        ),
+    ] {
+        let diagnostics = compiler(&[("virtual/IFoo.idl", source)])
+            .mir()
+            .expect_err(source);
+        assert!(
+            diagnostics.iter().any(|d| d.code == code),
+            "`{source}` should be rejected with {code}: {diagnostics:?}"
+        );
+    }
+
+    // Collisions with a base are reported too.
+    let diagnostics = compiler(&[
+        (
+            "virtual/IFoo.idl",
+            "include \"IBase.idl\"\ninterface IFoo : IBase { #[opcode = 0] method b(); };",
+        ),
+        ("shared/IBase.idl", "interface IBase { method a(); };"),
+    ])
+    .mir()
+    .expect_err("op-code 0 should collide");
+    assert!(diagnostics
+        .iter()
+        .any(|d| d.code == codes::OP_CODE_COLLISION && d.message.contains("`IBase::a`")));
+}
+
+#[test]
+fn propagates_deprecations() {
+    let compiler = compiler(&[
+        (
+            "virtual/IFoo.idl",
+            "include \"common.idl\"
+#[deprecated = \"use IBar instead\"]
+interface IOld { method a(in S s); };
+interface IFoo : IOld {
+  #[deprecated = \"use b2 instead\"]
+  method b(in S s);
+  method b2(in S s);
+};",
+        ),
+        (
+            "shared/common.idl",
+            "#[deprecated]\nstruct S { uint32 a; };",
+        ),
+    ]);
+    let _ = idlc_errors::take_diagnostics();
+    let files = compiler
+        .compile(Language::C, &Options::default())
+        .expect("input should compile");
+    // Uses from within deprecated declarations aren't reported.
+    let warnings = idlc_errors::take_diagnostics();
+    assert_eq!(warnings.len(), 2, "{warnings:?}");
+    assert!(warnings.iter().all(|w| w.code == codes::DEPRECATED_USE));
+    assert!(warnings[0]
+        .message
+        .contains("interface `IOld`: use IBar instead"));
+    assert!(warnings[1].message.contains("struct `S`"));
+
+    let c = &files[0].contents;
+    assert!(c.contains("__attribute__((deprecated(\"use IBar instead\"))) typedef Object IOld;"));
+    assert!(c.contains("__attribute__((deprecated(\"use b2 instead\"))) static inline int32_t"));
+    assert!(c.contains("#pragma GCC diagnostic ignored \"-Wdeprecated-declarations\""));
+
+    for (language, expected) in [
+        (
+            Language::CPP,
+            "[[deprecated(\"use b2 instead\")]] virtual int32_t b(",
+        ),
+        (Language::Rust, "#[deprecated(note = \"use b2 instead\")]"),
+        (Language::Java, "@Deprecated"),
+        (Language::Markdown, "Deprecated: use b2 instead"),
+    ] {
+        let files = compiler
+            .compile(language, &Options::default())
+            .expect("input should compile");
+        assert!(
+            files.iter().any(|file| file.contents.contains(expected)),
+            "{language:?} output should contain `{expected}`"
+        );
+    }
+    let _ = idlc_errors::take_diagnostics();
+}
+
+#[test]
+fn resolves_typedefs() {
+    let compiler = compiler(&[
+        (
+            "virtual/IFoo.idl",
+            "include \"common.idl\"
+using Digests = Sha256[2];
+struct Entry { Sha256 digest; Handle handle; Digests[2] more; };
+interface IFoo { method put(in Handle h, in Entry e, out Handle[] hs); };",
+        ),
+        (
+            "shared/common.idl",
+            "typedef uint8[32] Sha256;\nusing Handle = uint64;",
+        ),
+    ]);
+    // The layout is the one of the aliased types.
+    let mir = compiler.mir().expect("input should compile");
+    let Some(idlc_mir::Node::Struct(entry)) = mir.nodes.get(2) else {
+        panic!("unexpected nodes {:?}", mir.nodes);
+    };
+    let counts: Vec<_> = entry
+        .as_ref()
+        .fields
+        .iter()
+        .map(|field| field.val.1.get())
+        .collect();
+    assert_eq!(counts, [32, 1, 128]);
+    assert_eq!(entry.as_ref().size(), 168);
+
+    for (language, expected) in [
+        // Uses of an alias are printed with its name.
+        (Language::C, "typedef Sha256 Digests[2];"),
+        (Language::C, "Sha256 digest;"),
+        (Language::C, "Digests more[2];"),
+        (
+            Language::C,
+            "IFoo_put(Object self, Handle h_val, const Entry *e_ptr",
+        ),
+        (Language::CPP, "using Digests = Sha256[2];"),
+        (Language::CPP, "Handle handle;"),
+        (Language::CPP, "Handle *hs_ptr"),
+        (
+            Language::Rust,
+            "pub type r#Digests = [crate::interfaces::common::r#Sha256; 2];",
+        ),
+        (
+            Language::Rust,
+            "pub r#more: [crate::interfaces::ifoo::r#Digests; 2],",
+        ),
+        (
+            Language::Rust,
+            "r#hs: &mut [crate::interfaces::common::r#Handle]",
+        ),
+    ] {
+        let files = compiler
+            .compile(language, &Options::default())
+            .expect("input should compile");
+        assert!(
+            files.iter().any(|file| file.contents.contains(expected)),
+            "{language:?} output should contain `{expected}`"
+        );
+    }
+}
+
+#[test]
+fn rejects_invalid_typedefs() {
+    for (source, code) in [
+        (
+            "interface IBar {};\nusing B = IBar;",
+            codes::INVALID_TYPEDEF,
+        ),
+        ("using A = B;\nusing B = A;", codes::INVALID_TYPEDEF),
+        (
+            "typedef uint8[300] A;\ntypedef A[300] B;",
+            codes::INVALID_TYPEDEF,
+        ),
+        ("using A = Missing;", codes::UNRESOLVED_SYMBOL),
+        (
+            "typedef uint8[300] A;\nstruct S { A[300] a; };",
+            codes::INVALID_TYPEDEF_USE,
+        ),
+        (
+            "typedef uint8[3] A;\ninterface IFoo { method a(in A a); };",
+            codes::INVALID_TYPEDEF_USE,
+        ),
+        (
+            "struct S { uint32 a; };\nusing S = uint32;",
+            codes::DUPLICATE_SYMBOL,
+        ),
+    ] {
+        let diagnostics = compiler(&[("virtual/IFoo.idl", source)])
+            .mir()
+            .expect_err(source);
+        assert!(
+            diagnostics.iter().any(|d| d.code == code),
+            "`{source}` should be rejected with {code}: {diagnostics:?}"
+        );
+    }
+}
+
+#[test]
+fn evaluates_const_exprs() {
+    let compiler = compiler(&[
+        (
+            "virtual/IFoo.idl",
+            "include \"common.idl\"
+const uint32 MASK = (1 << BITS) - 1 | IBase::FLAG;
+const int8 MIN = -MAX - 1;
+const float64 HALF = 1.0 / 2.0;
+const uint16 KEY_BYTES = KEY_LEN * 2;
+struct Key { uint8[KEY_BYTES] bytes; };
+interface IFoo : IBase {
+    const uint16 LEN = FLAG + KEY_LEN;
+    const uint8 SLOTS = BITS - 1;
+    method put(in interface[SLOTS] objs);
+};",
+        ),
+        (
+            "shared/common.idl",
+            "const uint8 BITS = 4;
+const int8 MAX = 127;
+const uint16 KEY_LEN = 16;
+interface IBase { const uint32 FLAG = 0x100; };",
+        ),
+    ]);
+    let mir = compiler.mir().expect("input should compile");
+    let consts: Vec<_> = mir
+        .nodes
+        .iter()
+        .filter_map(|node| match node {
+            idlc_mir::Node::Const(c) => Some((c.ident.to_string(), c.value.clone())),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        consts,
+        [
+            ("MASK".to_string(), "271".to_string()),
+            ("MIN".to_string(), "-128".to_string()),
+            ("HALF".to_string(), "0.5".to_string()),
+            ("KEY_BYTES".to_string(), "32".to_string()),
+        ]
+    );
+    let Some(idlc_mir::Node::Struct(key)) = mir.nodes.get(5) else {
+        panic!("unexpected nodes {:?}", mir.nodes);
+    };
+    assert_eq!(key.as_ref().fields[0].val.1.get(), 32);
+
+    for (language, expected) in [
+        (Language::C, "uint8_t bytes[32];"),
+        (Language::C, "#define IFoo_LEN UINT16_C(272)"),
+        (Language::Rust, "pub const LEN: u16 = 272;"),
+        (
+            Language::Rust,
+            "r#objs: &[Option<crate::object::Object>; 3]",
+        ),
+    ] {
+        let files = compiler
+            .compile(language, &Options::default())
+            .expect("input should compile");
+        assert!(
+            files.iter().any(|file| file.contents.contains(expected)),
+            "{language:?} output should contain `{expected}`"
+        );
+    }
+}
+
+#[test]
+fn rejects_invalid_const_exprs() {
+    for (source, code) in [
+        ("const uint8 A = 200 + 100 - 50;", codes::INVALID_CONST_EXPR),
+        ("const int8 A = -(-128);", codes::INVALID_CONST_EXPR),
+        ("const uint32 A = 1 / (2 - 2);", codes::INVALID_CONST_EXPR),
+        ("const uint32 A = 1 << 32;", codes::INVALID_CONST_EXPR),
+        (
+            "const uint8 A = B;\nconst uint8 B = A + 1;",
+            codes::INVALID_CONST_EXPR,
+        ),
+        (
+            "const uint16 A = 300;\nconst uint8 B = A;",
+            codes::INVALID_CONST_EXPR,
+        ),
+        (
+            "const float32 A = 1.0;\nconst uint8 B = A;",
+            codes::INVALID_CONST_EXPR,
+        ),
+        ("const float32 A = 1.0 << 2;", codes::INVALID_CONST_EXPR),
+        ("const uint8 A = Missing;", codes::UNRESOLVED_SYMBOL),
+        (
+            "interface I {};\nconst uint8 A = I::B;",
+            codes::UNRESOLVED_SYMBOL,
+        ),
+        (
+            "const int8 N = -1;\nstruct S { uint8[N] a; };",
+            codes::INVALID_ARRAY_SIZE,
+        ),
+        (
+            "const uint32 N = 0x10000;\nusing T = uint8[N];",
+            codes::INVALID_ARRAY_SIZE,
+        ),
+        (
+            "interface IFoo { method a(in interface[N] a); };",
+            codes::UNRESOLVED_SYMBOL,
+        ),
+    ] {
+        let diagnostics = compiler(&[("virtual/IFoo.idl", source)])
+            .mir()
+            .expect_err(source);
+        assert!(
+            diagnostics.iter().any(|d| d.code == code),
+            "`{source}` should be rejected with {code}: {diagnostics:?}"
+        );
+    }
+}
+
+#[test]
+fn generates_bool_and_char() {
+    let compiler = compiler(&[(
+        "virtual/IFoo.idl",
+        "struct Flags { uint32 id; bool on; char tag; uint16 code; };
+interface IFoo {
+    method set(in bool on, in char tag, in Flags flags, out bool was);
+    method all(in bool[] bits, in Flags[] flags);
+};",
+    )]);
+    let options = Options {
+        skeleton: true,
+        ..Options::default()
+    };
+    for (language, expected) in [
+        (Language::C, "#include <stdbool.h>"),
+        (Language::C, "bool m_on;"),
+        (Language::C, "char m_tag;"),
+        (Language::C, "> 1) {"),
+        (
+            Language::C,
+            "for (size_t idlc_i = 0; idlc_i < bits_len; idlc_i++)",
+        ),
+        (
+            Language::CPP,
+            "for (size_t idlc_i = 0; idlc_i < bits_len; idlc_i++)",
+        ),
+        (
+            Language::CPP,
+            "for (size_t idlc_n = 0; idlc_n < flags_len; idlc_n++)",
+        ),
+        (Language::Rust, "pub r#on: bool,"),
+        (Language::Rust, "pub r#tag: core::ffi::c_char,"),
+        (Language::Rust, ".any(|b| *b > 1)"),
+        (Language::Rust, "invalid_bools = true;"),
+        (Language::Java, "boolean on"),
+        (Language::Java, "byte tag"),
+        (Language::Java, "== 0 ||"),
+    ] {
+        let files = compiler
+            .compile(language, &options)
+            .expect("input should compile");
+        assert!(
+            files.iter().any(|file| file.contents.contains(expected)),
+            "{language:?} output should contain `{expected}`"
+        );
+    }
+}
+
+#[test]
+fn bounds_input_buffers() {
+    let compiler = compiler(&[(
+        "virtual/IFoo.idl",
+        "const uint16 MAX = 8;
+struct S { uint32 a; uint32 b; };
+interface IFoo {
+    method put(in uint8[<=256] data, #[max_len = 64] in buffer blob, in S[<=MAX] ss);
+};",
+    )]);
+    let mir = compiler.mir().expect("input should compile");
+    let Some(idlc_mir::Node::Interface(iface)) = mir.nodes.last() else {
+        panic!("unexpected nodes {:?}", mir.nodes);
+    };
+    let Some(idlc_mir::InterfaceNode::Function(put)) = iface.nodes.first() else {
+        panic!("unexpected nodes {:?}", iface.nodes);
+    };
+    let max_lens: Vec<_> = put
+        .params
+        .iter()
+        .map(|param| param.max_len().map(|max_len| max_len.get()))
+        .collect();
+    assert_eq!(max_lens, [Some(256), Some(64), Some(8)]);
+
+    let skeleton = Options {
+        skeleton: true,
+        ..Options::default()
+    };
+    for (language, options, expected) in [
+        (
+            Language::C,
+            &skeleton,
+            "if (a[2].b.size > 64) { return Object_ERROR_INVALID; }",
+        ),
+        (
+            Language::C,
+            &Options::default(),
+            "if (data_len > 256) { return Object_ERROR_INVALID; }",
+        ),
+        (
+            Language::CPP,
+            &skeleton,
+            "if (a[0].b.size > 256) { return Object_ERROR_INVALID; }",
+        ),
+        (
+            Language::CPP,
+            &Options::default(),
+            "if (ss_len > 8) { return Object_ERROR_INVALID; }",
+        ),
+        (
+            Language::Rust,
+            &Options::default(),
+            "if r#blob.len() > 64 {",
+        ),
+        (
+            Language::Rust,
+            &Options::default(),
+            "if args[1].bi.size > 64 {",
+        ),
+    ] {
+        let files = compiler
+            .compile(language, options)
+            .expect("input should compile");
+        assert!(
+            files.iter().any(|file| file.contents.contains(expected)),
+            "{language:?} output should contain `{expected}`"
+        );
+    }
+}
+
+#[test]
+fn rejects_invalid_max_lens() {
+    for (source, code) in [
+        (
+            "interface IFoo { method a(out uint8[<=4] a); };",
+            codes::INVALID_MAX_LEN,
+        ),
+        (
+            "interface IFoo { method a(#[max_len = 4] in uint32 a); };",
+            codes::INVALID_MAX_LEN,
+        ),
+        (
+            "interface IFoo { method a(in uint8[<=N] a); };",
+            codes::UNRESOLVED_SYMBOL,
+        ),
+        (
+            "const int8 N = -1;\ninterface IFoo { method a(in uint8[<=N] a); };",
+            codes::INVALID_ARRAY_SIZE,
+        ),
+    ] {
+        let diagnostics = compiler(&[("virtual/IFoo.idl", source)])
+            .mir()
+            .expect_err(source);
+        assert!(
+            diagnostics.iter().any(|d| d.code == code),
+            "`{source}` should be rejected with {code}: {diagnostics:?}"
+        );
+    }
+}
+
+#[test]
+fn sends_strings() {
+    let compiler = compiler(&[(
+        "virtual/IFoo.idl",
+        "const uint32 MAX_PATH = 128;
+interface IFoo {
+    method rename(in string from, in string<64> to, out string<MAX_PATH> path);
+};",
+    )]);
+    let mir = compiler.mir().expect("input should compile");
+    let Some(idlc_mir::Node::Interface(iface)) = mir.nodes.last() else {
+        panic!("unexpected nodes {:?}", mir.nodes);
+    };
+    let Some(idlc_mir::InterfaceNode::Function(rename)) = iface.nodes.first() else {
+        panic!("unexpected nodes {:?}", iface.nodes);
+    };
+    let max_lens: Vec<_> = rename
+        .params
+        .iter()
+        .map(|param| param.max_len().map(|max_len| max_len.get()))
+        .collect();
+    assert_eq!(max_lens, [None, Some(64), Some(128)]);
+
+    let skeleton = Options {
+        skeleton: true,
+        ..Options::default()
+    };
+    for (language, options, expected) in [
+        (
+            Language::C,
+            &Options::default(),
+            "const char *from_ptr, size_t from_len",
+        ),
+        (
+            Language::C,
+            &skeleton,
+            "if (a[1].b.size > 64) { return Object_ERROR_INVALID; }",
+        ),
+        (
+            Language::CPP,
+            &Options::default(),
+            "std::string path_buf(128, '\\0');",
+        ),
+        (
+            Language::CPP,
+            &skeleton,
+            "std::string_view(from_ptr, from_len)",
+        ),
+        (
+            Language::Rust,
+            &Options::default(),
+            "let Ok(r#path) = String::from_utf8(r#path) else {",
+        ),
+        (
+            Language::Rust,
+            &skeleton,
+            "let Ok(r#from) = core::str::from_utf8(r#from) else {",
+        ),
+    ] {
+        let files = compiler
+            .compile(language, options)
+            .expect("input should compile");
+        assert!(
+            files.iter().any(|file| file.contents.contains(expected)),
+            "{language:?} output should contain `{expected}`"
+        );
+    }
+}
+
+#[test]
+fn rejects_unbounded_output_strings() {
+    let source = "interface IFoo { method a(out string a); };";
+    let diagnostics = compiler(&[("virtual/IFoo.idl", source)])
+        .mir()
+        .expect_err(source);
+    assert!(
+        diagnostics
+            .iter()
+            .any(|d| d.code == codes::UNBOUNDED_OUTPUT_STRING),
+        "{diagnostics:?}"
+    );
+}
+
+#[test]
+fn refers_to_rust_runtime_path() {
+    let options = Options {
+        rust_runtime_path: Some("::mink_object".to_string()),
+        ..Options::default()
+    };
+    let files = compiler(&[(
+        "virtual/IFoo.idl",
+        "interface IFoo { error ERROR_A; method a(in IFoo o); };",
+    )])
+    .compile(Language::Rust, &options)
+    .expect("input should compile");
+    let rust = &files[0].contents;
+    assert!(
+        rust.contains("pub struct IFoo(::mink_object::Object);"),
+        "{rust}"
+    );
+    assert!(rust.contains("::mink_object::wrapper::Wrapper"), "{rust}");
+    assert!(!rust.contains("crate::object"), "{rust}");
+}
+
+#[test]
+fn emits_rust_no_std() {
+    let source = "interface IFoo { error ERROR_A; method a(in string<8> s, out uint8[] b); };";
+    let options = Options {
+        rust_no_std: true,
+        ..Options::default()
+    };
+    let files = compiler(&[("virtual/IFoo.idl", source)])
+        .compile(Language::Rust, &options)
+        .expect("input should compile");
+    let rust = &files[0].contents;
+    assert!(rust.contains("use alloc::"), "{rust}");
+    assert!(
+        rust.contains("#[cfg(feature = \"std\")]\nimpl std::error::Error for Error {}"),
+        "{rust}"
+    );
+    assert_eq!(rust.matches("std::").count(), 1, "{rust}");
+
+    let files = compiler(&[("virtual/IFoo.idl", source)])
+        .compile(Language::Rust, &Options::default())
+        .expect("input should compile");
+    let rust = &files[0].contents;
+    assert!(!rust.contains("use alloc::"), "{rust}");
+    assert!(
+        rust.contains("\nimpl std::error::Error for Error {}"),
+        "{rust}"
+    );
+}
+
+#[test]
+fn emits_mocks() {
+    let compiler = compiler(&[
+        (
+            "virtual/IFoo.idl",
+            "include \"IBase.idl\"\ninterface IFoo : IBase { method b(in uint32 x, out uint32 y); };",
+        ),
+        ("virtual/IBase.idl", "interface IBase { method a(); };"),
+    ]);
+    let options = Options {
+        mock: true,
+        ..Options::default()
+    };
+
+    let files = compiler
+        .compile(Language::CPP, &options)
+        .expect("input should compile");
+    assert_eq!(files[0].path, PathBuf::from("IFoo_mock.hpp"));
+    let cpp = &files[0].contents;
+    assert!(cpp.contains("#include \"IFoo_invoke.hpp\""), "{cpp}");
+    assert!(
+        cpp.contains("class MockIFoo : public IFooImplBase {"),
+        "{cpp}"
+    );
+    assert!(
+        cpp.contains("MOCK_METHOD(int32_t, b, (uint32_t x_val, uint32_t *y_ptr), (override));"),
+        "{cpp}"
+    );
+    assert!(
+        cpp.contains("MOCK_METHOD(int32_t, a, (), (override));"),
+        "{cpp}"
+    );
+
+    let files = compiler
+        .compile(Language::Rust, &options)
+        .expect("input should compile");
+    let rust = &files[0].contents;
+    assert!(rust.contains("pub struct MockIFoo {"), "{rust}");
+    assert!(rust.contains("impl IIFoo for MockIFoo {"), "{rust}");
+    assert!(rust.contains("impl IIBase for MockIFoo {"), "{rust}");
+    assert!(rust.contains("pub fn expect_b("), "{rust}");
+    assert!(
+        rust.contains("unexpected call of `IBase::a`") && !rust.contains("panic!"),
+        "{rust}"
+    );
+
+    let files = compiler
+        .compile(Language::Rust, &Options::default())
+        .expect("input should compile");
+    assert!(!files[0].contents.contains("MockIFoo"));
+}
+
+#[test]
+fn emits_rust_async() {
+    let compiler = compiler(&[
+        (
+            "virtual/IFoo.idl",
+            "include \"IBase.idl\"\ninterface IFoo : IBase { method b(in uint32 x, out uint32 y); };",
+        ),
+        ("virtual/IBase.idl", "interface IBase { method a(); };"),
+    ]);
+    let options = Options {
+        rust_async: true,
+        ..Options::default()
+    };
+    let files = compiler
+        .compile(Language::Rust, &options)
+        .expect("input should compile");
+    let rust = &files[0].contents;
+    assert!(
+        rust.contains("pub async fn r#b_async(&self, r#x: u32) -> Result<(u32), Error> {"),
+        "{rust}"
+    );
+    assert!(
+        rust.contains("crate::object::asynchronous::blocking(move || {"),
+        "{rust}"
+    );
+    assert!(
+        rust.contains("let async_result = async_proxy.r#b(r#x);"),
+        "{rust}"
+    );
+    assert!(
+        rust.contains("pub trait AsyncIIFoo: AsyncIIBase + 'static {"),
+        "{rust}"
+    );
+    assert!(
+        rust.contains("impl<T: AsyncIIFoo> IIFoo for crate::object::asynchronous::Blocking<T> {"),
+        "{rust}"
+    );
+
+    // The synchronous API is the same either way.
+    let files = compiler
+        .compile(Language::Rust, &Options::default())
+        .expect("input should compile");
+    let sync = &files[0].contents;
+    assert!(!sync.contains("async"), "{sync}");
+    assert!(sync.lines().all(|line| rust.contains(line)));
+}
+
+#[test]
+fn emits_serde() {
+    let compiler = compiler(&[(
+        "virtual/IFoo.idl",
+        "struct Bytes { uint8[40] data; uint32 len; };\n\
+         interface IFoo { method a(); };\n\
+         struct Holder { IFoo foo; uint64 id; };",
+    )]);
+    let options = Options {
+        serde: true,
+        rust_runtime_path: Some("::mink_object".to_string()),
+        ..Options::default()
+    };
+
+    let files = compiler
+        .compile(Language::Rust, &options)
+        .expect("input should compile");
+    let rust = files
+        .iter()
+        .map(|file| file.contents.as_str())
+        .collect::<String>();
+    assert!(
+        rust.contains("#[derive(Debug,Clone,PartialEq,Copy, serde::Serialize, serde::Deserialize)]\npub struct r#Bytes {"),
+        "{rust}"
+    );
+    assert!(
+        rust.contains("#[serde(with = \"::mink_object::serde_array\")]\npub r#data: [u8; 40],"),
+        "{rust}"
+    );
+    assert!(
+        rust.contains("// No serde derives: `Holder` contains objects."),
+        "{rust}"
+    );
+    assert_eq!(rust.matches("serde::Serialize").count(), 1, "{rust}");
+
+    let files = compiler
+        .compile(Language::CPP, &options)
+        .expect("input should compile");
+    let cpp = &files[0].contents;
+    assert!(cpp.contains("#ifndef IDLC_JSON"), "{cpp}");
+    assert!(
+        cpp.contains("inline void to_json(std::ostream &os, const Bytes &value) {"),
+        "{cpp}"
+    );
+    assert!(
+        cpp.contains("os << \",\\\"len\\\":\";\n    idlc_json::write(os, value.len);"),
+        "{cpp}"
+    );
+    assert!(
+        cpp.contains("inline std::ostream &operator<<(std::ostream &os, const Bytes &value) {"),
+        "{cpp}"
+    );
+    assert!(
+        cpp.contains("// No JSON printer: `Holder` contains objects."),
+        "{cpp}"
+    );
+
+    let files = compiler
+        .compile(Language::CPP, &Options::default())
+        .expect("input should compile");
+    assert!(!files[0].contents.contains("to_json"));
+}
+
+#[test]
+fn bundles_params_by_size() {
+    let compiler = compiler(&[(
+        "virtual/IFoo.idl",
+        "struct Halves { uint16 a; uint16 b; uint16 c; uint16 d; };\ninterface IFoo { method m(in uint32 x, in Halves h); };",
+    )]);
+    let by_size = Options {
+        bundle_params_by_size: true,
+        ..Options::default()
+    };
+
+    // The setting only applies to its own compilation, in either order.
+    for _ in 0..2 {
+        let files = compiler
+            .compile(Language::Rust, &by_size)
+            .expect("input should compile");
+        let rust = &files[0].contents;
+        assert!(
+            rust.contains("struct BI(crate::interfaces::ifoo::r#Halves, u32);"),
+            "{rust}"
+        );
+
+        let files = compiler
+            .compile(Language::Rust, &Options::default())
+            .expect("input should compile");
+        let rust = &files[0].contents;
+        assert!(
+            rust.contains("struct BI(u32, crate::interfaces::ifoo::r#Halves);"),
+            "{rust}"
+        );
+    }
+}
+
+#[test]
+fn emits_wide_c_enums() {
+    let compiler = compiler(&[(
+        "virtual/IFoo.idl",
+        "enum Wide : uint32 { LOW = 1, HIGH = 2147483648 };\nenum Narrow : uint32 { A = 2147483647 };\ninterface IFoo { enum Min : int64 { MIN = -9223372036854775808 }; method foo(in Wide w); };",
+    )]);
+    let files = compiler
+        .compile(Language::C, &Options::default())
+        .expect("input should compile");
+    let c = &files[0].contents;
+    assert!(
+        c.contains("typedef uint32_t Wide;\n#define Wide_LOW UINT32_C(1)\n#define Wide_HIGH UINT32_C(2147483648)\n"),
+        "{c}"
+    );
+    assert!(
+        c.contains("typedef uint32_t Narrow;\nenum {\n    Narrow_A = 2147483647,\n};"),
+        "{c}"
+    );
+    assert!(
+        c.contains("#define IFoo_Min_MIN (INT64_C(-9223372036854775807) - 1)\n"),
+        "{c}"
+    );
+}
+
+#[test]
+fn bounds_output_strings() {
+    let compiler = compiler(&[(
+        "virtual/IFoo.idl",
+        "interface IFoo { method name(out string<16> name); };",
+    )]);
+    let skeleton = Options {
+        skeleton: true,
+        ..Options::default()
+    };
+
+    let files = compiler
+        .compile(Language::C, &skeleton)
+        .expect("input should compile");
+    let c = &files[0].contents;
+    assert!(
+        c.contains("if (name_len > 16) { return Object_ERROR_INVALID; }"),
+        "{c}"
+    );
+
+    let files = compiler
+        .compile(Language::CPP, &skeleton)
+        .expect("input should compile");
+    let cpp = &files[0].contents;
+    assert!(
+        cpp.contains("if (name_str.size() > 16) { return Object_ERROR_INVALID; }"),
+        "{cpp}"
+    );
+
+    let files = compiler
+        .compile(Language::Rust, &Options::default())
+        .expect("input should compile");
+    let rust = &files[0].contents;
+    assert!(rust.contains("if r#name.len() > 16 {"), "{rust}");
+}
+
+#[test]
+fn bundles_enums_like_primitives() {
+    let idl = |ty: &str| {
+        format!(
+            "enum Mode : uint16 {{ OFF, ON }};\ninterface IFoo {{\n  method g(in {ty} a, in {ty} b, out {ty} c);\n  method h(in uint32 x, in {ty} m, out {ty} c, out uint8 d);\n}};"
+        )
+    };
+    let layout = |ty: &str| {
+        let compiler = compiler(&[("virtual/IFoo.idl", &idl(ty))]);
+        let skeleton = Options {
+            skeleton: true,
+            ..Options::default()
+        };
+        let files = compiler
+            .compile(Language::C, &skeleton)
+            .expect("input should compile");
+        let mut layout = files[0]
+            .contents
+            .lines()
+            .filter(|line| line.contains("ObjectCounts_pack("))
+            .map(str::to_string)
+            .collect::<Vec<_>>();
+
+        let files = compiler
+            .compile(Language::Markdown, &Options::default())
+            .expect("input should compile");
+        let foo = files
+            .iter()
+            .find(|f| f.path.ends_with("IFoo.md"))
+            .expect("IFoo should be documented");
+        layout.extend(
+            foo.contents
+                .lines()
+                .filter(|line| line.contains("bundled at offset"))
+                .filter_map(|line| line.rsplit('|').nth(1).map(str::to_string)),
+        );
+        layout
+    };
+
+    let primitive = layout("uint16");
+    assert!(
+        primitive
+            .iter()
+            .any(|line| line.contains("ObjectCounts_pack(1, 1, 0, 0) || a[0].b.size != 6")),
+        "{primitive:?}"
+    );
+    assert!(
+        primitive
+            .iter()
+            .any(|line| line.contains("output buffer 0 (bundled at offset 2)")),
+        "{primitive:?}"
+    );
+    assert_eq!(layout("Mode"), primitive);
+}
@@ -0,0 +1,118 @@
+// Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
+// SPDX-License-Identifier: BSD-3-Clause
+
+#![cfg(unix)]
+
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+fn unique_temp_dir(label: &str) -> PathBuf {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .expect("clock should be monotonic since epoch")
+        .as_nanos();
+    let dir = std::env::temp_dir().join(format!(
+        "idlc-plugin-{label}-{}-{nanos}",
+        std::process::id()
+    ));
+    std::fs::create_dir_all(&dir).expect("temp dir should be creatable");
+    dir
+}
+
+/// Creates a plugin saving its request next to it and replying `response`.
+fn plugin(dir: &Path, response: &str) -> PathBuf {
+    let path = dir.join("idlc-gen-test");
+    std::fs::write(
+        &path,
+        format!(
+            "#!/bin/sh\ncat > \"{}\"\nprintf '%s' '{response}'\n",
+            dir.join("request.json").display()
+        ),
+    )
+    .expect("plugin should be writable");
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755))
+        .expect("plugin should be executable");
+    path
+}
+
+fn run_idlc(dir: &Path, plugin: &Path, extra: &[&str]) -> Output {
+    let input = dir.join("plugin.idl");
+    std::fs::write(&input, "interface ITest {\n  method ping();\n};\n")
+        .expect("input idl should be writable");
+    let out = dir.join("out");
+    std::fs::create_dir_all(&out).expect("output dir should be creatable");
+
+    Command::new(PathBuf::from(env!("CARGO_BIN_EXE_idlc")))
+        .arg(&input)
+        .arg("--plugin")
+        .arg(plugin)
+        .arg("-o")
+        .arg(&out)
+        .args(extra)
+        .output()
+        .expect("idlc should execute")
+}
+
+#[test]
+fn writes_plugin_output() {
+    let dir = unique_temp_dir("ok");
+    let plugin = plugin(
+        &dir,
+        r#"{"files":[["ITest.txt","ping"],["nested/ITest.txt","pong"]]}"#,
+    );
+
+    let output = run_idlc(
+        &dir,
+        &plugin,
+        &["--plugin-opt", "flavor=test", "--bundle-params-by-size"],
+    );
+    assert!(
+        output.status.success(),
+        "stderr:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let out = dir.join("out");
+    assert_eq!(
+        std::fs::read_to_string(out.join("ITest.txt")).unwrap(),
+        "ping"
+    );
+    assert_eq!(
+        std::fs::read_to_string(out.join("nested/ITest.txt")).unwrap(),
+        "pong"
+    );
+
+    let request: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(dir.join("request.json")).unwrap())
+            .expect("request should be valid json");
+    assert_eq!(request["protocol"], idlc_plugin::PROTOCOL_VERSION);
+    assert_eq!(request["parameters"], serde_json::json!(["flavor=test"]));
+    assert_eq!(request["bundle_params_by_size"], true);
+    assert!(request["mir"]["nodes"].is_array());
+}
+
+#[test]
+fn reports_plugin_error() {
+    let dir = unique_temp_dir("error");
+    let plugin = plugin(&dir, r#"{"error":"unsupported IDL"}"#);
+
+    let output = run_idlc(&dir, &plugin, &[]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(1), "stderr:\n{stderr}");
+    assert!(
+        stderr.contains("error[E0018]") && stderr.contains("unsupported IDL"),
+        "stderr:\n{stderr}"
+    );
+}
+
+#[test]
+fn rejects_paths_outside_output() {
+    let dir = unique_temp_dir("escape");
+    let plugin = plugin(&dir, r#"{"files":[["../escaped.txt","x"]]}"#);
+
+    let output = run_idlc(&dir, &plugin, &[]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(1), "stderr:\n{stderr}");
+    assert!(!dir.join("escaped.txt").exists());
+}
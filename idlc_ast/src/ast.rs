@@ -4,6 +4,7 @@
 use std::{num::NonZeroU16, path::PathBuf, rc::Rc, str::FromStr};
 
 use crate::Error;
+use serde::{Deserialize, Serialize};
 
 /// Maximum allowed size for a struct array [`u16::MAX`]
 pub type Count = NonZeroU16;
@@ -28,6 +29,10 @@ pub enum Node {
     Const(Const),
     /// Denotes a structure with arbitrary amount of fields.
     Struct(Struct),
+    /// Denotes an `enum <ident> : <type> { <variant> [= <val>], ... };` decl.
+    Enum(Enum),
+    /// Denotes a `typedef <type> <ident>;` or `using <ident> = <type>;` decl.
+    Typedef(Typedef),
     /// Denotes an interface with arbitrary amount of sub nodes,
     ///
     /// These subnodes are limited to what [`InterfaceNode`] defines and doesn't
@@ -40,6 +45,8 @@ impl Node {
         match self {
             Self::Const(c) => Some(&c.ident),
             Self::Struct(s) => Some(&s.ident),
+            Self::Enum(e) => Some(&e.ident),
+            Self::Typedef(t) => Some(&t.ident),
             Self::Interface(i) => Some(&i.ident),
             _ => None,
         }
@@ -51,6 +58,8 @@ impl Node {
             Self::Include(_) => "include",
             Self::Const(_) => "const",
             Self::Struct(_) => "struct",
+            Self::Enum(_) => "enum",
+            Self::Typedef(_) => "typedef",
             Self::Interface(_) => "interface",
         }
     }
@@ -58,6 +67,8 @@ impl Node {
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Struct {
+    pub doc: Option<Documentation>,
+    pub deprecated: Option<Deprecation>,
     pub ident: Ident,
     pub fields: Vec<StructField>,
 }
@@ -66,29 +77,96 @@ impl Struct {
     #[must_use]
     pub fn new_object(ident: &Ident) -> Self {
         Self {
+            doc: None,
+            deprecated: None,
             ident: ident.clone(),
             fields: vec![
                 StructField {
+                    doc: None,
                     ident: Ident::new_without_span("invoke".to_string()),
-                    val: (
-                        Type::Primitive(Primitive::Uint64),
-                        NonZeroU16::new(1).unwrap(),
-                    ),
+                    val: (Type::Primitive(Primitive::Uint64), ArraySize::ONE),
                 },
                 StructField {
+                    doc: None,
                     ident: Ident::new_without_span("context".to_string()),
-                    val: (
-                        Type::Primitive(Primitive::Uint64),
-                        NonZeroU16::new(1).unwrap(),
-                    ),
+                    val: (Type::Primitive(Primitive::Uint64), ArraySize::ONE),
                 },
             ],
         }
     }
 }
 
+/// Alias of a type, `typedef uint8[32] Sha256;` and `using Sha256 = uint8[32];`
+/// are the same declaration.
+///
+/// Uses of the alias are resolved to `val` so the wire layout stays the one of
+/// the aliased type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Typedef {
+    pub doc: Option<Documentation>,
+    pub ident: Ident,
+    pub val: (Type, ArraySize),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Enum {
+    pub doc: Option<Documentation>,
+    pub ident: Ident,
+    /// Underlying integer type, enums are sent over the wire as this type.
+    pub r#type: Primitive,
+    pub variants: Vec<EnumVariant>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnumVariant {
+    pub ident: Ident,
+    /// Variants without an explicit value follow the previous variant, the
+    /// first one starts at 0.
+    pub value: Option<String>,
+}
+
+impl Enum {
+    /// Resolves the value of every variant.
+    ///
+    /// Returns the first variant whose value is not an integer or doesn't fit
+    /// in the underlying type.
+    pub fn values(&self) -> Result<Vec<i128>, &EnumVariant> {
+        let range = self.r#type.range().ok_or(&self.variants[0])?;
+        let mut next = 0;
+        let mut values = Vec::with_capacity(self.variants.len());
+        for variant in &self.variants {
+            let value = match &variant.value {
+                Some(value) => parse_integer(value).ok_or(variant)?,
+                None => next,
+            };
+            if !range.contains(&value) {
+                return Err(variant);
+            }
+            values.push(value);
+            next = value + 1;
+        }
+        Ok(values)
+    }
+}
+
+/// Parses a decimal or hexadecimal integer literal as accepted by the grammar.
+#[must_use]
+pub fn parse_integer(value: &str) -> Option<i128> {
+    let value = value.trim();
+    let (negative, value) = value
+        .strip_prefix('-')
+        .map_or((false, value), |value| (true, value));
+    let value = match value.strip_prefix("0x") {
+        Some(hex) => i128::from_str_radix(hex, 16).ok()?,
+        None => value.parse::<i128>().ok()?,
+    };
+    Some(if negative { -value } else { value })
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Interface {
+    pub doc: Option<Documentation>,
+    pub deprecated: Option<Deprecation>,
     pub ident: Ident,
     pub base: Option<Ident>,
     pub nodes: Vec<InterfaceNode>,
@@ -98,11 +176,27 @@ pub struct Interface {
 #[repr(transparent)]
 pub struct Documentation(pub String);
 
+/// Denotes a `#[deprecated]` or `#[deprecated = "<note>"]` attribute.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Deprecation {
+    pub note: Option<String>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum InterfaceNode {
     Const(Const),
     Function(Function),
-    Error(Ident),
+    Error(ErrorCode),
+    Enum(Enum),
+}
+
+/// Denotes an `error <ident>;` decl, its value is assigned in the MIR unless
+/// pinned with `error <ident> = <value>;`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorCode {
+    pub doc: Option<Documentation>,
+    pub ident: Ident,
+    pub value: Option<i32>,
 }
 
 // The #[derive(Ord)] produces a lexicographic ordering based on the
@@ -110,7 +204,7 @@ pub enum InterfaceNode {
 // 1. Compare major
 // 2. If major is equal, compare minor
 // 3. If both are equal, the values are equal
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct APIVersion {
     pub major: u16,
     pub minor: u16,
@@ -175,9 +269,12 @@ impl FromStr for APIVersion {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum FunctionAttribute {
     Version(APIVersion),
+    /// Op-code pinned with `#[opcode = N]`, instead of the next free one.
+    OpCode(u32),
+    Deprecated(Deprecation),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -190,9 +287,107 @@ pub struct Function {
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Const {
+    pub doc: Option<Documentation>,
     pub ident: Ident,
     pub r#type: Primitive,
-    pub value: String,
+    pub value: Expr,
+}
+
+/// Value of a constant, evaluated once the constants of every file are known.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expr {
+    /// Integer or float literal as written, negative literals included.
+    Literal(String),
+    /// Another constant, `IFoo::NAME` names one declared in interface `IFoo`.
+    Const(Ident),
+    Unary(UnaryOp, Box<Expr>),
+    Binary(BinaryOp, Box<Expr>, Box<Expr>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnaryOp {
+    Neg,
+    BitNot,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Shl,
+    Shr,
+    BitOr,
+    BitAnd,
+}
+
+impl BinaryOp {
+    /// Binding strength of the operator, as in C.
+    #[must_use]
+    pub const fn precedence(self) -> u8 {
+        match self {
+            Self::BitOr => 1,
+            Self::BitAnd => 2,
+            Self::Shl | Self::Shr => 3,
+            Self::Add | Self::Sub => 4,
+            Self::Mul | Self::Div => 5,
+        }
+    }
+}
+
+impl std::fmt::Display for UnaryOp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Neg => "-",
+            Self::BitNot => "~",
+        })
+    }
+}
+
+impl std::fmt::Display for BinaryOp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Add => "+",
+            Self::Sub => "-",
+            Self::Mul => "*",
+            Self::Div => "/",
+            Self::Shl => "<<",
+            Self::Shr => ">>",
+            Self::BitOr => "|",
+            Self::BitAnd => "&",
+        })
+    }
+}
+
+impl std::fmt::Display for Expr {
+    /// Writes the expression back as IDL, with the parentheses it needs.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Literal(literal) => f.write_str(literal),
+            Self::Const(ident) => write!(f, "{ident}"),
+            Self::Unary(op, operand) => match operand.as_ref() {
+                Self::Binary(..) => write!(f, "{op}({operand})"),
+                _ => write!(f, "{op}{operand}"),
+            },
+            Self::Binary(op, lhs, rhs) => {
+                let operand = |f: &mut std::fmt::Formatter<'_>, expr: &Self, right: bool| match expr
+                {
+                    // Operators are left associative.
+                    Self::Binary(inner, ..)
+                        if inner.precedence() < op.precedence()
+                            || (right && inner.precedence() == op.precedence()) =>
+                    {
+                        write!(f, "({expr})")
+                    }
+                    _ => write!(f, "{expr}"),
+                };
+                operand(f, lhs, false)?;
+                write!(f, " {op} ")?;
+                operand(f, rhs, true)
+            }
+        }
+    }
 }
 
 impl Const {
@@ -201,14 +396,36 @@ impl Const {
         &self.r#type
     }
     #[must_use]
-    pub const fn value(&self) -> &String {
+    pub const fn value(&self) -> &Expr {
         &self.value
     }
 }
 
+/// Number of elements of a bounded array.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArraySize {
+    Count(Count),
+    /// Integer constant, resolved once the constants of every file are known.
+    Const(Ident),
+}
+
+impl ArraySize {
+    /// Size of the fields and typedefs which aren't arrays.
+    pub const ONE: Self = Self::Count(Count::MIN);
+}
+
+impl std::fmt::Display for ArraySize {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Count(count) => write!(f, "{count}"),
+            Self::Const(ident) => write!(f, "{ident}"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ParamTypeIn {
-    Array(Type, Option<Count>),
+    Array(Type, Option<ArraySize>),
     Value(Type),
 }
 
@@ -223,7 +440,7 @@ impl AsRef<Type> for ParamTypeIn {
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ParamTypeOut {
-    Array(Type, Option<Count>),
+    Array(Type, Option<ArraySize>),
     Reference(Type),
 }
 
@@ -238,8 +455,18 @@ impl AsRef<Type> for ParamTypeOut {
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Param {
-    In { r#type: ParamTypeIn, ident: Ident },
-    Out { r#type: ParamTypeOut, ident: Ident },
+    In {
+        r#type: ParamTypeIn,
+        ident: Ident,
+        /// Maximum number of elements of a buffer or unbounded array, given
+        /// with `[<=N]` or `#[max_len = N]`.
+        max_len: Option<ArraySize>,
+    },
+    Out {
+        r#type: ParamTypeOut,
+        ident: Ident,
+        max_len: Option<ArraySize>,
+    },
 }
 
 impl Param {
@@ -247,8 +474,33 @@ impl Param {
     #[must_use]
     pub const fn ident(&self) -> &Ident {
         match self {
-            Self::In { r#type: _, ident } => ident,
-            Self::Out { r#type: _, ident } => ident,
+            Self::In { ident, .. } | Self::Out { ident, .. } => ident,
+        }
+    }
+
+    /// Maximum number of elements of buffer and unbounded array parameters.
+    #[inline]
+    #[must_use]
+    pub const fn max_len(&self) -> Option<&ArraySize> {
+        match self {
+            Self::In { max_len, .. } | Self::Out { max_len, .. } => max_len.as_ref(),
+        }
+    }
+
+    /// Size of bounded array parameters.
+    #[inline]
+    #[must_use]
+    pub const fn array_size(&self) -> Option<&ArraySize> {
+        match self {
+            Self::In {
+                r#type: ParamTypeIn::Array(_, size),
+                ..
+            }
+            | Self::Out {
+                r#type: ParamTypeOut::Array(_, size),
+                ..
+            } => size.as_ref(),
+            _ => None,
         }
     }
 }
@@ -256,21 +508,22 @@ impl Param {
 impl AsRef<Type> for Param {
     fn as_ref(&self) -> &Type {
         match self {
-            Self::In { r#type, ident: _ } => r#type.as_ref(),
-            Self::Out { r#type, ident: _ } => r#type.as_ref(),
+            Self::In { r#type, .. } => r#type.as_ref(),
+            Self::Out { r#type, .. } => r#type.as_ref(),
         }
     }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct StructField {
+    pub doc: Option<Documentation>,
     pub ident: Ident,
-    pub val: (Type, Count),
+    pub val: (Type, ArraySize),
 }
 
 impl StructField {
     #[must_use]
-    pub const fn r#type(&self) -> &(Type, Count) {
+    pub const fn r#type(&self) -> &(Type, ArraySize) {
         &self.val
     }
 }
@@ -278,6 +531,8 @@ impl StructField {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Type {
     UntypedBuffer,
+    /// UTF-8 string of at most the given number of bytes, sent as a buffer.
+    String(Option<ArraySize>),
     Primitive(Primitive),
     Interface,
     Custom(Ident),
@@ -306,13 +561,15 @@ pub enum Primitive {
     Int64,
     Float32,
     Float64,
+    Bool,
+    Char,
 }
 
 impl Primitive {
     #[must_use]
     pub const fn size(self) -> usize {
         match self {
-            Self::Uint8 | Self::Int8 => 1,
+            Self::Uint8 | Self::Int8 | Self::Bool | Self::Char => 1,
             Self::Uint16 | Self::Int16 => 2,
             Self::Uint32 | Self::Int32 | Self::Float32 => 4,
             Self::Uint64 | Self::Int64 | Self::Float64 => 8,
@@ -325,6 +582,22 @@ impl Primitive {
         self.size()
     }
 
+    /// Range of values representable by integer types.
+    #[must_use]
+    pub const fn range(self) -> Option<std::ops::RangeInclusive<i128>> {
+        Some(match self {
+            Self::Uint8 => 0..=u8::MAX as i128,
+            Self::Uint16 => 0..=u16::MAX as i128,
+            Self::Uint32 => 0..=u32::MAX as i128,
+            Self::Uint64 => 0..=u64::MAX as i128,
+            Self::Int8 => i8::MIN as i128..=i8::MAX as i128,
+            Self::Int16 => i16::MIN as i128..=i16::MAX as i128,
+            Self::Int32 => i32::MIN as i128..=i32::MAX as i128,
+            Self::Int64 => i64::MIN as i128..=i64::MAX as i128,
+            Self::Float32 | Self::Float64 | Self::Bool | Self::Char => return None,
+        })
+    }
+
     /// Ensures the type is checked for range
     pub(crate) fn new(r#type: &str, value: &str) -> Result<Self, Error> {
         let value = value.trim();
@@ -384,6 +657,25 @@ impl Primitive {
     }
 }
 
+impl std::fmt::Display for Primitive {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Uint8 => "uint8",
+            Self::Uint16 => "uint16",
+            Self::Uint32 => "uint32",
+            Self::Uint64 => "uint64",
+            Self::Int8 => "int8",
+            Self::Int16 => "int16",
+            Self::Int32 => "int32",
+            Self::Int64 => "int64",
+            Self::Float32 => "float32",
+            Self::Float64 => "float64",
+            Self::Bool => "bool",
+            Self::Char => "char",
+        })
+    }
+}
+
 impl TryFrom<&str> for Primitive {
     type Error = Error;
 
@@ -399,13 +691,15 @@ impl TryFrom<&str> for Primitive {
             "int64" => Ok(Self::Int64),
             "float32" => Ok(Self::Float32),
             "float64" => Ok(Self::Float64),
+            "bool" => Ok(Self::Bool),
+            "char" => Ok(Self::Char),
             _ => Err(Error::UnknownPrimitiveType(value.to_string())),
         }
     }
 }
 
 /// Identifiers are utf-8 strings with a span.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Ident {
     pub span: Span,
     pub ident: String,
@@ -463,8 +757,14 @@ impl std::ops::Deref for Ident {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Span {
     pub start: usize,
     pub end: usize,
 }
+
+impl From<Span> for std::ops::Range<usize> {
+    fn from(span: Span) -> Self {
+        span.start..span.end
+    }
+}
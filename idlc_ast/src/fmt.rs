@@ -0,0 +1,616 @@
+// Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Canonical formatting of IDL sources.
+//!
+//! The source is re-emitted from its PST so that comments and documentation
+//! blocks are kept:
+//! - two spaces of indentation, `{` on the line of the declaration,
+//! - one field, variant or member per line, struct field names aligned,
+//! - attributes on their own line above the declaration,
+//! - one parameter per line for methods wider than [`MAX_WIDTH`],
+//! - trailing comments of consecutive lines aligned,
+//! - at most one blank line in a row, and one around multi-line declarations.
+//!
+//! Comments which aren't between two declarations are moved to the end of the
+//! declaration they were in.
+
+use pest::iterators::Pair;
+
+use crate::pst::{parse_to_pst, Error, Rule};
+
+const INDENT: &str = "  ";
+
+/// Width past which methods are split with one parameter per line.
+pub const MAX_WIDTH: usize = 100;
+
+/// Returns `source` formatted canonically.
+pub fn format(source: &str) -> Result<String, Error> {
+    let idl = parse_to_pst(source)?
+        .next()
+        .expect("`idl` is the root of the grammar");
+
+    let mut formatter = Formatter {
+        source,
+        lines: Vec::new(),
+    };
+    formatter.block(idl.into_inner().collect(), 0, 0, true);
+    let formatted = formatter.render();
+
+    // Guards against losing anything while re-emitting the source.
+    parse_to_pst(&formatted)?;
+    Ok(formatted)
+}
+
+enum Line {
+    Blank,
+    Code {
+        depth: usize,
+        code: String,
+        comment: Option<String>,
+    },
+    Comment {
+        depth: usize,
+        text: String,
+    },
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Include,
+    SingleLine,
+    MultiLine,
+}
+
+struct Formatter<'a> {
+    source: &'a str,
+    lines: Vec<Line>,
+}
+
+impl Formatter<'_> {
+    /// Emits the declarations and comments of a block, `start` being the end
+    /// of whatever precedes them in the source.
+    fn block(&mut self, pairs: Vec<Pair<'_, Rule>>, depth: usize, start: usize, top_level: bool) {
+        // Struct fields are the only members of their block, the widest type
+        // sets the column of the names.
+        let width = pairs
+            .iter()
+            .filter(|pair| pair.as_rule() == Rule::struct_field)
+            .map(|pair| field_text(pair.clone()).0.len())
+            .max()
+            .unwrap_or_default();
+        let first = self.lines.len();
+        let mut previous_end = start;
+        let mut previous_kind = None;
+        for pair in pairs {
+            let span = pair.as_span();
+            let between = &self.source[previous_end..span.start()];
+            let is_first = self.lines.len() == first;
+            previous_end = span.end();
+
+            if pair.as_rule() == Rule::EOI {
+                continue;
+            }
+            if pair.as_rule() == Rule::COMMENT {
+                if !between.contains('\n') && !pair.as_str().contains('\n') {
+                    if let Some(Line::Code { .. }) = self.lines.last() {
+                        self.trailing(pair.as_str());
+                        continue;
+                    }
+                }
+                let blank = blank_line(between)
+                    || (top_level && previous_kind.is_some_and(|kind| kind != Kind::SingleLine));
+                if blank && !is_first {
+                    self.blank();
+                }
+                self.lines.push(Line::Comment {
+                    depth,
+                    text: pair.as_str().to_string(),
+                });
+                previous_kind = None;
+                continue;
+            }
+
+            let kind = match pair.as_rule() {
+                Rule::include => Kind::Include,
+                Rule::r#struct | Rule::r#enum | Rule::interface => Kind::MultiLine,
+                _ => Kind::SingleLine,
+            };
+            let after_comment = matches!(self.lines.last(), Some(Line::Comment { .. }));
+            let blank = blank_line(between)
+                || (top_level
+                    && match previous_kind {
+                        Some(Kind::Include) => kind != Kind::Include,
+                        Some(Kind::MultiLine) => true,
+                        Some(Kind::SingleLine) => kind == Kind::MultiLine,
+                        None => kind == Kind::MultiLine && !after_comment,
+                    });
+            if blank && !is_first {
+                self.blank();
+            }
+            previous_kind = Some(kind);
+
+            if pair.as_rule() == Rule::struct_field {
+                self.field(pair, depth, width);
+            } else {
+                self.declaration(pair, depth);
+            }
+        }
+    }
+
+    fn declaration(&mut self, pair: Pair<'_, Rule>, depth: usize) {
+        let rule = pair.as_rule();
+        if matches!(rule, Rule::r#struct | Rule::r#enum | Rule::interface) {
+            return self.aggregate(pair, depth);
+        }
+        if rule == Rule::function {
+            return self.function(pair, depth);
+        }
+
+        let comments = nested_comments(&pair);
+        let mut inner = pair
+            .into_inner()
+            .filter(|pair| pair.as_rule() != Rule::COMMENT);
+        let code = match rule {
+            Rule::include => {
+                let path = inner.next().expect("includes have a path");
+                format!("include \"{}\"", path.as_str())
+            }
+            Rule::r#const => {
+                let ty = inner.nth(1).expect("consts have a type");
+                let ident = inner.next().expect("consts have a name");
+                let value = inner.next().expect("consts have a value");
+                format!(
+                    "const {} {} = {};",
+                    ty.as_str(),
+                    ident.as_str(),
+                    expr(value)
+                )
+            }
+            Rule::typedef => {
+                let mut ty = inner
+                    .nth(1)
+                    .expect("typedefs have a type")
+                    .as_str()
+                    .to_string();
+                let mut ident = inner.next().expect("typedefs have a name");
+                if ident.as_rule() == Rule::bounded_array {
+                    ty.push_str(&array(ident));
+                    ident = inner.next().expect("typedefs have a name");
+                }
+                format!("typedef {ty} {};", ident.as_str())
+            }
+            Rule::using => {
+                let ident = inner.nth(1).expect("typedefs have a name");
+                let mut ty = inner
+                    .next()
+                    .expect("typedefs have a type")
+                    .as_str()
+                    .to_string();
+                if let Some(size) = inner.next() {
+                    ty.push_str(&array(size));
+                }
+                format!("using {} = {ty};", ident.as_str())
+            }
+            Rule::error => {
+                let ident = inner.nth(1).expect("errors have a name");
+                match inner.next() {
+                    Some(value) => format!("error {} = {};", ident.as_str(), value.as_str()),
+                    None => format!("error {};", ident.as_str()),
+                }
+            }
+            Rule::enum_variant => {
+                let ident = inner.next().expect("variants have a name");
+                match inner.next() {
+                    Some(value) => format!("{} = {},", ident.as_str(), value.as_str()),
+                    None => format!("{},", ident.as_str()),
+                }
+            }
+            r => unreachable!("Unexpected rule `{r:?}`"),
+        };
+        self.code(depth, code);
+        for comment in comments {
+            self.trailing(&comment);
+        }
+    }
+
+    fn aggregate(&mut self, pair: Pair<'_, Rule>, depth: usize) {
+        let rule = pair.as_rule();
+        let children: Vec<_> = pair.into_inner().collect();
+        let first_member = children
+            .iter()
+            .position(|child| {
+                matches!(
+                    child.as_rule(),
+                    Rule::struct_field
+                        | Rule::enum_variant
+                        | Rule::function
+                        | Rule::r#const
+                        | Rule::error
+                        | Rule::r#enum
+                )
+            })
+            .unwrap_or(children.len());
+        let (header, members) = children.split_at(first_member);
+        let mut idents = header
+            .iter()
+            .filter(|child| child.as_rule() != Rule::COMMENT)
+            .skip(1);
+        if let Some(keyword) = header.iter().find(|child| child.as_rule() != Rule::COMMENT) {
+            self.attributes(keyword.clone(), depth);
+        }
+        let code = match rule {
+            Rule::r#struct => {
+                let ident = idents.next().expect("structs have a name");
+                format!("struct {} {{", ident.as_str())
+            }
+            Rule::r#enum => {
+                let ident = idents.next().expect("enums have a name");
+                let ty = idents.next().expect("enums have a type");
+                format!("enum {} : {} {{", ident.as_str(), ty.as_str())
+            }
+            _ => {
+                let iname = idents.next().expect("interfaces have a name");
+                let mut names = iname
+                    .clone()
+                    .into_inner()
+                    .filter(|pair| pair.as_rule() == Rule::ident)
+                    .map(|ident| ident.as_str());
+                let mut code = format!("interface {}", names.next().unwrap_or_default());
+                if let Some(base) = names.next() {
+                    code.push_str(&format!(" : {base}"));
+                }
+                code + " {"
+            }
+        };
+        self.code(depth, code);
+
+        // Comments before the `{` stay on the line of the declaration.
+        let header_end = header
+            .iter()
+            .filter(|child| child.as_rule() != Rule::COMMENT)
+            .map(|child| child.as_span().end())
+            .max()
+            .expect("declarations have a keyword");
+        let brace = header_end
+            + self.source[header_end..]
+                .find('{')
+                .expect("declarations have a body");
+        let mut body = Vec::new();
+        for child in header {
+            if child.as_rule() != Rule::COMMENT {
+                for comment in nested_comments(child) {
+                    self.trailing(&comment);
+                }
+            } else if child.as_span().start() < brace {
+                self.trailing(child.as_str());
+            } else {
+                body.push(child.clone());
+            }
+        }
+        body.extend(members.iter().cloned());
+
+        self.block(body, depth + 1, brace + 1, false);
+        self.code(depth, "};".to_string());
+    }
+
+    /// Emits the attributes of a declaration keyword, one per line.
+    fn attributes(&mut self, keyword: Pair<'_, Rule>, depth: usize) {
+        for attribute in keyword.into_inner() {
+            let attribute = attribute
+                .into_inner()
+                .flat_map(Pair::into_inner)
+                .next()
+                .expect("attributes are supported");
+            let mut inner = attribute.clone().into_inner();
+            let code = match attribute.as_rule() {
+                Rule::method_version => {
+                    let version = inner.next().expect("versions have a value");
+                    format!("#[version = {}]", version.as_str())
+                }
+                Rule::method_opcode => {
+                    let value = inner.next().expect("op-codes have a value");
+                    format!("#[opcode = {}]", value.as_str())
+                }
+                _ => match inner.next() {
+                    Some(note) => format!("#[deprecated = \"{}\"]", note.as_str()),
+                    None => "#[deprecated]".to_string(),
+                },
+            };
+            self.code(depth, code);
+        }
+    }
+
+    fn function(&mut self, pair: Pair<'_, Rule>, depth: usize) {
+        let mut inner = pair.into_inner();
+        let keyword = inner.next().expect("methods start with a keyword");
+        self.attributes(keyword, depth);
+
+        // Comments stick to the parameter they follow.
+        let mut name = None;
+        let mut head = Vec::new();
+        let mut params: Vec<(String, Vec<String>)> = Vec::new();
+        for child in inner {
+            match child.as_rule() {
+                Rule::COMMENT => match params.last_mut() {
+                    Some((_, comments)) => comments.push(child.as_str().to_string()),
+                    None => head.push(child.as_str().to_string()),
+                },
+                Rule::param => {
+                    let comments = nested_comments(&child);
+                    params.push((param_text(child), comments));
+                }
+                _ => name = Some(child.as_str()),
+            }
+        }
+        let name = name.expect("methods have a name");
+
+        let single = format!(
+            "method {name}({});",
+            params
+                .iter()
+                .map(|(param, _)| param.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        let commented = params.iter().any(|(_, comments)| !comments.is_empty());
+        if INDENT.len() * depth + single.len() <= MAX_WIDTH && !commented {
+            self.code(depth, single);
+            for comment in head {
+                self.trailing(&comment);
+            }
+            return;
+        }
+
+        self.code(depth, format!("method {name}("));
+        for comment in head {
+            self.trailing(&comment);
+        }
+        let count = params.len();
+        for (i, (param, comments)) in params.into_iter().enumerate() {
+            let separator = if i + 1 == count { "" } else { "," };
+            self.code(depth + 1, format!("{param}{separator}"));
+            for comment in comments {
+                self.trailing(&comment);
+            }
+        }
+        self.code(depth, ");".to_string());
+    }
+
+    fn field(&mut self, pair: Pair<'_, Rule>, depth: usize, width: usize) {
+        let comments = nested_comments(&pair);
+        let (ty, ident) = field_text(pair);
+        self.code(depth, format!("{ty:width$} {ident};"));
+        for comment in comments {
+            self.trailing(&comment);
+        }
+    }
+
+    fn code(&mut self, depth: usize, code: String) {
+        self.lines.push(Line::Code {
+            depth,
+            code,
+            comment: None,
+        });
+    }
+
+    fn blank(&mut self) {
+        if !matches!(self.lines.last(), None | Some(Line::Blank)) {
+            self.lines.push(Line::Blank);
+        }
+    }
+
+    /// Appends `comment` to the last line, or below it if it spans several lines.
+    fn trailing(&mut self, comment: &str) {
+        let depth = match self.lines.last_mut() {
+            Some(Line::Code {
+                comment: trailing @ None,
+                ..
+            }) if !comment.contains('\n') => {
+                *trailing = Some(comment.to_string());
+                return;
+            }
+            Some(Line::Code {
+                depth,
+                comment: Some(trailing),
+                ..
+            }) if !comment.contains('\n') && !trailing.starts_with("//") => {
+                trailing.push(' ');
+                trailing.push_str(comment);
+                return;
+            }
+            Some(Line::Code { depth, .. } | Line::Comment { depth, .. }) => *depth,
+            _ => 0,
+        };
+        self.lines.push(Line::Comment {
+            depth,
+            text: comment.to_string(),
+        });
+    }
+
+    fn render(&self) -> String {
+        let mut out = String::new();
+        let mut i = 0;
+        while i < self.lines.len() {
+            match &self.lines[i] {
+                Line::Blank => out.push('\n'),
+                Line::Comment { depth, text } => out.push_str(&reindent(text, *depth)),
+                Line::Code { depth, .. } => {
+                    // Trailing comments are aligned across consecutive lines of
+                    // the same depth.
+                    let group: Vec<_> = self.lines[i..]
+                        .iter()
+                        .map_while(|line| match line {
+                            Line::Code {
+                                depth: d,
+                                code,
+                                comment,
+                            } if d == depth => Some((code, comment)),
+                            _ => None,
+                        })
+                        .collect();
+                    let column = group
+                        .iter()
+                        .filter(|(_, comment)| comment.is_some())
+                        .map(|(code, _)| code.len())
+                        .max()
+                        .unwrap_or_default();
+                    for (code, comment) in &group {
+                        out.push_str(&INDENT.repeat(*depth));
+                        match comment {
+                            Some(comment) => {
+                                out.push_str(&format!("{code:column$} {comment}"));
+                            }
+                            None => out.push_str(code),
+                        }
+                        out.push('\n');
+                    }
+                    i += group.len();
+                    continue;
+                }
+            }
+            i += 1;
+        }
+        while out.ends_with("\n\n") {
+            out.pop();
+        }
+        out
+    }
+}
+
+/// Comments nested anywhere in `pair`, in order.
+fn nested_comments(pair: &Pair<'_, Rule>) -> Vec<String> {
+    pair.clone()
+        .into_inner()
+        .flatten()
+        .filter(|pair| pair.as_rule() == Rule::COMMENT)
+        .map(|pair| pair.as_str().to_string())
+        .collect()
+}
+
+fn blank_line(between: &str) -> bool {
+    between.matches('\n').count() > 1
+}
+
+fn array(pair: Pair<'_, Rule>) -> String {
+    let prefix = match pair.as_rule() {
+        Rule::bounded_array => "",
+        Rule::max_len_array => "<=",
+        _ => return "[]".to_string(),
+    };
+    format!(
+        "[{prefix}{}]",
+        pair.into_inner()
+            .find(|pair| pair.as_rule() == Rule::array_size)
+            .expect("bounded arrays have a size")
+            .as_str()
+    )
+}
+
+/// Binary operators are spaced, unary ones and parentheses aren't.
+fn expr(pair: Pair<'_, Rule>) -> String {
+    pair.into_inner()
+        .filter(|pair| pair.as_rule() != Rule::COMMENT)
+        .map(|pair| match pair.as_rule() {
+            Rule::expr => format!("({})", expr(pair)),
+            Rule::neg | Rule::bit_not | Rule::value | Rule::const_ref => pair.as_str().to_string(),
+            _ => format!(" {} ", pair.as_str()),
+        })
+        .collect()
+}
+
+fn field_text(pair: Pair<'_, Rule>) -> (String, String) {
+    let mut inner = pair
+        .into_inner()
+        .filter(|pair| pair.as_rule() != Rule::COMMENT);
+    let mut ty = inner
+        .next()
+        .expect("fields have a type")
+        .as_str()
+        .to_string();
+    let mut ident = inner.next().expect("fields have a name");
+    if ident.as_rule() == Rule::bounded_array {
+        ty.push_str(&array(ident));
+        ident = inner.next().expect("fields have a name");
+    }
+    (ty, ident.as_str().to_string())
+}
+
+fn param_text(pair: Pair<'_, Rule>) -> String {
+    let mut inner = pair
+        .into_inner()
+        .filter(|pair| pair.as_rule() != Rule::COMMENT);
+    let mut mutability = inner.next().expect("params have a direction");
+    let mut attribute = String::new();
+    if mutability.as_rule() == Rule::param_attribute {
+        let size = mutability
+            .into_inner()
+            .flatten()
+            .find(|pair| pair.as_rule() == Rule::array_size)
+            .expect("maximum lengths have a size");
+        attribute = format!("#[max_len = {}] ", size.as_str());
+        mutability = inner.next().expect("params have a direction");
+    }
+    let ty = inner.next().expect("params have a type");
+    let ident = inner.next().expect("params have a name");
+
+    let ty_text = ty.as_str();
+    let mut parts = ty
+        .into_inner()
+        .filter(|pair| pair.as_rule() != Rule::COMMENT);
+    let ty = match parts.next() {
+        None => ty_text.trim().to_string(),
+        Some(array_pair)
+            if matches!(
+                array_pair.as_rule(),
+                Rule::bounded_array | Rule::unbounded_array | Rule::max_len_array
+            ) =>
+        {
+            format!("interface{}", array(array_pair))
+        }
+        Some(base) if base.as_rule() == Rule::string_type => {
+            match base
+                .into_inner()
+                .flatten()
+                .find(|pair| pair.as_rule() == Rule::array_size)
+            {
+                Some(size) => format!("string<{}>", size.as_str()),
+                None => "string".to_string(),
+            }
+        }
+        Some(base) => {
+            let mut ty = base.as_str().to_string();
+            if let Some(array_pair) = parts.next() {
+                ty.push_str(&array(array_pair));
+            }
+            ty
+        }
+    };
+    format!("{attribute}{} {ty} {}", mutability.as_str(), ident.as_str())
+}
+
+/// Indents every line of a comment, continuation lines of `/* */` blocks
+/// starting with `*` are aligned under the first one.
+fn reindent(text: &str, depth: usize) -> String {
+    let indent = INDENT.repeat(depth);
+    let mut lines = text.lines();
+    let mut out = format!("{indent}{}\n", lines.next().unwrap_or_default().trim());
+    let rest: Vec<_> = lines.collect();
+    let common = rest
+        .iter()
+        .filter(|line| !line.trim().is_empty() && !line.trim_start().starts_with('*'))
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or_default();
+    for line in rest {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            out.push('\n');
+        } else if trimmed.starts_with('*') {
+            out.push_str(&format!("{indent} {trimmed}\n"));
+        } else {
+            out.push_str(&format!("{indent}{}\n", line[common..].trim_end()));
+        }
+    }
+    out
+}
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: BSD-3-Clause
 
 pub mod ast;
+pub mod fmt;
 pub mod pst;
 #[cfg(test)]
 mod tests;
@@ -26,6 +27,7 @@ pub fn from_string<S: AsRef<str>>(
     s: S,
     allow_undefined_behavior: bool,
 ) -> Result<Ast, Error> {
+    idlc_errors::register_source(&root, s.as_ref());
     let nodes = pst::parse_to_ast(s.as_ref(), allow_undefined_behavior)?;
     Ok(Ast { tag: root, nodes })
 }
@@ -11,9 +11,9 @@ use pest_derive::Parser;
 
 // Import all AST types
 use super::ast::{
-    APIVersion, Const, Count, Documentation, Function, FunctionAttribute, Ident, Interface,
-    InterfaceNode, Node, Param, ParamTypeIn, ParamTypeOut, Primitive, Span, Struct, StructField,
-    Type,
+    APIVersion, ArraySize, BinaryOp, Const, Deprecation, Documentation, Enum, EnumVariant,
+    ErrorCode, Expr, Function, FunctionAttribute, Ident, Interface, InterfaceNode, Node, Param,
+    ParamTypeIn, ParamTypeOut, Primitive, Span, Struct, StructField, Type, Typedef, UnaryOp,
 };
 
 #[derive(thiserror::Error, Debug)]
@@ -28,11 +28,46 @@ pub enum Error {
     ParseIntError(#[from] std::num::ParseIntError),
     #[error("Cannot parse float")]
     ParseFloatError(#[from] std::num::ParseFloatError),
-    #[error("Documentation for this node doesn't exist yet")]
+    #[error("Comment isn't a documentation block")]
     UnsupportedDocumentation,
     #[error("Parsed float translates to infinite")]
     FloatIsInfinite,
+    #[error("{message}")]
+    OutOfRange {
+        code: &'static str,
+        message: String,
+        span: Span,
+    },
 }
+impl Error {
+    /// Converts the error into a diagnostic pointing into `file`.
+    #[must_use]
+    pub fn to_diagnostic(&self, file: &std::path::Path) -> idlc_errors::Diagnostic {
+        use idlc_errors::{codes, Diagnostic, Label};
+
+        match self {
+            Self::AstGenerationFailure(e) => {
+                let range = match e.location {
+                    pest::error::InputLocation::Pos(pos) => pos..pos,
+                    pest::error::InputLocation::Span((start, end)) => start..end,
+                };
+                Diagnostic::error(codes::PARSE_FAILURE, "Parsing failed")
+                    .with_file(file)
+                    .with_label(Label::primary(range, e.variant.message()))
+            }
+            Self::OutOfRange {
+                code,
+                message,
+                span,
+            } => Diagnostic::error(code, message)
+                .with_file(file)
+                .with_label(Label::primary(*span, "out of range")),
+            e => Diagnostic::error(codes::PARSE_FAILURE, format!("Parsing failed: {e}"))
+                .with_file(file),
+        }
+    }
+}
+
 impl From<pest::error::Error<Rule>> for Error {
     fn from(value: pest::error::Error<Rule>) -> Self {
         Self::AstGenerationFailure(Box::new(value))
@@ -86,11 +121,22 @@ impl InterfaceNode {
         allow_undefined_behavior: bool,
     ) -> Self {
         match pair.as_rule() {
-            Rule::error => Self::Error(Ident {
-                span: pair.as_span().into(),
-                ident: pair.into_inner().nth(1).unwrap().as_str().to_string(),
-            }),
-            Rule::r#const => Self::Const(parse_const(pair, allow_undefined_behavior)),
+            Rule::error => {
+                let span = pair.as_span().into();
+                let mut inner = pair.into_inner();
+                let ident = ast_unwrap!(inner.nth(1)).as_str().to_string();
+                // Ranges are checked by `check_pinned_values`.
+                let value = inner
+                    .next()
+                    .map(|value| ast_unwrap!(parse_pinned(value.as_str())));
+                Self::Error(ErrorCode {
+                    doc,
+                    ident: Ident { span, ident },
+                    value,
+                })
+            }
+            Rule::r#const => Self::Const(parse_const(pair, doc, allow_undefined_behavior)),
+            Rule::r#enum => Self::Enum(parse_enum(pair, doc)),
             Rule::function => {
                 let mut inner = pair.into_inner();
                 let mut attributes = Vec::new();
@@ -102,7 +148,11 @@ impl InterfaceNode {
 
                     let span = attribute.as_span();
                     let attr = FunctionAttribute::from(attribute);
-                    if attributes.contains(&attr) {
+                    let deprecated_twice = matches!(attr, FunctionAttribute::Deprecated(_))
+                        && attributes
+                            .iter()
+                            .any(|attr| matches!(attr, FunctionAttribute::Deprecated(_)));
+                    if deprecated_twice || attributes.contains(&attr) {
                         idlc_errors::unrecoverable!("Duplicate attribute at:\n`{span:#?}`");
                     } else {
                         attributes.push(attr);
@@ -143,6 +193,23 @@ impl<'a> From<Pair<'a, Rule>> for FunctionAttribute {
                     Err(e) => idlc_errors::unrecoverable!("Error for `{}`: {}", attr, e),
                 }
             }
+            attr if attr.starts_with("opcode") => {
+                let method_opcode = ast_unwrap!(attribute.into_inner().next());
+                debug_assert_eq!(method_opcode.as_rule(), Rule::method_opcode);
+                let value = ast_unwrap!(method_opcode.into_inner().next());
+                debug_assert_eq!(value.as_rule(), Rule::pinned_value);
+                // Ranges are checked by `check_pinned_values`.
+                Self::OpCode(ast_unwrap!(parse_pinned(value.as_str())))
+            }
+            attr if attr.starts_with("deprecated") => {
+                let deprecated = ast_unwrap!(attribute.into_inner().next());
+                debug_assert_eq!(deprecated.as_rule(), Rule::deprecated);
+                let note = deprecated
+                    .into_inner()
+                    .next()
+                    .map(|note| note.as_str().to_string());
+                Self::Deprecated(Deprecation { note })
+            }
             attr => {
                 idlc_errors::unrecoverable!("Unknown function attribute `{attr}`")
             }
@@ -150,20 +217,99 @@ impl<'a> From<Pair<'a, Rule>> for FunctionAttribute {
     }
 }
 
+/// Parses the attributes of a struct or interface keyword, only
+/// `#[deprecated]` applies to them.
+fn parse_deprecation(keyword: Pair<'_, Rule>) -> Option<Deprecation> {
+    let mut deprecation = None;
+    for attribute in keyword.into_inner() {
+        let span = attribute.as_span();
+        match FunctionAttribute::from(attribute) {
+            FunctionAttribute::Deprecated(_) if deprecation.is_some() => {
+                idlc_errors::unrecoverable!("Duplicate attribute at:\n`{span:#?}`")
+            }
+            FunctionAttribute::Deprecated(d) => deprecation = Some(d),
+            _ => {
+                idlc_errors::unrecoverable!("Attribute `{}` only applies to methods", span.as_str())
+            }
+        }
+    }
+    deprecation
+}
+
+/// Rejects the pinned op-codes and error values of `pairs` which don't fit in
+/// their type, so that building the AST can assume they do.
+fn check_pinned_values(pairs: &Pairs<'_, Rule>) -> Result<(), Error> {
+    use idlc_errors::codes;
+
+    for pair in pairs.clone().flatten() {
+        let (code, what, ty, fits): (_, _, _, fn(&str) -> bool) = match pair.as_rule() {
+            Rule::method_opcode => (codes::OP_CODE_OUT_OF_RANGE, "Op-code", "a uint32", |v| {
+                parse_pinned::<u32>(v).is_some()
+            }),
+            Rule::error => (
+                codes::ERROR_VALUE_OUT_OF_RANGE,
+                "Error value",
+                "an int32",
+                |v| parse_pinned::<i32>(v).is_some(),
+            ),
+            _ => continue,
+        };
+        let value = pair
+            .into_inner()
+            .find(|pair| pair.as_rule() == Rule::pinned_value);
+        if let Some(value) = value.filter(|value| !fits(value.as_str())) {
+            return Err(Error::OutOfRange {
+                code,
+                message: format!("{what} `{}` doesn't fit in {ty}", value.as_str()),
+                span: value.as_span().into(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Parses a `pinned_value`, [`None`] if it doesn't fit in `T`.
+fn parse_pinned<T: TryFrom<i128>>(value: &str) -> Option<T> {
+    crate::parse_integer(value).and_then(|value| T::try_from(value).ok())
+}
+
 impl<'a> From<Pair<'a, Rule>> for Param {
     fn from(value: Pair<'a, Rule>) -> Self {
-        let mut params = value.into_inner();
+        let span = value.as_span();
+        let mut params = value.into_inner().peekable();
+        let attribute = params.next_if(|pair| pair.as_rule() == Rule::param_attribute);
         let mutability = ast_unwrap!(params.next()).as_str();
         let r#type = ast_unwrap!(params.next());
         let ident = ast_unwrap!(params.next()).into();
+        let array = r#type
+            .clone()
+            .into_inner()
+            .find(|pair| pair.as_rule() == Rule::max_len_array);
+        let max_len = match (attribute, array) {
+            (Some(_), Some(_)) => {
+                idlc_errors::unrecoverable!("Duplicate maximum length at:\n`{span:#?}`")
+            }
+            (Some(attribute), None) => {
+                Some(ArraySize::from(ast_unwrap!(attribute.into_inner().next())))
+            }
+            (None, array) => array.map(ArraySize::from),
+        };
         match mutability {
             "in" => {
                 let r#type = ParamTypeIn::from(r#type);
-                Self::In { r#type, ident }
+                Self::In {
+                    r#type,
+                    ident,
+                    max_len,
+                }
             }
             "out" => {
                 let r#type = ParamTypeOut::from(r#type);
-                Self::Out { r#type, ident }
+                Self::Out {
+                    r#type,
+                    ident,
+                    max_len,
+                }
             }
             _ => unreachable!(),
         }
@@ -172,6 +318,14 @@ impl<'a> From<Pair<'a, Rule>> for Param {
 
 impl From<Pair<'_, Rule>> for Type {
     fn from(value: Pair<'_, Rule>) -> Self {
+        if value.as_rule() == Rule::string_type {
+            return Self::String(
+                value
+                    .into_inner()
+                    .find(|pair| pair.as_rule() == Rule::string_bound)
+                    .map(ArraySize::from),
+            );
+        }
         Primitive::try_from(value.as_str()).map_or_else(
             |_| {
                 if value.as_str() == "interface" {
@@ -201,11 +355,8 @@ impl From<Pair<'_, Rule>> for ParamTypeIn {
 
         if let Some(pair) = inner.next() {
             match pair.as_rule() {
-                Rule::unbounded_array => Self::Array(r#type, None),
-                Rule::bounded_array => {
-                    let array_len: Count = ast_unwrap!(pair.into_inner().as_str().parse());
-                    Self::Array(r#type, Some(array_len))
-                }
+                Rule::unbounded_array | Rule::max_len_array => Self::Array(r#type, None),
+                Rule::bounded_array => Self::Array(r#type, Some(ArraySize::from(pair))),
                 _ => unreachable!(),
             }
         } else {
@@ -226,11 +377,8 @@ impl From<Pair<'_, Rule>> for ParamTypeOut {
 
         if let Some(pair) = inner.next() {
             match pair.as_rule() {
-                Rule::unbounded_array => Self::Array(r#type, None),
-                Rule::bounded_array => {
-                    let array_len: Count = ast_unwrap!(pair.into_inner().as_str().parse());
-                    Self::Array(r#type, Some(array_len))
-                }
+                Rule::unbounded_array | Rule::max_len_array => Self::Array(r#type, None),
+                Rule::bounded_array => Self::Array(r#type, Some(ArraySize::from(pair))),
                 _ => unreachable!(),
             }
         } else {
@@ -239,6 +387,21 @@ impl From<Pair<'_, Rule>> for ParamTypeOut {
     }
 }
 
+impl From<Pair<'_, Rule>> for ArraySize {
+    fn from(rule: Pair<Rule>) -> Self {
+        debug_assert!(matches!(
+            rule.as_rule(),
+            Rule::bounded_array | Rule::max_len_array | Rule::max_len | Rule::string_bound
+        ));
+        let size = ast_unwrap!(ast_unwrap!(rule.into_inner().next()).into_inner().next());
+        match size.as_rule() {
+            Rule::count => Self::Count(ast_unwrap!(size.as_str().parse())),
+            Rule::const_ref => Self::Const(size.into()),
+            _ => unreachable!(),
+        }
+    }
+}
+
 impl<'a> TryFrom<Pair<'a, Rule>> for Documentation {
     type Error = Error;
     fn try_from(value: Pair<'a, Rule>) -> Result<Self, Self::Error> {
@@ -265,10 +428,12 @@ fn parse_include(pair: Pair<Rule>) -> Rc<Node> {
     Rc::new(Node::Include(PathBuf::from(path.as_str())))
 }
 
-fn parse_struct(pair: Pair<Rule>) -> Rc<Node> {
-    let mut struct_pst = pair.into_inner().skip(1);
+fn parse_struct(pair: Pair<Rule>, doc: Option<Documentation>) -> Rc<Node> {
+    let mut struct_pst = pair.into_inner();
+    let deprecated = parse_deprecation(ast_unwrap!(struct_pst.next()));
     let ident: Ident = ast_unwrap!(struct_pst.next()).into();
     let mut fields = Vec::<StructField>::new();
+    let mut comment: Option<Documentation> = None;
     for rule in struct_pst {
         match rule.as_rule() {
             Rule::struct_field => {
@@ -277,19 +442,19 @@ fn parse_struct(pair: Pair<Rule>) -> Rc<Node> {
                 let next = ast_unwrap!(iter.next());
                 let (elem, ident) = match next.as_rule() {
                     Rule::bounded_array => {
-                        let array_len: Count =
-                            ast_unwrap!(next.clone().into_inner().as_str().parse());
+                        let array_len = ArraySize::from(next.clone());
                         let ident = ast_unwrap!(iter.next()).as_str().to_string();
                         (array_len, ident)
                     }
                     Rule::ident => {
                         let ident = next.as_str().to_string();
-                        (unsafe { Count::new_unchecked(1) }, ident)
+                        (ArraySize::ONE, ident)
                     }
                     _ => unreachable!(),
                 };
 
                 fields.push(StructField {
+                    doc: comment.take(),
                     ident: Ident {
                         span: Span::from(next.as_span()),
                         ident,
@@ -298,38 +463,156 @@ fn parse_struct(pair: Pair<Rule>) -> Rc<Node> {
                 });
             }
             Rule::COMMENT => {
-                // Currently unsupported for structs due to varying styles
+                comment = Documentation::try_from(rule).ok();
+            }
+            r => unreachable!("Unknown rule `{r:?}`"),
+        }
+    }
+    Rc::new(Node::Struct(Struct {
+        doc,
+        deprecated,
+        ident,
+        fields,
+    }))
+}
+
+/// Parses both `typedef` and `using` declarations.
+fn parse_typedef(pair: Pair<Rule>, doc: Option<Documentation>) -> Rc<Node> {
+    let is_using = pair.as_rule() == Rule::using;
+    let mut inner = pair.into_inner().skip(1);
+    let mut ident = None;
+    if is_using {
+        ident = Some(ast_unwrap!(inner.next()).into());
+    }
+    let r#type = Type::from(ast_unwrap!(inner.next()));
+    let mut count = ArraySize::ONE;
+    for rule in inner {
+        match rule.as_rule() {
+            Rule::bounded_array => count = ArraySize::from(rule),
+            Rule::ident => ident = Some(rule.into()),
+            Rule::COMMENT => (),
+            r => unreachable!("Unknown rule `{r:?}`"),
+        }
+    }
+    Rc::new(Node::Typedef(Typedef {
+        doc,
+        ident: ast_unwrap!(ident),
+        val: (r#type, count),
+    }))
+}
+
+fn parse_enum(pair: Pair<Rule>, doc: Option<Documentation>) -> Enum {
+    let mut inner = pair.into_inner().skip(1);
+    let ident = ast_unwrap!(inner.next()).into();
+    let r#type = ast_unwrap!(Primitive::try_from(ast_unwrap!(inner.next()).as_str()));
+    let mut variants = Vec::new();
+    for rule in inner {
+        match rule.as_rule() {
+            Rule::enum_variant => {
+                let mut variant = rule.into_inner();
+                let ident = ast_unwrap!(variant.next()).into();
+                let value = variant.next().map(|value| value.as_str().to_string());
+                variants.push(EnumVariant { ident, value });
+            }
+            Rule::COMMENT => {
+                // Currently unsupported for enum variants
             }
             r => unreachable!("Unknown rule `{r:?}`"),
         }
     }
-    Rc::new(Node::Struct(Struct { ident, fields }))
+
+    Enum {
+        doc,
+        ident,
+        r#type,
+        variants,
+    }
 }
 
-fn parse_const(pair: Pair<Rule>, allow_undefined_behavior: bool) -> Const {
+fn parse_const(
+    pair: Pair<Rule>,
+    doc: Option<Documentation>,
+    allow_undefined_behavior: bool,
+) -> Const {
     let mut inner = pair.into_inner().skip(1);
 
     let ty = ast_unwrap!(inner.next()).as_str();
     let ident = ast_unwrap!(inner.next()).into();
-    let value = ast_unwrap!(inner.next()).as_str();
-    let primitive = if allow_undefined_behavior {
-        Primitive::try_from(ty).unwrap()
-    } else {
-        Primitive::new(ty, value).unwrap_or_else(|e| {
-            idlc_errors::unrecoverable!("'{value}' isn't in range for type '{ty}' [{e}]")
-        })
+    let value = parse_expr(ast_unwrap!(inner.next()).into_inner());
+    // Expressions are evaluated once the constants they refer to are known.
+    let primitive = match &value {
+        Expr::Literal(value) if !allow_undefined_behavior => Primitive::new(ty, value)
+            .unwrap_or_else(|e| {
+                idlc_errors::unrecoverable!("'{value}' isn't in range for type '{ty}' [{e}]")
+            }),
+        _ => Primitive::try_from(ty).unwrap(),
     };
 
     Const {
+        doc,
         ident,
         r#type: primitive,
-        value: value.to_string(),
+        value,
+    }
+}
+
+fn parse_expr(pairs: Pairs<Rule>) -> Expr {
+    use pest::pratt_parser::{Assoc, Op, PrattParser};
+
+    thread_local! {
+        static PRATT: PrattParser<Rule> = PrattParser::new()
+            .op(Op::infix(Rule::bit_or, Assoc::Left))
+            .op(Op::infix(Rule::bit_and, Assoc::Left))
+            .op(Op::infix(Rule::shl, Assoc::Left) | Op::infix(Rule::shr, Assoc::Left))
+            .op(Op::infix(Rule::add, Assoc::Left) | Op::infix(Rule::sub, Assoc::Left))
+            .op(Op::infix(Rule::mul, Assoc::Left) | Op::infix(Rule::div, Assoc::Left))
+            .op(Op::prefix(Rule::neg) | Op::prefix(Rule::bit_not));
     }
+
+    PRATT.with(|pratt| {
+        pratt
+            .map_primary(|primary| match primary.as_rule() {
+                Rule::value => Expr::Literal(primary.as_str().to_string()),
+                Rule::const_ref => Expr::Const(primary.into()),
+                Rule::expr => parse_expr(primary.into_inner()),
+                r => unreachable!("Unknown rule `{r:?}`"),
+            })
+            .map_prefix(|op, operand| match (op.as_rule(), operand) {
+                // `-1` is a literal rather than the negation of one, so that
+                // the smallest value of a type can be written.
+                (Rule::neg, Expr::Literal(literal)) if !literal.starts_with('-') => {
+                    Expr::Literal(format!("-{literal}"))
+                }
+                (Rule::neg, operand) => Expr::Unary(UnaryOp::Neg, Box::new(operand)),
+                (Rule::bit_not, operand) => Expr::Unary(UnaryOp::BitNot, Box::new(operand)),
+                (r, _) => unreachable!("Unknown rule `{r:?}`"),
+            })
+            .map_infix(|lhs, op, rhs| {
+                let op = match op.as_rule() {
+                    Rule::add => BinaryOp::Add,
+                    Rule::sub => BinaryOp::Sub,
+                    Rule::mul => BinaryOp::Mul,
+                    Rule::div => BinaryOp::Div,
+                    Rule::shl => BinaryOp::Shl,
+                    Rule::shr => BinaryOp::Shr,
+                    Rule::bit_or => BinaryOp::BitOr,
+                    Rule::bit_and => BinaryOp::BitAnd,
+                    r => unreachable!("Unknown rule `{r:?}`"),
+                };
+                Expr::Binary(op, Box::new(lhs), Box::new(rhs))
+            })
+            .parse(pairs.filter(|pair| pair.as_rule() != Rule::COMMENT))
+    })
 }
 
-fn parse_interface(pair: Pair<Rule>, allow_undefined_behavior: bool) -> Rc<Node> {
+fn parse_interface(
+    pair: Pair<Rule>,
+    doc: Option<Documentation>,
+    allow_undefined_behavior: bool,
+) -> Rc<Node> {
     let span = Span::from(pair.as_span());
-    let mut interface = pair.into_inner().skip(1);
+    let mut interface = pair.into_inner();
+    let deprecated = parse_deprecation(ast_unwrap!(interface.next()));
     let mut pairs = ast_unwrap!(interface.next()).into_inner();
     let ident = ast_unwrap!(pairs.next()).as_str().to_string();
     let base = pairs
@@ -344,7 +627,7 @@ fn parse_interface(pair: Pair<Rule>, allow_undefined_behavior: bool) -> Rc<Node>
     let mut comment: Option<Documentation> = None;
     for rule in interface {
         match rule.as_rule() {
-            Rule::r#const | Rule::function | Rule::error => {
+            Rule::r#const | Rule::function | Rule::error | Rule::r#enum => {
                 let node = InterfaceNode::new(comment, rule, allow_undefined_behavior);
                 comment = None;
                 iface_nodes.push(node);
@@ -356,6 +639,8 @@ fn parse_interface(pair: Pair<Rule>, allow_undefined_behavior: bool) -> Rc<Node>
         }
     }
     Rc::new(Node::Interface(Interface {
+        doc,
+        deprecated,
         ident: Ident { span, ident },
         base,
         nodes: iface_nodes,
@@ -368,17 +653,25 @@ pub fn parse_to_pst(idl_str: &str) -> Result<Pairs<'_, Rule>, Error> {
 
 pub fn parse_to_ast(input: &str, allow_undefined_behavior: bool) -> Result<Vec<Rc<Node>>, Error> {
     let mut pairs = parse_to_pst(input)?;
+    check_pinned_values(&pairs)?;
     let mut nodes = Vec::new();
 
+    // Documentation attaches to the declaration right after it.
+    let mut comment: Option<Documentation> = None;
     for p in pairs.next().unwrap().into_inner() {
+        let doc = comment.take();
         match p.as_rule() {
             Rule::include => nodes.push(parse_include(p)),
-            Rule::r#struct => nodes.push(parse_struct(p)),
+            Rule::r#struct => nodes.push(parse_struct(p, doc)),
+            Rule::r#enum => nodes.push(Rc::new(Node::Enum(parse_enum(p, doc)))),
+            Rule::typedef | Rule::using => nodes.push(parse_typedef(p, doc)),
             Rule::r#const => nodes.push(Rc::new(Node::Const(parse_const(
                 p,
+                doc,
                 allow_undefined_behavior,
             )))),
-            Rule::interface => nodes.push(parse_interface(p, allow_undefined_behavior)),
+            Rule::interface => nodes.push(parse_interface(p, doc, allow_undefined_behavior)),
+            Rule::COMMENT => comment = Documentation::try_from(p).ok(),
             Rule::EOI => (),
             _ => {}
         }
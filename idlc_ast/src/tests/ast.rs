@@ -94,3 +94,275 @@ fn duplicately_defined_function_attribute() {
     )
     .unwrap_err();
 }
+
+#[test]
+fn pinned_values() {
+    use crate::{FunctionAttribute, InterfaceNode, Node};
+
+    let ast = crate::from_string(
+        std::path::PathBuf::new(),
+        r"interface IFoo {
+        error ERROR_A = 0x20;
+        error ERROR_B;
+        #[opcode = 12]
+        method a();
+        method b();
+    };",
+        false,
+    )
+    .unwrap();
+    let Node::Interface(iface) = ast.nodes[0].as_ref() else {
+        panic!("expected an interface");
+    };
+    let [InterfaceNode::Error(a), InterfaceNode::Error(b), InterfaceNode::Function(f), InterfaceNode::Function(g)] =
+        iface.nodes.as_slice()
+    else {
+        panic!("unexpected nodes {:?}", iface.nodes);
+    };
+    assert_eq!(a.value, Some(0x20));
+    assert_eq!(b.value, None);
+    assert_eq!(f.attributes, [FunctionAttribute::OpCode(12)]);
+    assert!(g.attributes.is_empty());
+}
+
+#[test]
+fn deprecations() {
+    use crate::{Deprecation, FunctionAttribute, InterfaceNode, Node};
+
+    let ast = crate::from_string(
+        std::path::PathBuf::new(),
+        r#"#[deprecated]
+    struct S { uint32 a; };
+    #[deprecated = "use IBar instead"]
+    interface IFoo {
+        #[deprecated = "use b instead"]
+        method a();
+        method b();
+    };"#,
+        false,
+    )
+    .unwrap();
+    let [Node::Struct(s), Node::Interface(iface)] = [ast.nodes[0].as_ref(), ast.nodes[1].as_ref()]
+    else {
+        panic!("unexpected nodes {:?}", ast.nodes);
+    };
+    assert_eq!(s.deprecated, Some(Deprecation { note: None }));
+    assert_eq!(
+        iface.deprecated,
+        Some(Deprecation {
+            note: Some("use IBar instead".to_string())
+        })
+    );
+    let [InterfaceNode::Function(a), InterfaceNode::Function(b)] = iface.nodes.as_slice() else {
+        panic!("unexpected nodes {:?}", iface.nodes);
+    };
+    assert_eq!(
+        a.attributes,
+        [FunctionAttribute::Deprecated(Deprecation {
+            note: Some("use b instead".to_string())
+        })]
+    );
+    assert!(b.attributes.is_empty());
+}
+
+#[test]
+fn typedefs() {
+    use crate::{ArraySize, Count, Ident, Node, Primitive, Type, Typedef};
+
+    let ast = crate::from_string(
+        std::path::PathBuf::new(),
+        "typedef uint8[32] Sha256; using Handle = uint64; using Digests = Sha256[2];",
+        false,
+    )
+    .unwrap();
+    let typedefs: Vec<_> = ast
+        .nodes
+        .iter()
+        .map(|node| match node.as_ref() {
+            Node::Typedef(Typedef { ident, val, .. }) => (ident.to_string(), val.clone()),
+            node => panic!("unexpected node {node:?}"),
+        })
+        .collect();
+    let count = |n| ArraySize::Count(Count::new(n).unwrap());
+    assert_eq!(
+        typedefs,
+        [
+            (
+                "Sha256".to_string(),
+                (Type::Primitive(Primitive::Uint8), count(32))
+            ),
+            (
+                "Handle".to_string(),
+                (Type::Primitive(Primitive::Uint64), count(1))
+            ),
+            (
+                "Digests".to_string(),
+                (
+                    Type::Custom(Ident::new_without_span("Sha256".to_string())),
+                    count(2)
+                )
+            ),
+        ]
+    );
+}
+
+#[test]
+fn const_exprs() {
+    use crate::{ArraySize, BinaryOp, Expr, Ident, Node, UnaryOp};
+
+    let ast = crate::from_string(
+        std::path::PathBuf::new(),
+        "const uint32 A = 1 + 2 * (3 - IFoo::B) | ~C << 4;
+         const int8 MIN = -128;
+         const int8 NEG = -(MIN + 1);
+         struct S { uint8[A] key; };",
+        false,
+    )
+    .unwrap();
+    let literal = |l: &str| Box::new(Expr::Literal(l.to_string()));
+    let r#const = |c: &str| Box::new(Expr::Const(Ident::new_without_span(c.to_string())));
+    let values: Vec<_> = ast
+        .nodes
+        .iter()
+        .filter_map(|node| match node.as_ref() {
+            Node::Const(c) => Some(c.value.clone()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        values,
+        [
+            Expr::Binary(
+                BinaryOp::BitOr,
+                Box::new(Expr::Binary(
+                    BinaryOp::Add,
+                    literal("1"),
+                    Box::new(Expr::Binary(
+                        BinaryOp::Mul,
+                        literal("2"),
+                        Box::new(Expr::Binary(
+                            BinaryOp::Sub,
+                            literal("3"),
+                            r#const("IFoo::B")
+                        )),
+                    )),
+                )),
+                Box::new(Expr::Binary(
+                    BinaryOp::Shl,
+                    Box::new(Expr::Unary(UnaryOp::BitNot, r#const("C"))),
+                    literal("4"),
+                )),
+            ),
+            *literal("-128"),
+            Expr::Unary(
+                UnaryOp::Neg,
+                Box::new(Expr::Binary(BinaryOp::Add, r#const("MIN"), literal("1"))),
+            ),
+        ]
+    );
+    assert_eq!(values[0].to_string(), "1 + 2 * (3 - IFoo::B) | ~C << 4");
+    assert_eq!(values[2].to_string(), "-(MIN + 1)");
+
+    let Node::Struct(s) = ast.nodes[3].as_ref() else {
+        panic!("struct expected");
+    };
+    assert_eq!(
+        s.fields[0].val.1,
+        ArraySize::Const(Ident::new_without_span("A".to_string()))
+    );
+}
+
+#[test]
+#[should_panic = "only applies to methods"]
+fn opcode_on_struct() {
+    crate::from_string(
+        std::path::PathBuf::new(),
+        "#[opcode = 1] struct S { uint32 a; };",
+        false,
+    )
+    .unwrap_err();
+}
+
+#[test]
+fn error_value_out_of_range() {
+    let error = crate::from_string(
+        std::path::PathBuf::new(),
+        "interface IFoo { error ERROR_A = 0x80000000; };",
+        false,
+    )
+    .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Error value `0x80000000` doesn't fit in an int32"
+    );
+    let diagnostic = error.to_diagnostic(std::path::Path::new("IFoo.idl"));
+    assert_eq!(
+        diagnostic.code,
+        idlc_errors::codes::ERROR_VALUE_OUT_OF_RANGE
+    );
+}
+
+#[test]
+fn documentation_of_declarations() {
+    use crate::{InterfaceNode, Node};
+
+    let ast = crate::from_string(
+        std::path::PathBuf::new(),
+        r"/**
+ * Limit.
+ */
+const uint32 LIMIT = 4;
+
+/**
+ * A point.
+ */
+struct P {
+  /**
+   * Abscissa.
+   */
+  uint32 x;
+  // Not a documentation block.
+  uint32 y;
+};
+
+/**
+ * Interface.
+ */
+interface IFoo {
+  /**
+   * Failure.
+   */
+  error ERROR_FOO;
+  const uint32 NONE = 0;
+};",
+        false,
+    )
+    .unwrap();
+    let doc = |doc: &Option<crate::Documentation>| doc.as_ref().map(|d| d.0.trim().to_string());
+
+    let Node::Const(c) = ast.nodes[0].as_ref() else {
+        panic!("expected a const");
+    };
+    assert_eq!(doc(&c.doc).as_deref(), Some("*\n * Limit.\n *"));
+    let Node::Struct(s) = ast.nodes[1].as_ref() else {
+        panic!("expected a struct");
+    };
+    assert_eq!(doc(&s.doc).as_deref(), Some("*\n * A point.\n *"));
+    assert_eq!(
+        doc(&s.fields[0].doc).as_deref(),
+        Some("*\n   * Abscissa.\n   *")
+    );
+    assert_eq!(s.fields[1].doc, None);
+    let Node::Interface(i) = ast.nodes[2].as_ref() else {
+        panic!("expected an interface");
+    };
+    assert_eq!(doc(&i.doc).as_deref(), Some("*\n * Interface.\n *"));
+    let InterfaceNode::Error(e) = &i.nodes[0] else {
+        panic!("expected an error");
+    };
+    assert_eq!(doc(&e.doc).as_deref(), Some("*\n   * Failure.\n   *"));
+    let InterfaceNode::Const(c) = &i.nodes[1] else {
+        panic!("expected a const");
+    };
+    assert_eq!(c.doc, None);
+}
@@ -0,0 +1,168 @@
+// Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
+// SPDX-License-Identifier: BSD-3-Clause
+
+use crate::fmt::format;
+
+fn formats(input: &str, expected: &str) {
+    let formatted = format(input).expect("input should parse");
+    assert_eq!(formatted, expected);
+    assert_eq!(
+        format(&formatted).expect("output should parse"),
+        formatted,
+        "formatting should be idempotent"
+    );
+}
+
+#[test]
+fn aligns_struct_fields() {
+    formats(
+        "include \"a.idl\"\nstruct S { uint32 a; // a\n uint8[4] bb; F c; };\nconst uint32 X=1;",
+        r#"include "a.idl"
+
+struct S {
+  uint32   a; // a
+  uint8[4] bb;
+  F        c;
+};
+
+const uint32 X = 1;
+"#,
+    );
+}
+
+#[test]
+fn keeps_comments() {
+    formats(
+        r#"/**
+   * Documented.
+   */
+interface I:J{ // open
+    /* before */ error E;
+
+
+    enum E:uint32{A,B=2 /* b */,C};
+   #[version=1.2]  method h(); // h
+};
+// end
+"#,
+        r#"/**
+ * Documented.
+ */
+interface I : J { // open
+  /* before */
+  error E;
+
+  enum E : uint32 {
+    A,
+    B = 2, /* b */
+    C,
+  };
+  #[version = 1.2]
+  method h(); // h
+};
+
+// end
+"#,
+    );
+}
+
+#[test]
+fn wraps_methods() {
+    let input = "interface I { method long_method_name(in uint32 first_parameter, out buffer second_parameter, in I[2] third_parameter); method f(in uint32 a /* a */); };";
+    formats(
+        input,
+        "interface I {
+  method long_method_name(
+    in uint32 first_parameter,
+    out buffer second_parameter,
+    in I[2] third_parameter
+  );
+  method f(
+    in uint32 a /* a */
+  );
+};
+",
+    );
+}
+
+#[test]
+fn keeps_pinned_values() {
+    formats(
+        "interface I { error E=0x20; #[version=1.2] #[opcode=7] method f(); };",
+        r#"interface I {
+  error E = 0x20;
+  #[version = 1.2]
+  #[opcode = 7]
+  method f();
+};
+"#,
+    );
+}
+
+#[test]
+fn keeps_deprecations() {
+    formats(
+        "#[deprecated] struct S { uint32 a; }; #[deprecated=\"use J\"] interface I { #[deprecated] method f(); };",
+        r#"#[deprecated]
+struct S {
+  uint32 a;
+};
+
+#[deprecated = "use J"]
+interface I {
+  #[deprecated]
+  method f();
+};
+"#,
+    );
+}
+
+#[test]
+fn keeps_typedef_syntax() {
+    formats(
+        "typedef  uint8 [32]Sha256 ;using Handle=uint64;",
+        r#"typedef uint8[32] Sha256;
+using Handle = uint64;
+"#,
+    );
+}
+
+#[test]
+fn spaces_const_exprs() {
+    formats(
+        "const uint32 A=(1<<4)|IFoo::B*~C;struct S{uint8[ A ] key;};",
+        r#"const uint32 A = (1 << 4) | IFoo::B * ~C;
+
+struct S {
+  uint8[A] key;
+};
+"#,
+    );
+}
+
+#[test]
+fn keeps_max_lens() {
+    formats(
+        "interface I { method f(in uint8[ <=256 ] a,#[max_len=N]  in buffer b); };",
+        r#"interface I {
+  method f(in uint8[<=256] a, #[max_len = N] in buffer b);
+};
+"#,
+    );
+}
+
+#[test]
+fn keeps_strings() {
+    formats(
+        "interface I { method f(in string a,out string< N > b); };",
+        r#"interface I {
+  method f(in string a, out string<N> b);
+};
+"#,
+    );
+}
+
+#[test]
+fn rejects_invalid() {
+    assert!(format("struct S {};").is_err());
+}
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: BSD-3-Clause
 
 mod ast;
+mod fmt;
 mod parser;
 
 macro_rules! valid {
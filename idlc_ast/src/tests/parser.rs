@@ -49,6 +49,8 @@ fn primitive_types() {
             [&format!("int{width}"), &format!("uint{width}"),]
         );
     }
+    valid!(primitive_type, ["bool", "char",]);
+    invalid!(primitive_type, ["boolean", "char_t",]);
 }
 
 #[test]
@@ -135,6 +137,10 @@ fn consts() {
             "const               uint8 foo = 123;",
             "const\tuint8 bar = -0xabc;",
             "const\n\nuint8 bar = -0xabc;",
+            "const uint8 bar = abc;",
+            "const uint8 bar = -abc;",
+            "const uint32 foo = (1 << 4) | IFoo::BAR;",
+            "const int32 foo = ~(A*2)-  -B / 3 & 0xff;",
         ]
     );
 
@@ -143,14 +149,48 @@ fn consts() {
         [
             "const uint8 foo = 0x123azf;",
             "const uint8 bar = 123abf;",
-            "const uint8 bar = abc;",
-            "const uint8 bar = -abc;",
             "const float32 bar = 5.;",
+            "const uint8 bar = 1 +;",
+            "const uint8 bar = (1 + 2;",
+            "const uint8 bar = 1 ** 2;",
+            "const uint8 bar = A::B::C;",
+            "const bool foo = 1;",
+            "const char foo = 65;",
             "constuint8 foo = 123;",
         ]
     );
 }
 
+#[test]
+fn enums() {
+    valid!(
+        r#enum,
+        [
+            "enum Color : uint32 { RED };",
+            "enum Color : uint32 { RED = 1, GREEN, BLUE = 0x10 };",
+            "enum Color : int8 { RED = -1, GREEN, };",
+            r"enum Color : uint16 {
+                  RED,
+                  GREEN = 5,
+              };",
+            "enum\tColor : uint64 { RED };",
+        ]
+    );
+
+    invalid!(
+        r#enum,
+        [
+            "enum Color { RED };",
+            "enum Color : uint32 {};",
+            "enum Color : float32 { RED };",
+            "enum Color : Custom { RED };",
+            "enum Color : uint32 { RED = };",
+            "enum Color : uint32 { RED GREEN };",
+            "enumColor : uint32 { RED };",
+        ]
+    );
+}
+
 #[test]
 fn function() {
     valid!(
@@ -182,6 +222,11 @@ fn function() {
               method foo(in buffer req, out buffer rsp);",
             r"#[version = 0.0]
               method foo(in buffer req, out buffer rsp);",
+            "method foo(in uint8[<=256] req);",
+            "method foo(in IHWKey[ <= MAX ] req);",
+            "method foo(#[max_len = 64] in buffer req, #[max_len=N] in uint32[] ids);",
+            "method foo(in string name, in string<64> tag, out string< MAX > path);",
+            "method foo(in stringy req);",
         ]
     );
 
@@ -207,6 +252,11 @@ fn function() {
               method foo(in buffer req, out buffer rsp);",
             r"#[version = a.i]
               method foo(in buffer req, out buffer rsp);",
+            "method foo(in uint8[<=] req);",
+            "method foo(in interface[<=2] req);",
+            "method foo(#[max_len] in buffer req);",
+            "method foo(in string[] req);",
+            "method foo(in string<> req);",
         ]
     );
 }
@@ -2,8 +2,8 @@
 // SPDX-License-Identifier: BSD-3-Clause
 
 use super::{
-    Ast, Const, Count, Documentation, Function, Ident, Interface, InterfaceNode, Node, Param,
-    Primitive, Struct, StructField, Type,
+    ArraySize, Ast, Const, Documentation, Enum, Expr, Function, Ident, Interface, InterfaceNode,
+    Node, Param, Primitive, Struct, StructField, Type, Typedef,
 };
 
 #[allow(unused_variables)]
@@ -18,20 +18,30 @@ pub trait Visitor<'ast>: Sized {
 
     fn visit_expr(&mut self, expr: &'ast str) {}
 
+    fn visit_const_expr(&mut self, expr: &'ast Expr) {}
+
     fn visit_struct(&mut self, r#struct: &'ast Struct) {
         walk_struct(self, r#struct);
     }
 
+    fn visit_enum(&mut self, r#enum: &'ast Enum) {
+        walk_enum(self, r#enum);
+    }
+
+    fn visit_typedef(&mut self, typedef: &'ast Typedef) {
+        walk_typedef(self, typedef);
+    }
+
     fn visit_struct_field(&mut self, field: &'ast StructField) {
         walk_struct_field(self, field);
     }
 
-    fn visit_ty(&mut self, ty: (&'ast Type, Count)) {
+    fn visit_ty(&mut self, ty: (&'ast Type, &'ast ArraySize)) {
         walk_ty(self, ty);
     }
 
-    fn visit_primitive_ty(&mut self, ty: (&'ast Primitive, Count)) {}
-    fn visit_custom_ty(&mut self, ty: (&'ast str, Count)) {}
+    fn visit_primitive_ty(&mut self, ty: (&'ast Primitive, &'ast ArraySize)) {}
+    fn visit_custom_ty(&mut self, ty: (&'ast str, &'ast ArraySize)) {}
     fn visit_interface(&mut self, iface: &'ast Interface) {
         walk_iface(self, iface);
     }
@@ -49,24 +59,55 @@ pub trait Visitor<'ast>: Sized {
 
 pub fn walk_const<'a, V: Visitor<'a>>(visitor: &mut V, constant: &'a Const) {
     visitor.visit_ident(&constant.ident);
-    visitor.visit_primitive_ty((&constant.r#type, Count::new(1).unwrap()));
-    visitor.visit_expr(&constant.value);
+    if let Some(doc) = &constant.doc {
+        visitor.visit_doc(doc);
+    }
+    visitor.visit_primitive_ty((&constant.r#type, &ArraySize::ONE));
+    visitor.visit_const_expr(&constant.value);
 }
 
 pub fn walk_struct<'a, V: Visitor<'a>>(visitor: &mut V, r#struct: &'a Struct) {
     visitor.visit_ident(&r#struct.ident);
+    if let Some(doc) = &r#struct.doc {
+        visitor.visit_doc(doc);
+    }
     for field in &r#struct.fields {
         visitor.visit_struct_field(field);
     }
 }
 
+pub fn walk_enum<'a, V: Visitor<'a>>(visitor: &mut V, r#enum: &'a Enum) {
+    visitor.visit_ident(&r#enum.ident);
+    if let Some(doc) = &r#enum.doc {
+        visitor.visit_doc(doc);
+    }
+    for variant in &r#enum.variants {
+        visitor.visit_ident(&variant.ident);
+        if let Some(value) = &variant.value {
+            visitor.visit_expr(value);
+        }
+    }
+}
+
+pub fn walk_typedef<'a, V: Visitor<'a>>(visitor: &mut V, typedef: &'a Typedef) {
+    visitor.visit_ident(&typedef.ident);
+    if let Some(doc) = &typedef.doc {
+        visitor.visit_doc(doc);
+    }
+    let (ty, ele) = &typedef.val;
+    visitor.visit_ty((ty, ele));
+}
+
 pub fn walk_struct_field<'a, V: Visitor<'a>>(visitor: &mut V, field: &'a StructField) {
     visitor.visit_ident(&field.ident);
+    if let Some(doc) = &field.doc {
+        visitor.visit_doc(doc);
+    }
     let (ty, ele) = &field.val;
-    visitor.visit_ty((ty, *ele));
+    visitor.visit_ty((ty, ele));
 }
 
-pub fn walk_ty<'a, V: Visitor<'a>>(visitor: &mut V, ty: (&'a Type, Count)) {
+pub fn walk_ty<'a, V: Visitor<'a>>(visitor: &mut V, ty: (&'a Type, &'a ArraySize)) {
     match ty.0 {
         Type::Primitive(p) => visitor.visit_primitive_ty((p, ty.1)),
         Type::Custom(c) => visitor.visit_custom_ty((c, ty.1)),
@@ -76,6 +117,9 @@ pub fn walk_ty<'a, V: Visitor<'a>>(visitor: &mut V, ty: (&'a Type, Count)) {
 
 pub fn walk_iface<'a, V: Visitor<'a>>(visitor: &mut V, iface: &'a Interface) {
     visitor.visit_ident(&iface.ident);
+    if let Some(doc) = &iface.doc {
+        visitor.visit_doc(doc);
+    }
     if let Some(base) = &iface.base {
         visitor.visit_ident(base);
     }
@@ -88,7 +132,13 @@ pub fn walk_iface_node<'a, V: Visitor<'a>>(visitor: &mut V, node: &'a InterfaceN
     match node {
         InterfaceNode::Const(c) => visitor.visit_const(c),
         InterfaceNode::Function(f) => visitor.visit_fn(f),
-        InterfaceNode::Error(e) => visitor.visit_error(e),
+        InterfaceNode::Error(e) => {
+            visitor.visit_error(&e.ident);
+            if let Some(doc) = &e.doc {
+                visitor.visit_doc(doc);
+            }
+        }
+        InterfaceNode::Enum(e) => visitor.visit_enum(e),
     }
 }
 
@@ -109,6 +159,8 @@ pub fn walk_all<'a, V: Visitor<'a>>(visitor: &mut V, ast: &'a Ast) {
             Node::Include(i) => visitor.visit_include(i),
             Node::Const(c) => visitor.visit_const(c),
             Node::Struct(s) => visitor.visit_struct(s),
+            Node::Enum(e) => visitor.visit_enum(e),
+            Node::Typedef(t) => visitor.visit_typedef(t),
             Node::Interface(i) => visitor.visit_interface(i),
         }
     }
@@ -0,0 +1,444 @@
+// Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Evaluates constant expressions and array sizes naming constants.
+//!
+//! Integer expressions are evaluated within the range of the declared type of
+//! the constant, intermediate results included, so `uint8 X = 200 + 100 - 50;`
+//! overflows even though its result would fit. Float expressions only support
+//! arithmetic.
+
+use idlc_ast::{ArraySize, BinaryOp, Const, Count, Expr, Ident, Node, Primitive, Span, UnaryOp};
+use idlc_errors::{codes, Diagnostic, Label};
+
+use crate::{idl_store::IDLStore, CompilerPass};
+
+/// Value of an evaluated constant.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Value {
+    Integer(i128),
+    Float(f64),
+}
+
+impl std::fmt::Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Integer(i) => write!(f, "{i}"),
+            Self::Float(v) => write!(f, "{v:?}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Error {
+    /// Invalid expression, located at `span` when known.
+    Invalid {
+        code: &'static str,
+        message: String,
+        span: Option<Span>,
+        label: String,
+    },
+    /// A constant referred to is invalid, which is reported on its own
+    /// declaration.
+    Dependency,
+    /// Evaluation came back to the constant named.
+    Cycle(String),
+}
+
+impl Error {
+    fn invalid(message: String, span: Option<Span>, label: impl Into<String>) -> Self {
+        Self::Invalid {
+            code: codes::INVALID_CONST_EXPR,
+            message,
+            span,
+            label: label.into(),
+        }
+    }
+}
+
+/// Evaluates `r#const`, declared in interface `scope` if any.
+///
+/// # Errors
+///
+/// When the expression overflows the type of the constant, divides by zero,
+/// refers to unknown or invalid constants or back to `r#const`.
+pub fn evaluate(
+    idl_store: &IDLStore,
+    r#const: &Const,
+    scope: Option<&str>,
+) -> Result<Value, Error> {
+    let name = qualified(&r#const.ident, scope);
+    Evaluator {
+        idl_store,
+        stack: Vec::new(),
+    }
+    .constant(r#const, scope)
+    .map_err(|e| match e {
+        Error::Cycle(cycle) if cycle == name => Error::invalid(
+            format!("Constant `{}` refers back to itself", r#const.ident),
+            None,
+            "cyclic constant",
+        ),
+        Error::Cycle(_) => Error::Dependency,
+        e => e,
+    })
+}
+
+/// Number of elements `size` stands for, with constants looked up from
+/// interface `scope` if any.
+///
+/// # Errors
+///
+/// When the constant named is unknown, invalid or isn't an integer between 1
+/// and [`Count::MAX`].
+pub fn array_size(
+    idl_store: &IDLStore,
+    size: &ArraySize,
+    scope: Option<&str>,
+) -> Result<Count, Error> {
+    let ident = match size {
+        ArraySize::Count(count) => return Ok(*count),
+        ArraySize::Const(ident) => ident,
+    };
+    let Some((r#const, scope)) = lookup(idl_store, ident, scope) else {
+        return Err(unresolved(ident));
+    };
+    let value = match evaluate(idl_store, &r#const, scope.as_deref()) {
+        Ok(value) => value,
+        Err(_) => return Err(Error::Dependency),
+    };
+    if let Value::Integer(i) = value {
+        if let Some(count) = u16::try_from(i).ok().and_then(Count::new) {
+            return Ok(count);
+        }
+    }
+    Err(Error::Invalid {
+        code: codes::INVALID_ARRAY_SIZE,
+        message: format!(
+            "Array size `{ident}` isn't an integer between 1 and {}",
+            Count::MAX
+        ),
+        span: Some(ident.span),
+        label: format!("evaluates to {value}"),
+    })
+}
+
+/// Constant `ident` refers to from interface `scope`, along with the interface
+/// it's declared in.
+///
+/// Unqualified names are looked up in `scope`, its bases and then at the top
+/// level, `IFoo::NAME` is looked up in `IFoo` and its bases.
+fn lookup(
+    idl_store: &IDLStore,
+    ident: &Ident,
+    scope: Option<&str>,
+) -> Option<(std::rc::Rc<Const>, Option<String>)> {
+    let (mut scope, name, top_level) = match ident.split_once("::") {
+        Some((iface, name)) => (Some(iface.to_string()), name, false),
+        None => (scope.map(str::to_string), &**ident, true),
+    };
+    // Cyclic inheritance is only reported by the `Cycles` pass.
+    let mut seen = Vec::new();
+    while let Some(iface) = scope {
+        if let Some((r#const, _)) = idl_store.const_lookup(&format!("{iface}::{name}")) {
+            return Some((r#const, Some(iface)));
+        }
+        scope = idl_store
+            .iface_lookup(&iface)
+            .and_then(|i| i.base.as_ref().map(ToString::to_string))
+            .filter(|base| !seen.contains(base));
+        seen.push(iface);
+    }
+    top_level
+        .then(|| idl_store.const_lookup(name))
+        .flatten()
+        .map(|(r#const, _)| (r#const, None))
+}
+
+fn qualified(ident: &Ident, scope: Option<&str>) -> String {
+    scope.map_or_else(|| ident.to_string(), |scope| format!("{scope}::{ident}"))
+}
+
+fn unresolved(ident: &Ident) -> Error {
+    Error::Invalid {
+        code: codes::UNRESOLVED_SYMBOL,
+        message: format!("Constant `{ident}` not found"),
+        span: Some(ident.span),
+        label: "unknown constant".to_string(),
+    }
+}
+
+struct Evaluator<'a> {
+    idl_store: &'a IDLStore,
+    /// Constants being evaluated, to detect cycles.
+    stack: Vec<String>,
+}
+
+impl Evaluator<'_> {
+    fn constant(&mut self, r#const: &Const, scope: Option<&str>) -> Result<Value, Error> {
+        let name = qualified(&r#const.ident, scope);
+        if self.stack.contains(&name) {
+            return Err(Error::Cycle(name));
+        }
+        self.stack.push(name);
+        let value = self.expr(&r#const.value, r#const.r#type, scope);
+        self.stack.pop();
+        value
+    }
+
+    fn expr(&mut self, expr: &Expr, ty: Primitive, scope: Option<&str>) -> Result<Value, Error> {
+        match expr {
+            Expr::Literal(literal) => literal_value(literal, ty),
+            Expr::Const(ident) => self.reference(ident, ty, scope),
+            Expr::Unary(op, operand) => {
+                let operand = self.expr(operand, ty, scope)?;
+                unary(*op, operand, ty)
+            }
+            Expr::Binary(op, lhs, rhs) => {
+                let lhs = self.expr(lhs, ty, scope)?;
+                let rhs = self.expr(rhs, ty, scope)?;
+                binary(*op, lhs, rhs, ty)
+            }
+        }
+    }
+
+    fn reference(
+        &mut self,
+        ident: &Ident,
+        ty: Primitive,
+        scope: Option<&str>,
+    ) -> Result<Value, Error> {
+        let Some((r#const, const_scope)) = lookup(self.idl_store, ident, scope) else {
+            return Err(unresolved(ident));
+        };
+        let value = self
+            .constant(&r#const, const_scope.as_deref())
+            .map_err(|e| match e {
+                Error::Cycle(cycle) => Error::Cycle(cycle),
+                _ => Error::Dependency,
+            })?;
+        match (value, ty.range()) {
+            (Value::Integer(i), Some(range)) if range.contains(&i) => Ok(value),
+            (Value::Integer(i), Some(_)) => Err(Error::invalid(
+                format!("Constant `{ident}` doesn't fit in `{ty}`"),
+                Some(ident.span),
+                format!("evaluates to {i}"),
+            )),
+            (Value::Float(_), None) => Ok(value),
+            _ => Err(Error::invalid(
+                format!(
+                    "Constant `{ident}` of type `{}` used in a `{ty}` expression",
+                    r#const.r#type
+                ),
+                Some(ident.span),
+                "mismatched type",
+            )),
+        }
+    }
+}
+
+fn overflow(ty: Primitive, value: impl std::fmt::Display) -> Error {
+    Error::invalid(
+        format!("Constant expression overflows `{ty}`"),
+        None,
+        format!("evaluates to {value}"),
+    )
+}
+
+/// Checks that `value` is representable by the integer type `ty`.
+fn integer(value: Option<i128>, ty: Primitive) -> Result<Value, Error> {
+    let range = ty.range().expect("ICE: Integer type expected.");
+    match value {
+        Some(value) if range.contains(&value) => Ok(Value::Integer(value)),
+        Some(value) => Err(overflow(ty, value)),
+        None => Err(overflow(ty, "more than 128 bits")),
+    }
+}
+
+/// Checks that `value` is representable by the float type `ty`.
+fn float(value: f64, ty: Primitive) -> Result<Value, Error> {
+    #[allow(clippy::cast_possible_truncation)]
+    let finite = match ty {
+        Primitive::Float32 => (value as f32).is_finite(),
+        _ => value.is_finite(),
+    };
+    if finite {
+        Ok(Value::Float(value))
+    } else {
+        Err(overflow(ty, value))
+    }
+}
+
+fn literal_value(literal: &str, ty: Primitive) -> Result<Value, Error> {
+    if ty.range().is_some() {
+        match idlc_ast::parse_integer(literal) {
+            Some(value) => integer(Some(value), ty),
+            None => Err(Error::invalid(
+                format!("`{literal}` isn't a `{ty}` literal"),
+                None,
+                "integer literal expected",
+            )),
+        }
+    } else {
+        match literal.parse::<f64>() {
+            Ok(value) => float(value, ty),
+            Err(_) => Err(Error::invalid(
+                format!("`{literal}` isn't a `{ty}` literal"),
+                None,
+                "float literal expected",
+            )),
+        }
+    }
+}
+
+fn unary(op: UnaryOp, operand: Value, ty: Primitive) -> Result<Value, Error> {
+    match (op, operand) {
+        (UnaryOp::Neg, Value::Integer(i)) => integer(i.checked_neg(), ty),
+        (UnaryOp::Neg, Value::Float(f)) => float(-f, ty),
+        (UnaryOp::BitNot, Value::Integer(i)) => match ty.range() {
+            Some(range) if *range.start() == 0 => integer(Some(range.end() - i), ty),
+            _ => integer(Some(!i), ty),
+        },
+        (UnaryOp::BitNot, Value::Float(_)) => Err(unsupported(op, ty)),
+    }
+}
+
+fn binary(op: BinaryOp, lhs: Value, rhs: Value, ty: Primitive) -> Result<Value, Error> {
+    match (lhs, rhs) {
+        (Value::Integer(lhs), Value::Integer(rhs)) => {
+            let bits = ty.size() * 8;
+            let value = match op {
+                BinaryOp::Add => lhs.checked_add(rhs),
+                BinaryOp::Sub => lhs.checked_sub(rhs),
+                BinaryOp::Mul => lhs.checked_mul(rhs),
+                BinaryOp::Div if rhs == 0 => {
+                    return Err(Error::invalid(
+                        "Division by zero in constant expression".to_string(),
+                        None,
+                        "divides by zero",
+                    ))
+                }
+                BinaryOp::Div => lhs.checked_div(rhs),
+                BinaryOp::Shl | BinaryOp::Shr if !(0..bits as i128).contains(&rhs) => {
+                    return Err(Error::invalid(
+                        format!("Shift by {rhs} isn't within the {bits} bits of `{ty}`"),
+                        None,
+                        "shift out of range",
+                    ))
+                }
+                BinaryOp::Shl => lhs.checked_mul(1 << rhs),
+                BinaryOp::Shr => Some(lhs >> rhs),
+                BinaryOp::BitOr => Some(lhs | rhs),
+                BinaryOp::BitAnd => Some(lhs & rhs),
+            };
+            integer(value, ty)
+        }
+        (Value::Float(lhs), Value::Float(rhs)) => match op {
+            BinaryOp::Add => float(lhs + rhs, ty),
+            BinaryOp::Sub => float(lhs - rhs, ty),
+            BinaryOp::Mul => float(lhs * rhs, ty),
+            BinaryOp::Div if rhs == 0.0 => Err(Error::invalid(
+                "Division by zero in constant expression".to_string(),
+                None,
+                "divides by zero",
+            )),
+            BinaryOp::Div => float(lhs / rhs, ty),
+            _ => Err(unsupported(op, ty)),
+        },
+        _ => unreachable!("ICE: Operands are of the type of the constant."),
+    }
+}
+
+fn unsupported(op: impl std::fmt::Display, ty: Primitive) -> Error {
+    Error::invalid(
+        format!("Operator `{op}` isn't supported on `{ty}`"),
+        None,
+        "unsupported operator",
+    )
+}
+
+/// Evaluates every constant expression and array size naming a constant.
+pub struct Consts<'ast> {
+    idl_store: &'ast IDLStore,
+}
+
+impl<'ast> Consts<'ast> {
+    #[must_use]
+    pub const fn new(idl_store: &'ast IDLStore) -> Self {
+        Self { idl_store }
+    }
+
+    fn verify(&self, r#const: &Const, scope: Option<&str>, ast: &idlc_ast::Ast) {
+        // Literals are checked against their type while parsing.
+        if matches!(r#const.value, Expr::Literal(_)) {
+            return;
+        }
+        if let Err(e) = evaluate(self.idl_store, r#const, scope) {
+            report(e, r#const.ident.span, ast);
+        }
+    }
+
+    fn verify_size(&self, size: &ArraySize, scope: Option<&str>, ast: &idlc_ast::Ast) {
+        if let (ArraySize::Const(ident), Err(e)) = (size, array_size(self.idl_store, size, scope)) {
+            report(e, ident.span, ast);
+        }
+    }
+}
+
+fn report(error: Error, fallback: Span, ast: &idlc_ast::Ast) {
+    if let Error::Invalid {
+        code,
+        message,
+        span,
+        label,
+    } = error
+    {
+        idlc_errors::emit(
+            Diagnostic::error(code, message)
+                .with_file(&ast.tag)
+                .with_label(Label::primary(span.unwrap_or(fallback), label)),
+        );
+    }
+}
+
+impl<'ast> CompilerPass<'ast> for Consts<'ast> {
+    type Output = ();
+
+    fn run_pass(&'ast mut self, ast: &'ast idlc_ast::Ast) -> Result<Self::Output, crate::Error> {
+        for node in &ast.nodes {
+            match node.as_ref() {
+                Node::Const(c) => self.verify(c, None, ast),
+                Node::Struct(s) => {
+                    for field in &s.fields {
+                        self.verify_size(&field.val.1, None, ast);
+                    }
+                }
+                Node::Typedef(t) => self.verify_size(&t.val.1, None, ast),
+                Node::Interface(i) => {
+                    let scope = Some(&*i.ident.ident);
+                    for node in &i.nodes {
+                        match node {
+                            idlc_ast::InterfaceNode::Const(c) => self.verify(c, scope, ast),
+                            idlc_ast::InterfaceNode::Function(f) => {
+                                for param in &f.params {
+                                    if let Some(size) = param.array_size() {
+                                        self.verify_size(size, scope, ast);
+                                    }
+                                    if let Some(max_len) = param.max_len() {
+                                        self.verify_size(max_len, scope, ast);
+                                    }
+                                    if let idlc_ast::Type::String(Some(bound)) = param.as_ref() {
+                                        self.verify_size(bound, scope, ast);
+                                    }
+                                }
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,6 +1,7 @@
 // Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
 // SPDX-License-Identifier: BSD-3-Clause
 
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
 
 use idlc_ast::{
@@ -9,11 +10,13 @@ use idlc_ast::{
 };
 
 use crate::{graph::Graph, idl_store::IDLStore, CompilerPass};
+use idlc_errors::{codes, Diagnostic, Label};
 
 pub struct Cycles<'ast> {
     struct_graph: Graph<String>,
     iface_graph: Graph<String>,
     idl_store: &'ast IDLStore,
+    current: Option<PathBuf>,
 }
 
 impl<'ast> Cycles<'ast> {
@@ -22,48 +25,81 @@ impl<'ast> Cycles<'ast> {
             idl_store,
             struct_graph: Graph::new(),
             iface_graph: Graph::new(),
+            current: None,
         }
     }
 
-    pub fn visit_iface_recurse(&mut self, iface: Rc<idlc_ast::Interface>) {
+    /// `origin` is the file `iface` is defined in, if known.
+    pub fn visit_iface_recurse(&mut self, iface: Rc<idlc_ast::Interface>, origin: Option<&Path>) {
         if self.iface_graph.cycle().is_some() {
             return;
         }
 
         if let Some(base) = &iface.base {
-            let base = self.idl_store.iface_lookup(base).unwrap();
+            let Some(base_iface) = self.idl_store.iface_lookup(base) else {
+                unresolved(base, "interface", origin);
+                return;
+            };
             self.iface_graph
-                .add_edge(iface.ident.to_string(), base.ident.to_string());
-            self.visit_iface_recurse(base);
+                .add_edge(iface.ident.to_string(), base_iface.ident.to_string());
+            self.visit_iface_recurse(base_iface, None);
         }
     }
 
-    pub fn visit_struct_recurse(&mut self, r#struct: Rc<idlc_ast::Struct>) {
+    /// `origin` is the file `r#struct` is defined in, if known.
+    pub fn visit_struct_recurse(&mut self, r#struct: Rc<idlc_ast::Struct>, origin: Option<&Path>) {
         if self.struct_graph.cycle().is_some() {
             return;
         }
 
         for field in &r#struct.fields {
-            if let Type::Custom(c) = &field.r#type().0 {
-                let Some((custom, _)) = self.idl_store.struct_lookup(c) else {
-                    panic!("Identifier {:?} not found", c.ident);
+            let (ty, _) = self.idl_store.resolve_typedef(&field.r#type().0);
+            if let Type::Custom(c) = &ty {
+                if self.idl_store.enum_lookup(c).is_some() {
+                    continue;
+                }
+                let Some((custom, custom_origin)) = self.idl_store.struct_lookup(c) else {
+                    // Targets of typedefs are reported by the `Typedefs` pass.
+                    if ty == field.r#type().0 {
+                        unresolved(c, "type", origin);
+                    }
+                    continue;
                 };
                 self.struct_graph
                     .add_edge(r#struct.ident.to_string(), custom.ident.to_string());
-                self.visit_struct_recurse(custom);
+                self.visit_struct_recurse(custom, Some(&custom_origin));
             }
         }
     }
 }
 
+fn unresolved(ident: &idlc_ast::Ident, kind: &str, origin: Option<&Path>) {
+    let diagnostic = Diagnostic::error(
+        codes::UNRESOLVED_SYMBOL,
+        format!("Identifier `{ident}` not found"),
+    );
+    idlc_errors::emit(match origin {
+        Some(origin) => diagnostic
+            .with_file(origin)
+            .with_label(Label::primary(ident.span, format!("unknown {kind}"))),
+        None => diagnostic,
+    });
+}
+
 impl<'ast> Visitor<'ast> for Cycles<'ast> {
+    fn visit_root_ident(&mut self, root_ident: &'ast Path) {
+        self.current = Some(root_ident.to_path_buf());
+    }
+
     fn visit_interface(&mut self, iface: &'ast idlc_ast::Interface) {
-        self.visit_iface_recurse(Rc::new(iface.clone()));
+        let current = self.current.clone();
+        self.visit_iface_recurse(Rc::new(iface.clone()), current.as_deref());
         self.iface_graph.add_node(iface.ident.to_string());
     }
 
     fn visit_struct(&mut self, r#struct: &'ast idlc_ast::Struct) {
-        self.visit_struct_recurse(Rc::new(r#struct.clone()));
+        let current = self.current.clone();
+        self.visit_struct_recurse(Rc::new(r#struct.clone()), current.as_deref());
         self.struct_graph.add_node(r#struct.ident.to_string());
     }
 }
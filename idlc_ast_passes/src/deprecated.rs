@@ -0,0 +1,94 @@
+// Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Warns on uses of deprecated structs and interfaces, typedefs included.
+//!
+//! Types are looked up in the whole include graph, uses within a deprecated
+//! declaration aren't reported.
+
+use idlc_ast::{Deprecation, Ident, InterfaceNode, Node, Type};
+use idlc_errors::{codes, Diagnostic, Label};
+
+use crate::{idl_store::IDLStore, CompilerPass};
+
+pub struct Deprecations<'ast> {
+    idl_store: &'ast IDLStore,
+}
+
+impl<'ast> Deprecations<'ast> {
+    #[must_use]
+    pub const fn new(idl_store: &'ast IDLStore) -> Self {
+        Self { idl_store }
+    }
+
+    fn lookup(&self, ident: &Ident) -> Option<(&'static str, Deprecation)> {
+        // Interfaces are registered as structs as well, so they go first.
+        if let Some(iface) = self.idl_store.iface_lookup(ident) {
+            return iface.deprecated.clone().map(|d| ("interface", d));
+        }
+        self.idl_store
+            .struct_lookup(ident)
+            .and_then(|(r#struct, _)| r#struct.deprecated.clone())
+            .map(|d| ("struct", d))
+    }
+
+    fn check(&self, ast: &idlc_ast::Ast, ty: &Type) {
+        if let Type::Custom(ident) = ty {
+            self.check_ident(ast, ident);
+        }
+    }
+
+    fn check_ident(&self, ast: &idlc_ast::Ast, ident: &Ident) {
+        let Some((kind, deprecation)) = self.lookup(ident) else {
+            return;
+        };
+        let mut message = format!("Use of deprecated {kind} `{ident}`");
+        if let Some(note) = &deprecation.note {
+            message.push_str(&format!(": {note}"));
+        }
+        idlc_errors::emit(
+            Diagnostic::warning(codes::DEPRECATED_USE, message)
+                .with_file(&ast.tag)
+                .with_label(Label::primary(ident.span, format!("deprecated {kind}"))),
+        );
+    }
+}
+
+impl<'ast> CompilerPass<'ast> for Deprecations<'_> {
+    type Output = ();
+
+    fn run_pass(&'ast mut self, ast: &'ast idlc_ast::Ast) -> Result<Self::Output, crate::Error> {
+        for node in &ast.nodes {
+            match node.as_ref() {
+                Node::Struct(s) if s.deprecated.is_none() => {
+                    for field in &s.fields {
+                        self.check(ast, &field.val.0);
+                    }
+                }
+                Node::Typedef(t) => self.check(ast, &t.val.0),
+                Node::Interface(i) if i.deprecated.is_none() => {
+                    if let Some(base) = &i.base {
+                        self.check_ident(ast, base);
+                    }
+                    for node in &i.nodes {
+                        let InterfaceNode::Function(f) = node else {
+                            continue;
+                        };
+                        let deprecated = f
+                            .attributes
+                            .iter()
+                            .any(|attr| matches!(attr, idlc_ast::FunctionAttribute::Deprecated(_)));
+                        if deprecated {
+                            continue;
+                        }
+                        for param in &f.params {
+                            self.check(ast, param.as_ref());
+                        }
+                    }
+                }
+                _ => (),
+            }
+        }
+        Ok(())
+    }
+}
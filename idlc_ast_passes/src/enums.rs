@@ -0,0 +1,109 @@
+// Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Validates enum declarations.
+//!
+//! Ensures every variant has a unique name and value, and that every value
+//! fits in the underlying integer type.
+
+use std::collections::HashMap;
+
+use idlc_ast::{Enum, InterfaceNode, Node};
+use idlc_errors::{codes, Diagnostic, Label};
+
+use crate::CompilerPass;
+
+#[derive(Default)]
+pub struct Enums;
+
+impl Enums {
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self
+    }
+
+    fn verify(r#enum: &Enum, ast: &idlc_ast::Ast) {
+        let mut names = HashMap::new();
+        for variant in &r#enum.variants {
+            if let Some(orig) = names.insert(&variant.ident, &variant.ident) {
+                idlc_errors::emit(
+                    Diagnostic::error(
+                        codes::DUPLICATE_ENUM_VARIANT,
+                        format!(
+                            "Enum `{}` has duplicate variant `{}`",
+                            r#enum.ident, variant.ident
+                        ),
+                    )
+                    .with_file(&ast.tag)
+                    .with_label(Label::primary(variant.ident.span, "duplicate variant"))
+                    .with_label(Label::secondary(orig.span, "first declared here")),
+                );
+            }
+        }
+
+        let values = match r#enum.values() {
+            Ok(values) => values,
+            Err(variant) => {
+                idlc_errors::emit(
+                    Diagnostic::error(
+                        codes::ENUM_VALUE_OUT_OF_RANGE,
+                        format!(
+                            "Value of `{}::{}` isn't in range for type '{}'",
+                            r#enum.ident, variant.ident, r#enum.r#type
+                        ),
+                    )
+                    .with_file(&ast.tag)
+                    .with_label(Label::primary(
+                        variant.ident.span,
+                        variant.value.as_ref().map_or_else(
+                            || "implicitly follows the previous variant".to_string(),
+                            |value| format!("`{value}` is out of range"),
+                        ),
+                    )),
+                );
+                return;
+            }
+        };
+
+        let mut seen = HashMap::new();
+        for (variant, value) in r#enum.variants.iter().zip(values) {
+            if let Some(orig) = seen.insert(value, &variant.ident) {
+                idlc_errors::emit(
+                    Diagnostic::error(
+                        codes::DUPLICATE_ENUM_VARIANT,
+                        format!(
+                            "Enum `{}` variants `{orig}` and `{}` have the same value `{value}`",
+                            r#enum.ident, variant.ident
+                        ),
+                    )
+                    .with_file(&ast.tag)
+                    .with_label(Label::primary(variant.ident.span, format!("= {value}")))
+                    .with_label(Label::secondary(orig.span, "first declared here")),
+                );
+            }
+        }
+    }
+}
+
+impl<'ast> CompilerPass<'ast> for Enums {
+    type Output = ();
+
+    fn run_pass(&'ast mut self, ast: &'ast idlc_ast::Ast) -> Result<Self::Output, crate::Error> {
+        for node in &ast.nodes {
+            match node.as_ref() {
+                Node::Enum(e) => Self::verify(e, ast),
+                Node::Interface(i) => {
+                    for node in &i.nodes {
+                        if let InterfaceNode::Enum(e) = node {
+                            Self::verify(e, ast);
+                        }
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -3,7 +3,10 @@
 
 use std::collections::HashSet;
 
-use idlc_ast::{APIVersion, InterfaceNode, Node, DEFAULT_VERSION};
+use idlc_ast::{
+    APIVersion, InterfaceNode, Node, Param, ParamTypeIn, ParamTypeOut, Type, DEFAULT_VERSION,
+};
+use idlc_errors::{codes, Diagnostic, Label};
 
 use crate::CompilerPass;
 
@@ -33,46 +36,144 @@ impl<'ast> CompilerPass<'ast> for Functions {
                     let mut params = HashSet::new();
                     for param in &function.params {
                         let ident = param.ident();
-                        if !params.insert(ident) {
-                            idlc_errors::unrecoverable!(
-                                "Function `{}::{}` has duplicate parameter `{}`",
-                                interface.ident,
-                                function.ident,
-                                ident
+                        if let Some(orig) = params.replace(ident) {
+                            idlc_errors::emit(
+                                Diagnostic::error(
+                                    codes::DUPLICATE_PARAM,
+                                    format!(
+                                        "Function `{}::{}` has duplicate parameter `{}`",
+                                        interface.ident, function.ident, ident
+                                    ),
+                                )
+                                .with_file(&ast.tag)
+                                .with_label(Label::primary(ident.span, "duplicate parameter"))
+                                .with_label(Label::secondary(orig.span, "first declared here")),
                             );
                         }
                     }
+                    // Check that maximum lengths bound input buffers or unbounded arrays
+                    for param in &function.params {
+                        let label = match param {
+                            _ if param.max_len().is_none() => continue,
+                            Param::In {
+                                r#type:
+                                    ParamTypeIn::Value(Type::UntypedBuffer)
+                                    | ParamTypeIn::Array(_, None),
+                                ..
+                            } => continue,
+                            Param::In { .. } => "not a buffer or unbounded array",
+                            Param::Out { .. } => "not an input",
+                        };
+                        idlc_errors::emit(
+                            Diagnostic::error(
+                                codes::INVALID_MAX_LEN,
+                                format!(
+                                    "Parameter `{}` of `{}::{}` can't have a maximum length",
+                                    param.ident(),
+                                    interface.ident,
+                                    function.ident
+                                ),
+                            )
+                            .with_file(&ast.tag)
+                            .with_label(Label::primary(param.ident().span, label)),
+                        );
+                    }
+                    // Check that output strings are bounded
+                    for param in &function.params {
+                        if let Param::Out {
+                            r#type: ParamTypeOut::Reference(Type::String(None)),
+                            ident,
+                            ..
+                        } = param
+                        {
+                            idlc_errors::emit(
+                                Diagnostic::error(
+                                    codes::UNBOUNDED_OUTPUT_STRING,
+                                    format!(
+                                        "Output string `{ident}` of `{}::{}` needs a maximum length",
+                                        interface.ident, function.ident
+                                    ),
+                                )
+                                .with_file(&ast.tag)
+                                .with_label(Label::primary(ident.span, "use `string<N>`")),
+                            );
+                        }
+                    }
+                    // Check that the op-code is pinned at most once
+                    let op_codes: Vec<String> = function
+                        .attributes
+                        .iter()
+                        .filter_map(|attr| match attr {
+                            idlc_ast::FunctionAttribute::OpCode(op_code) => {
+                                Some(op_code.to_string())
+                            }
+                            _ => None,
+                        })
+                        .collect();
+                    if op_codes.len() > 1 {
+                        idlc_errors::emit(
+                            Diagnostic::error(
+                                codes::DUPLICATE_OP_CODE,
+                                format!(
+                                    "Function `{}::{}` has multiple 'opcode' attributes: {}",
+                                    interface.ident,
+                                    function.ident,
+                                    op_codes.join(", "),
+                                ),
+                            )
+                            .with_file(&ast.tag)
+                            .with_label(Label::primary(function.ident.span, "")),
+                        );
+                    }
                     // Check the version of this function
                     // - Gather any and all listed version attributes
                     let version_attrs: Vec<&APIVersion> = function
                         .attributes
                         .iter()
-                        .map(|attr| match attr {
-                            idlc_ast::FunctionAttribute::Version(a) => a,
+                        .filter_map(|attr| match attr {
+                            idlc_ast::FunctionAttribute::Version(a) => Some(a),
+                            _ => None,
                         })
                         .collect();
                     // - Ensure that no more than 1 version is listed
                     if version_attrs.len() > 1 {
-                        idlc_errors::unrecoverable!(
-                            "Function `{}::{}` has multiple 'version' attributes: {}",
-                            interface.ident,
-                            function.ident,
-                            version_attrs
-                                .iter()
-                                .map(|a| a.to_string())
-                                .collect::<Vec<String>>()
-                                .join(", "),
+                        idlc_errors::emit(
+                            Diagnostic::error(
+                                codes::DUPLICATE_VERSION,
+                                format!(
+                                    "Function `{}::{}` has multiple 'version' attributes: {}",
+                                    interface.ident,
+                                    function.ident,
+                                    version_attrs
+                                        .iter()
+                                        .map(|a| a.to_string())
+                                        .collect::<Vec<String>>()
+                                        .join(", "),
+                                ),
+                            )
+                            .with_file(&ast.tag)
+                            .with_label(Label::primary(function.ident.span, "")),
                         );
                     }
                     // - Ensure that method versions are monotonically increasing
                     for func_ver in version_attrs {
                         if func_ver < &current_version {
-                            idlc_errors::unrecoverable!(
-                                "Function `{}::{}` version `{}` cannot be less than the previous method `{}`",
-                                interface.ident,
-                                function.ident,
-                                func_ver,
-                                current_version,
+                            idlc_errors::emit(
+                                Diagnostic::error(
+                                    codes::VERSION_REGRESSION,
+                                    format!(
+                                        "Function `{}::{}` version `{}` cannot be less than the previous method `{}`",
+                                        interface.ident,
+                                        function.ident,
+                                        func_ver,
+                                        current_version,
+                                    ),
+                                )
+                                .with_file(&ast.tag)
+                                .with_label(Label::primary(
+                                    function.ident.span,
+                                    format!("declared with version `{func_ver}`"),
+                                )),
                             );
                         }
                         if func_ver > &current_version {
@@ -10,14 +10,14 @@ use crate::CompilerPass;
 use std::{
     cell::RefCell,
     collections::HashMap,
-    path::{Path, PathBuf},
+    path::{Component, Path, PathBuf},
     rc::Rc,
 };
 
-use idlc_ast::{Ast, Interface, Node, Struct};
+use idlc_ast::{Ast, Const, Count, Interface, Node, Struct, Type, Typedef};
 
 use idlc_ast::visitor::{walk_all, Visitor};
-use idlc_errors::warn;
+use idlc_errors::{codes, warn, Diagnostic, Label};
 
 /// `DependencyResolver` structure
 /// Compilation unit is split into a hashmap here.
@@ -30,13 +30,112 @@ pub struct IDLStore {
     graph: Graph<String>,
     include_paths: Vec<PathBuf>,
     allow_undefined_behavior: bool,
+    cache: AstCache,
+    sources: Sources,
 }
 
+/// Where the store reads the IDL files from.
+#[derive(Debug, Clone, Default)]
+pub enum Sources {
+    /// Files are read from the disk, their paths are canonicalized.
+    #[default]
+    Disk,
+    /// Files only exist in memory, their paths are normalized lexically.
+    Memory(Rc<HashMap<PathBuf, String>>),
+    /// Files are read from the disk, unless they're kept in memory under
+    /// their canonical path (e.g. unsaved editor buffers).
+    Overlay(Rc<HashMap<PathBuf, String>>),
+}
+
+impl Sources {
+    /// Keeps `files` in memory, none of them is read from the disk.
+    #[must_use]
+    pub fn memory(files: impl IntoIterator<Item = (PathBuf, String)>) -> Self {
+        Self::Memory(Rc::new(
+            files
+                .into_iter()
+                .map(|(path, source)| (normalize(&path), source))
+                .collect(),
+        ))
+    }
+
+    /// returns the canonical form of `path` if it exists
+    #[must_use]
+    pub fn canonicalize(&self, path: &Path) -> Option<PathBuf> {
+        match self {
+            Self::Disk | Self::Overlay(_) => path.canonicalize().ok(),
+            Self::Memory(files) => {
+                let path = normalize(path);
+                files.contains_key(&path).then_some(path)
+            }
+        }
+    }
+
+    /// returns the contents of `path`
+    pub fn read(&self, path: &Path) -> Result<String, idlc_ast::Error> {
+        match self {
+            Self::Disk => std::fs::read_to_string(path),
+            Self::Overlay(files) => files
+                .get(path)
+                .cloned()
+                .map_or_else(|| std::fs::read_to_string(path), Ok),
+            Self::Memory(files) => files
+                .get(&normalize(path))
+                .cloned()
+                .ok_or_else(|| std::io::ErrorKind::NotFound.into()),
+        }
+        .map_err(|e| idlc_ast::Error::Io(e, path.display().to_string()))
+    }
+
+    fn parse(&self, path: &Path, allow_undefined_behavior: bool) -> Result<Ast, idlc_ast::Error> {
+        let source = self.read(path)?;
+        idlc_ast::from_string(path.to_path_buf(), source, allow_undefined_behavior)
+    }
+}
+
+/// Resolves the `.` and `..` components of `path` without looking at the disk.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir if normalized.file_name().is_some() => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+/// ASTs parsed by the stores sharing the cache, so that files included by
+/// several inputs are only parsed once.
+///
+/// Only the parsing is shared, symbols are still resolved per store.
+#[derive(Debug, Clone, Default)]
+pub struct AstCache(Rc<RefCell<HashMap<PathBuf, Rc<Ast>>>>);
+
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
 enum Symbol {
     Struct(String),
     Interface(String),
     Const(String),
+    /// Enums declared within an interface are scoped as `<interface>::<enum>`.
+    Enum(String),
+    Typedef(String),
+}
+
+impl Symbol {
+    /// Structs (including interfaces), enums and typedefs can all be used as
+    /// field types, so they share a namespace.
+    fn shadowed(&self) -> Vec<Self> {
+        match self {
+            Self::Struct(s) => vec![Self::Enum(s.clone()), Self::Typedef(s.clone())],
+            Self::Enum(e) => vec![Self::Struct(e.clone()), Self::Typedef(e.clone())],
+            Self::Typedef(t) => vec![Self::Struct(t.clone()), Self::Enum(t.clone())],
+            _ => Vec::new(),
+        }
+    }
 }
 
 impl Visitor<'_> for IDLStore {
@@ -46,7 +145,10 @@ impl Visitor<'_> for IDLStore {
 
     fn visit_include(&mut self, include: &'_ Path) {
         let current = self.current.take().unwrap();
-        let cano_path = self.change_to_canonical(&current, include);
+        let Some(cano_path) = self.change_to_canonical(&current, include) else {
+            self.current = Some(current);
+            return;
+        };
         self.graph.add_edge(
             current.clone().display().to_string(),
             cano_path.display().to_string(),
@@ -80,6 +182,16 @@ impl IDLStore {
     /// takes the vector of include paths as an argument which will be saved in `include_paths` field
     #[must_use]
     pub fn with_includes(include_paths: &[PathBuf], allow_undefined_behavior: bool) -> Self {
+        Self::with_cache(include_paths, allow_undefined_behavior, AstCache::default())
+    }
+
+    /// same as [`Self::with_includes`], reusing the ASTs already in `cache`
+    #[must_use]
+    pub fn with_cache(
+        include_paths: &[PathBuf],
+        allow_undefined_behavior: bool,
+        cache: AstCache,
+    ) -> Self {
         Self {
             ast_store: RefCell::new(HashMap::new()),
             symbols: RefCell::new(HashMap::new()),
@@ -88,9 +200,18 @@ impl IDLStore {
             graph: Graph::new(),
             include_paths: include_paths.to_vec(),
             allow_undefined_behavior,
+            cache,
+            sources: Sources::Disk,
         }
     }
 
+    /// reads the files from `sources` instead of the disk
+    #[must_use]
+    pub fn with_sources(mut self, sources: Sources) -> Self {
+        self.sources = sources;
+        self
+    }
+
     #[must_use]
     pub fn new() -> Self {
         Self::with_includes(&[], true)
@@ -104,18 +225,42 @@ impl IDLStore {
         ))
     }
 
-    fn change_to_canonical<'a>(&mut self, current: &'a Path, path: &'a Path) -> PathBuf {
+    /// returns the ASTs of every file parsed so far, ordered by their path
+    #[must_use]
+    pub fn asts(&self) -> Vec<Rc<Ast>> {
+        let store = self.ast_store.borrow();
+        let mut asts: Vec<_> = store.iter().collect();
+        asts.sort_by(|a, b| a.0.cmp(b.0));
+        asts.into_iter().map(|(_, ast)| Rc::clone(ast)).collect()
+    }
+
+    /// returns the canonical path of every file parsed so far, sorted
+    #[must_use]
+    pub fn files(&self) -> Vec<PathBuf> {
+        let mut files: Vec<_> = self.ast_store.borrow().keys().cloned().collect();
+        files.sort();
+        files
+    }
+
+    fn change_to_canonical<'a>(&mut self, current: &'a Path, path: &'a Path) -> Option<PathBuf> {
         if self.ast_store.borrow().contains_key(path) {
-            return path.to_path_buf();
+            return Some(path.to_path_buf());
         }
 
         if !path.parent().unwrap().display().to_string().is_empty() {
             let uncanonicalized = current.parent().unwrap().join(path);
-            let Ok(canonicalized) = uncanonicalized.canonicalize() else {
-                panic!("{uncanonicalized:?} cannot be found!");
+            let Some(canonicalized) = self.sources.canonicalize(&uncanonicalized) else {
+                idlc_errors::emit(
+                    Diagnostic::error(
+                        codes::INCLUDE_NOT_FOUND,
+                        format!("{uncanonicalized:?} cannot be found!"),
+                    )
+                    .with_file(current),
+                );
+                return None;
             };
 
-            return canonicalized;
+            return Some(canonicalized);
         }
 
         let mut possibilities = std::collections::HashSet::new();
@@ -123,8 +268,7 @@ impl IDLStore {
 
         for include_path in &self.include_paths {
             let possibility = include_path.join(path);
-            if possibility.exists() {
-                let canonical = possibility.canonicalize().unwrap();
+            if let Some(canonical) = self.sources.canonicalize(&possibility) {
                 if first.is_none() {
                     first = Some(canonical.clone());
                 }
@@ -132,7 +276,22 @@ impl IDLStore {
             }
         }
         let Some(first) = first else {
-            idlc_errors::unrecoverable!("File not found: {}", path.display());
+            idlc_errors::emit(
+                Diagnostic::error(
+                    codes::INCLUDE_NOT_FOUND,
+                    format!("File not found: {}", path.display()),
+                )
+                .with_file(current)
+                .with_note(format!(
+                    "searched in: {}",
+                    self.include_paths
+                        .iter()
+                        .map(|p| p.display().to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )),
+            );
+            return None;
         };
 
         if possibilities.len() > 1 {
@@ -145,7 +304,7 @@ impl IDLStore {
             }
             warn!("Selecting '{}'", first.display());
         }
-        first
+        Some(first)
     }
 
     fn check_includes(&mut self, ast: &'_ idlc_ast::Ast) -> Result<Vec<String>, crate::Error> {
@@ -161,39 +320,107 @@ impl IDLStore {
     fn gather_symbols_from_ast(ast: &Ast, map: &mut HashMap<Symbol, (Rc<Node>, PathBuf)>) {
         let tag = &ast.tag;
         for node in &ast.nodes {
-            assert_eq!(
-                match node.as_ref() {
-                    Node::Struct(s) => map.insert(
-                        Symbol::Struct(s.ident.to_string()),
-                        (Rc::clone(node), tag.clone())
-                    ),
-                    Node::Interface(i) => {
-                        map.insert(
-                            Symbol::Struct(i.ident.to_string()),
-                            (
-                                Rc::new(Node::Struct(Struct::new_object(&i.ident))),
-                                tag.clone(),
-                            ),
-                        )
-                        .or_else(|| {
-                            map.insert(
-                                Symbol::Interface(i.ident.to_string()),
-                                (Rc::clone(node), tag.clone()),
-                            )
-                        })
-                    }
-                    Node::Const(c) => {
-                        map.insert(
-                            Symbol::Const(c.ident.to_string()),
-                            (Rc::clone(node), tag.clone()),
-                        )
-                    }
+            let (ident, symbol, value) = match node.as_ref() {
+                Node::Struct(s) => (
+                    &s.ident,
+                    Symbol::Struct(s.ident.to_string()),
+                    Rc::clone(node),
+                ),
+                // Interfaces can be used as struct fields, so they share the
+                // namespace with structs.
+                Node::Interface(i) => (
+                    &i.ident,
+                    Symbol::Struct(i.ident.to_string()),
+                    Rc::new(Node::Struct(Struct::new_object(&i.ident))),
+                ),
+                Node::Const(c) => (
+                    &c.ident,
+                    Symbol::Const(c.ident.to_string()),
+                    Rc::clone(node),
+                ),
+                Node::Enum(e) => (&e.ident, Symbol::Enum(e.ident.to_string()), Rc::clone(node)),
+                Node::Typedef(t) => (
+                    &t.ident,
+                    Symbol::Typedef(t.ident.to_string()),
+                    Rc::clone(node),
+                ),
+                Node::Include(_) => continue,
+            };
+            if !Self::insert_symbol(map, ident, symbol, value, tag) {
+                continue;
+            }
+            if let Node::Interface(i) = node.as_ref() {
+                map.insert(
+                    Symbol::Interface(i.ident.to_string()),
+                    (Rc::clone(node), tag.clone()),
+                );
+                // Duplicates within an interface are reported by the
+                // `InterfaceVerifier`.
+                for c in i.nodes.iter().filter_map(|node| match node {
+                    idlc_ast::InterfaceNode::Const(c) => Some(c),
                     _ => None,
-                },
-                None,
-                "Duplicate symbol detected!"
-            );
+                }) {
+                    map.entry(Symbol::Const(format!("{}::{}", i.ident, c.ident)))
+                        .or_insert_with(|| (Rc::new(Node::Const(c.clone())), tag.clone()));
+                }
+                for e in i.nodes.iter().filter_map(|node| match node {
+                    idlc_ast::InterfaceNode::Enum(e) => Some(e),
+                    _ => None,
+                }) {
+                    Self::insert_symbol(
+                        map,
+                        &e.ident,
+                        Symbol::Enum(format!("{}::{}", i.ident, e.ident)),
+                        Rc::new(Node::Enum(e.clone())),
+                        tag,
+                    );
+                }
+            }
+        }
+    }
+
+    /// Returns `false` and emits a diagnostic if the symbol already exists.
+    fn insert_symbol(
+        map: &mut HashMap<Symbol, (Rc<Node>, PathBuf)>,
+        ident: &idlc_ast::Ident,
+        symbol: Symbol,
+        value: Rc<Node>,
+        tag: &Path,
+    ) -> bool {
+        let existing = map.get(&symbol).or_else(|| {
+            symbol
+                .shadowed()
+                .iter()
+                .find_map(|shadowed| map.get(shadowed))
+        });
+        if let Some((orig, orig_tag)) = existing {
+            Self::duplicate_symbol(ident, tag, orig, orig_tag);
+            return false;
         }
+        map.insert(symbol, (value, tag.to_path_buf()));
+        true
+    }
+
+    fn duplicate_symbol(ident: &idlc_ast::Ident, tag: &Path, orig: &Node, orig_tag: &Path) {
+        let mut diagnostic = Diagnostic::error(
+            codes::DUPLICATE_SYMBOL,
+            format!("Duplicate symbol detected! `{ident}` is defined multiple times"),
+        )
+        .with_file(tag)
+        .with_label(Label::primary(ident.span, "redefined here"));
+        match orig.ident() {
+            Some(orig_ident) if orig_tag == tag => {
+                diagnostic = diagnostic.with_label(Label::secondary(
+                    orig_ident.span,
+                    "previous definition here",
+                ));
+            }
+            _ => {
+                diagnostic =
+                    diagnostic.with_note(format!("previous definition in {}", orig_tag.display()));
+            }
+        }
+        idlc_errors::emit(diagnostic);
     }
 
     /// returns the AST corresponding to the given path
@@ -201,14 +428,39 @@ impl IDLStore {
     pub fn get_or_insert(&self, file_path: &Path) -> Rc<Ast> {
         let mut include_path = PathBuf::from(file_path);
         if !self.ast_store.borrow().contains_key(file_path) {
-            include_path = file_path
-                .canonicalize()
-                .expect("Failed to canonicalize path.");
-            let node = idlc_ast::from_file(include_path.clone(), self.allow_undefined_behavior)
-                .unwrap_or_else(|e| {
-                    idlc_errors::unrecoverable!("Parsing failed: \n{e}\n");
-                });
-            Self::insert_canonical(self, &include_path, &node);
+            // Unreadable inputs are reported by the parser below.
+            include_path = self
+                .sources
+                .canonicalize(file_path)
+                .unwrap_or_else(|| file_path.to_path_buf());
+            let cached = self.cache.0.borrow().get(&include_path).cloned();
+            let node = cached.unwrap_or_else(|| {
+                match self
+                    .sources
+                    .parse(&include_path, self.allow_undefined_behavior)
+                {
+                    Ok(node) => {
+                        let node = Rc::new(node);
+                        self.cache
+                            .0
+                            .borrow_mut()
+                            .insert(include_path.clone(), Rc::clone(&node));
+                        node
+                    }
+                    Err(e) => {
+                        idlc_errors::emit(e.to_diagnostic(&include_path));
+                        // Continue with an empty unit to report errors from the rest of the files.
+                        Rc::new(Ast {
+                            tag: include_path.clone(),
+                            nodes: Vec::new(),
+                        })
+                    }
+                }
+            });
+            Self::gather_symbols_from_ast(&node, &mut self.symbols.borrow_mut());
+            self.ast_store
+                .borrow_mut()
+                .insert(include_path.clone(), node);
         }
 
         Rc::clone(self.ast_store.borrow().get(&include_path).unwrap())
@@ -222,6 +474,67 @@ impl IDLStore {
             .insert(canonical.to_path_buf(), ast.clone().into());
     }
 
+    /// returns the constant corresponding to the given name
+    ///
+    /// Constants declared within an interface are looked up as
+    /// `<interface>::<constant>`.
+    pub fn const_lookup(&self, name: &str) -> Option<(Rc<Const>, PathBuf)> {
+        self.symbols
+            .borrow()
+            .get(&Symbol::Const(name.to_string()))
+            .map(|(node, tag)| {
+                let Node::Const(c) = node.as_ref() else {
+                    unreachable!("ICE: Const node expected.")
+                };
+                (Rc::new(c.clone()), tag.clone())
+            })
+    }
+
+    /// returns the typedef corresponding to the given name
+    pub fn typedef_lookup(&self, name: &str) -> Option<(Rc<Typedef>, PathBuf)> {
+        self.symbols
+            .borrow()
+            .get(&Symbol::Typedef(name.to_string()))
+            .map(|(node, tag)| {
+                let Node::Typedef(t) = node.as_ref() else {
+                    unreachable!("ICE: Typedef node expected.")
+                };
+                (Rc::new(t.clone()), tag.clone())
+            })
+    }
+
+    /// returns the type `ty` stands for once the typedefs it names are
+    /// followed, along with the number of elements of the array typedefs
+    /// along the way
+    ///
+    /// `ty` is returned as is if it doesn't name a typedef. Cyclic typedefs
+    /// and overflowing counts are reported by the `Typedefs` pass, the
+    /// resolution stops at the first typedef which can't be resolved.
+    #[must_use]
+    pub fn resolve_typedef(&self, ty: &Type) -> (Type, Count) {
+        let mut ty = ty.clone();
+        let mut count = Count::MIN;
+        let mut seen = Vec::new();
+        while let Type::Custom(ident) = &ty {
+            let Some((typedef, _)) = self.typedef_lookup(ident) else {
+                break;
+            };
+            let Ok(size) = crate::consts::array_size(self, &typedef.val.1, None) else {
+                break;
+            };
+            let Some(total) = count.checked_mul(size) else {
+                break;
+            };
+            if seen.contains(&typedef.ident.ident) {
+                break;
+            }
+            seen.push(typedef.ident.ident.clone());
+            count = total;
+            ty = typedef.val.0.clone();
+        }
+        (ty, count)
+    }
+
     /// returns the interface corresponding to the given name
     pub fn struct_lookup(&self, name: &str) -> Option<(Rc<Struct>, PathBuf)> {
         self.symbols
@@ -235,6 +548,21 @@ impl IDLStore {
             })
     }
 
+    /// returns the enum corresponding to the given name
+    ///
+    /// Enums declared within an interface are looked up as `<interface>::<enum>`.
+    pub fn enum_lookup(&self, name: &str) -> Option<(Rc<idlc_ast::Enum>, PathBuf)> {
+        self.symbols
+            .borrow()
+            .get(&Symbol::Enum(name.to_string()))
+            .map(|(node, tag)| {
+                let Node::Enum(e) = node.as_ref() else {
+                    unreachable!("ICE: Enum node expected.")
+                };
+                (Rc::new(e.clone()), tag.clone())
+            })
+    }
+
     /// returns the interface corresponding to the given name
     pub fn iface_lookup(&self, name: &str) -> Option<Rc<Interface>> {
         self.symbols
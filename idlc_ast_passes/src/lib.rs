@@ -38,13 +38,15 @@ pub enum Error {
     DuplicateDefinition { occ1: Ident, occ2: Ident },
     #[error("Couldn't find defintions for the symbol `{0}`")]
     UnresolvedSymbol(String),
-    #[error("Struct requirements not met: `{0}`")]
-    StructVerifier(#[from] struct_verifier::Error),
 }
 
 mod graph;
 
+pub mod consts;
 pub mod cycles;
+pub mod deprecated;
+pub mod enums;
 pub mod functions;
 pub mod idl_store;
 pub mod struct_verifier;
+pub mod typedefs;
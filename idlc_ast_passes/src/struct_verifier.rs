@@ -12,6 +12,7 @@
 use std::collections::{HashMap, HashSet};
 
 use idlc_ast::{Ident, Type};
+use idlc_errors::{codes, Diagnostic, Label};
 
 use crate::idl_store::IDLStore;
 
@@ -21,32 +22,20 @@ type Alignment = usize;
 #[derive(Debug, Clone, Copy)]
 pub struct StructVerifier;
 
-#[derive(Debug, Clone, thiserror::Error)]
-pub enum Error {
-    #[error("struct member `{member}` in `{parent}` was not aligned to required alignment `{alignment}`; offset is `{offset}`")]
-    StructMemberNotAligned {
-        member: String,
-        parent: String,
-        alignment: usize,
-        offset: usize,
-    },
-    #[error("struct `{parent}` is not aligned to it's natural alignment `{alignment}`; size is `{size}`")]
-    StructNotAligned {
-        parent: String,
-        alignment: usize,
-        size: usize,
-    },
-    #[error("struct `{parent}` contains duplicate field names: `{names:?}`")]
-    StructFieldSameName { parent: Ident, names: Vec<Ident> },
-    #[error("struct `{parent}` contains Object array: `{obj_arr}`. Object arrays are not allowed as struct fields.")]
-    StructFieldObjArray { parent: Ident, obj_arr: Ident },
-}
-
 impl StructVerifier {
-    pub fn run_pass(idl_store: &IDLStore, toposort: &[String]) -> Result<(), Error> {
+    /// Emits a diagnostic for every misaligned struct or field, duplicate
+    /// field and array of objects of the structs of `toposort`.
+    pub fn run_pass(idl_store: &IDLStore, toposort: &[String]) {
         let mut store: HashMap<String, (Size, Alignment)> = HashMap::new();
         for r#struct in toposort {
-            let (node, _) = idl_store.struct_lookup(r#struct).unwrap();
+            let (node, path) = idl_store.struct_lookup(r#struct).unwrap();
+            let error = |code, message: String, label: Label| {
+                idlc_errors::emit(
+                    Diagnostic::error(code, message)
+                        .with_file(&path)
+                        .with_label(label),
+                );
+            };
             let mut size = 0;
             let mut alignment = 0;
             let mut fields: HashSet<&Ident> = HashSet::new();
@@ -54,51 +43,60 @@ impl StructVerifier {
             for field in &node.fields {
                 let ident = &field.ident;
                 if let Some(existing) = fields.get(&ident) {
-                    let existing: Ident = (*existing).clone();
-                    return Err(Error::StructFieldSameName {
-                        parent: node.ident.clone(),
-                        names: vec![existing, ident.clone()],
-                    });
+                    idlc_errors::emit(
+                        Diagnostic::error(
+                            codes::DUPLICATE_FIELD,
+                            format!("struct `{}` contains duplicate field `{ident}`", node.ident),
+                        )
+                        .with_file(&path)
+                        .with_label(Label::primary(ident.span, "duplicate field"))
+                        .with_label(Label::secondary(existing.span, "first declared here")),
+                    );
                 }
                 fields.insert(ident);
 
-                let (ty, count) = field.r#type();
-                let count = count.get() as usize;
+                let (ty, array_size) = field.r#type();
+                let count = crate::consts::array_size(idl_store, array_size, None)
+                    .expect("ICE: Array sizes are verified by the `Consts` pass.");
+                let (ty, alias_count) = idl_store.resolve_typedef(ty);
+                let ty = &ty;
+                let count = count.get() as usize * alias_count.get() as usize;
 
-                // Checks for array fields
-                if count > 1 {
-                    match ty {
-                        // literally defined as 'interface' type
-                        Type::Interface => {
-                            return Err(Error::StructFieldObjArray {
-                                parent: node.ident.clone(),
-                                obj_arr: ident.clone(),
-                            });
-                        }
-                        // Custom type that is a known Mink interface
-                        Type::Custom(c) if idl_store.iface_lookup(c).is_some() => {
-                            return Err(Error::StructFieldObjArray {
-                                parent: node.ident.clone(),
-                                obj_arr: c.clone(),
-                            });
-                        }
-                        _ => {}
-                    }
+                // Checks for array fields, either literally of the `interface`
+                // type or of a custom type that is a known Mink interface.
+                let is_object = match ty {
+                    Type::Interface => true,
+                    Type::Custom(c) => idl_store.iface_lookup(c).is_some(),
+                    _ => false,
+                };
+                if count > 1 && is_object {
+                    error(
+                        codes::OBJECT_ARRAY_FIELD,
+                        format!(
+                            "struct `{}` contains Object array `{ident}`. Object arrays are not allowed as struct fields.",
+                            node.ident
+                        ),
+                        Label::primary(ident.span, "array of objects"),
+                    );
                 }
 
                 let (i_size, i_alignment) = match ty {
                     Type::Primitive(p) => (p.size(), p.alignment()),
-                    Type::Custom(c) => *store.get(&c.ident).unwrap(),
+                    Type::Custom(c) => match idl_store.enum_lookup(c) {
+                        Some((e, _)) => (e.r#type.size(), e.r#type.alignment()),
+                        None => *store.get(&c.ident).unwrap(),
+                    },
                     Type::Interface => (Type::interface_size(), Type::interface_align()),
                     _ => unreachable!(),
                 };
                 if size % i_alignment != 0 {
-                    return Err(Error::StructMemberNotAligned {
-                        member: field.ident.to_string(),
-                        parent: r#struct.to_string(),
-                        alignment: i_alignment,
-                        offset: size,
-                    });
+                    error(
+                        codes::UNALIGNED_FIELD,
+                        format!(
+                            "struct member `{ident}` in `{struct}` was not aligned to required alignment `{i_alignment}`; offset is `{size}`"
+                        ),
+                        Label::primary(ident.span, format!("at offset {size}")),
+                    );
                 }
 
                 size += i_size * count;
@@ -106,15 +104,15 @@ impl StructVerifier {
             }
 
             if size % alignment != 0 {
-                return Err(Error::StructNotAligned {
-                    parent: r#struct.to_string(),
-                    alignment,
-                    size,
-                });
+                error(
+                    codes::UNALIGNED_STRUCT,
+                    format!(
+                        "struct `{struct}` is not aligned to it's natural alignment `{alignment}`; size is `{size}`"
+                    ),
+                    Label::primary(node.ident.span, format!("{size} bytes")),
+                );
             }
             store.insert(r#struct.clone(), (size, alignment));
         }
-
-        Ok(())
     }
 }
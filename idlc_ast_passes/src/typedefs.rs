@@ -0,0 +1,199 @@
+// Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Validates typedef declarations and their uses.
+//!
+//! Typedefs alias a primitive, a struct, an enum or another typedef, their
+//! uses are resolved to the aliased type so the element counts of array
+//! typedefs multiply with the ones of the uses.
+
+use idlc_ast::{Ident, InterfaceNode, Node, Type, Typedef};
+use idlc_errors::{codes, Diagnostic, Label};
+
+use crate::{idl_store::IDLStore, CompilerPass};
+
+pub struct Typedefs<'ast> {
+    idl_store: &'ast IDLStore,
+}
+
+impl<'ast> Typedefs<'ast> {
+    #[must_use]
+    pub const fn new(idl_store: &'ast IDLStore) -> Self {
+        Self { idl_store }
+    }
+
+    fn verify(&self, typedef: &Typedef, ast: &idlc_ast::Ast) {
+        let error = |code, message: String, span, label: &str| {
+            idlc_errors::emit(
+                Diagnostic::error(code, message)
+                    .with_file(&ast.tag)
+                    .with_label(Label::primary(span, label)),
+            );
+        };
+        let ident = &typedef.ident;
+        let target = match &typedef.val.0 {
+            Type::Custom(target) => target,
+            Type::Interface => {
+                return error(
+                    codes::INVALID_TYPEDEF,
+                    format!("Typedef `{ident}` can't alias an interface"),
+                    ident.span,
+                    "aliases `interface`",
+                );
+            }
+            _ => return,
+        };
+        if self.idl_store.iface_lookup(target).is_some() {
+            return error(
+                codes::INVALID_TYPEDEF,
+                format!("Typedef `{ident}` can't alias interface `{target}`"),
+                target.span,
+                "interface",
+            );
+        }
+        let known = self.idl_store.typedef_lookup(target).is_some()
+            || self.idl_store.enum_lookup(target).is_some()
+            || self.idl_store.struct_lookup(target).is_some();
+        if !known {
+            return error(
+                codes::UNRESOLVED_SYMBOL,
+                format!("Identifier `{target}` not found"),
+                target.span,
+                "unknown type",
+            );
+        }
+        if self.is_cyclic(typedef) {
+            return error(
+                codes::INVALID_TYPEDEF,
+                format!("Typedef `{ident}` refers back to itself"),
+                target.span,
+                "cyclic typedef",
+            );
+        }
+        // Overflows further down the chain are reported on their own typedef.
+        let (resolved, count) = self.idl_store.resolve_typedef(&typedef.val.0);
+        let resolved_fully = !matches!(
+            &resolved,
+            Type::Custom(c) if self.idl_store.typedef_lookup(c).is_some()
+        );
+        // Invalid sizes are reported by the `Consts` pass.
+        let Ok(size) = crate::consts::array_size(self.idl_store, &typedef.val.1, None) else {
+            return;
+        };
+        if resolved_fully && count.checked_mul(size).is_none() {
+            error(
+                codes::INVALID_TYPEDEF,
+                format!(
+                    "Typedef `{ident}` has more than {} elements",
+                    idlc_ast::Count::MAX
+                ),
+                ident.span,
+                "too many elements",
+            );
+        }
+    }
+
+    /// Whether following the typedefs from `typedef` leads back to it.
+    fn is_cyclic(&self, typedef: &Typedef) -> bool {
+        let mut seen: Vec<Ident> = Vec::new();
+        let mut ty = typedef.val.0.clone();
+        while let Type::Custom(ident) = &ty {
+            if ident.ident == typedef.ident.ident {
+                return true;
+            }
+            if seen.contains(ident) {
+                return false;
+            }
+            let Some((next, _)) = self.idl_store.typedef_lookup(ident) else {
+                return false;
+            };
+            seen.push(ident.clone());
+            ty = next.val.0.clone();
+        }
+        false
+    }
+
+    fn verify_field(&self, field: &idlc_ast::StructField, ast: &idlc_ast::Ast) {
+        let Type::Custom(alias) = &field.val.0 else {
+            return;
+        };
+        if self.idl_store.typedef_lookup(alias).is_none() {
+            return;
+        }
+        let (_, count) = self.idl_store.resolve_typedef(&field.val.0);
+        let Ok(size) = crate::consts::array_size(self.idl_store, &field.val.1, None) else {
+            return;
+        };
+        if count.checked_mul(size).is_none() {
+            idlc_errors::emit(
+                Diagnostic::error(
+                    codes::INVALID_TYPEDEF_USE,
+                    format!(
+                        "Field `{}` has more than {} elements",
+                        field.ident,
+                        idlc_ast::Count::MAX
+                    ),
+                )
+                .with_file(&ast.tag)
+                .with_label(Label::primary(
+                    field.ident.span,
+                    format!("array of `{alias}`, which holds {count} elements"),
+                )),
+            );
+        }
+    }
+
+    /// Parameters can't be bounded arrays of values, neither can typedefs of
+    /// them.
+    fn verify_param(&self, param: &idlc_ast::Param, ast: &idlc_ast::Ast) {
+        let Type::Custom(alias) = param.as_ref() else {
+            return;
+        };
+        if self.idl_store.typedef_lookup(alias).is_none() {
+            return;
+        }
+        let (_, count) = self.idl_store.resolve_typedef(param.as_ref());
+        if count.get() > 1 {
+            idlc_errors::emit(
+                Diagnostic::error(
+                    codes::INVALID_TYPEDEF_USE,
+                    format!("Array typedef `{alias}` can't be used as a parameter"),
+                )
+                .with_file(&ast.tag)
+                .with_label(Label::primary(
+                    alias.span,
+                    format!("array of {count} elements"),
+                ))
+                .with_note("wrap it in a struct to pass it by value"),
+            );
+        }
+    }
+}
+
+impl<'ast> CompilerPass<'ast> for Typedefs<'_> {
+    type Output = ();
+
+    fn run_pass(&'ast mut self, ast: &'ast idlc_ast::Ast) -> Result<Self::Output, crate::Error> {
+        for node in &ast.nodes {
+            match node.as_ref() {
+                Node::Typedef(t) => self.verify(t, ast),
+                Node::Struct(s) => {
+                    for field in &s.fields {
+                        self.verify_field(field, ast);
+                    }
+                }
+                Node::Interface(i) => {
+                    for node in &i.nodes {
+                        if let InterfaceNode::Function(f) = node {
+                            for param in &f.params {
+                                self.verify_param(param, ast);
+                            }
+                        }
+                    }
+                }
+                _ => (),
+            }
+        }
+        Ok(())
+    }
+}
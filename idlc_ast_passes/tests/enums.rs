@@ -0,0 +1,73 @@
+// Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
+// SPDX-License-Identifier: BSD-3-Clause
+
+use idlc_ast_passes::{enums::Enums, CompilerPass};
+
+fn verify(idl: &str) -> Vec<idlc_errors::Diagnostic> {
+    let name = std::path::PathBuf::from("enums.idl");
+    let ast = idlc_ast::from_string(name, idl, true).unwrap();
+    Enums::new().run_pass(&ast).unwrap();
+    idlc_errors::take_errors()
+}
+
+#[test]
+fn valid() {
+    let diagnostics = verify(
+        r"
+        enum Color : uint8 { RED, GREEN = 254, BLUE };
+        interface IFoo {
+            enum Mode : int8 { LOW = -128, HIGH = 127 };
+        };",
+    );
+    assert!(diagnostics.is_empty(), "{diagnostics:?}");
+}
+
+#[test]
+fn duplicate_variant() {
+    let diagnostics = verify("enum Color : uint32 { RED, GREEN, RED };");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0].code,
+        idlc_errors::codes::DUPLICATE_ENUM_VARIANT
+    );
+    assert_eq!(
+        diagnostics[0].message,
+        "Enum `Color` has duplicate variant `RED`"
+    );
+}
+
+#[test]
+fn duplicate_value() {
+    let diagnostics = verify(
+        r"
+        interface IFoo {
+            enum Color : uint32 { RED = 2, GREEN = 1, BLUE };
+        };",
+    );
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0].code,
+        idlc_errors::codes::DUPLICATE_ENUM_VARIANT
+    );
+    assert_eq!(
+        diagnostics[0].message,
+        "Enum `Color` variants `RED` and `BLUE` have the same value `2`"
+    );
+}
+
+#[test]
+fn out_of_range() {
+    for idl in [
+        "enum Color : uint8 { RED = 256 };",
+        "enum Color : uint8 { RED = -1 };",
+        "enum Color : int8 { RED = 127, GREEN };",
+        "enum Color : uint32 { RED = 1.5 };",
+    ] {
+        let diagnostics = verify(idl);
+        assert_eq!(diagnostics.len(), 1, "{idl}");
+        assert_eq!(
+            diagnostics[0].code,
+            idlc_errors::codes::ENUM_VALUE_OUT_OF_RANGE
+        );
+    }
+}
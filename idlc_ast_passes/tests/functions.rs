@@ -8,6 +8,7 @@ fn verify(idl: &'static str) {
     let node = idlc_ast::from_string("test.idl".into(), idl, true).unwrap();
     let mut functions = Functions::new();
     functions.run_pass(&node).unwrap();
+    idlc_errors::assert_no_errors();
 }
 
 #[should_panic = "Function `Duplicates::foo` has duplicate parameter `param1`"]
@@ -32,7 +32,9 @@ fn verify(
         .expect("Invalid input file.");
 
     let ast = store.get_or_insert(&input_file);
-    store.run_pass(&ast)
+    let result = store.run_pass(&ast);
+    idlc_errors::assert_no_errors();
+    result
 }
 
 fn verify_memory(
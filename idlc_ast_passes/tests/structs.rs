@@ -6,16 +6,24 @@ use idlc_ast_passes::*;
 
 use crate::idl_store::IDLStore;
 
-fn verify(idl: &'static str) -> Result<(), idlc_ast_passes::Error> {
+fn verify(idl: &'static str) -> Result<(), Vec<String>> {
     let store = IDLStore::new();
     let name = std::path::PathBuf::from("struct-verifier.idl");
     let node = idlc_ast::from_string(name.clone(), idl, true).unwrap();
     store.insert_canonical(&name, &node);
+    idlc_errors::assert_no_errors();
     let mut cycles = cycles::Cycles::new(&store);
-    let toposort = cycles.run_pass(&node)?;
-    Ok(idlc_ast_passes::struct_verifier::StructVerifier::run_pass(
-        &store, &toposort,
-    )?)
+    let toposort = cycles.run_pass(&node).map_err(|e| vec![e.to_string()])?;
+    idlc_ast_passes::struct_verifier::StructVerifier::run_pass(&store, &toposort);
+    let errors: Vec<String> = idlc_errors::take_errors()
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
 }
 
 #[test]
@@ -190,6 +198,34 @@ fn field_same_name() {
     .is_err());
 }
 
+#[test]
+fn reports_every_error() {
+    let errors = verify(
+        r"
+        interface Foo {};
+        struct s1 {
+            uint8 a;
+            uint16 b;
+            uint8 a;
+            Foo[2] f;
+        };
+        struct s2 {
+            uint32 c;
+            uint8 d;
+        };",
+    )
+    .unwrap_err();
+    // Structs are verified in topological order, which doesn't settle
+    // independent ones.
+    let mut codes: Vec<&str> = errors.iter().map(|e| &e[6..11]).collect();
+    codes.sort_unstable();
+    assert_eq!(
+        codes,
+        ["E0031", "E0032", "E0033", "E0033", "E0034", "E0034"],
+        "{errors:#?}"
+    );
+}
+
 #[test]
 #[should_panic(expected = "Duplicate symbol detected")]
 fn struct_interface_same_name() {
@@ -0,0 +1,206 @@
+// Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Compiles Mink IDL files into Rust from a Cargo build script.
+//!
+//! ```no_run
+//! // In `fn main` of build.rs
+//! idlc_build::Config::new()
+//!     .include("idl/")
+//!     .compile(&["ITest.idl"])
+//!     .unwrap();
+//! ```
+//! The generated code refers to the interfaces it inherits from through
+//! `crate::interfaces`, so the generated `mod.rs` is expected there:
+//! ```ignore
+//! pub mod interfaces {
+//!     include!(concat!(env!("OUT_DIR"), "/mod.rs"));
+//! }
+//! ```
+//! Every IDL file defining a base interface has to be compiled as well.
+//!
+//! The runtime is expected in a `crate::object` module, unless
+//! [`Config::runtime_path`] names another one such as the `mink_object` crate.
+//! [`Config::no_std`] generates code for `no_std` crates.
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use idlc::{AstCache, Compiler, Language, Options};
+use idlc_errors::{Diagnostic, Severity};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("`OUT_DIR` isn't set, is this running from a build script?")]
+    OutDir,
+    #[error("`{}` wasn't found in the current directory nor in the include paths", .0.display())]
+    NotFound(PathBuf),
+    #[error("Couldn't write `{}`: {1}", .0.display())]
+    Io(PathBuf, #[source] std::io::Error),
+    #[error("{}", render(.0))]
+    Compile(Vec<Diagnostic>),
+}
+
+fn render(diagnostics: &[Diagnostic]) -> String {
+    diagnostics
+        .iter()
+        .map(idlc_errors::render)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Config {
+    includes: Vec<PathBuf>,
+    out_dir: Option<PathBuf>,
+    allow_undefined_behavior: bool,
+    runtime_path: Option<String>,
+    no_std: bool,
+    mocks: bool,
+    asynchronous: bool,
+    serde: bool,
+}
+
+impl Config {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `dir` to the include path, inputs are looked up there as well.
+    pub fn include(&mut self, dir: impl AsRef<Path>) -> &mut Self {
+        self.includes.push(dir.as_ref().to_path_buf());
+        self
+    }
+
+    /// Writes the generated files to `dir` instead of `OUT_DIR`.
+    pub fn out_dir(&mut self, dir: impl AsRef<Path>) -> &mut Self {
+        self.out_dir = Some(dir.as_ref().to_path_buf());
+        self
+    }
+
+    /// Allows integer widths to overflow, see `idlc --help`.
+    pub fn allow_undefined_behavior(&mut self, allow: bool) -> &mut Self {
+        self.allow_undefined_behavior = allow;
+        self
+    }
+
+    /// Refers to the runtime at `path`, e.g. `::mink_object`, instead of a
+    /// `crate::object` module.
+    pub fn runtime_path(&mut self, path: impl Into<String>) -> &mut Self {
+        self.runtime_path = Some(path.into());
+        self
+    }
+
+    /// Only depends on `core` and `alloc` in the generated code, see
+    /// `idlc --help`.
+    pub fn no_std(&mut self, no_std: bool) -> &mut Self {
+        self.no_std = no_std;
+        self
+    }
+
+    /// Also generates a `Mock<interface>` of each interface, e.g. for the
+    /// bindings of tests.
+    pub fn mocks(&mut self, mocks: bool) -> &mut Self {
+        self.mocks = mocks;
+        self
+    }
+
+    /// Also generates the async flavor of the proxies and of the
+    /// implementation traits, see `idlc --help`.
+    pub fn asynchronous(&mut self, asynchronous: bool) -> &mut Self {
+        self.asynchronous = asynchronous;
+        self
+    }
+
+    /// Also derives serde on the structs that don't contain objects, see
+    /// `idlc --help`.
+    pub fn serde(&mut self, serde: bool) -> &mut Self {
+        self.serde = serde;
+        self
+    }
+
+    /// Generates the Rust code of `inputs`, along with a `mod.rs` declaring a
+    /// module for each generated file.
+    ///
+    /// Cargo is told to rerun the build script when any of the inputs or of
+    /// their includes changes, and warnings are forwarded to it.
+    pub fn compile(&self, inputs: &[impl AsRef<Path>]) -> Result<(), Error> {
+        let out_dir = match &self.out_dir {
+            Some(dir) => dir.clone(),
+            None => std::env::var_os("OUT_DIR").ok_or(Error::OutDir)?.into(),
+        };
+        std::fs::create_dir_all(&out_dir).map_err(|e| Error::Io(out_dir.clone(), e))?;
+
+        let options = Options {
+            rust_runtime_path: self.runtime_path.clone(),
+            rust_no_std: self.no_std,
+            mock: self.mocks,
+            rust_async: self.asynchronous,
+            serde: self.serde,
+            ..Options::default()
+        };
+        let cache = AstCache::default();
+        let mut modules = BTreeSet::new();
+        let mut dependencies = BTreeSet::new();
+        for input in inputs {
+            let input = self.find(input.as_ref())?;
+            let mut includes = self.includes.clone();
+            includes.extend(input.parent().map(Path::to_path_buf));
+
+            let result = Compiler::new(
+                input,
+                out_dir.clone(),
+                includes,
+                Language::Rust,
+                self.allow_undefined_behavior,
+            )
+            .with_cache(cache.clone())
+            .compile_with_dependencies(Language::Rust, &options);
+            for diagnostic in idlc_errors::take_diagnostics() {
+                if diagnostic.severity == Severity::Warning {
+                    println!("cargo:warning={}", diagnostic.message);
+                }
+            }
+            let (files, deps) = result.map_err(Error::Compile)?;
+
+            for file in files {
+                let path = out_dir.join(&file.path);
+                std::fs::write(&path, file.contents).map_err(|e| Error::Io(path.clone(), e))?;
+                modules.insert(path);
+            }
+            dependencies.extend(deps);
+        }
+
+        for dependency in &dependencies {
+            println!("cargo:rerun-if-changed={}", dependency.display());
+        }
+
+        let mod_rs = out_dir.join("mod.rs");
+        std::fs::write(&mod_rs, module(&modules)).map_err(|e| Error::Io(mod_rs, e))
+    }
+
+    /// Looks `input` up in the current directory, then in the include path.
+    fn find(&self, input: &Path) -> Result<PathBuf, Error> {
+        std::iter::once(input.to_path_buf())
+            .chain(self.includes.iter().map(|dir| dir.join(input)))
+            .find_map(|path| path.canonicalize().ok())
+            .ok_or_else(|| Error::NotFound(input.to_path_buf()))
+    }
+}
+
+/// Declares a module named after each of `files`.
+fn module(files: &BTreeSet<PathBuf>) -> String {
+    let mut module = String::from("// Generated by idlc_build, do not edit.\n");
+    for file in files {
+        let name = file
+            .file_stem()
+            .expect("generated files are named")
+            .to_string_lossy();
+        module.push_str(&format!(
+            "\n#[allow(unused, deprecated, nonstandard_style, clippy::all, clippy::pedantic, clippy::nursery)]\npub mod {name} {{\n    include!({:?});\n}}\n",
+            file.display().to_string()
+        ));
+    }
+    module
+}
@@ -0,0 +1,65 @@
+// Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::path::{Path, PathBuf};
+
+fn out_dir(name: &str) -> PathBuf {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .expect("clock should be monotonic since epoch")
+        .as_nanos();
+    std::env::temp_dir().join(format!("idlc-build-{name}-{}-{nanos}", std::process::id()))
+}
+
+fn idl_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../tests/idl")
+}
+
+#[test]
+fn generates_modules() {
+    let out = out_dir("ok");
+    idlc_build::Config::new()
+        .include(idl_dir())
+        .out_dir(&out)
+        .compile(&["ITest.idl", "ITest3.idl"])
+        .expect("inputs should compile");
+
+    assert!(out.join("itest.rs").is_file());
+    assert!(out.join("itest3.rs").is_file());
+    let module = std::fs::read_to_string(out.join("mod.rs")).expect("mod.rs should be written");
+    assert!(module.contains("pub mod itest {"), "{module}");
+    assert!(module.contains("pub mod itest3 {"), "{module}");
+    assert!(
+        module.contains(&format!(
+            "include!({:?});",
+            out.join("itest.rs").display().to_string()
+        )),
+        "{module}"
+    );
+}
+
+#[test]
+fn reports_diagnostics() {
+    let dir = out_dir("err");
+    std::fs::create_dir_all(&dir).expect("temp dir should be creatable");
+    std::fs::write(dir.join("IBad.idl"), "include \"missing.idl\"")
+        .expect("idl should be writable");
+
+    let error = idlc_build::Config::new()
+        .include(&dir)
+        .out_dir(dir.join("out"))
+        .compile(&["IBad.idl"])
+        .expect_err("include should be missing");
+    let idlc_build::Error::Compile(diagnostics) = &error else {
+        panic!("unexpected error: {error}");
+    };
+    assert_eq!(diagnostics[0].code, idlc_errors::codes::INCLUDE_NOT_FOUND);
+    assert!(error.to_string().contains("missing.idl"), "{error}");
+
+    assert!(matches!(
+        idlc_build::Config::new()
+            .out_dir(dir.join("out"))
+            .compile(&["nowhere.idl"]),
+        Err(idlc_build::Error::NotFound(_))
+    ));
+}
@@ -0,0 +1,49 @@
+// Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Deprecated declarations in the generated code.
+
+use idlc_mir::{Interface, InterfaceNode, Mir, Node, Struct, StructInner, Type};
+
+/// Whether the code generated for `mir` declares or refers to deprecated
+/// structs, interfaces or methods, which the backends then mark.
+///
+/// The generated code's own uses of them aren't worth a warning, backends
+/// silence them when this holds. Interfaces are only referred to by name in
+/// the MIR, those declared in other files aren't accounted for.
+#[must_use]
+pub fn is_used(mir: &Mir) -> bool {
+    mir.nodes.iter().any(|node| match node {
+        Node::Struct(s) => is_struct_used(s.as_ref()),
+        Node::Interface(i) => is_interface_used(i),
+        _ => false,
+    })
+}
+
+fn is_struct_used(r#struct: &StructInner) -> bool {
+    r#struct.deprecated.is_some()
+        || r#struct
+            .fields
+            .iter()
+            .any(|field| is_type_used(&field.val.0))
+}
+
+fn is_type_used(ty: &Type) -> bool {
+    match ty {
+        Type::Struct(Struct::Big(s) | Struct::Small(s)) => is_struct_used(s),
+        _ => false,
+    }
+}
+
+fn is_interface_used(interface: &Interface) -> bool {
+    interface.iter().any(|iface| {
+        iface.deprecated.is_some()
+            || iface.nodes.iter().any(|node| match node {
+                InterfaceNode::Function(f) => {
+                    f.deprecation().is_some()
+                        || f.params.iter().any(|param| is_type_used(param.r#type()))
+                }
+                _ => false,
+            })
+    })
+}
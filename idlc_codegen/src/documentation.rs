@@ -56,12 +56,30 @@ impl AsRef<str> for Documentation {
 impl Documentation {
     #[must_use]
     pub fn new(function: &idlc_mir::Function, style: DocumentationStyle) -> Self {
-        function.doc.as_ref().map_or_else(
+        Self::with_doc(function.doc.as_deref(), style)
+    }
+
+    /// Documentation of any declaration, empty when it has none.
+    #[must_use]
+    pub fn with_doc(doc: Option<&str>, style: DocumentationStyle) -> Self {
+        doc.map_or_else(
             || Self(String::new()),
             |doc| Self::new_with_idlc_doc(doc, style),
         )
     }
 
+    /// Returns the documentation on its own lines, each prefixed with
+    /// `indent`, or nothing when there is no documentation.
+    #[must_use]
+    pub fn indented(&self, indent: &str) -> String {
+        self.0.lines().fold(String::new(), |mut result, line| {
+            result.push_str(indent);
+            result.push_str(line);
+            result.push('\n');
+            result
+        })
+    }
+
     fn new_with_idlc_doc(doc: &str, style: DocumentationStyle) -> Self {
         let mut documentation = style.start().to_string();
         documentation.push('\n');
@@ -163,6 +181,17 @@ starts with nothing
         );
     }
 
+    #[test]
+    fn indented() {
+        let documentation = Documentation::with_doc(Some("*\n * Field\n *"), DocumentationStyle::C);
+        assert_eq!(
+            documentation.indented("    "),
+            "    /*\n    *\n    *  Field\n    *\n    */\n"
+        );
+        let documentation = Documentation::with_doc(None, DocumentationStyle::C);
+        assert_eq!(documentation.indented("    "), "");
+    }
+
     #[test]
     fn java() {
         let documentation =
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: BSD-3-Clause
 
 use idlc_mir::{
-    Count, Function, Ident, ParamTypeIn, ParamTypeOut, Primitive, Struct, StructInner, Type,
+    Count, Enum, Function, Ident, ParamTypeIn, ParamTypeOut, Primitive, Struct, StructInner, Type,
 };
 
 use crate::serialization::PackedPrimitives;
@@ -13,20 +13,47 @@ pub trait ParameterVisitor {
     fn visit_input_untyped_buffer(&mut self, ident: &Ident) {
         self.visit_input_primitive_buffer(ident, Primitive::Uint8);
     }
+    /// UTF-8 string of at most `max_len` bytes, sent as an untyped buffer
+    /// whose maximum length is checked like the one of a buffer.
+    fn visit_input_string(&mut self, ident: &Ident, max_len: Option<Count>) {
+        if let Some(max_len) = max_len {
+            self.visit_input_max_len(ident, 1, max_len);
+        }
+        self.visit_input_untyped_buffer(ident);
+    }
     fn visit_input_struct_buffer(&mut self, ident: &Ident, ty: &StructInner) {}
+    fn visit_input_enum_buffer(&mut self, ident: &Ident, ty: &Enum) {
+        self.visit_input_primitive_buffer(ident, ty.r#type);
+    }
     fn visit_input_primitive(&mut self, ident: &Ident, ty: Primitive) {}
+    fn visit_input_enum(&mut self, ident: &Ident, ty: &Enum) {
+        self.visit_input_primitive(ident, ty.r#type);
+    }
     fn visit_input_bundled(&mut self, packed_primitives: &PackedPrimitives) {}
     fn visit_input_big_struct(&mut self, ident: &Ident, ty: &StructInner) {}
     fn visit_input_small_struct(&mut self, ident: &Ident, ty: &StructInner) {}
     fn visit_input_object(&mut self, ident: &Ident, ty: Option<&str>) {}
     fn visit_input_object_array(&mut self, ident: &Ident, ty: Option<&str>, cnt: Count) {}
+    /// Visited right before the input buffer `ident` holding at most `max_len`
+    /// elements of `element_size` bytes.
+    fn visit_input_max_len(&mut self, ident: &Ident, element_size: usize, max_len: Count) {}
 
     fn visit_output_primitive_buffer(&mut self, ident: &Ident, ty: Primitive) {}
     fn visit_output_untyped_buffer(&mut self, ident: &Ident) {
         self.visit_output_primitive_buffer(ident, Primitive::Uint8);
     }
+    /// UTF-8 string of at most `max_len` bytes, sent as an untyped buffer.
+    fn visit_output_string(&mut self, ident: &Ident, max_len: Count) {
+        self.visit_output_untyped_buffer(ident);
+    }
     fn visit_output_struct_buffer(&mut self, ident: &Ident, ty: &StructInner) {}
+    fn visit_output_enum_buffer(&mut self, ident: &Ident, ty: &Enum) {
+        self.visit_output_primitive_buffer(ident, ty.r#type);
+    }
     fn visit_output_primitive(&mut self, ident: &Ident, ty: Primitive) {}
+    fn visit_output_enum(&mut self, ident: &Ident, ty: &Enum) {
+        self.visit_output_primitive(ident, ty.r#type);
+    }
     fn visit_output_bundled(&mut self, packed_primitives: &PackedPrimitives) {}
     fn visit_output_big_struct(&mut self, ident: &Ident, ty: &StructInner) {}
     fn visit_output_small_struct(&mut self, ident: &Ident, ty: &StructInner) {}
@@ -48,20 +75,46 @@ impl<'a> Param<'a> {
                 Param::InputBundledPrimitives(b) => visitor.visit_input_bundled(b),
                 Param::OutputBundledPrimitives(b) => visitor.visit_output_bundled(b),
                 Param::Params(p) => match p {
-                    idlc_mir::Param::In { r#type, ident } => match r#type {
+                    idlc_mir::Param::In {
+                        r#type,
+                        ident,
+                        max_len,
+                        ..
+                    } => match r#type {
                         ParamTypeIn::Array(t, cnt) => match t {
-                            &Type::Primitive(p) => visitor.visit_input_primitive_buffer(ident, p),
+                            &Type::Primitive(p) => {
+                                if let Some(max_len) = *max_len {
+                                    visitor.visit_input_max_len(ident, p.size(), max_len);
+                                }
+                                visitor.visit_input_primitive_buffer(ident, p);
+                            }
+                            Type::Enum(e) => {
+                                if let Some(max_len) = *max_len {
+                                    visitor.visit_input_max_len(ident, e.r#type.size(), max_len);
+                                }
+                                visitor.visit_input_enum_buffer(ident, e);
+                            }
                             Type::Interface(i) => {
                                 visitor.visit_input_object_array(ident, i.as_deref(), cnt.unwrap())
                             }
                             Type::Struct(Struct::Big(s) | Struct::Small(s)) => {
-                                visitor.visit_input_struct_buffer(ident, s)
+                                if let Some(max_len) = *max_len {
+                                    visitor.visit_input_max_len(ident, s.size(), max_len);
+                                }
+                                visitor.visit_input_struct_buffer(ident, s);
                             }
                             _ => unreachable!(),
                         },
                         ParamTypeIn::Value(t) => match t {
-                            Type::UntypedBuffer => visitor.visit_input_untyped_buffer(ident),
+                            Type::UntypedBuffer => {
+                                if let Some(max_len) = *max_len {
+                                    visitor.visit_input_max_len(ident, 1, max_len);
+                                }
+                                visitor.visit_input_untyped_buffer(ident);
+                            }
+                            &Type::String(bound) => visitor.visit_input_string(ident, bound),
                             &Type::Primitive(p) => visitor.visit_input_primitive(ident, p),
+                            Type::Enum(e) => visitor.visit_input_enum(ident, e),
                             Type::Interface(i) => visitor.visit_input_object(ident, i.as_deref()),
                             Type::Struct(Struct::Big(s)) => {
                                 visitor.visit_input_big_struct(ident, s);
@@ -71,9 +124,10 @@ impl<'a> Param<'a> {
                             }
                         },
                     },
-                    idlc_mir::Param::Out { r#type, ident } => match r#type {
+                    idlc_mir::Param::Out { r#type, ident, .. } => match r#type {
                         ParamTypeOut::Array(t, cnt) => match t {
                             &Type::Primitive(p) => visitor.visit_output_primitive_buffer(ident, p),
+                            Type::Enum(e) => visitor.visit_output_enum_buffer(ident, e),
                             Type::Interface(i) => {
                                 visitor.visit_output_object_array(ident, i.as_deref(), cnt.unwrap())
                             }
@@ -84,7 +138,11 @@ impl<'a> Param<'a> {
                         },
                         ParamTypeOut::Reference(t) => match t {
                             Type::UntypedBuffer => visitor.visit_output_untyped_buffer(ident),
+                            &Type::String(bound) => {
+                                visitor.visit_output_string(ident, bound.unwrap());
+                            }
                             &Type::Primitive(p) => visitor.visit_output_primitive(ident, p),
+                            Type::Enum(e) => visitor.visit_output_enum(ident, e),
                             Type::Interface(i) => visitor.visit_output_object(ident, i.as_deref()),
                             Type::Struct(Struct::Big(s)) => {
                                 visitor.visit_output_big_struct(ident, s);
@@ -108,14 +166,10 @@ impl<'a> Param<'a> {
             params
                 .iter()
                 .filter(|&x| {
-                    !(packed_primitives.n_inputs() > 1
-                        && x.is_input()
-                        && (x.is_primitive_value() || x.is_small_struct_value()))
+                    !(packed_primitives.n_inputs() > 1 && x.is_input() && x.is_bundled_value())
                 })
                 .filter(|x| {
-                    !(packed_primitives.n_outputs() > 1
-                        && x.is_output()
-                        && (x.is_primitive_value() || x.is_small_struct_value()))
+                    !(packed_primitives.n_outputs() > 1 && x.is_output() && x.is_bundled_value())
                 })
                 .map(Param::Params),
         );
@@ -124,6 +178,7 @@ impl<'a> Param<'a> {
             let me = idlc_mir::Param::Out {
                 r#type: idlc_mir::ParamTypeOut::Array(Type::Primitive(Primitive::Uint8), None),
                 ident: idlc_mir::Ident::new_without_span(String::new()),
+                alias: None,
             };
             let idx = out.iter().position(|x| {
                 if let Param::Params(p) = *x {
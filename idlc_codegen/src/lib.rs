@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: BSD-3-Clause
 
 pub mod counts;
+pub mod deprecation;
 pub mod documentation;
 pub mod functions;
 pub mod marking;
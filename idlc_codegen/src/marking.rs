@@ -6,6 +6,8 @@ pub enum MarkingStyle {
     Rust,
     C,
     Java,
+    /// HTML comment, also used for Markdown.
+    Html,
 }
 
 impl MarkingStyle {
@@ -13,6 +15,7 @@ impl MarkingStyle {
         match self {
             Self::Rust | Self::C => "",
             Self::Java => "/*\n",
+            Self::Html => "<!--\n",
         }
     }
 
@@ -20,6 +23,7 @@ impl MarkingStyle {
         match self {
             Self::Rust | Self::C => "",
             Self::Java => "*/\n",
+            Self::Html => "-->\n",
         }
     }
 
@@ -27,6 +31,7 @@ impl MarkingStyle {
         match self {
             Self::Rust | Self::C => "//",
             Self::Java => "*",
+            Self::Html => "",
         }
     }
 }
@@ -115,6 +120,21 @@ Confidential and Proprietary - Qualcomm Technologies, Inc.
 * Confidential and Proprietary - Qualcomm Technologies, Inc.
 */
 
+"
+        );
+    }
+
+    #[test]
+    fn html() {
+        let marking = Marking::new(MARKING, MarkingStyle::Html);
+        assert_eq!(
+            marking.as_ref(),
+            r"<!--
+ Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
+ All rights reserved.
+ Confidential and Proprietary - Qualcomm Technologies, Inc.
+-->
+
 "
         );
     }
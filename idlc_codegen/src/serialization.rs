@@ -1,29 +1,47 @@
 // Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
 // SPDX-License-Identifier: BSD-3-Clause
 
-use std::sync::OnceLock;
-static BUNDLE_PARAMS_BY_SIZE: OnceLock<bool> = OnceLock::new();
+use std::cell::Cell;
 
-pub fn init(bundle_params_by_size: bool) {
-    // Set global static parameter for PackedPrimitives
-    BUNDLE_PARAMS_BY_SIZE.set(bundle_params_by_size).unwrap();
+thread_local! {
+    static BUNDLE_PARAMS_BY_SIZE: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Runs `f` with the bundled parameters of [`PackedPrimitives`] ordered by
+/// size if `bundle_params_by_size`, by alignment otherwise.
+///
+/// The setting only holds on the current thread, until `f` returns.
+pub fn with_bundling<R>(bundle_params_by_size: bool, f: impl FnOnce() -> R) -> R {
+    struct Restore(bool);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            BUNDLE_PARAMS_BY_SIZE.set(self.0);
+        }
+    }
+
+    let _restore = Restore(BUNDLE_PARAMS_BY_SIZE.replace(bundle_params_by_size));
+    f()
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Type {
     Primitive(idlc_mir::Primitive),
+    /// Bundled like its underlying primitive.
+    Enum(idlc_mir::Enum),
     SmallStruct(idlc_mir::StructInner),
 }
 impl Type {
     pub fn size(&self) -> usize {
         match self {
             Self::Primitive(p) => p.size(),
+            Self::Enum(e) => e.r#type.size(),
             Self::SmallStruct(s) => s.size(),
         }
     }
     pub fn align(&self) -> usize {
         match self {
             Self::Primitive(p) => p.align(),
+            Self::Enum(e) => e.r#type.align(),
             Self::SmallStruct(s) => s.align(),
         }
     }
@@ -63,6 +81,13 @@ impl super::functions::ParameterVisitor for PackedPrimitives {
         self.input_size += ty.size();
     }
 
+    fn visit_input_enum(&mut self, ident: &idlc_mir::Ident, ty: &idlc_mir::Enum) {
+        let nth_param = self.inputs.len();
+        self.inputs
+            .push(Pair::new(ident, Type::Enum(ty.clone()), nth_param));
+        self.input_size += ty.r#type.size();
+    }
+
     fn visit_input_small_struct(&mut self, ident: &idlc_mir::Ident, ty: &idlc_mir::StructInner) {
         let nth_param = self.inputs.len();
         self.inputs
@@ -77,6 +102,13 @@ impl super::functions::ParameterVisitor for PackedPrimitives {
         self.output_size += ty.size();
     }
 
+    fn visit_output_enum(&mut self, ident: &idlc_mir::Ident, ty: &idlc_mir::Enum) {
+        let nth_param = self.outputs.len();
+        self.outputs
+            .push(Pair::new(ident, Type::Enum(ty.clone()), nth_param));
+        self.output_size += ty.r#type.size();
+    }
+
     fn visit_output_small_struct(&mut self, ident: &idlc_mir::Ident, ty: &idlc_mir::StructInner) {
         let nth_param = self.outputs.len();
         self.outputs
@@ -91,10 +123,7 @@ impl PackedPrimitives {
         let mut me = Self::default();
         super::functions::visit_params(function, &mut me);
 
-        if *BUNDLE_PARAMS_BY_SIZE
-            .get()
-            .expect("BUNDLE_PARAMS_BY_SIZE not initialized")
-        {
+        if BUNDLE_PARAMS_BY_SIZE.get() {
             // Sort by size for compatibility with previous (buggy) behavior
             me.inputs.sort_by_key(|b| std::cmp::Reverse(b.ty.size()));
             me.outputs.sort_by_key(|b| std::cmp::Reverse(b.ty.size()));
@@ -137,6 +166,12 @@ impl PackedPrimitives {
         self.input_size
     }
 
+    /// Byte ranges of the `bool`s within the bundled input buffer.
+    #[must_use]
+    pub fn input_bools(&self) -> Vec<std::ops::Range<usize>> {
+        bools(&self.inputs)
+    }
+
     #[must_use]
     pub fn outputs_by_idents(&self) -> impl ExactSizeIterator<Item = (&idlc_mir::Ident, &Type)> {
         self.outputs.iter().map(|pair| (&pair.ident, &pair.ty))
@@ -166,4 +201,31 @@ impl PackedPrimitives {
     pub const fn packed_output_size(&self) -> usize {
         self.output_size
     }
+
+    /// Byte ranges of the `bool`s within the bundled output buffer.
+    #[must_use]
+    pub fn output_bools(&self) -> Vec<std::ops::Range<usize>> {
+        bools(&self.outputs)
+    }
+}
+
+/// Byte ranges of the `bool`s within the bundled buffer of `pairs`.
+fn bools(pairs: &[Pair]) -> Vec<std::ops::Range<usize>> {
+    let mut bools = Vec::new();
+    let mut offset = 0;
+    for pair in pairs {
+        match &pair.ty {
+            Type::Primitive(idlc_mir::Primitive::Bool) => {
+                idlc_mir::push_range(&mut bools, offset..offset + 1);
+            }
+            Type::SmallStruct(s) => {
+                for range in s.bools() {
+                    idlc_mir::push_range(&mut bools, offset + range.start..offset + range.end);
+                }
+            }
+            Type::Primitive(_) | Type::Enum(_) => {}
+        }
+        offset += pair.ty.size();
+    }
+    bools
 }
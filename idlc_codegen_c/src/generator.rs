@@ -6,7 +6,10 @@ use idlc_mir::Node;
 use idlc_codegen::MINKIDL_HEADER_COMMENT;
 
 use crate::{
-    globals::{emit_const, emit_include, emit_struct},
+    globals::{
+        allow_deprecated, deprecated, emit_const, emit_enum, emit_include, emit_struct,
+        emit_typedef,
+    },
     interface::{emit_interface_impl, emit_interface_invoke},
 };
 
@@ -25,7 +28,6 @@ impl Generator {
 impl idlc_codegen::SplitInvokeGenerator for Generator {
     fn generate_implementation(&self, mir: &idlc_mir::Mir) -> String {
         let mut result = String::new();
-        result.push_str(&generate_common());
 
         for node in &mir.nodes {
             match node {
@@ -36,7 +38,13 @@ impl idlc_codegen::SplitInvokeGenerator for Generator {
                     result.push_str(&emit_const(c));
                 }
                 Node::Struct(s) => {
-                    result.push_str(&emit_struct(s.as_ref()));
+                    result.push_str(&emit_struct(s.as_ref(), deprecated));
+                }
+                Node::Enum(e) => {
+                    result.push_str(&emit_enum(e));
+                }
+                Node::Typedef(t) => {
+                    result.push_str(&emit_typedef(t));
                 }
                 Node::Interface(i) => {
                     result.push_str(&emit_interface_impl(i, self.is_no_typed_objects));
@@ -44,14 +52,12 @@ impl idlc_codegen::SplitInvokeGenerator for Generator {
             }
         }
 
-        result
+        generate_common() + &allow_deprecated(mir, result)
     }
 
     fn generate_invoke(&self, mir: &idlc_mir::Mir) -> String {
-        let mut result = generate_common();
-
         let input_name = &mir.tag.file_stem().unwrap().to_str().unwrap();
-        result.push_str(&format!("#include \"{}.h\"\n", input_name));
+        let mut result = format!("#include \"{}.h\"\n", input_name);
 
         for node in &mir.nodes {
             match node {
@@ -65,7 +71,7 @@ impl idlc_codegen::SplitInvokeGenerator for Generator {
             }
         }
 
-        result
+        generate_common() + &allow_deprecated(mir, result)
     }
 }
 
@@ -74,6 +80,7 @@ fn generate_common() -> String {
         r#"// {MINKIDL_HEADER_COMMENT}
 #pragma once
 
+#include <stdbool.h>
 #include <stdint.h>
 #include "object.h"
 "#
@@ -1,29 +1,31 @@
 // Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
 // SPDX-License-Identifier: BSD-3-Clause
 
-use idlc_mir::{Const, StructInner};
+use idlc_codegen::documentation::{Documentation, DocumentationStyle};
+use idlc_mir::{Const, Deprecation, Enum, Mir, StructInner, Typedef};
 
 use crate::interface::variable_names::invoke::INDENT;
-use crate::types::{change_const_primitive, change_primitive};
+use crate::types::{change_aliased_type, change_const_primitive, change_enum, change_primitive};
 
 pub fn emit_include(include: &std::path::Path) -> String {
     let inc_name = include.display().to_string().replace(".idl", "");
     format!("#include \"{}.h\"\n", inc_name)
 }
 
-pub fn emit_struct(r#struct: &StructInner) -> String {
-    let mut result = String::new();
+/// `deprecated` renders the attribute of a deprecated struct, C++ shares this
+/// with its own syntax.
+pub fn emit_struct(
+    r#struct: &StructInner,
+    deprecated: fn(Option<&Deprecation>) -> String,
+) -> String {
+    let mut result = doc(r#struct.doc.as_deref(), "");
+    result.push_str(&deprecated(r#struct.deprecated.as_ref()));
     result.push_str("typedef struct {\n");
 
     for field in &r#struct.fields {
         let ident = crate::safe_ident_c(field.ident.as_ref());
-        let count = field.val.1.get();
-        let ty = match &field.val.0 {
-            &idlc_mir::Type::Primitive(primitive) => change_primitive(primitive).to_string(),
-            idlc_mir::Type::Struct(s) => s.as_ref().ident.to_string(),
-            idlc_mir::Type::Interface(_) => "Object".to_string(),
-            _ => unreachable!(),
-        };
+        let (ty, count) = change_aliased_type(&field.val.0, field.val.1, field.alias.as_ref());
+        result.push_str(&doc(field.doc.as_deref(), INDENT));
         result.push_str(&if count == 1 {
             format!("{INDENT}{ty} {ident};\n")
         } else {
@@ -39,6 +41,84 @@ pub fn emit_const(r#const: &Const) -> String {
     let ident = r#const.ident.to_string();
     let ty = change_const_primitive(r#const.r#type);
     let value = &r#const.value;
+    let doc = doc(r#const.doc.as_deref(), "");
 
-    format!("#define {ident} {ty}({value})\n\n")
+    format!("{doc}#define {ident} {ty}({value})\n\n")
+}
+
+pub fn emit_enum(r#enum: &Enum) -> String {
+    let ident = change_enum(r#enum);
+    let ty = change_primitive(r#enum.r#type);
+
+    let doc = doc(r#enum.doc.as_deref(), "");
+    let mut result = format!("{doc}typedef {ty} {ident};\n");
+    // Enumerators must fit an `int` before C23, wider values are macros.
+    if r#enum
+        .variants
+        .iter()
+        .any(|variant| i32::try_from(variant.value).is_err())
+    {
+        let constant = change_const_primitive(r#enum.r#type);
+        for variant in &r#enum.variants {
+            let value = if variant.value == i128::from(i64::MIN) {
+                // The literal of `-INT64_MIN` doesn't fit an `int64_t`.
+                format!("({constant}({}) - 1)", i64::MIN + 1)
+            } else {
+                format!("{constant}({})", variant.value)
+            };
+            result.push_str(&format!("#define {ident}_{} {value}\n", variant.ident));
+        }
+        result.push('\n');
+        return result;
+    }
+    result.push_str("enum {\n");
+    for variant in &r#enum.variants {
+        result.push_str(&format!(
+            "{INDENT}{ident}_{} = {},\n",
+            variant.ident, variant.value
+        ));
+    }
+    result.push_str("};\n\n");
+    result
+}
+
+pub fn emit_typedef(typedef: &Typedef) -> String {
+    let ident = crate::safe_ident_c(typedef.ident.as_ref());
+    let (ty, count) = change_aliased_type(&typedef.r#type, typedef.count, typedef.alias.as_ref());
+    let doc = doc(typedef.doc.as_deref(), "");
+
+    match count {
+        1 => format!("{doc}typedef {ty} {ident};\n\n"),
+        count => format!("{doc}typedef {ty} {ident}[{count}];\n\n"),
+    }
+}
+
+/// Doxygen block of a declaration, on its own lines.
+pub fn doc(doc: Option<&str>, indent: &str) -> String {
+    Documentation::with_doc(doc, DocumentationStyle::C).indented(indent)
+}
+
+/// Attribute prefixing a deprecated declaration.
+pub fn deprecated(deprecation: Option<&Deprecation>) -> String {
+    match deprecation {
+        Some(Deprecation { note: Some(note) }) => {
+            format!("__attribute__((deprecated(\"{note}\"))) ")
+        }
+        Some(Deprecation { note: None }) => "__attribute__((deprecated)) ".to_string(),
+        None => String::new(),
+    }
+}
+
+/// Silences the warnings of `code` about its own uses of deprecated
+/// declarations, the code including the header is still warned.
+pub fn allow_deprecated(mir: &Mir, code: String) -> String {
+    if !idlc_codegen::deprecation::is_used(mir) {
+        return code;
+    }
+    format!(
+        "#pragma GCC diagnostic push\n\
+         #pragma GCC diagnostic ignored \"-Wdeprecated-declarations\"\n\
+         {code}\
+         #pragma GCC diagnostic pop\n"
+    )
 }
@@ -1,7 +1,7 @@
 // Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
 // SPDX-License-Identifier: BSD-3-Clause
 
-use idlc_mir::Ident;
+use idlc_mir::{Ident, ParamTypeIn, ParamTypeOut, Type};
 
 use crate::interface::variable_names::invoke::{
     ARGS, BI_NAME, BO_NAME, INDENT, OBJECTBUF, OBJECTBUFIN, OP_PREFIX,
@@ -74,6 +74,17 @@ impl idlc_codegen::functions::ParameterVisitor for Implementation {
         ));
     }
 
+    fn visit_input_max_len(
+        &mut self,
+        ident: &Ident,
+        _element_size: usize,
+        max_len: idlc_mir::Count,
+    ) {
+        self.initializations.push(format!(
+            "if ({ident}_len > {max_len}) {{ return Object_ERROR_INVALID; }}"
+        ));
+    }
+
     fn visit_input_struct_buffer(&mut self, ident: &Ident, ty: &idlc_mir::StructInner) {
         let _idx = self.idx();
         let name = format!("{}_ptr", ident);
@@ -284,6 +295,42 @@ impl idlc_codegen::functions::ParameterVisitor for Implementation {
     }
 }
 
+/// Comments on the encoding of the string parameters of `function`, which
+/// aren't NUL-terminated.
+fn string_notes(function: &idlc_mir::Function) -> String {
+    let mut notes = String::new();
+    for param in &function.params {
+        let ident = param.ident();
+        match (param, param.max_len()) {
+            (
+                idlc_mir::Param::In {
+                    r#type: ParamTypeIn::Value(Type::String(_)),
+                    ..
+                },
+                max_len,
+            ) => {
+                let max_len = max_len.map_or_else(String::new, |max_len| {
+                    format!(", at most {max_len}")
+                });
+                notes.push_str(&format!(
+                    "/* `{ident}_ptr` holds `{ident}_len` bytes of UTF-8, not NUL-terminated{max_len}. */\n"
+                ));
+            }
+            (
+                idlc_mir::Param::Out {
+                    r#type: ParamTypeOut::Reference(Type::String(_)),
+                    ..
+                },
+                Some(max_len),
+            ) => notes.push_str(&format!(
+                "/* `{ident}_ptr` receives at most {max_len} bytes of UTF-8, not NUL-terminated, `*{ident}_lenout` is set to their number. */\n"
+            )),
+            _ => {}
+        }
+    }
+    notes
+}
+
 pub fn emit(
     function: &idlc_mir::Function,
     current_iface_ident: &str,
@@ -325,11 +372,13 @@ pub fn emit(
     body.extend(implementation.post_call_assignments());
     body.push("return result;".to_string());
     let formatted_body = idlc_codegen::join_with_prefix(&body, INDENT, 1, "\n");
+    let deprecated = crate::globals::deprecated(function.deprecation());
+    let strings = string_notes(function);
 
     format!(
         r#"
 {documentation}
-static inline int32_t {current_iface_ident}_{ident}(Object self{params})
+{strings}{deprecated}static inline int32_t {current_iface_ident}_{ident}(Object self{params})
 {{
 {formatted_body}
 }}
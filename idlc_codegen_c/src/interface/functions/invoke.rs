@@ -3,7 +3,7 @@
 
 use crate::{
     interface::variable_names::invoke::{ARGS, BI, BO, CONST, COUNTS, INDENT, OP_PREFIX},
-    types::change_primitive,
+    types::{change_enum, change_primitive},
 };
 
 use super::serialization::TransportBuffer;
@@ -59,13 +59,10 @@ impl Invoke {
 
         idx
     }
-}
 
-impl idlc_codegen::functions::ParameterVisitor for Invoke {
-    fn visit_input_primitive_buffer(&mut self, ident: &idlc_mir::Ident, ty: idlc_mir::Primitive) {
+    /// Buffer of `sz` sized elements, passed to the implementation as `ty`.
+    pub fn input_buffer(&mut self, ident: &idlc_mir::Ident, ty: &str, sz: usize) {
         let idx = self.idx();
-        let sz = ty.size();
-        let ty: &str = change_primitive(ty);
         self.pre.push(format!(
             "{CONST} {ty} *{ident}_ptr = ({CONST} {ty}*){ARGS}[{idx}].b.ptr;"
         ));
@@ -73,6 +70,80 @@ impl idlc_codegen::functions::ParameterVisitor for Invoke {
             .push(format!("size_t {ident}_len = {ARGS}[{idx}].b.size / {sz};"));
     }
 
+    pub fn input_value(&mut self, ident: &idlc_mir::Ident, ty: &str, sz: usize) {
+        let idx = self.idx();
+        let name = format!("*{}_ptr", ident);
+        self.args.push(format!("{ARGS}[{idx}].b.size != {sz}"));
+        self.pre.push(format!(
+            "{CONST} {ty} {name} = ({CONST} {ty}*){ARGS}[{idx}].b.ptr;"
+        ));
+    }
+
+    /// Rejects the invocation unless the `bool`s at `ranges` of `bytes` hold
+    /// `0` or `1`.
+    pub fn check_bools(&mut self, bytes: &str, ranges: &[std::ops::Range<usize>]) {
+        for range in ranges {
+            self.pre.push(if range.len() == 1 {
+                format!(
+                    "if ({bytes}[{}] > 1) {{ return Object_ERROR_INVALID; }}",
+                    range.start
+                )
+            } else {
+                format!(
+                    "for (size_t idlc_i = {}; idlc_i < {}; idlc_i++) {{ if ({bytes}[idlc_i] > 1) {{ return Object_ERROR_INVALID; }} }}",
+                    range.start, range.end
+                )
+            });
+        }
+    }
+
+    pub fn output_buffer(&mut self, ident: &idlc_mir::Ident, ty: &str, sz: usize) {
+        let idx = self.idx();
+        let name = format!("*{}_ptr", ident);
+        self.pre
+            .push(format!("{ty} {name} = ({ty}*){ARGS}[{idx}].b.ptr;"));
+        self.pre
+            .push(format!("size_t {ident}_len = {ARGS}[{idx}].b.size / {sz};"));
+        self.post
+            .push(format!("{ARGS}[{idx}].b.size = {ident}_len * {sz};"));
+    }
+
+    pub fn output_value(&mut self, ident: &idlc_mir::Ident, ty: &str, sz: usize) {
+        let idx = self.idx();
+        let name = format!("*{}_ptr", ident);
+        self.args.push(format!("{ARGS}[{idx}].b.size != {sz}"));
+        self.pre
+            .push(format!("{ty} {name} = ({ty}*){ARGS}[{idx}].b.ptr;"));
+    }
+}
+
+impl idlc_codegen::functions::ParameterVisitor for Invoke {
+    fn visit_input_primitive_buffer(&mut self, ident: &idlc_mir::Ident, ty: idlc_mir::Primitive) {
+        self.input_buffer(ident, change_primitive(ty), ty.size());
+        if ty == idlc_mir::Primitive::Bool {
+            self.pre.push(format!(
+                "for (size_t idlc_i = 0; idlc_i < {ident}_len; idlc_i++) {{ if (*({CONST} uint8_t *)&{ident}_ptr[idlc_i] > 1) {{ return Object_ERROR_INVALID; }} }}"
+            ));
+        }
+    }
+
+    fn visit_input_max_len(
+        &mut self,
+        _ident: &idlc_mir::Ident,
+        element_size: usize,
+        max_len: idlc_mir::Count,
+    ) {
+        let idx = self.idx;
+        let size = usize::from(max_len.get()) * element_size;
+        self.pre.push(format!(
+            "if ({ARGS}[{idx}].b.size > {size}) {{ return Object_ERROR_INVALID; }}"
+        ));
+    }
+
+    fn visit_input_enum_buffer(&mut self, ident: &idlc_mir::Ident, ty: &idlc_mir::Enum) {
+        self.input_buffer(ident, &change_enum(ty), ty.r#type.size());
+    }
+
     fn visit_input_untyped_buffer(&mut self, ident: &idlc_mir::Ident) {
         let idx = self.idx();
         let ty = "void".to_string();
@@ -84,9 +155,20 @@ impl idlc_codegen::functions::ParameterVisitor for Invoke {
             .push(format!("size_t {ident}_len = {ARGS}[{idx}].b.size;"));
     }
 
-    fn visit_input_struct_buffer(&mut self, ident: &idlc_mir::Ident, ty: &idlc_mir::StructInner) {
+    fn visit_input_string(&mut self, ident: &idlc_mir::Ident, max_len: Option<idlc_mir::Count>) {
+        if let Some(max_len) = max_len {
+            self.visit_input_max_len(ident, 1, max_len);
+        }
+        self.input_buffer(ident, "char", 1);
+    }
+
+    fn visit_input_struct_buffer(
+        &mut self,
+        ident: &idlc_mir::Ident,
+        r#struct: &idlc_mir::StructInner,
+    ) {
         let idx = self.idx();
-        let ty: &str = ty.ident.as_ref();
+        let ty: &str = r#struct.ident.as_ref();
         let name = format!("*{}_ptr", ident);
         self.pre.push(format!(
             "{CONST} {ty} {name} = ({CONST} {ty}*){ARGS}[{idx}].b.ptr;"
@@ -94,6 +176,18 @@ impl idlc_codegen::functions::ParameterVisitor for Invoke {
         self.pre.push(format!(
             "size_t {ident}_len = {ARGS}[{idx}].b.size / sizeof({ty});"
         ));
+        let bools = r#struct.bools();
+        if !bools.is_empty() {
+            let mut checks = Self::default();
+            checks.check_bools(
+                &format!("(({CONST} uint8_t *)&{ident}_ptr[idlc_n])"),
+                &bools,
+            );
+            self.pre.push(format!(
+                "for (size_t idlc_n = 0; idlc_n < {ident}_len; idlc_n++) {{ {} }}",
+                checks.pre.join(" ")
+            ));
+        }
     }
 
     fn visit_input_object_array(
@@ -117,14 +211,18 @@ impl idlc_codegen::functions::ParameterVisitor for Invoke {
     }
 
     fn visit_input_primitive(&mut self, ident: &idlc_mir::Ident, ty: idlc_mir::Primitive) {
-        let idx = self.idx();
-        let sz = ty.size();
-        let ty: &str = change_primitive(ty);
-        let name = format!("*{}_ptr", ident);
-        self.args.push(format!("{ARGS}[{idx}].b.size != {sz}"));
-        self.pre.push(format!(
-            "{CONST} {ty} {name} = ({CONST} {ty}*){ARGS}[{idx}].b.ptr;"
-        ));
+        let idx = self.idx;
+        self.input_value(ident, change_primitive(ty), ty.size());
+        if ty == idlc_mir::Primitive::Bool {
+            self.check_bools(
+                &format!("(({CONST} uint8_t *){ARGS}[{idx}].b.ptr)"),
+                std::slice::from_ref(&(0..1)),
+            );
+        }
+    }
+
+    fn visit_input_enum(&mut self, ident: &idlc_mir::Ident, ty: &idlc_mir::Enum) {
+        self.input_value(ident, &change_enum(ty), ty.r#type.size());
     }
 
     fn visit_input_bundled(
@@ -143,6 +241,10 @@ impl idlc_codegen::functions::ParameterVisitor for Invoke {
         };
         let idx = self.idx();
         self.args.push(format!("{ARGS}[{idx}].b.size != {size}"));
+        self.check_bools(
+            &format!("(({CONST} uint8_t *){ARGS}[{idx}].b.ptr)"),
+            &packed_primitives.input_bools(),
+        );
         self.pre.push(format!("{CONST} {}", definition.remove(0)));
         self.pre.extend(definition);
         self.pre
@@ -157,6 +259,10 @@ impl idlc_codegen::functions::ParameterVisitor for Invoke {
         let sz = ty.size();
         let ty_ident = ty.ident.to_string();
         self.args.push(format!("{ARGS}[{idx}].b.size != {sz}"));
+        self.check_bools(
+            &format!("(({CONST} uint8_t *){ARGS}[{idx}].b.ptr)"),
+            &ty.bools(),
+        );
         if ty.contains_interfaces() {
             self.pre.push(format!(
                 "{ty_ident} {name} = *({CONST} {ty_ident}*){ARGS}[{idx}].b.ptr;"
@@ -196,16 +302,11 @@ impl idlc_codegen::functions::ParameterVisitor for Invoke {
     }
 
     fn visit_output_primitive_buffer(&mut self, ident: &idlc_mir::Ident, ty: idlc_mir::Primitive) {
-        let idx = self.idx();
-        let sz = ty.size();
-        let ty: &str = change_primitive(ty);
-        let name = format!("*{}_ptr", ident);
-        self.pre
-            .push(format!("{ty} {name} = ({ty}*){ARGS}[{idx}].b.ptr;"));
-        self.pre
-            .push(format!("size_t {ident}_len = {ARGS}[{idx}].b.size / {sz};"));
-        self.post
-            .push(format!("{ARGS}[{idx}].b.size = {ident}_len * {sz};"));
+        self.output_buffer(ident, change_primitive(ty), ty.size());
+    }
+
+    fn visit_output_enum_buffer(&mut self, ident: &idlc_mir::Ident, ty: &idlc_mir::Enum) {
+        self.output_buffer(ident, &change_enum(ty), ty.r#type.size());
     }
 
     fn visit_output_untyped_buffer(&mut self, ident: &idlc_mir::Ident) {
@@ -220,6 +321,13 @@ impl idlc_codegen::functions::ParameterVisitor for Invoke {
             .push(format!("{ARGS}[{idx}].b.size = {ident}_len;"));
     }
 
+    fn visit_output_string(&mut self, ident: &idlc_mir::Ident, max_len: idlc_mir::Count) {
+        self.output_buffer(ident, "char", 1);
+        self.post.push(format!(
+            "if ({ident}_len > {max_len}) {{ return Object_ERROR_INVALID; }}"
+        ));
+    }
+
     fn visit_output_struct_buffer(&mut self, ident: &idlc_mir::Ident, ty: &idlc_mir::StructInner) {
         let idx = self.idx();
         let ty: &str = ty.ident.as_ref();
@@ -299,13 +407,11 @@ impl idlc_codegen::functions::ParameterVisitor for Invoke {
     }
 
     fn visit_output_primitive(&mut self, ident: &idlc_mir::Ident, ty: idlc_mir::Primitive) {
-        let idx = self.idx();
-        let sz = ty.size();
-        let ty: &str = change_primitive(ty);
-        let name = format!("*{}_ptr", ident);
-        self.args.push(format!("{ARGS}[{idx}].b.size != {sz}"));
-        self.pre
-            .push(format!("{ty} {name} = ({ty}*){ARGS}[{idx}].b.ptr;"));
+        self.output_value(ident, change_primitive(ty), ty.size());
+    }
+
+    fn visit_output_enum(&mut self, ident: &idlc_mir::Ident, ty: &idlc_mir::Enum) {
+        self.output_value(ident, &change_enum(ty), ty.r#type.size());
     }
 
     fn visit_output_bundled(
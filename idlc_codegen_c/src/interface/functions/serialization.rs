@@ -7,7 +7,7 @@ use idlc_codegen::serialization::Type;
 
 use crate::interface::variable_names::invoke::{BI, BI_NAME, BO, BO_NAME, INDENT};
 
-use crate::types::change_primitive;
+use crate::types::{change_enum, change_primitive};
 
 #[derive(Debug, Clone)]
 pub struct TransportBuffer {
@@ -32,7 +32,7 @@ impl<'a> PackedPrimitives<'a> {
     pub fn bi_assignments(&self) -> Vec<String> {
         let mut assignments = vec![];
         self.0.inputs_by_idents().for_each(|(ident, ty)| match ty {
-            Type::Primitive(_) => {
+            Type::Primitive(_) | Type::Enum(_) => {
                 assignments.push(format!("{BI_NAME}.m_{ident} = {ident}_val;"));
             }
             Type::SmallStruct(_s) => {
@@ -45,7 +45,7 @@ impl<'a> PackedPrimitives<'a> {
     pub fn bi_embedded(&self) -> Vec<String> {
         let mut assignments = vec![];
         self.0.inputs_by_idents().for_each(|(ident, ty)| match ty {
-            Type::Primitive(_) => {}
+            Type::Primitive(_) | Type::Enum(_) => {}
             Type::SmallStruct(s) => {
                 if s.contains_interfaces() {
                     let ty = s.ident.to_string();
@@ -63,7 +63,7 @@ impl<'a> PackedPrimitives<'a> {
     pub fn post_bo_assignments(&self) -> Vec<String> {
         let mut assignments = vec![];
         self.0.outputs_by_idents().for_each(|(ident, ty)| match ty {
-            Type::Primitive(_) => {
+            Type::Primitive(_) | Type::Enum(_) => {
                 assignments.push(format!("*{ident}_ptr = {BO_NAME}.m_{ident};"));
             }
             Type::SmallStruct(_) => {
@@ -77,7 +77,7 @@ impl<'a> PackedPrimitives<'a> {
     pub fn bo_embedded(&self) -> Vec<(idlc_mir::Ident, &idlc_mir::Ident, Option<&str>)> {
         let mut embedded_objs: Vec<(idlc_mir::Ident, &idlc_mir::Ident, Option<&str>)> = vec![];
         self.0.outputs_by_idents().for_each(|(ident, ty)| match ty {
-            Type::Primitive(_) => {}
+            Type::Primitive(_) | Type::Enum(_) => {}
             Type::SmallStruct(s) => {
                 if s.contains_interfaces() {
                     for field in &s.fields {
@@ -99,7 +99,7 @@ impl<'a> PackedPrimitives<'a> {
             let (ty, size) = &field.val;
             let count = size.get();
             match ty {
-                idlc_mir::Type::Primitive(_) => {
+                idlc_mir::Type::Primitive(_) | idlc_mir::Type::Enum(_) => {
                     if count > 1 {
                         raw_elements.push("{0}".to_string());
                     } else {
@@ -143,6 +143,10 @@ impl<'a> PackedPrimitives<'a> {
                     init.push("0".to_string());
                     Cow::Borrowed(change_primitive(p))
                 }
+                Type::Enum(e) => {
+                    init.push("0".to_string());
+                    Cow::Owned(change_enum(e))
+                }
                 Type::SmallStruct(s) => {
                     let mut inner_struct = String::new();
                     Self::struct_init(s, &mut inner_struct);
@@ -1,10 +1,12 @@
 // Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
 // SPDX-License-Identifier: BSD-3-Clause
 
-use idlc_mir::{Ident, Primitive, StructInner};
+use std::collections::HashMap;
+
+use idlc_mir::{Enum, Ident, Primitive, StructInner};
 
 use crate::interface::variable_names::invoke::CONST;
-use crate::types::change_primitive;
+use crate::types::{change_enum, change_primitive};
 
 #[derive(Debug, Clone, Default)]
 pub struct Signature {
@@ -14,6 +16,9 @@ pub struct Signature {
     bundled_outputs: Vec<String>,
     input_obj_arg: Vec<String>,
 
+    /// Typedefs naming the types of the parameters, by parameter.
+    aliases: HashMap<String, String>,
+
     total_bundled_input: u8,
     total_bundled_output: u8,
     is_no_typed_objects: bool,
@@ -31,6 +36,17 @@ impl Signature {
             bundled_inputs: vec![],
             bundled_outputs: vec![],
             input_obj_arg: vec![],
+            aliases: function
+                .params
+                .iter()
+                .filter_map(|param| {
+                    let alias = param.alias()?;
+                    Some((
+                        param.ident().to_string(),
+                        crate::safe_ident_c(alias.ident.as_ref()).into_owned(),
+                    ))
+                })
+                .collect(),
             total_bundled_input: counts.total_bundled_input,
             total_bundled_output: counts.total_bundled_output,
             is_no_typed_objects,
@@ -71,6 +87,64 @@ impl Signature {
         self.outputs.iter().map(|(ident, _)| ident.as_str())
     }
 
+    /// `ty` of the parameter `ident`, or the typedef naming it.
+    fn aliased(&self, ident: &Ident, ty: &str) -> String {
+        self.aliases
+            .get(ident.as_ref())
+            .map_or_else(|| ty.to_string(), String::clone)
+    }
+
+    fn input_buffer(&mut self, ident: &Ident, ty: &str) {
+        let name = format!("*{}_ptr", ident);
+        let ty = format!("{CONST} {}", ty);
+        self.inputs.push((name, ty));
+        self.inputs
+            .push((format!("{}_len", ident), "size_t".to_string()));
+        self.outputs
+            .push((format!("{}_ptr", ident), "size_t".to_string()));
+        self.outputs
+            .push((format!("{}_len", ident), "size_t".to_string()));
+    }
+
+    fn input_value(&mut self, ident: &Ident, ty: String) {
+        self.inputs.push((format!("{}_val", ident), ty.clone()));
+        if self.total_bundled_input > 1
+            && self.bundled_inputs.contains(&ident.to_string())
+            && self.bundled_inputs.len() > 1
+        {
+            self.outputs.push((format!("i->m_{}", ident), ty));
+        } else {
+            self.outputs.push((format!("*{}_ptr", ident), ty));
+        }
+    }
+
+    fn output_buffer(&mut self, ident: &Ident, ty: String) {
+        let name = format!("*{}_ptr", ident);
+        self.inputs.push((name, ty));
+        self.inputs
+            .push((format!("{}_len", ident), "size_t".to_string()));
+        self.inputs
+            .push((format!("*{}_lenout", ident), "size_t".to_string()));
+        self.outputs
+            .push((format!("{}_ptr", ident), "size_t".to_string()));
+        self.outputs
+            .push((format!("{}_len", ident), "size_t".to_string()));
+        self.outputs
+            .push((format!("&{}_len", ident), "size_t".to_string()));
+    }
+
+    fn output_value(&mut self, ident: &Ident, ty: String) {
+        self.inputs.push((format!("*{}_ptr", ident), ty.clone()));
+        if self.total_bundled_output > 1
+            && self.bundled_outputs.contains(&ident.to_string())
+            && self.bundled_outputs.len() > 1
+        {
+            self.outputs.push((format!("&o->m_{}", ident), ty));
+        } else {
+            self.outputs.push((format!("{}_ptr", ident), ty));
+        }
+    }
+
     pub fn return_idents(&self) -> String {
         let mut acc = String::new();
         for ident in self.return_idents_iter() {
@@ -83,15 +157,11 @@ impl Signature {
 
 impl idlc_codegen::functions::ParameterVisitor for Signature {
     fn visit_input_primitive_buffer(&mut self, ident: &Ident, ty: Primitive) {
-        let name = format!("*{}_ptr", ident);
-        let ty = format!("{CONST} {}", change_primitive(ty));
-        self.inputs.push((name, ty));
-        self.inputs
-            .push((format!("{}_len", ident), "size_t".to_string()));
-        self.outputs
-            .push((format!("{}_ptr", ident), "size_t".to_string()));
-        self.outputs
-            .push((format!("{}_len", ident), "size_t".to_string()));
+        self.input_buffer(ident, &self.aliased(ident, change_primitive(ty)));
+    }
+
+    fn visit_input_enum_buffer(&mut self, ident: &Ident, ty: &Enum) {
+        self.input_buffer(ident, &self.aliased(ident, &change_enum(ty)));
     }
 
     fn visit_input_untyped_buffer(&mut self, ident: &Ident) {
@@ -107,10 +177,14 @@ impl idlc_codegen::functions::ParameterVisitor for Signature {
             .push((format!("{}_len", ident), "size_t".to_string()));
     }
 
+    fn visit_input_string(&mut self, ident: &Ident, _max_len: Option<idlc_mir::Count>) {
+        self.input_buffer(ident, "char");
+    }
+
     fn visit_input_struct_buffer(&mut self, ident: &Ident, ty: &StructInner) {
+        let aliased = self.aliased(ident, &ty.ident);
         let name = format!("*{}_ptr", ident);
-        let ty = format!("{CONST} {}", ty.ident);
-        self.inputs.push((name, ty));
+        self.inputs.push((name, format!("{CONST} {aliased}")));
         self.inputs
             .push((format!("{}_len", ident), "size_t".to_string()));
         self.outputs
@@ -133,52 +207,47 @@ impl idlc_codegen::functions::ParameterVisitor for Signature {
     }
 
     fn visit_input_primitive(&mut self, ident: &Ident, ty: Primitive) {
-        self.inputs
-            .push((format!("{}_val", ident), change_primitive(ty).to_string()));
-        if self.total_bundled_input > 1
-            && self.bundled_inputs.contains(&ident.to_string())
-            && self.bundled_inputs.len() > 1
-        {
-            self.outputs
-                .push((format!("i->m_{}", ident), change_primitive(ty).to_string()));
-        } else {
-            self.outputs
-                .push((format!("*{}_ptr", ident), change_primitive(ty).to_string()));
-        }
+        self.input_value(ident, self.aliased(ident, change_primitive(ty)));
+    }
+
+    fn visit_input_enum(&mut self, ident: &Ident, ty: &Enum) {
+        self.input_value(ident, self.aliased(ident, &change_enum(ty)));
     }
 
     fn visit_input_big_struct(&mut self, ident: &Ident, ty: &StructInner) {
+        let aliased = self.aliased(ident, &ty.ident);
         self.inputs
-            .push((format!("*{}_ptr", ident), format!("{CONST} {}", ty.ident)));
+            .push((format!("*{}_ptr", ident), format!("{CONST} {aliased}")));
         if self.total_bundled_input > 1
             && self.bundled_inputs.contains(&ident.to_string())
             && self.bundled_inputs.len() > 1
         {
             self.outputs
-                .push((format!("&i->m_{}", ident), format!("{CONST} {}", ty.ident)));
+                .push((format!("&i->m_{}", ident), format!("{CONST} {aliased}")));
         } else if ty.contains_interfaces() {
             self.outputs
-                .push((format!("&{}_ptr", ident), format!("{CONST} {}", ty.ident)));
+                .push((format!("&{}_ptr", ident), format!("{CONST} {aliased}")));
         } else {
             self.outputs
-                .push((format!("{}_ptr", ident), format!("{CONST} {}", ty.ident)));
+                .push((format!("{}_ptr", ident), format!("{CONST} {aliased}")));
         }
     }
     fn visit_input_small_struct(&mut self, ident: &Ident, ty: &StructInner) {
+        let aliased = self.aliased(ident, &ty.ident);
         self.inputs
-            .push((format!("*{}_ptr", ident), format!("{CONST} {}", ty.ident)));
+            .push((format!("*{}_ptr", ident), format!("{CONST} {aliased}")));
         if self.total_bundled_input > 1
             && self.bundled_inputs.contains(&ident.to_string())
             && self.bundled_inputs.len() > 1
         {
             self.outputs
-                .push((format!("&i->m_{}", ident), format!("{CONST} {}", ty.ident)));
+                .push((format!("&i->m_{}", ident), format!("{CONST} {aliased}")));
         } else if ty.contains_interfaces() {
             self.outputs
-                .push((format!("&{}_ptr", ident), format!("{CONST} {}", ty.ident)));
+                .push((format!("&{}_ptr", ident), format!("{CONST} {aliased}")));
         } else {
             self.outputs
-                .push((format!("{}_ptr", ident), format!("{CONST} {}", ty.ident)));
+                .push((format!("{}_ptr", ident), format!("{CONST} {aliased}")));
         }
     }
 
@@ -194,19 +263,11 @@ impl idlc_codegen::functions::ParameterVisitor for Signature {
     }
 
     fn visit_output_primitive_buffer(&mut self, ident: &Ident, ty: Primitive) {
-        let name = format!("*{}_ptr", ident);
-        let ty = change_primitive(ty).to_string();
-        self.inputs.push((name, ty));
-        self.inputs
-            .push((format!("{}_len", ident), "size_t".to_string()));
-        self.inputs
-            .push((format!("*{}_lenout", ident), "size_t".to_string()));
-        self.outputs
-            .push((format!("{}_ptr", ident), "size_t".to_string()));
-        self.outputs
-            .push((format!("{}_len", ident), "size_t".to_string()));
-        self.outputs
-            .push((format!("&{}_len", ident), "size_t".to_string()));
+        self.output_buffer(ident, self.aliased(ident, change_primitive(ty)));
+    }
+
+    fn visit_output_enum_buffer(&mut self, ident: &Ident, ty: &Enum) {
+        self.output_buffer(ident, self.aliased(ident, &change_enum(ty)));
     }
 
     fn visit_output_untyped_buffer(&mut self, ident: &Ident) {
@@ -225,10 +286,14 @@ impl idlc_codegen::functions::ParameterVisitor for Signature {
             .push((format!("&{}_len", ident), "size_t".to_string()));
     }
 
+    fn visit_output_string(&mut self, ident: &Ident, _max_len: idlc_mir::Count) {
+        self.output_buffer(ident, "char".to_string());
+    }
+
     fn visit_output_struct_buffer(&mut self, ident: &Ident, ty: &StructInner) {
+        let aliased = self.aliased(ident, &ty.ident);
         let name = format!("*{}_ptr", ident);
-        let ty = ty.ident.to_string();
-        self.inputs.push((name, ty));
+        self.inputs.push((name, aliased));
         self.inputs
             .push((format!("{}_len", ident), "size_t".to_string()));
         self.inputs
@@ -254,46 +319,37 @@ impl idlc_codegen::functions::ParameterVisitor for Signature {
     }
 
     fn visit_output_primitive(&mut self, ident: &Ident, ty: Primitive) {
-        self.inputs
-            .push((format!("*{}_ptr", ident), change_primitive(ty).to_string()));
-        if self.total_bundled_output > 1
-            && self.bundled_outputs.contains(&ident.to_string())
-            && self.bundled_outputs.len() > 1
-        {
-            self.outputs
-                .push((format!("&o->m_{}", ident), change_primitive(ty).to_string()));
-        } else {
-            self.outputs
-                .push((format!("{}_ptr", ident), change_primitive(ty).to_string()));
-        }
+        self.output_value(ident, self.aliased(ident, change_primitive(ty)));
+    }
+
+    fn visit_output_enum(&mut self, ident: &Ident, ty: &Enum) {
+        self.output_value(ident, self.aliased(ident, &change_enum(ty)));
     }
 
     fn visit_output_big_struct(&mut self, ident: &Ident, ty: &StructInner) {
+        let aliased = self.aliased(ident, &ty.ident);
         self.inputs
-            .push((format!("*{}_ptr", ident), ty.ident.to_string()));
+            .push((format!("*{}_ptr", ident), aliased.clone()));
         if self.total_bundled_output > 1
             && self.bundled_outputs.contains(&ident.to_string())
             && self.bundled_outputs.len() > 1
         {
-            self.outputs
-                .push((format!("&o->m_{}", ident), ty.ident.to_string()));
+            self.outputs.push((format!("&o->m_{}", ident), aliased));
         } else {
-            self.outputs
-                .push((format!("{}_ptr", ident), ty.ident.to_string()));
+            self.outputs.push((format!("{}_ptr", ident), aliased));
         }
     }
     fn visit_output_small_struct(&mut self, ident: &Ident, ty: &StructInner) {
+        let aliased = self.aliased(ident, &ty.ident);
         self.inputs
-            .push((format!("*{}_ptr", ident), ty.ident.to_string()));
+            .push((format!("*{}_ptr", ident), aliased.clone()));
         if self.total_bundled_output > 1
             && self.bundled_outputs.contains(&ident.to_string())
             && self.bundled_outputs.len() > 1
         {
-            self.outputs
-                .push((format!("&o->m_{}", ident), ty.ident.to_string()));
+            self.outputs.push((format!("&o->m_{}", ident), aliased));
         } else {
-            self.outputs
-                .push((format!("{}_ptr", ident), ty.ident.to_string()));
+            self.outputs.push((format!("{}_ptr", ident), aliased));
         }
     }
 
@@ -12,6 +12,7 @@ use variable_names::invoke::{ARGS, CONTEXT, COUNTS, INDENT, OP_CODE};
 pub fn emit_interface_impl(interface: &Interface, is_no_typed_objects: bool) -> String {
     let ident = interface.ident.to_string();
 
+    let mut enums = String::new();
     let mut constants = String::new();
     let mut errors = String::new();
     let mut op_codes = String::new();
@@ -20,6 +21,7 @@ pub fn emit_interface_impl(interface: &Interface, is_no_typed_objects: bool) ->
     // A closure to hold logic for the base class(es) and the root class
     let mut process_intf_node = |node: &InterfaceNode, prefix: &str, is_root: bool| match node {
         InterfaceNode::Const(c) => {
+            constants.push_str(&crate::globals::doc(c.doc.as_deref(), ""));
             constants.push_str(&format!(
                 "#define {}_{} {}({})\n",
                 ident,
@@ -28,7 +30,13 @@ pub fn emit_interface_impl(interface: &Interface, is_no_typed_objects: bool) ->
                 c.value
             ));
         }
+        // Enums of the base class(es) are declared in their own header
+        InterfaceNode::Enum(e) if is_root => {
+            enums.push_str(&crate::globals::emit_enum(e));
+        }
+        InterfaceNode::Enum(_) => (),
         InterfaceNode::Error(e) => {
+            errors.push_str(&crate::globals::doc(e.doc.as_deref(), ""));
             errors.push_str(&format!(
                 "#define {}_{} INT32_C({})\n",
                 ident, e.ident, e.value
@@ -79,10 +87,14 @@ pub fn emit_interface_impl(interface: &Interface, is_no_typed_objects: bool) ->
     let object_defined = if is_no_typed_objects {
         "".to_string()
     } else {
-        format!("typedef Object {ident};")
+        format!(
+            "{}typedef Object {ident};",
+            crate::globals::deprecated(interface.deprecated.as_ref())
+        )
     };
 
     let interface_version = interface.get_version();
+    let documentation = crate::globals::doc(interface.doc.as_deref(), "");
 
     format!(
         r#"
@@ -92,9 +104,9 @@ pub fn emit_interface_impl(interface: &Interface, is_no_typed_objects: bool) ->
 #define {ident}_MINOR_SHIFT ((uint32_t)12)
 #define {ident}_PATCH_MASK  ((uint32_t)0xFFF)  /* 12 bits */
 
-// '{ident}' interface at version '{interface_version}'
+{documentation}// '{ident}' interface at version '{interface_version}'
 {object_defined}
-{constants}
+{enums}{constants}
 {errors}
 {op_codes}
 static inline int32_t
@@ -160,7 +172,10 @@ pub fn emit_interface_invoke(interface: &Interface, is_no_typed_objects: bool) -
     }
 
     let typed_objects = (!is_no_typed_objects)
-        .then_some(format!(r#"typedef Object {ident};"#))
+        .then_some(format!(
+            "{}typedef Object {ident};",
+            crate::globals::deprecated(interface.deprecated.as_ref())
+        ))
         .unwrap_or_default();
 
     let APIVersion { major, minor } = interface.get_version();
@@ -8,7 +8,7 @@ pub mod types;
 
 pub use generator::Generator;
 
-pub(crate) fn safe_ident_c(ident: &str) -> std::borrow::Cow<'_, str> {
+pub fn safe_ident_c(ident: &str) -> std::borrow::Cow<'_, str> {
     if idlc_codegen::keywords::is_reserved_for_c(ident) {
         idlc_errors::warn!(
             "Identifier `{ident}` is a reserved C keyword; renamed to `_{ident}` to avoid compilation issues"
@@ -13,6 +13,9 @@ pub const fn change_const_primitive(primitive: idlc_mir::Primitive) -> &'static
         idlc_mir::Primitive::Int64 => "INT64_C",
         idlc_mir::Primitive::Float32 => "FLOAT",
         idlc_mir::Primitive::Float64 => "DOUBLE",
+        idlc_mir::Primitive::Bool | idlc_mir::Primitive::Char => {
+            panic!("ICE: Constants are integers or floats.")
+        }
     }
 }
 
@@ -28,5 +31,42 @@ pub const fn change_primitive(primitive: idlc_mir::Primitive) -> &'static str {
         idlc_mir::Primitive::Int64 => "int64_t",
         idlc_mir::Primitive::Float32 => "float",
         idlc_mir::Primitive::Float64 => "double",
+        idlc_mir::Primitive::Bool => "bool",
+        idlc_mir::Primitive::Char => "char",
+    }
+}
+
+/// C identifier of `r#enum`, enums declared in an interface are prefixed by it.
+pub fn change_enum(r#enum: &idlc_mir::Enum) -> String {
+    match &r#enum.scope {
+        Some(scope) => format!("{scope}_{}", r#enum.ident),
+        None => r#enum.ident.to_string(),
+    }
+}
+
+/// C type of a struct field or typedef, without its array suffix.
+pub fn change_type(ty: &idlc_mir::Type) -> String {
+    match ty {
+        &idlc_mir::Type::Primitive(primitive) => change_primitive(primitive).to_string(),
+        idlc_mir::Type::Struct(s) => s.as_ref().ident.to_string(),
+        idlc_mir::Type::Enum(e) => change_enum(e),
+        idlc_mir::Type::Interface(_) => "Object".to_string(),
+        idlc_mir::Type::UntypedBuffer | idlc_mir::Type::String(_) => unreachable!(),
+    }
+}
+
+/// C type of `count` elements of `ty`, or of the typedef `alias` naming it,
+/// along with the elements of its array suffix.
+pub fn change_aliased_type(
+    ty: &idlc_mir::Type,
+    count: idlc_mir::Count,
+    alias: Option<&idlc_mir::Alias>,
+) -> (String, u16) {
+    match alias {
+        Some(alias) => (
+            crate::safe_ident_c(alias.ident.as_ref()).into_owned(),
+            alias.elements(count),
+        ),
+        None => (change_type(ty), count.get()),
     }
 }
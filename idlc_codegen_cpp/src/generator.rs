@@ -1,19 +1,31 @@
 // Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
 // SPDX-License-Identifier: BSD-3-Clause
 
-use idlc_codegen_c::globals::emit_struct;
-use idlc_codegen_c::types::{change_const_primitive, change_primitive};
+use idlc_codegen_c::globals::{allow_deprecated, emit_struct};
+use idlc_codegen_c::types::{change_const_primitive, change_primitive, change_type};
 use idlc_mir::Node;
 
-use crate::interface::{emit_interface_impl, emit_interface_invoke};
+use crate::interface::{emit_interface_impl, emit_interface_invoke, emit_interface_mock};
+use crate::json::{emit_struct_json, HELPERS as JSON_HELPERS};
 
 use idlc_codegen::MINKIDL_HEADER_COMMENT;
-pub struct Generator;
+
+#[derive(Default)]
+pub struct Generator {
+    json: bool,
+}
+
+impl Generator {
+    /// With `json`, the stubs also print the structs that don't contain
+    /// objects as JSON through `to_json` and `operator<<`.
+    pub fn new(json: bool) -> Self {
+        Self { json }
+    }
+}
 
 impl idlc_codegen::SplitInvokeGenerator for Generator {
     fn generate_implementation(&self, mir: &idlc_mir::Mir) -> String {
         let mut result = String::new();
-        result.push_str(&generate_common());
 
         for node in &mir.nodes {
             match node {
@@ -26,13 +38,38 @@ impl idlc_codegen::SplitInvokeGenerator for Generator {
                     let cnt_ty = change_primitive(c.r#type);
                     let ty = change_const_primitive(c.r#type);
                     let value = &c.value;
+                    let doc = idlc_codegen_c::globals::doc(c.doc.as_deref(), "");
 
                     result.push_str(&format!(
-                        "static constexpr {cnt_ty} {ident} = {ty}({value});\n\n"
+                        "{doc}static constexpr {cnt_ty} {ident} = {ty}({value});\n\n"
                     ));
                 }
                 Node::Struct(s) => {
-                    result.push_str(&emit_struct(s.as_ref()));
+                    result.push_str(&emit_struct(s.as_ref(), crate::deprecated));
+                    if self.json {
+                        result.push_str(&emit_struct_json(s.as_ref()));
+                    }
+                }
+                Node::Enum(e) => {
+                    result.push_str(&crate::emit_enum(e, ""));
+                    result.push('\n');
+                }
+                Node::Typedef(t) => {
+                    let ident = crate::safe_ident_cpp(t.ident.as_ref());
+                    let (ty, count) = match &t.alias {
+                        Some(alias) => (
+                            crate::safe_ident_cpp(alias.ident.as_ref()).into_owned(),
+                            alias.elements(t.count),
+                        ),
+                        None => (change_type(&t.r#type), t.count.get()),
+                    };
+                    let doc = idlc_codegen_c::globals::doc(t.doc.as_deref(), "");
+                    let suffix = match count {
+                        1 => String::new(),
+                        count => format!("[{count}]"),
+                    };
+
+                    result.push_str(&format!("{doc}using {ident} = {ty}{suffix};\n\n"));
                 }
                 Node::Interface(i) => {
                     result.push_str(&emit_interface_impl(i));
@@ -40,11 +77,17 @@ impl idlc_codegen::SplitInvokeGenerator for Generator {
             }
         }
 
-        result
+        let has_structs = mir.nodes.iter().any(|node| matches!(node, Node::Struct(_)));
+        let json_helpers = if self.json && has_structs {
+            JSON_HELPERS
+        } else {
+            ""
+        };
+        generate_common() + string_includes(mir) + json_helpers + &allow_deprecated(mir, result)
     }
 
     fn generate_invoke(&self, mir: &idlc_mir::Mir) -> String {
-        let mut result = generate_common();
+        let mut result = String::new();
 
         let input_name = &mir.tag.file_stem().unwrap().to_str().unwrap();
         result.push_str(&format!(
@@ -66,7 +109,49 @@ impl idlc_codegen::SplitInvokeGenerator for Generator {
             }
         }
 
-        result
+        generate_common() + &allow_deprecated(mir, result)
+    }
+}
+
+impl Generator {
+    /// GoogleMock classes deriving from the skeletons of `generate_invoke`.
+    pub fn generate_mock(&self, mir: &idlc_mir::Mir) -> String {
+        let mut result = String::new();
+
+        let input_name = &mir.tag.file_stem().unwrap().to_str().unwrap();
+        result.push_str(&format!(
+            r#"#include <gmock/gmock.h>
+#include "{input_name}_invoke.hpp"
+"#
+        ));
+
+        // The skeleton header includes the headers of the includes.
+        for node in &mir.nodes {
+            if let Node::Interface(i) = node {
+                result.push_str(&emit_interface_mock(i));
+            }
+        }
+
+        generate_common() + &allow_deprecated(mir, result)
+    }
+}
+
+/// Headers of the types surfacing string parameters, only included when used.
+fn string_includes(mir: &idlc_mir::Mir) -> &'static str {
+    let uses_strings = mir.nodes.iter().any(|node| match node {
+        Node::Interface(i) => i.nodes.iter().any(|node| match node {
+            idlc_mir::InterfaceNode::Function(f) => f
+                .params
+                .iter()
+                .any(|param| matches!(param.r#type(), idlc_mir::Type::String(_))),
+            _ => false,
+        }),
+        _ => false,
+    });
+    if uses_strings {
+        "#include <string>\n#include <string_view>\n"
+    } else {
+        ""
     }
 }
 
@@ -29,6 +29,27 @@ impl idlc_codegen::functions::ParameterVisitor for Implementation {
         self.0.visit_input_struct_buffer(ident, ty);
     }
 
+    fn visit_input_max_len(
+        &mut self,
+        ident: &Ident,
+        element_size: usize,
+        max_len: idlc_mir::Count,
+    ) {
+        self.0.visit_input_max_len(ident, element_size, max_len);
+    }
+
+    fn visit_input_string(&mut self, ident: &Ident, max_len: Option<idlc_mir::Count>) {
+        if let Some(max_len) = max_len {
+            self.0.initializations.push(format!(
+                "if ({ident}_val.size() > {max_len}) {{ return Object_ERROR_INVALID; }}"
+            ));
+        }
+        let _idx = self.0.idx();
+        self.0.args.push(format!(
+            "{INDENT}{{.bi = ({OBJECTBUFIN}) {{ {ident}_val.data(), {ident}_val.size() }} }},"
+        ));
+    }
+
     fn visit_input_object_array(&mut self, ident: &Ident, _ty: Option<&str>, cnt: idlc_mir::Count) {
         for i in 0..cnt.into() {
             let _idx = self.0.idx();
@@ -123,6 +144,22 @@ impl idlc_codegen::functions::ParameterVisitor for Implementation {
         self.0.visit_output_primitive_buffer(ident, ty);
     }
 
+    fn visit_output_string(&mut self, ident: &Ident, max_len: idlc_mir::Count) {
+        let idx = self.0.idx();
+        self.0
+            .initializations
+            .push(format!("std::string {ident}_buf({max_len}, '\\0');"));
+        self.0.args.push(format!(
+            "{INDENT}{{.b = ({OBJECTBUF}) {{ {ident}_buf.data(), {ident}_buf.size() }} }},"
+        ));
+        self.0
+            .post_call
+            .push(format!("{ident}_buf.resize({ARGS}[{idx}].b.size);"));
+        self.0
+            .post_call
+            .push(format!("{ident}_ref.swap({ident}_buf);"));
+    }
+
     fn visit_output_struct_buffer(&mut self, ident: &Ident, ty: &idlc_mir::StructInner) {
         self.0.visit_output_struct_buffer(ident, ty);
     }
@@ -238,6 +275,7 @@ pub fn emit(
     let total = counts.total();
 
     let params = signature.params();
+    let deprecated = crate::deprecated(function.deprecation());
 
     let implementation = Implementation::new(function);
 
@@ -272,7 +310,7 @@ pub fn emit(
     format!(
         r#"
 {documentation}
-{INDENT}virtual int32_t {fn_ident}({params}) {{
+{INDENT}{deprecated}virtual int32_t {fn_ident}({params}) {{
 {formatted_body}
 {INDENT}}}
 "#
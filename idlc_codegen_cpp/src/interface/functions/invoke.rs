@@ -26,10 +26,23 @@ impl idlc_codegen::functions::ParameterVisitor for Invoke {
         self.0.visit_input_untyped_buffer(ident);
     }
 
+    fn visit_input_string(&mut self, ident: &Ident, max_len: Option<idlc_mir::Count>) {
+        self.0.visit_input_string(ident, max_len);
+    }
+
     fn visit_input_struct_buffer(&mut self, ident: &Ident, ty: &idlc_mir::StructInner) {
         self.0.visit_input_struct_buffer(ident, ty);
     }
 
+    fn visit_input_max_len(
+        &mut self,
+        ident: &Ident,
+        element_size: usize,
+        max_len: idlc_mir::Count,
+    ) {
+        self.0.visit_input_max_len(ident, element_size, max_len);
+    }
+
     fn visit_input_object_array(
         &mut self,
         ident: &idlc_mir::Ident,
@@ -54,6 +67,16 @@ impl idlc_codegen::functions::ParameterVisitor for Invoke {
         self.0.visit_input_primitive(ident, ty);
     }
 
+    fn visit_input_enum(&mut self, ident: &Ident, ty: &idlc_mir::Enum) {
+        self.0
+            .input_value(ident, &crate::change_enum(ty), ty.r#type.size());
+    }
+
+    fn visit_input_enum_buffer(&mut self, ident: &Ident, ty: &idlc_mir::Enum) {
+        self.0
+            .input_buffer(ident, &crate::change_enum(ty), ty.r#type.size());
+    }
+
     fn visit_input_bundled(
         &mut self,
         packed_primitives: &idlc_codegen::serialization::PackedPrimitives,
@@ -84,6 +107,20 @@ impl idlc_codegen::functions::ParameterVisitor for Invoke {
         self.0.visit_output_untyped_buffer(ident);
     }
 
+    fn visit_output_string(&mut self, ident: &Ident, max_len: idlc_mir::Count) {
+        let idx = self.0.idx();
+        self.0.pre.push(format!("std::string {ident}_str;"));
+        self.0.post.push(format!(
+            "if ({ident}_str.size() > {max_len}) {{ return Object_ERROR_INVALID; }}"
+        ));
+        self.0.post.push(format!(
+            "if ({ident}_str.size() > {ARGS}[{idx}].b.size) {{ return Object_ERROR_SIZE_OUT; }}"
+        ));
+        self.0.post.push(format!(
+            "{ARGS}[{idx}].b.size = {ident}_str.copy(static_cast<char *>({ARGS}[{idx}].b.ptr), {ident}_str.size());"
+        ));
+    }
+
     fn visit_output_struct_buffer(&mut self, ident: &Ident, ty: &idlc_mir::StructInner) {
         self.0.visit_output_struct_buffer(ident, ty);
     }
@@ -115,6 +152,16 @@ impl idlc_codegen::functions::ParameterVisitor for Invoke {
         self.0.visit_output_primitive(ident, ty);
     }
 
+    fn visit_output_enum(&mut self, ident: &Ident, ty: &idlc_mir::Enum) {
+        self.0
+            .output_value(ident, &crate::change_enum(ty), ty.r#type.size());
+    }
+
+    fn visit_output_enum_buffer(&mut self, ident: &Ident, ty: &idlc_mir::Enum) {
+        self.0
+            .output_buffer(ident, &crate::change_enum(ty), ty.r#type.size());
+    }
+
     fn visit_output_bundled(
         &mut self,
         packed_primitives: &idlc_codegen::serialization::PackedPrimitives,
@@ -32,7 +32,7 @@ impl<'a> PackedPrimitives<'a> {
     pub fn bi_assignments(&self) -> Vec<String> {
         let mut assignments = vec![];
         self.0.inputs_by_idents().for_each(|(ident, ty)| match ty {
-            Type::Primitive(_) => {
+            Type::Primitive(_) | Type::Enum(_) => {
                 assignments.push(format!("{BI_NAME}.m_{ident} = {ident}_val;"));
             }
             Type::SmallStruct(s) => {
@@ -50,7 +50,7 @@ impl<'a> PackedPrimitives<'a> {
     pub fn bi_embedded(&self) -> Vec<String> {
         let mut assignments = vec![];
         self.0.inputs_by_idents().for_each(|(ident, ty)| match ty {
-            Type::Primitive(_) => {}
+            Type::Primitive(_) | Type::Enum(_) => {}
             Type::SmallStruct(s) => {
                 if s.contains_interfaces() {
                     let ty = s.ident.to_string();
@@ -68,7 +68,7 @@ impl<'a> PackedPrimitives<'a> {
     pub fn post_bo_assignments(&self) -> Vec<String> {
         let mut assignments = vec![];
         self.0.outputs_by_idents().for_each(|(ident, ty)| match ty {
-            Type::Primitive(_) => {
+            Type::Primitive(_) | Type::Enum(_) => {
                 assignments.push(format!("*{ident}_ptr = {BO_NAME}.m_{ident};"));
             }
             Type::SmallStruct(_) => {
@@ -97,6 +97,11 @@ impl<'a> PackedPrimitives<'a> {
                     init.push("0".to_string());
                     Cow::Borrowed(change_primitive(p))
                 }
+                Type::Enum(e) => {
+                    // Scoped enums don't convert from `0`.
+                    init.push("{}".to_string());
+                    Cow::Owned(crate::change_enum(e))
+                }
                 Type::SmallStruct(s) => {
                     let mut inner_struct = String::new();
                     idlc_codegen_c::interface::functions::serialization::PackedPrimitives::struct_init(s, &mut inner_struct);
@@ -1,7 +1,9 @@
 // Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
 // SPDX-License-Identifier: BSD-3-Clause
 
-use idlc_mir::{Ident, Primitive, StructInner};
+use std::collections::HashMap;
+
+use idlc_mir::{Enum, Ident, ParamTypeIn, ParamTypeOut, Primitive, StructInner, Type};
 
 use idlc_codegen_c::interface::variable_names::invoke::CONST;
 use idlc_codegen_c::types::change_primitive;
@@ -15,6 +17,9 @@ pub struct Signature {
     input_obj_arg: Vec<String>,
     output_obj_arg: Vec<String>,
 
+    /// Typedefs naming the types of the parameters, by parameter.
+    aliases: HashMap<String, String>,
+
     total_bundled_input: u8,
     total_bundled_output: u8,
 }
@@ -28,6 +33,17 @@ impl Signature {
             bundled_outputs: vec![],
             input_obj_arg: vec![],
             output_obj_arg: vec![],
+            aliases: function
+                .params
+                .iter()
+                .filter_map(|param| {
+                    let alias = param.alias()?;
+                    Some((
+                        param.ident().to_string(),
+                        crate::safe_ident_cpp(alias.ident.as_ref()).into_owned(),
+                    ))
+                })
+                .collect(),
             total_bundled_input: counts.total_bundled_input,
             total_bundled_output: counts.total_bundled_output,
         };
@@ -61,19 +77,102 @@ impl Signature {
     pub fn return_idents(&self) -> String {
         self.return_idents_iter().collect::<Vec<_>>().join(", ")
     }
-}
 
-impl idlc_codegen::functions::ParameterVisitor for Signature {
-    fn visit_input_primitive_buffer(&mut self, ident: &Ident, ty: Primitive) {
+    /// `ty` of the parameter `ident`, or the typedef naming it.
+    fn aliased(&self, ident: &Ident, ty: &str) -> String {
+        self.aliases
+            .get(ident.as_ref())
+            .map_or_else(|| ty.to_string(), String::clone)
+    }
+
+    fn input_buffer(&mut self, ident: &Ident, ty: &str) {
+        let name = format!("*{}_ptr", ident);
+        let ty = format!("{CONST} {}", ty);
+        self.inputs.push((name, ty));
+        self.inputs
+            .push((format!("{}_len", ident), "size_t".to_string()));
+        self.outputs
+            .push((format!("{}_ptr", ident), "size_t".to_string()));
+        self.outputs
+            .push((format!("{}_len", ident), "size_t".to_string()));
+    }
+
+    fn input_value(&mut self, ident: &Ident, ty: String) {
+        self.inputs.push((format!("{}_val", ident), ty.clone()));
+        if self.total_bundled_input > 1 && self.bundled_inputs.contains(&ident.to_string()) {
+            self.outputs.push((format!("i->m_{}", ident), ty));
+        } else {
+            self.outputs.push((format!("*{}_ptr", ident), ty));
+        }
+    }
+
+    fn output_buffer(&mut self, ident: &Ident, ty: String) {
         let name = format!("*{}_ptr", ident);
-        let ty = format!("{CONST} {}", change_primitive(ty));
         self.inputs.push((name, ty));
         self.inputs
             .push((format!("{}_len", ident), "size_t".to_string()));
+        self.inputs
+            .push((format!("*{}_lenout", ident), "size_t".to_string()));
         self.outputs
             .push((format!("{}_ptr", ident), "size_t".to_string()));
         self.outputs
             .push((format!("{}_len", ident), "size_t".to_string()));
+        self.outputs
+            .push((format!("&{}_len", ident), "size_t".to_string()));
+    }
+
+    fn output_value(&mut self, ident: &Ident, ty: String) {
+        self.inputs.push((format!("*{}_ptr", ident), ty.clone()));
+        if self.total_bundled_output > 1 && self.bundled_outputs.contains(&ident.to_string()) {
+            self.outputs.push((format!("&o->m_{}", ident), ty));
+        } else {
+            self.outputs.push((format!("{}_ptr", ident), ty));
+        }
+    }
+}
+
+/// Comments on the string parameters of `function`, `std::string_view`s
+/// aren't NUL-terminated.
+pub fn string_notes(function: &idlc_mir::Function) -> String {
+    let mut notes = String::new();
+    for param in &function.params {
+        let ident = param.ident();
+        match (param, param.max_len()) {
+            (
+                idlc_mir::Param::In {
+                    r#type: ParamTypeIn::Value(Type::String(_)),
+                    ..
+                },
+                max_len,
+            ) => {
+                let max_len = max_len
+                    .map_or_else(String::new, |max_len| format!(", at most {max_len} bytes"));
+                notes.push_str(&format!(
+                    "    // `{ident}_val` is UTF-8, not NUL-terminated{max_len}.\n"
+                ));
+            }
+            (
+                idlc_mir::Param::Out {
+                    r#type: ParamTypeOut::Reference(Type::String(_)),
+                    ..
+                },
+                Some(max_len),
+            ) => notes.push_str(&format!(
+                "    // `{ident}_ref` receives at most {max_len} bytes of UTF-8.\n"
+            )),
+            _ => {}
+        }
+    }
+    notes
+}
+
+impl idlc_codegen::functions::ParameterVisitor for Signature {
+    fn visit_input_primitive_buffer(&mut self, ident: &Ident, ty: Primitive) {
+        self.input_buffer(ident, &self.aliased(ident, change_primitive(ty)));
+    }
+
+    fn visit_input_enum_buffer(&mut self, ident: &Ident, ty: &Enum) {
+        self.input_buffer(ident, &self.aliased(ident, &crate::change_enum(ty)));
     }
 
     fn visit_input_untyped_buffer(&mut self, ident: &Ident) {
@@ -89,10 +188,19 @@ impl idlc_codegen::functions::ParameterVisitor for Signature {
             .push((format!("{}_len", ident), "size_t".to_string()));
     }
 
+    fn visit_input_string(&mut self, ident: &Ident, _max_len: Option<idlc_mir::Count>) {
+        self.inputs
+            .push((format!("{}_val", ident), "std::string_view".to_string()));
+        self.outputs.push((
+            format!("std::string_view({ident}_ptr, {ident}_len)"),
+            "std::string_view".to_string(),
+        ));
+    }
+
     fn visit_input_struct_buffer(&mut self, ident: &Ident, ty: &StructInner) {
+        let aliased = self.aliased(ident, &ty.ident);
         let name = format!("*{}_ptr", ident);
-        let ty = format!("{CONST} {}", ty.ident);
-        self.inputs.push((name, ty));
+        self.inputs.push((name, format!("{CONST} {aliased}")));
         self.inputs
             .push((format!("{}_len", ident), "size_t".to_string()));
         self.outputs
@@ -111,43 +219,41 @@ impl idlc_codegen::functions::ParameterVisitor for Signature {
     }
 
     fn visit_input_primitive(&mut self, ident: &Ident, ty: Primitive) {
-        self.inputs
-            .push((format!("{}_val", ident), change_primitive(ty).to_string()));
-        if self.total_bundled_input > 1 && self.bundled_inputs.contains(&ident.to_string()) {
-            self.outputs
-                .push((format!("i->m_{}", ident), change_primitive(ty).to_string()));
-        } else {
-            self.outputs
-                .push((format!("*{}_ptr", ident), change_primitive(ty).to_string()));
-        }
+        self.input_value(ident, self.aliased(ident, change_primitive(ty)));
+    }
+
+    fn visit_input_enum(&mut self, ident: &Ident, ty: &Enum) {
+        self.input_value(ident, self.aliased(ident, &crate::change_enum(ty)));
     }
 
     fn visit_input_big_struct(&mut self, ident: &Ident, ty: &StructInner) {
+        let aliased = self.aliased(ident, &ty.ident);
         self.inputs
-            .push((format!("&{}_ref", ident), format!("{CONST} {}", ty.ident)));
+            .push((format!("&{}_ref", ident), format!("{CONST} {aliased}")));
         if self.total_bundled_input > 1 && self.bundled_inputs.contains(&ident.to_string()) {
             self.outputs
-                .push((format!("i->m_{}", ident), format!("{CONST} {}", ty.ident)));
+                .push((format!("i->m_{}", ident), format!("{CONST} {aliased}")));
         } else if ty.contains_interfaces() {
             self.outputs
-                .push((format!("{}_ptr", ident), format!("{CONST} {}", ty.ident)));
+                .push((format!("{}_ptr", ident), format!("{CONST} {aliased}")));
         } else {
             self.outputs
-                .push((format!("*{}_ptr", ident), format!("{CONST} {}", ty.ident)));
+                .push((format!("*{}_ptr", ident), format!("{CONST} {aliased}")));
         }
     }
     fn visit_input_small_struct(&mut self, ident: &Ident, ty: &StructInner) {
+        let aliased = self.aliased(ident, &ty.ident);
         self.inputs
-            .push((format!("&{}_ref", ident), format!("{CONST} {}", ty.ident)));
+            .push((format!("&{}_ref", ident), format!("{CONST} {aliased}")));
         if self.total_bundled_input > 1 && self.bundled_inputs.contains(&ident.to_string()) {
             self.outputs
-                .push((format!("i->m_{}", ident), format!("{CONST} {}", ty.ident)));
+                .push((format!("i->m_{}", ident), format!("{CONST} {aliased}")));
         } else if ty.contains_interfaces() {
             self.outputs
-                .push((format!("{}_ptr", ident), format!("{CONST} {}", ty.ident)));
+                .push((format!("{}_ptr", ident), format!("{CONST} {aliased}")));
         } else {
             self.outputs
-                .push((format!("*{}_ptr", ident), format!("{CONST} {}", ty.ident)));
+                .push((format!("*{}_ptr", ident), format!("{CONST} {aliased}")));
         }
     }
 
@@ -161,19 +267,11 @@ impl idlc_codegen::functions::ParameterVisitor for Signature {
     }
 
     fn visit_output_primitive_buffer(&mut self, ident: &Ident, ty: Primitive) {
-        let name = format!("*{}_ptr", ident);
-        let ty = change_primitive(ty).to_string();
-        self.inputs.push((name, ty));
-        self.inputs
-            .push((format!("{}_len", ident), "size_t".to_string()));
-        self.inputs
-            .push((format!("*{}_lenout", ident), "size_t".to_string()));
-        self.outputs
-            .push((format!("{}_ptr", ident), "size_t".to_string()));
-        self.outputs
-            .push((format!("{}_len", ident), "size_t".to_string()));
-        self.outputs
-            .push((format!("&{}_len", ident), "size_t".to_string()));
+        self.output_buffer(ident, self.aliased(ident, change_primitive(ty)));
+    }
+
+    fn visit_output_enum_buffer(&mut self, ident: &Ident, ty: &Enum) {
+        self.output_buffer(ident, self.aliased(ident, &crate::change_enum(ty)));
     }
 
     fn visit_output_untyped_buffer(&mut self, ident: &Ident) {
@@ -192,10 +290,17 @@ impl idlc_codegen::functions::ParameterVisitor for Signature {
             .push((format!("&{}_len", ident), "size_t".to_string()));
     }
 
+    fn visit_output_string(&mut self, ident: &Ident, _max_len: idlc_mir::Count) {
+        self.inputs
+            .push((format!("&{}_ref", ident), "std::string".to_string()));
+        self.outputs
+            .push((format!("{}_str", ident), "std::string".to_string()));
+    }
+
     fn visit_output_struct_buffer(&mut self, ident: &Ident, ty: &StructInner) {
+        let aliased = self.aliased(ident, &ty.ident);
         let name = format!("*{}_ptr", ident);
-        let ty = ty.ident.to_string();
-        self.inputs.push((name, ty));
+        self.inputs.push((name, aliased));
         self.inputs
             .push((format!("{}_len", ident), "size_t".to_string()));
         self.inputs
@@ -218,37 +323,31 @@ impl idlc_codegen::functions::ParameterVisitor for Signature {
     }
 
     fn visit_output_primitive(&mut self, ident: &Ident, ty: Primitive) {
-        self.inputs
-            .push((format!("*{}_ptr", ident), change_primitive(ty).to_string()));
-        if self.total_bundled_output > 1 && self.bundled_outputs.contains(&ident.to_string()) {
-            self.outputs
-                .push((format!("&o->m_{}", ident), change_primitive(ty).to_string()));
-        } else {
-            self.outputs
-                .push((format!("{}_ptr", ident), change_primitive(ty).to_string()));
-        }
+        self.output_value(ident, self.aliased(ident, change_primitive(ty)));
+    }
+
+    fn visit_output_enum(&mut self, ident: &Ident, ty: &Enum) {
+        self.output_value(ident, self.aliased(ident, &crate::change_enum(ty)));
     }
 
     fn visit_output_big_struct(&mut self, ident: &Ident, ty: &StructInner) {
+        let aliased = self.aliased(ident, &ty.ident);
         self.inputs
-            .push((format!("&{}_ref", ident), ty.ident.to_string()));
+            .push((format!("&{}_ref", ident), aliased.clone()));
         if self.total_bundled_output > 1 && self.bundled_outputs.contains(&ident.to_string()) {
-            self.outputs
-                .push((format!("o->m_{}", ident), ty.ident.to_string()));
+            self.outputs.push((format!("o->m_{}", ident), aliased));
         } else {
-            self.outputs
-                .push((format!("*{}_ptr", ident), ty.ident.to_string()));
+            self.outputs.push((format!("*{}_ptr", ident), aliased));
         }
     }
     fn visit_output_small_struct(&mut self, ident: &Ident, ty: &StructInner) {
+        let aliased = self.aliased(ident, &ty.ident);
         self.inputs
-            .push((format!("&{}_ref", ident), ty.ident.to_string()));
+            .push((format!("&{}_ref", ident), aliased.clone()));
         if self.total_bundled_output > 1 && self.bundled_outputs.contains(&ident.to_string()) {
-            self.outputs
-                .push((format!("o->m_{}", ident), ty.ident.to_string()));
+            self.outputs.push((format!("o->m_{}", ident), aliased));
         } else {
-            self.outputs
-                .push((format!("*{}_ptr", ident), ty.ident.to_string()));
+            self.outputs.push((format!("*{}_ptr", ident), aliased));
         }
     }
 
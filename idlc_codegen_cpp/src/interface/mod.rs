@@ -5,6 +5,7 @@ use idlc_mir::{APIVersion, Interface, InterfaceNode, VERSION_FUNC_NAME};
 
 mod functions;
 
+use idlc_codegen_c::globals::doc;
 use idlc_codegen_c::interface::variable_names::invoke::{ARGS, COUNTS, INDENT, OP_CODE, OP_PREFIX};
 use idlc_codegen_c::types::{change_const_primitive, change_primitive};
 
@@ -12,6 +13,7 @@ pub fn emit_interface_impl(interface: &Interface) -> String {
     let ident = interface.ident.to_string();
 
     let mut base_iface = String::new();
+    let mut enums = String::new();
     let mut constants = String::new();
     let mut errors = String::new();
     let mut op_codes = String::new();
@@ -23,18 +25,27 @@ pub fn emit_interface_impl(interface: &Interface) -> String {
         InterfaceNode::Const(c) => {
             constants.push_str(&format!(
                 r#"
-    static constexpr {} {} = {}({});"#,
+{}    static constexpr {} {} = {}({});"#,
+                doc(c.doc.as_deref(), "    "),
                 change_primitive(c.r#type),
                 c.ident,
                 change_const_primitive(c.r#type),
                 c.value
             ));
         }
+        // Enums of the base class(es) are inherited
+        InterfaceNode::Enum(e) if is_root => {
+            enums.push('\n');
+            enums.push_str(&crate::emit_enum(e, "    "));
+        }
+        InterfaceNode::Enum(_) => (),
         InterfaceNode::Error(e) => {
             errors.push_str(&format!(
                 r#"
-    static constexpr int32_t {} = INT32_C({});"#,
-                e.ident, e.value
+{}    static constexpr int32_t {} = INT32_C({});"#,
+                doc(e.doc.as_deref(), "    "),
+                e.ident,
+                e.value
             ));
         }
         InterfaceNode::Function(f) => {
@@ -49,8 +60,11 @@ pub fn emit_interface_impl(interface: &Interface) -> String {
                 let params = signature.params();
                 func_titles.push_str(&format!(
                     r#"
-    virtual int32_t {}({}) = 0;"#,
-                    fn_ident, params,
+{}    {}virtual int32_t {}({}) = 0;"#,
+                    functions::signature::string_notes(f),
+                    crate::deprecated(f.deprecation()),
+                    fn_ident,
+                    params,
                 ));
                 op_codes.push_str(&format!(
                     r#"
@@ -95,13 +109,15 @@ pub fn emit_interface_impl(interface: &Interface) -> String {
     }
 
     let interface_version = interface.get_version();
+    let documentation = doc(interface.doc.as_deref(), "");
+    let deprecated = crate::deprecated(interface.deprecated.as_ref());
 
     format!(
         r#"
-// '{ident}' interface at version '{interface_version}'
+{documentation}// '{ident}' interface at version '{interface_version}'
 class {ident};
-class I{ident} {base_iface}{{
-  public:{constants}
+class {deprecated}I{ident} {base_iface}{{
+  public:{enums}{constants}
     static constexpr uint16_t PATCH_MASK  = 0x0FFF; /* 12 bits */
     static constexpr uint16_t MINOR_MASK  = 0x03FF; /* 10 bits */
     static constexpr uint16_t MAJOR_MASK  = 0x03FF; /* 10 bits */
@@ -116,7 +132,7 @@ class I{ident} {base_iface}{{
 {op_codes}
 }};
 
-class {ident} : public I{ident}, public ProxyBase {{
+class {deprecated}{ident} : public I{ident}, public ProxyBase {{
   public:
     {ident}() {{}}
     {ident}(Object impl) : ProxyBase(impl) {{}}
@@ -163,10 +179,11 @@ pub fn emit_interface_invoke(interface: &Interface) -> String {
     }
 
     let APIVersion { major, minor } = interface.get_version();
+    let deprecated = crate::deprecated(interface.deprecated.as_ref());
 
     format!(
         r#"
-class {ident}ImplBase : protected ImplBase, public I{ident} {{
+class {deprecated}{ident}ImplBase : protected ImplBase, public I{ident} {{
   public:
     {ident}ImplBase() {{}}
     virtual ~{ident}ImplBase() {{}}
@@ -195,3 +212,32 @@ class {ident}ImplBase : protected ImplBase, public I{ident} {{
 "#
     )
 }
+
+/// Emits a GoogleMock `Mock<ident>` of the skeleton of `interface`, mocking
+/// the methods of its bases as well.
+pub fn emit_interface_mock(interface: &Interface) -> String {
+    let ident = interface.ident.to_string();
+
+    let mut methods = String::new();
+    for iface in interface.iter() {
+        for node in &iface.nodes {
+            if let InterfaceNode::Function(f) = node {
+                let fn_ident = crate::safe_ident_cpp(f.ident.as_ref());
+                let counts = idlc_codegen::counts::Counter::new(f);
+                let signature = functions::signature::Signature::new(f, &counts);
+                methods.push_str(&format!(
+                    "{INDENT}MOCK_METHOD(int32_t, {fn_ident}, ({}), (override));\n",
+                    signature.params()
+                ));
+            }
+        }
+    }
+
+    format!(
+        r#"
+class Mock{ident} : public {ident}ImplBase {{
+  public:
+{methods}}};
+"#
+    )
+}
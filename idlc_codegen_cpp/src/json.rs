@@ -0,0 +1,106 @@
+// Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! JSON printers of the structs, matching the serde derives of the Rust
+//! structs: fields are keyed by their IDL name, arrays are JSON arrays and
+//! non-finite floats are `null`.
+
+use idlc_codegen_c::interface::variable_names::invoke::INDENT;
+use idlc_mir::StructInner;
+
+/// Overloads of `idlc_json::write` shared by every header printing JSON.
+pub(crate) const HELPERS: &str = r#"
+#ifndef IDLC_JSON
+#define IDLC_JSON
+
+#include <cmath>
+#include <cstddef>
+#include <limits>
+#include <ostream>
+#include <type_traits>
+
+namespace idlc_json {
+
+inline void write(std::ostream &os, bool value) { os << (value ? "true" : "false"); }
+inline void write(std::ostream &os, char value) { os << static_cast<int>(value); }
+inline void write(std::ostream &os, int8_t value) { os << static_cast<int>(value); }
+inline void write(std::ostream &os, uint8_t value) { os << static_cast<unsigned>(value); }
+inline void write(std::ostream &os, int16_t value) { os << value; }
+inline void write(std::ostream &os, uint16_t value) { os << value; }
+inline void write(std::ostream &os, int32_t value) { os << value; }
+inline void write(std::ostream &os, uint32_t value) { os << value; }
+inline void write(std::ostream &os, int64_t value) { os << value; }
+inline void write(std::ostream &os, uint64_t value) { os << value; }
+
+template <typename T> inline void write_float(std::ostream &os, T value) {
+    if (!std::isfinite(value)) {
+        os << "null";
+        return;
+    }
+    const std::streamsize precision = os.precision(std::numeric_limits<T>::max_digits10);
+    os << value;
+    os.precision(precision);
+}
+
+inline void write(std::ostream &os, float value) { write_float(os, value); }
+inline void write(std::ostream &os, double value) { write_float(os, value); }
+
+template <typename T>
+inline typename std::enable_if<std::is_enum<T>::value>::type write(std::ostream &os, T value) {
+    write(os, static_cast<typename std::underlying_type<T>::type>(value));
+}
+
+// Structs are printed by their `to_json`, found by ADL.
+template <typename T>
+inline typename std::enable_if<std::is_class<T>::value>::type write(std::ostream &os, const T &value) {
+    to_json(os, value);
+}
+
+template <typename T, size_t N> inline void write(std::ostream &os, const T (&values)[N]) {
+    os << '[';
+    for (size_t i = 0; i < N; ++i) {
+        if (i != 0) {
+            os << ',';
+        }
+        write(os, values[i]);
+    }
+    os << ']';
+}
+
+} // namespace idlc_json
+
+#endif // IDLC_JSON
+"#;
+
+/// `to_json` and `operator<<` of `r#struct`, or a comment explaining why
+/// they're skipped if it contains objects.
+pub(crate) fn emit_struct_json(r#struct: &StructInner) -> String {
+    let ident = idlc_codegen_c::safe_ident_c(r#struct.ident.as_ref());
+    if r#struct.contains_interfaces() {
+        return format!("// No JSON printer: `{ident}` contains objects.\n\n");
+    }
+
+    let mut fields = String::new();
+    for (i, field) in r#struct.fields.iter().enumerate() {
+        let separator = if i == 0 { "" } else { "," };
+        let member = idlc_codegen_c::safe_ident_c(field.ident.as_ref());
+        fields.push_str(&format!(
+            "{INDENT}os << \"{separator}\\\"{}\\\":\";\n{INDENT}idlc_json::write(os, value.{member});\n",
+            field.ident
+        ));
+    }
+
+    format!(
+        r#"inline void to_json(std::ostream &os, const {ident} &value) {{
+{INDENT}os << '{{';
+{fields}{INDENT}os << '}}';
+}}
+
+inline std::ostream &operator<<(std::ostream &os, const {ident} &value) {{
+{INDENT}to_json(os, value);
+{INDENT}return os;
+}}
+
+"#
+    )
+}
@@ -3,6 +3,7 @@
 
 mod generator;
 pub mod interface;
+mod json;
 
 pub use generator::Generator;
 
@@ -16,3 +17,36 @@ pub(crate) fn safe_ident_cpp(ident: &str) -> std::borrow::Cow<'_, str> {
         std::borrow::Cow::Borrowed(ident)
     }
 }
+
+/// Attribute prefixing a deprecated declaration.
+pub(crate) fn deprecated(deprecation: Option<&idlc_mir::Deprecation>) -> String {
+    match deprecation {
+        Some(idlc_mir::Deprecation { note: Some(note) }) => format!("[[deprecated(\"{note}\")]] "),
+        Some(idlc_mir::Deprecation { note: None }) => "[[deprecated]] ".to_string(),
+        None => String::new(),
+    }
+}
+
+/// C++ type of `r#enum`, enums declared in an interface are nested in its class.
+pub(crate) fn change_enum(r#enum: &idlc_mir::Enum) -> String {
+    match &r#enum.scope {
+        Some(scope) => format!("I{scope}::{}", r#enum.ident),
+        None => r#enum.ident.to_string(),
+    }
+}
+
+pub(crate) fn emit_enum(r#enum: &idlc_mir::Enum, indent: &str) -> String {
+    let ty = idlc_codegen_c::types::change_primitive(r#enum.r#type);
+    let mut result = idlc_codegen_c::globals::doc(r#enum.doc.as_deref(), indent);
+    result.push_str(&format!("{indent}enum class {} : {ty} {{\n", r#enum.ident));
+    for variant in &r#enum.variants {
+        result.push_str(&format!(
+            "{indent}{}{} = {},\n",
+            idlc_codegen_c::interface::variable_names::invoke::INDENT,
+            safe_ident_cpp(variant.ident.as_ref()),
+            variant.value
+        ));
+    }
+    result.push_str(&format!("{indent}}};\n"));
+    result
+}
@@ -0,0 +1,160 @@
+// Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::path::PathBuf;
+
+use idlc_codegen::Descriptor;
+use idlc_mir::{Node, Typedef};
+
+use crate::globals::{
+    consts, emit_enum, emit_struct, file_page, interface_target, section, type_text,
+};
+use crate::page::{summary, Block, Format, Inline, Page, Target};
+
+pub struct Markdown;
+pub struct Html;
+
+impl idlc_codegen::Generator for Markdown {
+    fn generate(mir: &idlc_mir::Mir) -> Descriptor {
+        generate(mir, Format::Markdown)
+    }
+}
+
+impl idlc_codegen::Generator for Html {
+    fn generate(mir: &idlc_mir::Mir) -> Descriptor {
+        generate(mir, Format::Html)
+    }
+}
+
+fn generate(mir: &idlc_mir::Mir, format: Format) -> Descriptor {
+    let file = file_page(&mir.tag);
+    let mut pages = vec![emit_file(mir, &file)];
+    for node in &mir.nodes {
+        if let Node::Interface(interface) = node {
+            pages.push(crate::interface::emit(interface, &file));
+        }
+    }
+    pages
+        .into_iter()
+        .map(|page| {
+            (
+                PathBuf::from(format!("{}.{}", page.name, format.extension())),
+                page.render(format),
+            )
+        })
+        .collect()
+}
+
+/// Page of the input file, listing its includes, interfaces and top-level
+/// declarations.
+fn emit_file(mir: &idlc_mir::Mir, file: &str) -> Page {
+    let mut blocks = vec![Block::Heading {
+        level: 1,
+        text: vec![Inline::Code(file.to_string())],
+        anchor: None,
+    }];
+
+    let includes: Vec<_> = mir
+        .nodes
+        .iter()
+        .filter_map(|node| match node {
+            Node::Include(path) => {
+                let page = file_page(path);
+                Some(vec![Inline::Link(
+                    page.clone(),
+                    Target { page, anchor: None },
+                )])
+            }
+            _ => None,
+        })
+        .collect();
+    if !includes.is_empty() {
+        blocks.push(section("Includes", "includes"));
+        blocks.push(Block::List(includes));
+    }
+
+    let interfaces: Vec<_> = mir
+        .nodes
+        .iter()
+        .filter_map(|node| match node {
+            Node::Interface(i) => {
+                let mut item = vec![Inline::Link(
+                    i.ident.to_string(),
+                    interface_target(i.ident.as_ref()),
+                )];
+                let summary = summary(i.doc.as_deref());
+                if !summary.is_empty() {
+                    item.push(Inline::Text(": ".to_string()));
+                    item.extend(summary);
+                }
+                Some(item)
+            }
+            _ => None,
+        })
+        .collect();
+    if !interfaces.is_empty() {
+        blocks.push(section("Interfaces", "interfaces"));
+        blocks.push(Block::List(interfaces));
+    }
+
+    let constants: Vec<_> = mir
+        .nodes
+        .iter()
+        .filter_map(|node| match node {
+            Node::Const(c) => Some(c),
+            _ => None,
+        })
+        .collect();
+    if !constants.is_empty() {
+        blocks.push(section("Constants", "constants"));
+        blocks.push(consts(constants, ""));
+    }
+
+    let typedefs: Vec<_> = mir
+        .nodes
+        .iter()
+        .filter_map(|node| match node {
+            Node::Typedef(t) => Some(t),
+            _ => None,
+        })
+        .collect();
+    if !typedefs.is_empty() {
+        blocks.push(section("Typedefs", "typedefs"));
+        blocks.push(self::typedefs(typedefs, file));
+    }
+
+    for node in &mir.nodes {
+        match node {
+            Node::Enum(e) => blocks.extend(emit_enum(e, 2)),
+            Node::Struct(s) => blocks.extend(emit_struct(s.as_ref(), file)),
+            _ => (),
+        }
+    }
+
+    Page {
+        name: file.to_string(),
+        title: file.to_string(),
+        blocks,
+    }
+}
+
+/// Typedefs are documented as the type they alias.
+fn typedefs(typedefs: Vec<&Typedef>, file: &str) -> Block {
+    Block::Table {
+        header: vec!["Typedef", "Type", "Description"],
+        rows: typedefs
+            .into_iter()
+            .map(|t| {
+                let suffix = match t.count.get() {
+                    1 => String::new(),
+                    count => format!("[{count}]"),
+                };
+                vec![
+                    vec![Inline::Code(t.ident.to_string())],
+                    type_text(&t.r#type, &suffix, file),
+                    summary(t.doc.as_deref()),
+                ]
+            })
+            .collect(),
+    }
+}
@@ -0,0 +1,205 @@
+// Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::path::Path;
+
+use idlc_mir::{Const, Deprecation, Enum, StructInner, Type};
+
+use crate::page::{doc, summary, Block, Inline, Target, Text};
+
+/// Name of the page of an IDL file.
+pub fn file_page(path: &Path) -> String {
+    path.file_name().map_or_else(
+        || path.display().to_string(),
+        |name| name.to_string_lossy().to_string(),
+    )
+}
+
+/// Where `r#struct` is documented, `file` is the page of the IDL being
+/// documented.
+pub fn struct_target(r#struct: &StructInner, file: &str) -> Target {
+    Target {
+        page: r#struct
+            .origin
+            .as_deref()
+            .map_or_else(|| file.to_string(), file_page),
+        anchor: Some(format!("struct-{}", r#struct.ident)),
+    }
+}
+
+/// Where `r#enum` is documented, on the page of its interface if it's
+/// declared in one.
+pub fn enum_target(r#enum: &Enum, file: &str) -> Target {
+    let page = match (&r#enum.scope, &r#enum.origin) {
+        (Some(scope), _) => scope.clone(),
+        (None, Some(origin)) => file_page(origin),
+        (None, None) => file.to_string(),
+    };
+    Target {
+        page,
+        anchor: Some(format!("enum-{}", r#enum.ident)),
+    }
+}
+
+pub fn interface_target(ident: &str) -> Target {
+    Target {
+        page: ident.to_string(),
+        anchor: None,
+    }
+}
+
+/// Name of `ty` linking to its declaration, with its array suffix if any.
+pub fn type_text(ty: &Type, suffix: &str, file: &str) -> Text {
+    let link = match ty {
+        Type::UntypedBuffer => return vec![Inline::Code(format!("buffer{suffix}"))],
+        Type::String(None) => return vec![Inline::Code(format!("string{suffix}"))],
+        Type::String(Some(bound)) => {
+            return vec![Inline::Code(format!("string<{bound}>{suffix}"))];
+        }
+        Type::Primitive(primitive) => return vec![Inline::Code(format!("{primitive}{suffix}"))],
+        Type::Interface(None) => return vec![Inline::Code(format!("interface{suffix}"))],
+        Type::Struct(s) => Inline::Link(
+            s.as_ref().ident.to_string(),
+            struct_target(s.as_ref(), file),
+        ),
+        Type::Enum(e) => Inline::Link(e.ident.to_string(), enum_target(e, file)),
+        Type::Interface(Some(ident)) => Inline::Link(ident.clone(), interface_target(ident)),
+    };
+    let mut text = vec![link];
+    if !suffix.is_empty() {
+        text.push(Inline::Code(suffix.to_string()));
+    }
+    text
+}
+
+/// Heading of a declaration, followed by its documentation.
+pub fn heading(level: u8, text: Text, anchor: String, documentation: Option<&str>) -> Vec<Block> {
+    let mut blocks = vec![Block::Heading {
+        level,
+        text,
+        anchor: Some(anchor),
+    }];
+    blocks.extend(documentation.map(|d| Block::Doc(doc(d))));
+    blocks
+}
+
+/// Notice of a deprecated declaration, following its documentation.
+pub fn deprecation(deprecation: Option<&Deprecation>) -> Option<Block> {
+    let text = match &deprecation?.note {
+        Some(note) => format!("Deprecated: {note}"),
+        None => "Deprecated.".to_string(),
+    };
+    Some(Block::Paragraph(vec![Inline::Text(text)]))
+}
+
+/// Second level heading grouping declarations.
+pub fn section(title: &str, anchor: &str) -> Block {
+    Block::Heading {
+        level: 2,
+        text: vec![Inline::Text(title.to_string())],
+        anchor: Some(anchor.to_string()),
+    }
+}
+
+pub fn consts<'a>(consts: impl IntoIterator<Item = &'a Const>, prefix: &str) -> Block {
+    Block::Table {
+        header: vec!["Constant", "Type", "Value", "Description"],
+        rows: consts
+            .into_iter()
+            .map(|c| {
+                vec![
+                    vec![Inline::Code(format!("{prefix}{}", c.ident))],
+                    vec![Inline::Code(c.r#type.to_string())],
+                    vec![Inline::Code(c.value.clone())],
+                    summary(c.doc.as_deref()),
+                ]
+            })
+            .collect(),
+    }
+}
+
+pub fn emit_enum(r#enum: &Enum, level: u8) -> Vec<Block> {
+    let mut blocks = heading(
+        level,
+        vec![
+            Inline::Text("Enum ".to_string()),
+            Inline::Code(r#enum.ident.to_string()),
+        ],
+        format!("enum-{}", r#enum.ident),
+        r#enum.doc.as_deref(),
+    );
+    blocks.push(Block::Paragraph(vec![
+        Inline::Text("Sent as ".to_string()),
+        Inline::Code(r#enum.r#type.to_string()),
+        Inline::Text(".".to_string()),
+    ]));
+    blocks.push(Block::Table {
+        header: vec!["Variant", "Value"],
+        rows: r#enum
+            .variants
+            .iter()
+            .map(|variant| {
+                vec![
+                    vec![Inline::Code(variant.ident.to_string())],
+                    vec![Inline::Code(variant.value.to_string())],
+                ]
+            })
+            .collect(),
+    });
+    blocks
+}
+
+/// Layout of `r#struct` as C lays it out, padding included.
+pub fn emit_struct(r#struct: &StructInner, file: &str) -> Vec<Block> {
+    let mut blocks = heading(
+        2,
+        vec![
+            Inline::Text("Struct ".to_string()),
+            Inline::Code(r#struct.ident.to_string()),
+        ],
+        format!("struct-{}", r#struct.ident),
+        r#struct.doc.as_deref(),
+    );
+    blocks.extend(deprecation(r#struct.deprecated.as_ref()));
+
+    let mut rows = Vec::new();
+    let mut offset = 0_usize;
+    for field in &r#struct.fields {
+        let padding = offset.next_multiple_of(field.alignment()) - offset;
+        offset += padding;
+        let count = field.val.1.get();
+        let suffix = if count == 1 {
+            String::new()
+        } else {
+            format!("[{count}]")
+        };
+        rows.push(vec![
+            vec![Inline::Code(offset.to_string())],
+            vec![Inline::Code(field.ident.to_string())],
+            type_text(&field.val.0, &suffix, file),
+            vec![Inline::Text(field.size().to_string())],
+            vec![Inline::Text(padding.to_string())],
+            summary(field.doc.as_deref()),
+        ]);
+        offset += field.size();
+    }
+    let align = r#struct.align();
+    let size = offset.next_multiple_of(align);
+
+    let mut layout = vec![Inline::Text(format!(
+        "Size: {size} bytes, alignment: {align} bytes"
+    ))];
+    if size != offset {
+        layout.push(Inline::Text(format!(
+            ", {} bytes of trailing padding",
+            size - offset
+        )));
+    }
+    layout.push(Inline::Text(".".to_string()));
+    blocks.push(Block::Paragraph(layout));
+    blocks.push(Block::Table {
+        header: vec!["Offset", "Field", "Type", "Size", "Padding", "Description"],
+        rows,
+    });
+    blocks
+}
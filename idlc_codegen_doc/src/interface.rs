@@ -0,0 +1,237 @@
+// Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
+// SPDX-License-Identifier: BSD-3-Clause
+
+use idlc_mir::{Function, Interface, InterfaceNode, Param, ParamTypeIn, ParamTypeOut, Type};
+
+use crate::globals::{
+    consts, deprecation, emit_enum, heading, interface_target, section, type_text,
+};
+use crate::page::{summary, Block, Inline, Page, Target};
+use crate::placement::Placements;
+
+pub fn emit(interface: &Interface, file: &str) -> Page {
+    let ident = interface.ident.to_string();
+    let mut blocks = heading(
+        1,
+        vec![
+            Inline::Text("Interface ".to_string()),
+            Inline::Code(ident.clone()),
+        ],
+        ident.clone(),
+        interface.doc.as_deref(),
+    );
+    blocks.extend(deprecation(interface.deprecated.as_ref()));
+
+    blocks.push(Block::Paragraph(vec![
+        Inline::Text("Declared in ".to_string()),
+        Inline::Link(
+            file.to_string(),
+            Target {
+                page: file.to_string(),
+                anchor: None,
+            },
+        ),
+        Inline::Text(", at version ".to_string()),
+        Inline::Code(interface.get_version().to_string()),
+        Inline::Text(".".to_string()),
+    ]));
+
+    let mut inheritance = vec![
+        Inline::Text("Inheritance: ".to_string()),
+        Inline::Code(ident.clone()),
+    ];
+    for base in interface.iter().skip(1) {
+        inheritance.push(Inline::Text(" → ".to_string()));
+        inheritance.push(Inline::Link(
+            base.ident.to_string(),
+            interface_target(base.ident.as_ref()),
+        ));
+    }
+    inheritance.push(Inline::Text(" → ".to_string()));
+    inheritance.push(Inline::Code("Object".to_string()));
+    blocks.push(Block::Paragraph(inheritance));
+
+    let constants: Vec<_> = interface
+        .nodes
+        .iter()
+        .filter_map(|node| match node {
+            InterfaceNode::Const(c) => Some(c),
+            _ => None,
+        })
+        .collect();
+    if !constants.is_empty() {
+        blocks.push(section("Constants", "constants"));
+        blocks.push(consts(constants, &format!("{ident}::")));
+    }
+
+    let enums: Vec<_> = interface
+        .nodes
+        .iter()
+        .filter_map(|node| match node {
+            InterfaceNode::Enum(e) => Some(e),
+            _ => None,
+        })
+        .collect();
+    if !enums.is_empty() {
+        blocks.push(section("Enums", "enums"));
+        for r#enum in enums {
+            blocks.extend(emit_enum(r#enum, 3));
+        }
+    }
+
+    // Errors and methods of the bases are part of the interface as well.
+    let mut errors = Vec::new();
+    let mut methods = Vec::new();
+    for iface in interface.iter().collect::<Vec<_>>().into_iter().rev() {
+        for node in &iface.nodes {
+            match node {
+                InterfaceNode::Error(e) => errors.push((iface, e)),
+                InterfaceNode::Function(f) => methods.push((iface, f)),
+                _ => (),
+            }
+        }
+    }
+
+    if !errors.is_empty() {
+        blocks.push(section("Errors", "errors"));
+        blocks.push(Block::Table {
+            header: vec!["Error", "Value", "Declared by", "Description"],
+            rows: errors
+                .into_iter()
+                .map(|(iface, error)| {
+                    vec![
+                        vec![Inline::Code(error.ident.to_string())],
+                        vec![Inline::Code(error.value.to_string())],
+                        declared_by(iface, None),
+                        summary(error.doc.as_deref()),
+                    ]
+                })
+                .collect(),
+        });
+    }
+
+    if !methods.is_empty() {
+        blocks.push(section("Methods", "methods"));
+        blocks.push(Block::Table {
+            header: vec!["Op-code", "Method", "Version", "Description"],
+            rows: methods
+                .iter()
+                .map(|(iface, function)| {
+                    vec![
+                        vec![Inline::Code(function.id.to_string())],
+                        declared_by(iface, Some(function)),
+                        function
+                            .get_version()
+                            .map(|v| vec![Inline::Code(v.to_string())])
+                            .unwrap_or_default(),
+                        summary(function.doc.as_deref()),
+                    ]
+                })
+                .collect(),
+        });
+        for function in methods
+            .iter()
+            .filter(|(iface, _)| std::ptr::eq(*iface, interface))
+            .map(|(_, function)| function)
+        {
+            blocks.extend(emit_method(function, file));
+        }
+    }
+
+    Page {
+        name: ident.clone(),
+        title: ident,
+        blocks,
+    }
+}
+
+/// Links to the interface of the hierarchy declaring the error or method.
+fn declared_by(iface: &Interface, function: Option<&Function>) -> Vec<Inline> {
+    let mut target = interface_target(iface.ident.as_ref());
+    let text = match function {
+        Some(function) => {
+            target.anchor = Some(format!("method-{}", function.ident));
+            function.ident.to_string()
+        }
+        None => iface.ident.to_string(),
+    };
+    vec![Inline::Link(text, target)]
+}
+
+fn emit_method(function: &Function, file: &str) -> Vec<Block> {
+    let mut blocks = heading(
+        3,
+        vec![
+            Inline::Text("Method ".to_string()),
+            Inline::Code(function.ident.to_string()),
+        ],
+        format!("method-{}", function.ident),
+        function.doc.as_deref(),
+    );
+    blocks.extend(deprecation(function.deprecation()));
+
+    let mut attributes = vec![
+        Inline::Text("Op-code ".to_string()),
+        Inline::Code(function.id.to_string()),
+    ];
+    if let Some(version) = function.get_version() {
+        attributes.push(Inline::Text(", ".to_string()));
+        attributes.push(Inline::Code(format!("#[version = {version}]")));
+    }
+    let counts = idlc_codegen::counts::Counter::new(function);
+    attributes.push(Inline::Text(format!(
+        ". Sends {} buffer(s) and {} object(s), receives {} buffer(s) and {} object(s).",
+        counts.input_buffers, counts.input_objects, counts.output_buffers, counts.output_objects
+    )));
+    blocks.push(Block::Paragraph(attributes));
+
+    if function.params.is_empty() {
+        return blocks;
+    }
+    let placements = Placements::new(function);
+    blocks.push(Block::Table {
+        header: vec!["Parameter", "Direction", "Type", "Placement"],
+        rows: function
+            .params
+            .iter()
+            .map(|param| {
+                let placement = placements
+                    .get(param.ident())
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                vec![
+                    vec![Inline::Code(param.ident().to_string())],
+                    vec![Inline::Text(
+                        if param.is_input() { "in" } else { "out" }.to_string(),
+                    )],
+                    type_text(param.r#type(), &array_suffix(param), file),
+                    vec![Inline::Text(placement)],
+                ]
+            })
+            .collect(),
+    });
+    blocks
+}
+
+fn array_suffix(param: &Param) -> String {
+    if let Type::String(_) = param.r#type() {
+        return String::new();
+    }
+    if let Some(max_len) = param.max_len() {
+        return format!("[<={max_len}]");
+    }
+    let count = match param {
+        Param::In {
+            r#type: ParamTypeIn::Array(_, count),
+            ..
+        }
+        | Param::Out {
+            r#type: ParamTypeOut::Array(_, count),
+            ..
+        } => count,
+        _ => return String::new(),
+    };
+    count.map_or_else(|| "[]".to_string(), |count| format!("[{count}]"))
+}
@@ -0,0 +1,18 @@
+// Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! API reference of IDL files, as Markdown or HTML pages.
+//!
+//! Every input gets a `<file>.idl` page with its constants, enums and
+//! structs, and every interface a `<interface>` page. Pages are named after
+//! what they describe rather than after the input, so links to declarations
+//! of other includes resolve once their pages are generated in the same
+//! directory.
+
+mod generator;
+mod globals;
+mod interface;
+mod page;
+mod placement;
+
+pub use generator::{Html, Markdown};
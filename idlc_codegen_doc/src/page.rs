@@ -0,0 +1,288 @@
+// Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Format independent model of the pages and its renderers.
+
+use idlc_codegen::MINKIDL_HEADER_COMMENT;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Markdown,
+    Html,
+}
+
+impl Format {
+    pub const fn extension(self) -> &'static str {
+        match self {
+            Self::Markdown => "md",
+            Self::Html => "html",
+        }
+    }
+}
+
+/// Location of a declaration, `page` is named without its extension.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Target {
+    pub page: String,
+    pub anchor: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Inline {
+    Text(String),
+    Code(String),
+    /// Identifier linking to its declaration.
+    Link(String, Target),
+}
+
+pub type Text = Vec<Inline>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Block {
+    Heading {
+        level: u8,
+        text: Text,
+        anchor: Option<String>,
+    },
+    Paragraph(Text),
+    /// Documentation of a declaration, see [`doc`].
+    Doc(String),
+    List(Vec<Text>),
+    Table {
+        header: Vec<&'static str>,
+        rows: Vec<Vec<Text>>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Page {
+    /// File name of the page, without its extension.
+    pub name: String,
+    pub title: String,
+    pub blocks: Vec<Block>,
+}
+
+/// Strips the comment markers of an IDL documentation block.
+pub fn doc(doc: &str) -> String {
+    let doc = doc.strip_prefix('*').unwrap_or(doc);
+    let doc = doc.strip_suffix('*').unwrap_or(doc);
+    let lines: Vec<_> = doc
+        .lines()
+        .map(|line| {
+            let line = line.trim_start();
+            let line = line.strip_prefix('*').unwrap_or(line);
+            line.strip_prefix(' ').unwrap_or(line).trim_end()
+        })
+        .collect();
+    lines.join("\n").trim_matches('\n').to_string()
+}
+
+/// First paragraph of a documentation block on a single line, for table
+/// cells.
+pub fn summary(text: Option<&str>) -> Text {
+    let Some(text) = text else {
+        return Vec::new();
+    };
+    let text = doc(text);
+    let summary: Vec<_> = text
+        .lines()
+        .take_while(|line| !line.trim().is_empty())
+        .map(str::trim)
+        .collect();
+    vec![Inline::Text(summary.join(" "))]
+}
+
+impl Page {
+    pub fn render(&self, format: Format) -> String {
+        match format {
+            Format::Markdown => self.markdown(),
+            Format::Html => self.html(),
+        }
+    }
+
+    fn markdown(&self) -> String {
+        let mut out = format!("<!-- {MINKIDL_HEADER_COMMENT} -->\n");
+        for block in &self.blocks {
+            out.push('\n');
+            match block {
+                Block::Heading {
+                    level,
+                    text,
+                    anchor,
+                } => {
+                    if let Some(anchor) = anchor {
+                        out.push_str(&format!("<a id=\"{anchor}\"></a>\n\n"));
+                    }
+                    out.push_str(&"#".repeat(usize::from(*level)));
+                    out.push(' ');
+                    out.push_str(&markdown_text(text));
+                    out.push('\n');
+                }
+                Block::Paragraph(text) => {
+                    out.push_str(&markdown_text(text));
+                    out.push('\n');
+                }
+                Block::Doc(doc) => {
+                    out.push_str(doc);
+                    out.push('\n');
+                }
+                Block::List(items) => {
+                    for item in items {
+                        out.push_str(&format!("- {}\n", markdown_text(item)));
+                    }
+                }
+                Block::Table { header, rows } => {
+                    out.push_str(&format!("| {} |\n", header.join(" | ")));
+                    out.push_str(&format!("|{}\n", "---|".repeat(header.len())));
+                    for row in rows {
+                        let cells: Vec<_> = row
+                            .iter()
+                            .map(|cell| markdown_text(cell).replace('|', "\\|"))
+                            .collect();
+                        out.push_str(&format!("| {} |\n", cells.join(" | ")));
+                    }
+                }
+            }
+        }
+        out
+    }
+
+    fn html(&self) -> String {
+        let mut out = format!(
+            "<!-- {MINKIDL_HEADER_COMMENT} -->\n<!DOCTYPE html>\n<html>\n<head>\n\
+             <meta charset=\"utf-8\">\n<title>{}</title>\n</head>\n<body>\n",
+            escape(&self.title)
+        );
+        for block in &self.blocks {
+            match block {
+                Block::Heading {
+                    level,
+                    text,
+                    anchor,
+                } => {
+                    let id = anchor
+                        .as_ref()
+                        .map(|anchor| format!(" id=\"{anchor}\""))
+                        .unwrap_or_default();
+                    out.push_str(&format!("<h{level}{id}>{}</h{level}>\n", html_text(text)));
+                }
+                Block::Paragraph(text) => {
+                    out.push_str(&format!("<p>{}</p>\n", html_text(text)));
+                }
+                Block::Doc(doc) => {
+                    for paragraph in doc.split("\n\n") {
+                        out.push_str(&format!("<p>{}</p>\n", escape(paragraph.trim())));
+                    }
+                }
+                Block::List(items) => {
+                    out.push_str("<ul>\n");
+                    for item in items {
+                        out.push_str(&format!("<li>{}</li>\n", html_text(item)));
+                    }
+                    out.push_str("</ul>\n");
+                }
+                Block::Table { header, rows } => {
+                    out.push_str("<table>\n<tr>");
+                    for cell in header {
+                        out.push_str(&format!("<th>{cell}</th>"));
+                    }
+                    out.push_str("</tr>\n");
+                    for row in rows {
+                        out.push_str("<tr>");
+                        for cell in row {
+                            out.push_str(&format!("<td>{}</td>", html_text(cell)));
+                        }
+                        out.push_str("</tr>\n");
+                    }
+                    out.push_str("</table>\n");
+                }
+            }
+        }
+        out.push_str("</body>\n</html>\n");
+        out
+    }
+}
+
+fn href(target: &Target, format: Format) -> String {
+    let anchor = target
+        .anchor
+        .as_ref()
+        .map(|anchor| format!("#{anchor}"))
+        .unwrap_or_default();
+    format!("{}.{}{anchor}", target.page, format.extension())
+}
+
+fn markdown_text(text: &Text) -> String {
+    text.iter()
+        .map(|inline| match inline {
+            Inline::Text(text) => text.clone(),
+            Inline::Code(code) => format!("`{code}`"),
+            Inline::Link(ident, target) => {
+                format!("[`{ident}`]({})", href(target, Format::Markdown))
+            }
+        })
+        .collect()
+}
+
+fn html_text(text: &Text) -> String {
+    text.iter()
+        .map(|inline| match inline {
+            Inline::Text(text) => escape(text),
+            Inline::Code(code) => format!("<code>{}</code>", escape(code)),
+            Inline::Link(ident, target) => format!(
+                "<a href=\"{}\"><code>{}</code></a>",
+                href(target, Format::Html),
+                escape(ident)
+            ),
+        })
+        .collect()
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_comment_markers() {
+        assert_eq!(
+            doc("*\n * Draws `p`.\n *\n *   Indented.\n *"),
+            "Draws `p`.\n\n  Indented."
+        );
+        assert_eq!(doc("*\n  No asterisks *"), "No asterisks");
+        assert_eq!(
+            summary(Some("*\n * First\n * line.\n *\n * Second.\n *")),
+            vec![Inline::Text("First line.".to_string())]
+        );
+    }
+
+    #[test]
+    fn renders_links() {
+        let page = Page {
+            name: "IFoo".to_string(),
+            title: "IFoo".to_string(),
+            blocks: vec![Block::Paragraph(vec![
+                Inline::Text("Takes ".to_string()),
+                Inline::Link(
+                    "S".to_string(),
+                    Target {
+                        page: "common.idl".to_string(),
+                        anchor: Some("struct-S".to_string()),
+                    },
+                ),
+            ])],
+        };
+        assert!(page
+            .render(Format::Markdown)
+            .contains("Takes [`S`](common.idl.md#struct-S)"));
+        assert!(page
+            .render(Format::Html)
+            .contains("<p>Takes <a href=\"common.idl.html#struct-S\"><code>S</code></a></p>"));
+    }
+}
@@ -0,0 +1,196 @@
+// Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Where each parameter of a method travels in the invoke arguments.
+
+use std::collections::HashMap;
+
+use idlc_codegen::functions::ParameterVisitor;
+use idlc_codegen::serialization::PackedPrimitives;
+use idlc_mir::{Count, Ident, Primitive, StructInner};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Slot {
+    /// Member of the bundle of the small parameters.
+    Bundled {
+        buffer: usize,
+        offset: usize,
+    },
+    Buffer(usize),
+    Objects {
+        first: usize,
+        count: usize,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Placement {
+    pub output: bool,
+    pub slot: Slot,
+}
+
+impl std::fmt::Display for Placement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let direction = if self.output { "output" } else { "input" };
+        match self.slot {
+            Slot::Bundled { buffer, offset } => {
+                write!(
+                    f,
+                    "{direction} buffer {buffer} (bundled at offset {offset})"
+                )
+            }
+            Slot::Buffer(index) => write!(f, "{direction} buffer {index}"),
+            Slot::Objects { first, count: 1 } => write!(f, "{direction} object {first}"),
+            Slot::Objects { first, count } => {
+                write!(f, "{direction} objects {first}-{}", first + count - 1)
+            }
+        }
+    }
+}
+
+/// Placements of the parameters of a function, by parameter name.
+///
+/// Buffers and objects are indexed within their own kind, in the order of
+/// the arguments of `Object_invoke`.
+#[derive(Debug, Default)]
+pub struct Placements {
+    input_buffers: usize,
+    input_objects: usize,
+    output_buffers: usize,
+    output_objects: usize,
+    params: HashMap<String, Vec<Placement>>,
+}
+
+impl Placements {
+    pub fn new(function: &idlc_mir::Function) -> Self {
+        let mut me = Self::default();
+        idlc_codegen::functions::visit_params_with_bundling(function, &mut me);
+        me
+    }
+
+    pub fn get(&self, ident: &Ident) -> &[Placement] {
+        self.params.get(ident.as_ref()).map_or(&[], Vec::as_slice)
+    }
+
+    fn push(&mut self, ident: &Ident, output: bool, slot: Slot) {
+        self.params
+            .entry(ident.to_string())
+            .or_default()
+            .push(Placement { output, slot });
+    }
+
+    fn buffer(&mut self, ident: &Ident, output: bool) {
+        let counter = if output {
+            &mut self.output_buffers
+        } else {
+            &mut self.input_buffers
+        };
+        let slot = Slot::Buffer(*counter);
+        *counter += 1;
+        self.push(ident, output, slot);
+    }
+
+    fn objects(&mut self, ident: &Ident, output: bool, count: usize) {
+        if count == 0 {
+            return;
+        }
+        let counter = if output {
+            &mut self.output_objects
+        } else {
+            &mut self.input_objects
+        };
+        let slot = Slot::Objects {
+            first: *counter,
+            count,
+        };
+        *counter += count;
+        self.push(ident, output, slot);
+    }
+
+    fn bundle<'a>(
+        &mut self,
+        members: impl Iterator<Item = (&'a Ident, &'a idlc_codegen::serialization::Type)>,
+        output: bool,
+    ) {
+        let counter = if output {
+            &mut self.output_buffers
+        } else {
+            &mut self.input_buffers
+        };
+        let buffer = *counter;
+        *counter += 1;
+        let mut offset = 0;
+        for (ident, ty) in members {
+            self.push(ident, output, Slot::Bundled { buffer, offset });
+            offset += ty.size();
+        }
+    }
+}
+
+impl ParameterVisitor for Placements {
+    fn visit_input_primitive_buffer(&mut self, ident: &Ident, _: Primitive) {
+        self.buffer(ident, false);
+    }
+
+    fn visit_input_struct_buffer(&mut self, ident: &Ident, _: &StructInner) {
+        self.buffer(ident, false);
+    }
+
+    fn visit_input_primitive(&mut self, ident: &Ident, _: Primitive) {
+        self.buffer(ident, false);
+    }
+
+    fn visit_input_bundled(&mut self, packed_primitives: &PackedPrimitives) {
+        self.bundle(packed_primitives.inputs_by_idents(), false);
+    }
+
+    fn visit_input_big_struct(&mut self, ident: &Ident, ty: &StructInner) {
+        self.buffer(ident, false);
+        self.objects(ident, false, ty.objects().len());
+    }
+
+    fn visit_input_small_struct(&mut self, ident: &Ident, _: &StructInner) {
+        self.buffer(ident, false);
+    }
+
+    fn visit_input_object(&mut self, ident: &Ident, _: Option<&str>) {
+        self.objects(ident, false, 1);
+    }
+
+    fn visit_input_object_array(&mut self, ident: &Ident, _: Option<&str>, cnt: Count) {
+        self.objects(ident, false, usize::from(cnt.get()));
+    }
+
+    fn visit_output_primitive_buffer(&mut self, ident: &Ident, _: Primitive) {
+        self.buffer(ident, true);
+    }
+
+    fn visit_output_struct_buffer(&mut self, ident: &Ident, _: &StructInner) {
+        self.buffer(ident, true);
+    }
+
+    fn visit_output_primitive(&mut self, ident: &Ident, _: Primitive) {
+        self.buffer(ident, true);
+    }
+
+    fn visit_output_bundled(&mut self, packed_primitives: &PackedPrimitives) {
+        self.bundle(packed_primitives.outputs_by_idents(), true);
+    }
+
+    fn visit_output_big_struct(&mut self, ident: &Ident, ty: &StructInner) {
+        self.buffer(ident, true);
+        self.objects(ident, true, ty.objects().len());
+    }
+
+    fn visit_output_small_struct(&mut self, ident: &Ident, _: &StructInner) {
+        self.buffer(ident, true);
+    }
+
+    fn visit_output_object(&mut self, ident: &Ident, _: Option<&str>) {
+        self.objects(ident, true, 1);
+    }
+
+    fn visit_output_object_array(&mut self, ident: &Ident, _: Option<&str>, cnt: Count) {
+        self.objects(ident, true, usize::from(cnt.get()));
+    }
+}
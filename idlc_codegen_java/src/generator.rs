@@ -9,7 +9,7 @@ use idlc_mir::Node;
 use idlc_codegen::MINKIDL_HEADER_COMMENT;
 
 use crate::{
-    globals::{emit_const, emit_struct},
+    globals::{emit_const, emit_enum, emit_struct},
     interface::emit_interface,
 };
 
@@ -49,6 +49,9 @@ package com.qualcomm.qti.mink;
                 Node::Const(c) => {
                     interfaces.get_mut(&base).unwrap().push_str(&emit_const(c));
                 }
+                Node::Enum(e) => {
+                    interfaces.get_mut(&base).unwrap().push_str(&emit_enum(e));
+                }
                 Node::Struct(s) => {
                     interfaces
                         .get_mut(&base)
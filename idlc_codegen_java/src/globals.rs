@@ -1,7 +1,8 @@
 // Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
 // SPDX-License-Identifier: BSD-3-Clause
 
-use idlc_mir::{Const, StructInner};
+use idlc_codegen::documentation::{Documentation, DocumentationStyle};
+use idlc_mir::{Const, Deprecation, Enum, StructInner};
 
 use crate::types::change_primitive;
 
@@ -15,11 +16,13 @@ pub fn emit_struct(r#struct: &StructInner) -> String {
         let ty = match &field.val.0 {
             &idlc_mir::Type::Primitive(primitive) => change_primitive(primitive).to_string(),
             idlc_mir::Type::Struct(s) => s.as_ref().ident.to_string(),
+            idlc_mir::Type::Enum(e) => change_primitive(e.r#type).to_string(),
             idlc_mir::Type::Interface(_) => {
                 unimplemented!("Java codegen doesn't support objects in struct")
             }
             _ => unreachable!(),
         };
+        contents.push_str(&doc(field.doc.as_deref(), "        "));
         contents.push_str(&if count == 1 {
             format!(
                 r#"public {ty} {ident};
@@ -33,9 +36,12 @@ pub fn emit_struct(r#struct: &StructInner) -> String {
         });
     }
 
+    let doc = doc(r#struct.doc.as_deref(), "    ");
+    let deprecated = deprecated(r#struct.deprecated.as_ref(), "    ");
+
     format!(
         r#"
-    class {struct_ident} {{
+    {doc}{deprecated}class {struct_ident} {{
         {contents}
     }}
     "#
@@ -46,9 +52,56 @@ pub fn emit_const(r#const: &Const) -> String {
     let ident = r#const.ident.to_string(); // Const ident should be uppercase, but leave it for now for backward compatibility.
     let ty = change_primitive(r#const.r#type);
     let value = &r#const.value;
+    let doc = doc(r#const.doc.as_deref(), "    ");
 
     format!(
-        r#"{ty} {ident} = {value};
+        r#"{doc}{ty} {ident} = {value};
     "#
     )
 }
+
+/// Java has no enums with a fixed width, so variants are constants of the
+/// underlying primitive.
+pub fn emit_enum(r#enum: &Enum) -> String {
+    let enum_ident = crate::safe_ident_java(r#enum.ident.as_ref());
+    let ty = change_primitive(r#enum.r#type);
+    let mut contents = String::new();
+    for variant in &r#enum.variants {
+        let ident = crate::safe_ident_java(variant.ident.as_ref());
+        // Java integers are signed, so unsigned values wrap around.
+        let value = match r#enum.r#type.size() {
+            1 => format!("({ty}) {}", variant.value as i8),
+            2 => format!("({ty}) {}", variant.value as i16),
+            4 => format!("{}", variant.value as i32),
+            _ => format!("{}L", variant.value as i64),
+        };
+        contents.push_str(&format!(
+            r#"public static final {ty} {ident} = {value};
+        "#
+        ));
+    }
+
+    let doc = doc(r#enum.doc.as_deref(), "    ");
+
+    format!(
+        r#"
+    {doc}final class {enum_ident} {{
+        {contents}
+    }}
+    "#
+    )
+}
+
+/// Javadoc of a declaration, every line followed by the indentation of the
+/// next one.
+pub fn doc(doc: Option<&str>, indent: &str) -> String {
+    Documentation::with_doc(doc, DocumentationStyle::Java)
+        .lines()
+        .map(|line| format!("{line}\n{indent}"))
+        .collect()
+}
+
+/// Annotation of a deprecated declaration, Java has no room for its note.
+pub fn deprecated(deprecation: Option<&Deprecation>, indent: &str) -> String {
+    deprecation.map_or_else(String::new, |_| format!("@Deprecated\n{indent}"))
+}
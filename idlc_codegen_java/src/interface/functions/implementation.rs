@@ -4,9 +4,9 @@
 use idlc_mir::Ident;
 
 use crate::interface::mink_primitives::{
-    BI, BO, BUNDLE_IN, BUNDLE_OUT, BYTE_BUFFER, BYTE_ORDER, OI, OO, OP_ID,
+    BI, BO, BUNDLE_IN, BUNDLE_OUT, BYTE_BUFFER, BYTE_ORDER, OI, OO, OP_ID, UTF_8,
 };
-use crate::types::{capitalize_first_letter, change_primitive, get_struct_pair};
+use crate::types::{capitalize_first_letter, change_primitive, get, get_struct_pair, put};
 
 use super::serialization::TransportBuffer;
 
@@ -89,22 +89,41 @@ impl idlc_codegen::functions::ParameterVisitor for Implementation {
         let bi_idx = self.bi_idx();
         let name = format!("{}_val", ident);
         let sz = ty.size();
-        let ty = capitalize_first_letter(change_primitive(ty));
-        let to_buffer = if !ty.is_empty() {
-            format!("as{ty}Buffer().")
+        let put = if ty == idlc_mir::Primitive::Bool {
+            format!(
+                r#"for (boolean b : {name}) {{
+                    buffer_{name}.put((byte) (b ? 1 : 0));
+                }}"#
+            )
         } else {
-            "".to_string()
+            let ty = capitalize_first_letter(change_primitive(ty));
+            let to_buffer = if !ty.is_empty() {
+                format!("as{ty}Buffer().")
+            } else {
+                "".to_string()
+            };
+            format!("buffer_{name}.{to_buffer}put({name});")
         };
         self.initializations.push(format!(
             r#"if({name} != null) {{
                 {BYTE_BUFFER} buffer_{name} = {BYTE_BUFFER}.allocate({name}.length*{sz}).order({BYTE_ORDER});
-                buffer_{name}.{to_buffer}put({name});
+                {put}
                 {BI}[{bi_idx}] = buffer_{name}.array();
             }}
             "#
         ));
     }
 
+    fn visit_input_max_len(&mut self, ident: &Ident, _: usize, max_len: idlc_mir::Count) {
+        let name = format!("{}_val", ident);
+        self.initializations.push(format!(
+            r#"if ({name} != null && {name}.length > {max_len}) {{
+                throw new IMinkObject.InvokeException(IMinkObject.ERROR_INVALID);
+            }}
+            "#
+        ));
+    }
+
     fn visit_input_untyped_buffer(&mut self, ident: &idlc_mir::Ident) {
         let bi_idx = self.bi_idx();
         let name = format!("{}_val", ident);
@@ -114,6 +133,25 @@ impl idlc_codegen::functions::ParameterVisitor for Implementation {
         ));
     }
 
+    fn visit_input_string(&mut self, ident: &Ident, max_len: Option<idlc_mir::Count>) {
+        let bi_idx = self.bi_idx();
+        let name = format!("{}_val", ident);
+        self.initializations.push(format!(
+            r#"if({name} != null) {{
+                {BI}[{bi_idx}] = {name}.getBytes({UTF_8});
+            }}
+            "#
+        ));
+        if let Some(max_len) = max_len {
+            self.initializations.push(format!(
+                r#"if ({BI}[{bi_idx}] != null && {BI}[{bi_idx}].length > {max_len}) {{
+                throw new IMinkObject.InvokeException(IMinkObject.ERROR_INVALID);
+            }}
+            "#
+            ));
+        }
+    }
+
     fn visit_input_struct_buffer(&mut self, ident: &Ident, ty: &idlc_mir::StructInner) {
         let bi_idx = self.bi_idx();
         let name = format!("{}_val", ident);
@@ -123,12 +161,13 @@ impl idlc_codegen::functions::ParameterVisitor for Implementation {
 
         get_struct_pair(ty, &mut field_idents, "".to_string());
         for (field_ident, field_ty) in field_idents {
-            let mut capitalized_ty = String::new();
-            if let idlc_mir::Type::Primitive(p) = field_ty {
-                capitalized_ty = capitalize_first_letter(change_primitive(p));
-            }
+            let write = put(
+                &format!("buffer_{name}"),
+                &field_ty,
+                &format!("i.{field_ident}"),
+            );
             buffer_in.push_str(&format!(
-                r#"buffer_{name}.put{capitalized_ty}(i.{field_ident});
+                r#"{write};
                     "#
             ));
         }
@@ -167,9 +206,13 @@ impl idlc_codegen::functions::ParameterVisitor for Implementation {
         let bi_idx = self.bi_idx();
         let name = format!("{}_val", ident);
         let sz = ty.size();
-        let ty = capitalize_first_letter(change_primitive(ty));
+        let write = put(
+            &format!("{BYTE_BUFFER}.allocate({sz}).order({BYTE_ORDER})"),
+            &idlc_mir::Type::Primitive(ty),
+            &name,
+        );
         self.initializations.push(format!(
-            r#"{BI}[{bi_idx}] = {BYTE_BUFFER}.allocate({sz}).order({BYTE_ORDER}).put{ty}({name}).array();
+            r#"{BI}[{bi_idx}] = {write}.array();
             "#
         ));
     }
@@ -202,12 +245,13 @@ impl idlc_codegen::functions::ParameterVisitor for Implementation {
         let mut field_idents = Vec::new();
         get_struct_pair(ty, &mut field_idents, "".to_string());
         for (field_ident, field_ty) in field_idents {
-            let mut capitalized_ty = String::new();
-            if let idlc_mir::Type::Primitive(p) = field_ty {
-                capitalized_ty = capitalize_first_letter(change_primitive(p));
-            }
+            let write = put(
+                &format!("buffer_{name}"),
+                &field_ty,
+                &format!("{name}.{field_ident}"),
+            );
             fields.push_str(&format!(
-                r#"buffer_{name}.put{capitalized_ty}({name}.{field_ident});
+                r#"{write};
             "#
             ));
         }
@@ -237,13 +281,22 @@ impl idlc_codegen::functions::ParameterVisitor for Implementation {
         let bo_sz_idx = self.bo_sz_idx();
         let name = format!("{}_ptr", ident);
         let sz = ty.size();
-        let ty = change_primitive(ty);
-        let capitalized_ty = capitalize_first_letter(ty);
-        let to_buffer = if !capitalized_ty.is_empty() {
-            format!("as{capitalized_ty}Buffer().")
+        let get = if ty == idlc_mir::Primitive::Bool {
+            format!(
+                r#"for (int k = 0; k < {ident}_lenout; k++) {{
+                    {name}[0][k] = {BO}[{bo_idx}][k] != 0;
+                }}"#
+            )
         } else {
-            "".to_string()
+            let capitalized_ty = capitalize_first_letter(change_primitive(ty));
+            let to_buffer = if !capitalized_ty.is_empty() {
+                format!("as{capitalized_ty}Buffer().")
+            } else {
+                "".to_string()
+            };
+            format!("{BYTE_BUFFER}.wrap({BO}[{bo_idx}]).order({BYTE_ORDER}).{to_buffer}get({name}[0], 0, {ident}_lenout);")
         };
+        let ty = change_primitive(ty);
         self.initializations.push(format!(
             r#"boSizes[{bo_sz_idx}] = {ident}_len*{sz};
             "#,
@@ -252,7 +305,7 @@ impl idlc_codegen::functions::ParameterVisitor for Implementation {
             r#"if({name} != null) {{
                 int {ident}_lenout = Math.min({BO}[{bo_idx}].length/{sz}, {ident}_len);
                 {name}[0] = new {ty}[{ident}_lenout];
-                {BYTE_BUFFER}.wrap({BO}[{bo_idx}]).order({BYTE_ORDER}).{to_buffer}get({name}[0], 0, {ident}_lenout);
+                {get}
             }}
             "#,
         ));
@@ -274,6 +327,26 @@ impl idlc_codegen::functions::ParameterVisitor for Implementation {
         ));
     }
 
+    fn visit_output_string(&mut self, ident: &Ident, max_len: idlc_mir::Count) {
+        let bo_idx = self.bo_idx();
+        let bo_sz_idx = self.bo_sz_idx();
+        let name = format!("{}_ptr", ident);
+        self.initializations.push(format!(
+            r#"boSizes[{bo_sz_idx}] = {max_len};
+            "#,
+        ));
+        self.post_call.push(format!(
+            r#"if({name} != null) {{
+                try {{
+                    {name}[0] = {UTF_8}.newDecoder().decode({BYTE_BUFFER}.wrap({BO}[{bo_idx}])).toString();
+                }} catch (java.nio.charset.CharacterCodingException e) {{
+                    throw new IMinkObject.InvokeException(IMinkObject.ERROR_INVALID);
+                }}
+            }}
+            "#,
+        ));
+    }
+
     fn visit_output_struct_buffer(&mut self, ident: &Ident, ty: &idlc_mir::StructInner) {
         let bo_idx = self.bo_idx();
         let bo_sz_idx = self.bo_sz_idx();
@@ -286,12 +359,9 @@ impl idlc_codegen::functions::ParameterVisitor for Implementation {
         get_struct_pair(ty, &mut field_idents, "".to_string());
         let ty = ty.ident.to_string();
         for (field_ident, field_ty) in field_idents {
-            let mut capitalized_ty = String::new();
-            if let idlc_mir::Type::Primitive(p) = field_ty {
-                capitalized_ty = capitalize_first_letter(change_primitive(p));
-            }
+            let read = get(&format!("buffer_{name}"), &field_ty);
             buffer_in.push_str(&format!(
-                r#"{name}[0][i].{field_ident} = buffer_{name}.get{capitalized_ty}();
+                r#"{name}[0][i].{field_ident} = {read};
                     "#
             ));
         }
@@ -335,7 +405,7 @@ impl idlc_codegen::functions::ParameterVisitor for Implementation {
         let bo_sz_idx = self.bo_sz_idx();
         let name = format!("{}_ptr", ident);
         let sz = ty.size();
-        let ty = capitalize_first_letter(change_primitive(ty));
+        let read = get(BUNDLE_OUT, &idlc_mir::Type::Primitive(ty));
         self.initializations.push(format!(
             r#"boSizes[{bo_sz_idx}] = {sz};
             "#,
@@ -344,7 +414,7 @@ impl idlc_codegen::functions::ParameterVisitor for Implementation {
             r#"{BYTE_BUFFER} {BUNDLE_OUT};
             {BUNDLE_OUT} = {BYTE_BUFFER}.wrap({BO}[{bo_idx}]).order({BYTE_ORDER});
             if({name} != null) {{
-                {name}[0] = {BUNDLE_OUT}.get{ty}();
+                {name}[0] = {read};
             }}
             "#,
         ));
@@ -380,12 +450,9 @@ impl idlc_codegen::functions::ParameterVisitor for Implementation {
         let mut field_idents = Vec::new();
         get_struct_pair(ty, &mut field_idents, "".to_string());
         for (field_ident, field_ty) in field_idents {
-            let mut capitalized_ty = String::new();
-            if let idlc_mir::Type::Primitive(p) = field_ty {
-                capitalized_ty = capitalize_first_letter(change_primitive(p));
-            }
+            let read = get(BUNDLE_OUT, &field_ty);
             fields.push_str(&format!(
-                r#"{name}[0].{field_ident} = {BUNDLE_OUT}.get{capitalized_ty}();
+                r#"{name}[0].{field_ident} = {read};
                 "#
             ));
         }
@@ -431,6 +498,7 @@ pub fn emit(
     let initializations = implementation.initializations();
     let post_call_assignments = implementation.post_call_assignments();
     let documentation = documentation.to_string().replace('\n', "\n        ");
+    let deprecated = crate::globals::deprecated(function.deprecation(), "        ");
 
     let params = super::signature::iter_to_string(signature.params());
 
@@ -487,7 +555,7 @@ pub fn emit(
     format!(
         r#"
         {documentation}
-        @Override
+        {deprecated}@Override
         public void {fn_ident}({params}) throws IMinkObject.InvokeException {{
             {inputs}
             {initializations}
@@ -2,9 +2,11 @@
 // SPDX-License-Identifier: BSD-3-Clause
 
 use crate::interface::mink_primitives::{
-    BI, BO, BUNDLE_IN, BUNDLE_OUT, BYTE_BUFFER, BYTE_ORDER, IMINK_OBJECT, OI, OO, OP_ID,
+    BI, BO, BUNDLE_IN, BUNDLE_OUT, BYTE_BUFFER, BYTE_ORDER, IMINK_OBJECT, OI, OO, OP_ID, UTF_8,
 };
-use crate::types::{capitalize_first_letter, change_primitive, get_struct_pair};
+use crate::types::{capitalize_first_letter, change_primitive, get, get_struct_pair, put};
+
+use std::ops::Range;
 
 use super::serialization::TransportBuffer;
 
@@ -28,6 +30,33 @@ impl Invoke {
         me
     }
 
+    /// Breaks out of the invoke unless every byte of `BI[bi_idx]` in `ranges`
+    /// of each `element_size` sized element is a valid `boolean`.
+    fn check_bools(&mut self, bi_idx: usize, element_size: usize, ranges: &[Range<usize>]) {
+        if ranges.is_empty() {
+            return;
+        }
+        let mut checks = String::new();
+        for Range { start, end } in ranges {
+            checks.push_str(&format!(
+                r#"for (int k = n + {start}; k < n + {end}; k++) {{
+                            bools_{bi_idx} &= {BI}[{bi_idx}][k] == 0 || {BI}[{bi_idx}][k] == 1;
+                        }}
+                        "#
+            ));
+        }
+        self.pre.push(format!(
+            r#"boolean bools_{bi_idx} = true;
+                    for (int n = 0; n + {element_size} <= {BI}[{bi_idx}].length; n += {element_size}) {{
+                        {checks}
+                    }}
+                    if (!bools_{bi_idx}) {{
+                        break;
+                    }}
+                    "#
+        ));
+    }
+
     pub fn pre(&self) -> String {
         self.pre.concat()
     }
@@ -82,6 +111,17 @@ impl Invoke {
 impl idlc_codegen::functions::ParameterVisitor for Invoke {
     fn visit_input_primitive_buffer(&mut self, ident: &idlc_mir::Ident, ty: idlc_mir::Primitive) {
         let bi_idx = self.bi_idx();
+        if ty == idlc_mir::Primitive::Bool {
+            self.check_bools(bi_idx, 1, std::slice::from_ref(&(0..1)));
+            self.pre.push(format!(
+                r#"boolean[] {ident} = new boolean[{BI}[{bi_idx}].length];
+                    for (int k = 0; k < {ident}.length; k++) {{
+                        {ident}[k] = {BI}[{bi_idx}][k] != 0;
+                    }}
+                    "#
+            ));
+            return;
+        }
         let ty = change_primitive(ty);
         let capitalized_ty = capitalize_first_letter(ty);
         let to_buffer = if !capitalized_ty.is_empty() {
@@ -96,18 +136,48 @@ impl idlc_codegen::functions::ParameterVisitor for Invoke {
         ));
     }
 
+    fn visit_input_max_len(
+        &mut self,
+        _: &idlc_mir::Ident,
+        element_size: usize,
+        max_len: idlc_mir::Count,
+    ) {
+        let bi_idx = self.bi_idx;
+        let size = usize::from(max_len.get()) * element_size;
+        self.pre.push(format!(
+            r#"if ({BI}[{bi_idx}].length > {size}) {{
+                        break;
+                    }}
+                    "#
+        ));
+    }
+
+    fn visit_input_string(&mut self, ident: &idlc_mir::Ident, max_len: Option<idlc_mir::Count>) {
+        if let Some(max_len) = max_len {
+            self.visit_input_max_len(ident, 1, max_len);
+        }
+        let bi_idx = self.bi_idx();
+        self.pre.push(format!(
+            r#"String {ident};
+                    try {{
+                        {ident} = {UTF_8}.newDecoder().decode({BYTE_BUFFER}.wrap({BI}[{bi_idx}])).toString();
+                    }} catch (java.nio.charset.CharacterCodingException e) {{
+                        break;
+                    }}
+                    "#
+        ));
+    }
+
     fn visit_input_struct_buffer(&mut self, ident: &idlc_mir::Ident, ty: &idlc_mir::StructInner) {
         let bi_idx = self.bi_idx();
+        self.check_bools(bi_idx, ty.size(), &ty.bools());
         let mut fields = String::new();
         let mut field_idents = Vec::new();
         get_struct_pair(ty, &mut field_idents, "".to_string());
         for (field_ident, field_ty) in field_idents {
-            let mut capitalized_ty = String::new();
-            if let idlc_mir::Type::Primitive(p) = field_ty {
-                capitalized_ty = capitalize_first_letter(change_primitive(p));
-            }
+            let read = get(&format!("{BUNDLE_IN}{bi_idx}"), &field_ty);
             fields.push_str(&format!(
-                r#"{ident}[0].{field_ident}={BUNDLE_IN}{bi_idx}.get{capitalized_ty}();
+                r#"{ident}[0].{field_ident}={read};
                     "#
             ));
         }
@@ -138,14 +208,23 @@ impl idlc_codegen::functions::ParameterVisitor for Invoke {
     fn visit_input_primitive(&mut self, ident: &idlc_mir::Ident, ty: idlc_mir::Primitive) {
         let bi_idx = self.bi_idx();
         let sz = ty.size();
-        let ty = change_primitive(ty);
-        let capitalized_ty = capitalize_first_letter(ty);
+        let read = get(
+            &format!("{BYTE_BUFFER}.wrap({BI}[{bi_idx}]).order({BYTE_ORDER})"),
+            &idlc_mir::Type::Primitive(ty),
+        );
 
         self.pre.push(format!(
             r#"if ({BI}[{bi_idx}].length != {sz}) {{
                         break;
                     }}
-                    {ty} {ident} = {BYTE_BUFFER}.wrap({BI}[{bi_idx}]).order({BYTE_ORDER}).get{capitalized_ty}();
+                    "#
+        ));
+        if ty == idlc_mir::Primitive::Bool {
+            self.check_bools(bi_idx, sz, std::slice::from_ref(&(0..1)));
+        }
+        let ty = change_primitive(ty);
+        self.pre.push(format!(
+            r#"{ty} {ident} = {read};
                     "#
         ));
     }
@@ -163,7 +242,11 @@ impl idlc_codegen::functions::ParameterVisitor for Invoke {
             r#"if ({BI}[{bi_idx}].length != {size}) {{
                         break;
                     }}
-                    {BYTE_BUFFER} {BUNDLE_IN} = {BYTE_BUFFER}.wrap({BI}[{bi_idx}]).order({BYTE_ORDER});
+                    "#,
+        ));
+        self.check_bools(bi_idx, size, &packed_primitives.input_bools());
+        self.pre.push(format!(
+            r#"{BYTE_BUFFER} {BUNDLE_IN} = {BYTE_BUFFER}.wrap({BI}[{bi_idx}]).order({BYTE_ORDER});
                     {definition}
                     "#,
         ));
@@ -177,21 +260,23 @@ impl idlc_codegen::functions::ParameterVisitor for Invoke {
         let mut field_idents = Vec::new();
         get_struct_pair(ty, &mut field_idents, "".to_string());
         for (field_ident, field_ty) in field_idents {
-            let mut capitalized_ty = String::new();
-            if let idlc_mir::Type::Primitive(p) = field_ty {
-                capitalized_ty = capitalize_first_letter(change_primitive(p));
-            }
+            let read = get(&format!("{BUNDLE_IN}{bi_idx}"), &field_ty);
             fields.push_str(&format!(
-                r#"{ident}.{field_ident}={BUNDLE_IN}{bi_idx}.get{capitalized_ty}();
+                r#"{ident}.{field_ident}={read};
                     "#
             ));
         }
+        let bools = ty.bools();
         let ty = ty.ident.to_string();
         self.pre.push(format!(
             r#"if ({BI}[{bi_idx}].length != {sz}) {{
                         break;
                     }}
-                    {ty} {ident} = new {ty}();
+                    "#
+        ));
+        self.check_bools(bi_idx, sz, &bools);
+        self.pre.push(format!(
+            r#"{ty} {ident} = new {ty}();
                     {BYTE_BUFFER} {BUNDLE_IN}{bi_idx} = {BYTE_BUFFER}.wrap({BI}[{bi_idx}]).order({BYTE_ORDER});
                     {fields}
                     "#
@@ -216,6 +301,7 @@ impl idlc_codegen::functions::ParameterVisitor for Invoke {
         let bo_idx = self.bo_idx();
         let bo_sz_idx = self.bo_sz_idx();
         let sz = ty.size();
+        let is_bool = ty == idlc_mir::Primitive::Bool;
         let ty = change_primitive(ty);
         let capitalized_ty = capitalize_first_letter(ty);
         let to_buffer = if !capitalized_ty.is_empty() {
@@ -223,6 +309,15 @@ impl idlc_codegen::functions::ParameterVisitor for Invoke {
         } else {
             "".to_string()
         };
+        let put = if is_bool {
+            format!(
+                r#"for (boolean b : {ident}[0]) {{
+                        buffer_{ident}.put((byte) (b ? 1 : 0));
+                    }}"#
+            )
+        } else {
+            format!("buffer_{ident}.{to_buffer}put({ident}[0]);")
+        };
 
         self.pre.push(format!(
             r#"{ty}[][] {ident} = new {ty}[1][];
@@ -231,7 +326,7 @@ impl idlc_codegen::functions::ParameterVisitor for Invoke {
         ));
         self.post.push(format!(
             r#"{BYTE_BUFFER} buffer_{ident} = {BYTE_BUFFER}.allocate({ident}.length*{sz}).order({BYTE_ORDER});
-                    buffer_{ident}.{to_buffer}put({ident}[0]);
+                    {put}
                     {BO}[{bo_idx}] = buffer_{ident}.array();
                     "#,
         ));
@@ -251,6 +346,23 @@ impl idlc_codegen::functions::ParameterVisitor for Invoke {
         ));
     }
 
+    fn visit_output_string(&mut self, ident: &idlc_mir::Ident, _: idlc_mir::Count) {
+        let bo_idx = self.bo_idx();
+        let bo_sz_idx = self.bo_sz_idx();
+        self.pre.push(format!(
+            r#"String[] {ident} = new String[1];
+                    "#,
+        ));
+        self.post.push(format!(
+            r#"byte[] bytes_{ident} = {ident}[0].getBytes({UTF_8});
+                    if (bytes_{ident}.length > boSizes[{bo_sz_idx}]) {{
+                        break;
+                    }}
+                    {BO}[{bo_idx}] = bytes_{ident};
+                    "#,
+        ));
+    }
+
     fn visit_output_struct_buffer(&mut self, ident: &idlc_mir::Ident, ty: &idlc_mir::StructInner) {
         let bo_idx = self.bo_idx();
         let bo_sz_idx = self.bo_sz_idx();
@@ -261,12 +373,13 @@ impl idlc_codegen::functions::ParameterVisitor for Invoke {
 
         get_struct_pair(ty, &mut field_idents, "".to_string());
         for (field_ident, field_ty) in field_idents {
-            let mut capitalized_ty = String::new();
-            if let idlc_mir::Type::Primitive(p) = field_ty {
-                capitalized_ty = capitalize_first_letter(change_primitive(p));
-            }
+            let write = put(
+                &format!("buffer_{ident}"),
+                &field_ty,
+                &format!("i.{field_ident}"),
+            );
             buffer_in.push_str(&format!(
-                r#"buffer_{ident}.put{capitalized_ty}(i.{field_ident});
+                r#"{write};
                     "#
             ));
         }
@@ -318,12 +431,13 @@ impl idlc_codegen::functions::ParameterVisitor for Invoke {
         let mut field_idents = Vec::new();
         get_struct_pair(ty, &mut field_idents, "".to_string());
         for (field_ident, field_ty) in field_idents {
-            let mut capitalized_ty = String::new();
-            if let idlc_mir::Type::Primitive(p) = field_ty {
-                capitalized_ty = capitalize_first_letter(change_primitive(p));
-            }
+            let write = put(
+                &format!("buffer_{ident}"),
+                &field_ty,
+                &format!("{ident}[0].{field_ident}"),
+            );
             fields.push_str(&format!(
-                r#"buffer_{ident}.put{capitalized_ty}({ident}[0].{field_ident});
+                r#"{write};
                     "#
             ));
         }
@@ -351,8 +465,12 @@ impl idlc_codegen::functions::ParameterVisitor for Invoke {
         let bo_idx = self.bo_idx();
         let bo_sz_idx = self.bo_sz_idx();
         let sz = ty.size();
+        let write = put(
+            &format!("{BYTE_BUFFER}.allocate({sz}).order({BYTE_ORDER})"),
+            &idlc_mir::Type::Primitive(ty),
+            &format!("{ident}[0]"),
+        );
         let ty = change_primitive(ty);
-        let capitalized_ty = capitalize_first_letter(ty);
 
         self.pre.push(format!(
             r#"if (boSizes[{bo_sz_idx}] != {sz}) {{
@@ -362,7 +480,7 @@ impl idlc_codegen::functions::ParameterVisitor for Invoke {
                     "#,
         ));
         self.post.push(format!(
-            r#"{BO}[{bo_idx}] = {BYTE_BUFFER}.allocate({sz}).order({BYTE_ORDER}).put{capitalized_ty}({ident}[0]).array();
+            r#"{BO}[{bo_idx}] = {write}.array();
                     "#,
         ));
     }
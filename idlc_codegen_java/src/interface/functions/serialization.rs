@@ -5,7 +5,7 @@ use idlc_codegen::serialization::Type;
 
 use crate::interface::mink_primitives::{BUNDLE_IN, BUNDLE_OUT};
 
-use crate::types::{capitalize_first_letter, change_primitive, get_struct_pair};
+use crate::types::{change_primitive, get, get_struct_pair, put};
 
 #[derive(Debug, Clone)]
 pub struct TransportBuffer {
@@ -34,7 +34,8 @@ impl<'a> PackedPrimitives<'a> {
     pub fn pre_bo_assignments(&self) -> String {
         let mut assignments = String::new();
         self.0.outputs_by_idents().for_each(|(ident, ty)| match ty {
-            &Type::Primitive(p) => {
+            // Enums are their underlying primitive in Java.
+            &Type::Primitive(p) | &Type::Enum(idlc_mir::Enum { r#type: p, .. }) => {
                 let ty = change_primitive(p);
                 assignments += &format!(
                     r#"{ty}[] {ident} = new {ty}[1];
@@ -75,30 +76,34 @@ impl<'a> PackedPrimitives<'a> {
         let mut fields = String::new();
         for (ident, ty) in pairs {
             match ty {
-                &Type::Primitive(p) => {
+                &Type::Primitive(p) | &Type::Enum(idlc_mir::Enum { r#type: p, .. }) => {
                     let ty = change_primitive(p);
-                    let capitalized_ty = capitalize_first_letter(ty);
+                    let p = idlc_mir::Type::Primitive(p);
                     if is_invoke {
                         if in_out == BUNDLE_IN {
+                            let read = get(in_out, &p);
                             fields.push_str(&format!(
-                                r#"{ty} {ident}={in_out}.get{capitalized_ty}();
+                                r#"{ty} {ident}={read};
                     "#
                             ));
                         } else {
+                            let write = put(in_out, &p, &format!("{ident}[0]"));
                             fields.push_str(&format!(
-                                r#"{in_out}.put{capitalized_ty}({ident}[0]);
+                                r#"{write};
                     "#
                             ));
                         }
                     } else if in_out == BUNDLE_IN {
+                        let write = put(in_out, &p, &format!("{ident}_val"));
                         fields.push_str(&format!(
-                            r#"{in_out}.put{capitalized_ty}({ident}_val);
+                            r#"{write};
                 "#
                         ));
                     } else {
+                        let read = get(in_out, &p);
                         fields.push_str(&format!(
                             r#"if ({ident}_ptr != null) {{
-                    {ident}_ptr[0] = {in_out}.get{capitalized_ty}();
+                    {ident}_ptr[0] = {read};
                 }}
                 "#
                         ));
@@ -115,31 +120,33 @@ impl<'a> PackedPrimitives<'a> {
                     }
                     get_struct_pair(s, &mut field_idents, "".to_string());
                     for (field_ident, field_ty) in field_idents {
-                        let mut capitalized_ty = String::new();
-                        if let idlc_mir::Type::Primitive(p) = field_ty {
-                            capitalized_ty = capitalize_first_letter(change_primitive(p));
-                        }
                         if is_invoke {
                             if in_out == BUNDLE_IN {
+                                let read = get(in_out, &field_ty);
                                 fields.push_str(&format!(
-                                    r#"{ident}.{field_ident}={BUNDLE_IN}.get{capitalized_ty}();
+                                    r#"{ident}.{field_ident}={read};
                     "#,
                                 ));
                             } else {
+                                let write =
+                                    put(in_out, &field_ty, &format!("{ident}[0].{field_ident}"));
                                 fields.push_str(&format!(
-                                    r#"{BUNDLE_OUT}.put{capitalized_ty}({ident}[0].{field_ident});
+                                    r#"{write};
                     "#,
                                 ));
                             }
                         } else if in_out == BUNDLE_IN {
+                            let write =
+                                put(in_out, &field_ty, &format!("{ident}_val.{field_ident}"));
                             fields.push_str(&format!(
-                                r#"{in_out}.put{capitalized_ty}({ident}_val.{field_ident});
+                                r#"{write};
             "#,
                             ));
                         } else {
+                            let read = get(in_out, &field_ty);
                             fields.push_str(&format!(
                                 r#"if ({ident}_ptr != null) {{
-                    {ident}_ptr[0].{field_ident} = {in_out}.get{capitalized_ty}();
+                    {ident}_ptr[0].{field_ident} = {read};
                 }}
                 "#,
                             ));
@@ -53,6 +53,12 @@ impl idlc_codegen::functions::ParameterVisitor for Signature {
         self.returns.push((ident.to_string(), ty));
     }
 
+    fn visit_input_string(&mut self, ident: &Ident, _: Option<idlc_mir::Count>) {
+        self.inputs
+            .push((format!("{}_val", ident), "String".to_string()));
+        self.returns.push((ident.to_string(), "String".to_string()));
+    }
+
     fn visit_input_struct_buffer(&mut self, ident: &Ident, ty: &StructInner) {
         let new_ty = format!("{}[]", ty.ident);
         self.inputs.push((format!("{}_val", ident), new_ty));
@@ -109,6 +115,12 @@ impl idlc_codegen::functions::ParameterVisitor for Signature {
         self.returns.push((format!("{}_len", ident), ty));
     }
 
+    fn visit_output_string(&mut self, ident: &Ident, _: idlc_mir::Count) {
+        let ty = "String[]".to_string();
+        self.inputs.push((format!("{}_ptr", ident), ty.clone()));
+        self.returns.push((ident.to_string(), ty));
+    }
+
     fn visit_output_struct_buffer(&mut self, ident: &Ident, ty: &StructInner) {
         let new_ty = format!("{}[][]", ty.ident);
         self.inputs.push((format!("{}_ptr", ident), new_ty));
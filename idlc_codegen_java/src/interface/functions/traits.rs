@@ -2,14 +2,16 @@
 // SPDX-License-Identifier: BSD-3-Clause
 
 pub fn emit(
+    function: &idlc_mir::Function,
     documentation: &str,
     signature: &super::signature::Signature,
     fn_ident: &str,
 ) -> String {
     let params = super::signature::iter_to_string(signature.params());
+    let deprecated = crate::globals::deprecated(function.deprecation(), "    ");
     format!(
         r#"{documentation}
-    void {fn_ident}({params}) throws IMinkObject.InvokeException;
+    {deprecated}void {fn_ident}({params}) throws IMinkObject.InvokeException;
     "#
     )
 }
@@ -18,3 +18,4 @@ pub(super) const OO: &str = "oo";
 
 pub(super) const BUNDLE_IN: &str = "bundleIn";
 pub(super) const BUNDLE_OUT: &str = "bundleOut";
+pub(super) const UTF_8: &str = "java.nio.charset.StandardCharsets.UTF_8";
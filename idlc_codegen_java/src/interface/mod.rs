@@ -25,16 +25,23 @@ pub fn emit_interface(interface: &Interface, input_name: &str) -> String {
                 let const_ident = r#const.ident.to_uppercase();
                 let ty = change_primitive(r#const.r#type);
                 let value = &r#const.value;
+                let doc = crate::globals::doc(r#const.doc.as_deref(), "    ");
                 constants.push_str(&format!(
-                    r#"{ty} {ident}_{const_ident} = {value};
+                    r#"{doc}{ty} {ident}_{const_ident} = {value};
     "#
                 ))
             }
+            // Enums of the base interface(s) are inherited
+            InterfaceNode::Enum(e) if std::ptr::eq(iface, interface) => {
+                constants.push_str(&crate::globals::emit_enum(e));
+            }
+            InterfaceNode::Enum(_) => (),
             InterfaceNode::Error(e) => {
                 let error_ident = e.ident.to_uppercase();
                 let value = &e.value;
+                let doc = crate::globals::doc(e.doc.as_deref(), "    ");
                 errors.push_str(&format!(
-                    r#"int {ident}_{error_ident} = {value};
+                    r#"{doc}int {ident}_{error_ident} = {value};
     "#
                 ))
             }
@@ -63,6 +70,7 @@ pub fn emit_interface(interface: &Interface, input_name: &str) -> String {
                 ));
                 invokes.push_str(&functions::invoke::emit(f, ident, &iface.ident, &signature));
                 traits.push_str(&functions::traits::emit(
+                    f,
                     &documentation,
                     &signature,
                     &fn_ident,
@@ -90,10 +98,13 @@ pub fn emit_interface(interface: &Interface, input_name: &str) -> String {
             format!("{0}.{MINK_OBJECT}", x.ident.as_ref())
         });
 
+    let documentation = crate::globals::doc(interface.doc.as_deref(), "");
+    let deprecated = crate::globals::deprecated(interface.deprecated.as_ref(), "");
+
     if interface.base.is_some() {
         format!(
             r#"
-public interface {ident} extends {base_ident} {{
+{documentation}{deprecated}public interface {ident} extends {base_ident} {{
     {constants}
     {errors}
     {op_codes}
@@ -124,7 +135,7 @@ public interface {ident} extends {base_ident} {{
     } else {
         format!(
             r#"
-public interface {ident} extends {base_ident} {{
+{documentation}{deprecated}public interface {ident} extends {base_ident} {{
     {constants}
     {errors}
     {op_codes}
@@ -5,13 +5,16 @@ use convert_case::Casing;
 
 pub const fn change_primitive(primitive: idlc_mir::Primitive) -> &'static str {
     match primitive {
-        idlc_mir::Primitive::Uint8 | idlc_mir::Primitive::Int8 => "byte",
+        idlc_mir::Primitive::Uint8 | idlc_mir::Primitive::Int8 | idlc_mir::Primitive::Char => {
+            "byte"
+        }
         idlc_mir::Primitive::Uint16 => "char",
         idlc_mir::Primitive::Int16 => "char", // need to be changed to 'short' in the future for the java size correctness, but leave it for now for backward compatibility.
         idlc_mir::Primitive::Uint32 | idlc_mir::Primitive::Int32 => "int",
         idlc_mir::Primitive::Uint64 | idlc_mir::Primitive::Int64 => "long",
         idlc_mir::Primitive::Float32 => "float",
         idlc_mir::Primitive::Float64 => "double",
+        idlc_mir::Primitive::Bool => "boolean",
     }
 }
 
@@ -22,6 +25,35 @@ pub fn capitalize_first_letter(s: &str) -> String {
     s.to_case(convert_case::Case::UpperCamel)
 }
 
+/// Reads a value of type `ty` from the `ByteBuffer` `buffer`, `boolean`s are
+/// a byte on the wire.
+pub fn get(buffer: &str, ty: &idlc_mir::Type) -> String {
+    match ty {
+        idlc_mir::Type::Primitive(idlc_mir::Primitive::Bool) => format!("({buffer}.get() != 0)"),
+        &idlc_mir::Type::Primitive(p) => {
+            format!(
+                "{buffer}.get{}()",
+                capitalize_first_letter(change_primitive(p))
+            )
+        }
+        _ => format!("{buffer}.get()"),
+    }
+}
+
+/// Writes `value` of type `ty` to the `ByteBuffer` `buffer`.
+pub fn put(buffer: &str, ty: &idlc_mir::Type, value: &str) -> String {
+    match ty {
+        idlc_mir::Type::Primitive(idlc_mir::Primitive::Bool) => {
+            format!("{buffer}.put((byte) ({value} ? 1 : 0))")
+        }
+        &idlc_mir::Type::Primitive(p) => format!(
+            "{buffer}.put{}({value})",
+            capitalize_first_letter(change_primitive(p))
+        ),
+        _ => format!("{buffer}.put({value})"),
+    }
+}
+
 pub fn get_struct_pair(
     r#struct: &idlc_mir::StructInner,
     result: &mut Vec<(String, idlc_mir::Type)>,
@@ -35,6 +67,10 @@ pub fn get_struct_pair(
             idlc_mir::Type::Primitive(_) => {
                 result.push((format!("{parent}{}", field.ident), field.val.0.clone()))
             }
+            idlc_mir::Type::Enum(e) => result.push((
+                format!("{parent}{}", field.ident),
+                idlc_mir::Type::Primitive(e.r#type),
+            )),
             idlc_mir::Type::Struct(idlc_mir::Struct::Big(s) | idlc_mir::Struct::Small(s)) => {
                 let new_parent = format!("{parent}{}", field.ident);
                 get_struct_pair(s, result, new_parent);
@@ -9,14 +9,69 @@ use idlc_mir::Node;
 use idlc_codegen::MINKIDL_HEADER_COMMENT;
 
 use crate::{
-    globals::{emit_const, emit_struct},
-    interface::emit,
+    globals::{emit_const, emit_enum, emit_struct, emit_typedef},
+    interface::{
+        emit,
+        mink_primitives::{with_runtime, DEFAULT_RUNTIME_PATH},
+    },
 };
 
-pub struct Generator;
+pub struct Generator {
+    runtime_path: String,
+    no_std: bool,
+    mocks: bool,
+    asynchronous: bool,
+    serde: bool,
+}
+
+impl Default for Generator {
+    fn default() -> Self {
+        Self::new(DEFAULT_RUNTIME_PATH, false)
+    }
+}
+
+impl Generator {
+    /// Creates a generator whose output refers to the runtime at
+    /// `runtime_path`, e.g. `::mink_object`.
+    ///
+    /// With `no_std`, the output only depends on `core` and `alloc`, and
+    /// implements `std::error::Error` with the `std` feature of its crate.
+    pub fn new(runtime_path: &str, no_std: bool) -> Self {
+        Self {
+            runtime_path: runtime_path.to_string(),
+            no_std,
+            mocks: false,
+            asynchronous: false,
+            serde: false,
+        }
+    }
+
+    /// Also emits a `Mock<interface>` implementing the trait of each interface
+    /// with queues of expected calls.
+    #[must_use]
+    pub fn with_mocks(mut self, mocks: bool) -> Self {
+        self.mocks = mocks;
+        self
+    }
 
-impl idlc_codegen::Generator for Generator {
-    fn generate(mir: &idlc_mir::Mir) -> Descriptor {
+    /// Also emits `_async` proxy methods and an `AsyncI<interface>` trait
+    /// for async implementations, both relying on the executor hook of the
+    /// runtime.
+    #[must_use]
+    pub fn with_async(mut self, asynchronous: bool) -> Self {
+        self.asynchronous = asynchronous;
+        self
+    }
+
+    /// Also derives `serde::Serialize` and `serde::Deserialize` on the structs
+    /// that don't contain objects, with the `serde` feature of the runtime.
+    #[must_use]
+    pub fn with_serde(mut self, serde: bool) -> Self {
+        self.serde = serde;
+        self
+    }
+
+    pub fn generate(&self, mir: &idlc_mir::Mir) -> Descriptor {
         let mut base = std::path::PathBuf::from(
             mir.tag
                 .file_name()
@@ -27,7 +82,13 @@ impl idlc_codegen::Generator for Generator {
         );
         base.set_extension("rs");
         let mut interfaces = HashMap::new();
-        let prologue = &format!("// {MINKIDL_HEADER_COMMENT}\n");
+        let mut prologue = format!("// {MINKIDL_HEADER_COMMENT}\n");
+        if self.no_std {
+            prologue.push_str(
+                "#[allow(unused_imports)]\nuse alloc::{boxed::Box, string::String, vec};\n",
+            );
+        }
+        let prologue = &prologue;
         interfaces.insert(base.clone(), prologue.to_owned());
 
         for node in &mir.nodes {
@@ -35,11 +96,20 @@ impl idlc_codegen::Generator for Generator {
                 Node::Const(c) => {
                     interfaces.get_mut(&base).unwrap().push_str(&emit_const(c));
                 }
-                Node::Struct(s) => {
+                Node::Enum(e) => {
+                    interfaces.get_mut(&base).unwrap().push_str(&emit_enum(e));
+                }
+                Node::Typedef(t) => {
                     interfaces
                         .get_mut(&base)
                         .unwrap()
-                        .push_str(&emit_struct(s.as_ref()));
+                        .push_str(&emit_typedef(t));
+                }
+                Node::Struct(s) => {
+                    interfaces.get_mut(&base).unwrap().push_str(&with_runtime(
+                        emit_struct(s.as_ref(), self.serde),
+                        &self.runtime_path,
+                    ));
                 }
                 Node::Interface(i) => {
                     let mut interface_content = String::new();
@@ -50,7 +120,13 @@ impl idlc_codegen::Generator for Generator {
                         ));
                     }
                     interface_content.push('\n');
-                    interface_content.push_str(&emit(i));
+                    interface_content.push_str(&emit(
+                        i,
+                        &self.runtime_path,
+                        self.no_std,
+                        self.mocks,
+                        self.asynchronous,
+                    ));
                     let name = format!("{}.rs", i.ident.to_lowercase());
                     if name == base.to_str().unwrap() {
                         interfaces
@@ -1,20 +1,38 @@
 // Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
 // SPDX-License-Identifier: BSD-3-Clause
 
-use idlc_mir::{Const, StructInner};
+use idlc_codegen::documentation::{Documentation, DocumentationStyle};
+use idlc_mir::{Const, Deprecation, Enum, StructInner, Typedef};
+
+use crate::ident::EscapedIdent;
 
 use crate::types::change_primitive;
 
-pub fn emit_struct(r#struct: &StructInner) -> String {
+/// Emits `r#struct`, along with serde derives with `serde` unless it contains
+/// objects.
+pub fn emit_struct(r#struct: &StructInner, serde: bool) -> String {
+    use crate::interface::mink_primitives::SERDE_ARRAY;
+
     let mut inner = String::new();
     let derives = ["Debug", "Clone", "PartialEq", "Copy"];
     let mut contains_interface = false;
+    let skipped = if serde && r#struct.contains_interfaces() {
+        format!(
+            "// No serde derives: `{}` contains objects.\n",
+            r#struct.ident
+        )
+    } else {
+        String::new()
+    };
+    let serde = serde && r#struct.is_primitive_struct();
 
     for field in &r#struct.fields {
         let ident = &field.ident;
         let count = field.val.1.get();
         let ty = match &field.val.0 {
             &idlc_mir::Type::Primitive(primitive) => change_primitive(primitive).to_string(),
+            // Structs are transmuted from the wire, so unknown values must remain representable.
+            idlc_mir::Type::Enum(e) => change_primitive(e.r#type).to_string(),
             idlc_mir::Type::Struct(s) => {
                 contains_interface |= s.as_ref().contains_interfaces();
                 crate::types::namespaced_struct(s.as_ref())
@@ -33,21 +51,41 @@ pub fn emit_struct(r#struct: &StructInner) -> String {
             _ => unreachable!(),
         };
 
-        inner.push_str(&if count == 1 {
+        let (ty, elements) = match &field.alias {
+            // Enums remain their primitive.
+            Some(_) if matches!(field.val.0, idlc_mir::Type::Enum(_)) => (ty, count),
+            // Serde doesn't handle the arrays of arrays longer than 32 elements.
+            Some(alias) if !serde || alias.count.get() == 1 => {
+                let ty = crate::types::namespaced_alias(alias);
+                (ty, alias.elements(field.val.1))
+            }
+            _ => (ty, count),
+        };
+
+        inner.push_str(&doc(field.doc.as_deref()));
+        if serde && count > 1 {
+            inner.push_str(&format!("#[serde(with = \"{SERDE_ARRAY}\")]\n"));
+        }
+        inner.push_str(&if elements == 1 {
             format!("pub r#{ident}: {ty},\n")
         } else {
-            format!("pub r#{ident}: [{ty}; {count}],\n")
+            format!("pub r#{ident}: [{ty}; {elements}],\n")
         });
     }
+    let mut derives = derives[..derives.len() - contains_interface as usize].join(",");
+    if serde {
+        derives.push_str(", serde::Serialize, serde::Deserialize");
+    }
     format!(
         r#"
-#[repr(C)]
+{doc}{deprecated}{skipped}#[repr(C)]
 #[derive({derives})]
 pub struct r#{ident} {{
     {inner}
 }}
 "#,
-        derives = derives[..derives.len() - contains_interface as usize].join(","),
+        doc = doc(r#struct.doc.as_deref()),
+        deprecated = deprecated(r#struct.deprecated.as_ref()),
         ident = r#struct.ident,
     )
 }
@@ -56,6 +94,95 @@ pub fn emit_const(r#const: &Const) -> String {
     let ident = r#const.ident.to_uppercase();
     let ty = change_primitive(r#const.r#type);
     let value = &r#const.value;
+    let doc = doc(r#const.doc.as_deref());
+
+    format!("{doc}pub const {ident}: {ty} = {value};\n")
+}
+
+pub fn emit_enum(r#enum: &Enum) -> String {
+    let ident = EscapedIdent::new(&r#enum.ident);
+    let ty = change_primitive(r#enum.r#type);
+    let mut variants = String::new();
+    let mut arms = String::new();
+    for variant in &r#enum.variants {
+        let variant_ident = EscapedIdent::new(&variant.ident);
+        let value = variant.value;
+        variants.push_str(&format!("{variant_ident} = {value},\n"));
+        arms.push_str(&format!("{value} => Ok(Self::{variant_ident}),\n"));
+    }
+
+    let doc = doc(r#enum.doc.as_deref());
+
+    format!(
+        r#"
+{doc}#[repr({ty})]
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum {ident} {{
+    {variants}
+}}
+
+impl TryFrom<{ty}> for {ident} {{
+    type Error = {ty};
 
-    format!("pub const {ident}: {ty} = {value};\n")
+    /// Returns the unknown value back if it doesn't match any variant.
+    fn try_from(value: {ty}) -> Result<Self, Self::Error> {{
+        match value {{
+            {arms}
+            unknown => Err(unknown),
+        }}
+    }}
+}}
+
+impl From<{ident}> for {ty} {{
+    #[inline(always)]
+    fn from(value: {ident}) -> Self {{
+        value as {ty}
+    }}
+}}
+"#
+    )
+}
+
+pub fn emit_typedef(typedef: &Typedef) -> String {
+    let (ty, count) = match &typedef.alias {
+        Some(alias) => (
+            crate::types::namespaced_alias(alias),
+            alias.elements(typedef.count),
+        ),
+        None => match &typedef.r#type {
+            &idlc_mir::Type::Primitive(primitive) => {
+                (change_primitive(primitive).to_string(), typedef.count.get())
+            }
+            idlc_mir::Type::Enum(e) => (crate::types::namespaced_enum(e), typedef.count.get()),
+            idlc_mir::Type::Struct(s) => (
+                crate::types::namespaced_struct(s.as_ref()),
+                typedef.count.get(),
+            ),
+            _ => unreachable!(),
+        },
+    };
+    let ty = match count {
+        1 => ty,
+        count => format!("[{ty}; {count}]"),
+    };
+    format!(
+        "\n{doc}pub type r#{ident} = {ty};\n",
+        doc = doc(typedef.doc.as_deref()),
+        ident = typedef.ident,
+    )
+}
+
+/// Doc comment of a declaration, on its own lines.
+pub fn doc(doc: Option<&str>) -> String {
+    Documentation::with_doc(doc, DocumentationStyle::Rust).indented("")
+}
+
+/// Attribute of a deprecated declaration, on its own line.
+pub fn deprecated(deprecation: Option<&Deprecation>) -> String {
+    match deprecation {
+        Some(Deprecation { note: Some(note) }) => format!("#[deprecated(note = \"{note}\")]\n"),
+        Some(Deprecation { note: None }) => "#[deprecated]\n".to_string(),
+        None => String::new(),
+    }
 }
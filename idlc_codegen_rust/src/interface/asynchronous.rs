@@ -0,0 +1,216 @@
+// Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
+// SPDX-License-Identifier: BSD-3-Clause
+
+use idlc_mir::{Count, Enum, Function, Ident, Interface, InterfaceNode, Primitive, StructInner};
+
+use super::functions::signature::Signature;
+use super::mink_primitives::ASYNC;
+use crate::ident::EscapedIdent;
+
+fn functions(interface: &Interface) -> impl Iterator<Item = &Function> {
+    interface.nodes.iter().filter_map(|node| match node {
+        InterfaceNode::Function(f) => Some(f),
+        _ => None,
+    })
+}
+
+/// Moves the parameters of an `_async` proxy method into the invocation handed
+/// to the executor, which may run it on another thread, and copies the output
+/// buffers back once it's done.
+#[derive(Default)]
+struct Owned {
+    /// Owned copies of the borrowed parameters.
+    copies: String,
+    /// Arguments of the synchronous method, from within the invocation.
+    args: Vec<String>,
+    /// Copies of the output buffers, moved out of the invocation.
+    outputs: Vec<String>,
+    /// Copies the output buffers back into the parameters.
+    copy_back: String,
+}
+
+impl Owned {
+    fn new(function: &Function) -> Self {
+        let mut me = Self::default();
+        idlc_codegen::functions::visit_params(function, &mut me);
+
+        me
+    }
+
+    fn by_value(&mut self, ident: &Ident) {
+        self.args.push(EscapedIdent::new(ident).to_string());
+    }
+
+    /// Copies the parameter with `to_owned`, e.g. `{}.to_vec()`.
+    fn by_ref(&mut self, ident: &Ident, to_owned: &str) {
+        let escaped = EscapedIdent::new(ident);
+        let owned = to_owned.replace("{}", &escaped.to_string());
+        self.copies
+            .push_str(&format!("let async_{ident} = {owned};\n"));
+        self.args.push(format!("&async_{ident}"));
+    }
+
+    fn output_buffer(&mut self, ident: &Ident) {
+        let escaped = EscapedIdent::new(ident);
+        self.copies.push_str(&format!(
+            "let mut async_{ident} = {escaped}.to_vec();\nlet mut async_{ident}_lenout = 0;\n"
+        ));
+        self.args.push(format!("&mut async_{ident}"));
+        self.args.push(format!("&mut async_{ident}_lenout"));
+        self.outputs.push(format!("async_{ident}"));
+        self.outputs.push(format!("async_{ident}_lenout"));
+        self.copy_back.push_str(&format!(
+            "{escaped}.copy_from_slice(&async_{ident});\n*r#{ident}_lenout = async_{ident}_lenout;\n"
+        ));
+    }
+}
+
+impl idlc_codegen::functions::ParameterVisitor for Owned {
+    fn visit_input_primitive_buffer(&mut self, ident: &Ident, _: Primitive) {
+        self.by_ref(ident, "{}.to_vec()");
+    }
+
+    fn visit_input_string(&mut self, ident: &Ident, _: Option<Count>) {
+        self.by_ref(ident, "String::from({})");
+    }
+
+    fn visit_input_struct_buffer(&mut self, ident: &Ident, _: &StructInner) {
+        self.by_ref(ident, "{}.to_vec()");
+    }
+
+    fn visit_input_primitive(&mut self, ident: &Ident, _: Primitive) {
+        self.by_value(ident);
+    }
+
+    fn visit_input_enum(&mut self, ident: &Ident, _: &Enum) {
+        self.by_value(ident);
+    }
+
+    fn visit_input_big_struct(&mut self, ident: &Ident, _: &StructInner) {
+        self.by_ref(ident, "{}.clone()");
+    }
+
+    fn visit_input_small_struct(&mut self, ident: &Ident, _: &StructInner) {
+        self.by_ref(ident, "{}.clone()");
+    }
+
+    fn visit_input_object(&mut self, ident: &Ident, _: Option<&str>) {
+        let escaped = EscapedIdent::new(ident);
+        self.copies
+            .push_str(&format!("let async_{ident} = {escaped}.cloned();\n"));
+        self.args.push(format!("async_{ident}.as_ref()"));
+    }
+
+    fn visit_input_object_array(&mut self, ident: &Ident, _: Option<&str>, _: Count) {
+        self.by_ref(ident, "{}.clone()");
+    }
+
+    fn visit_output_primitive_buffer(&mut self, ident: &Ident, _: Primitive) {
+        self.output_buffer(ident);
+    }
+
+    fn visit_output_struct_buffer(&mut self, ident: &Ident, _: &StructInner) {
+        self.output_buffer(ident);
+    }
+
+    // Returned like the other outputs which aren't buffers.
+    fn visit_output_string(&mut self, _: &Ident, _: Count) {}
+}
+
+/// Emits the `_async` flavor of the proxy methods of `interface`, handing the
+/// invocations to the executor of the runtime.
+///
+/// The invocations own copies of the parameters, as they may outlive the
+/// future when it's dropped before completing.
+pub(super) fn emit_proxy_methods(interface: &Interface) -> String {
+    let mut methods = String::new();
+    for f in functions(interface) {
+        let name = &f.ident;
+        let signature = Signature::new(f);
+        let params = signature.params();
+        let returns = signature.return_types().collect::<Vec<_>>().join(", ");
+        let Owned {
+            copies,
+            args,
+            outputs,
+            copy_back,
+        } = Owned::new(f);
+        let args = args.join(", ");
+        let outputs = outputs.join(", ");
+        let deprecated = crate::globals::deprecated(f.deprecation());
+        methods.push_str(&format!(
+            r#"
+            /// Async flavor of `{name}`, run through the executor of the runtime.
+            {deprecated}#[allow(deprecated)]
+            pub async fn r#{name}_async(&self, {params}) -> Result<({returns}), Error> {{
+                let async_proxy = self.clone();
+                {copies}
+                let (async_result, ({outputs})) = {ASYNC}::blocking(move || {{
+                    let async_result = async_proxy.r#{name}({args});
+                    (async_result, ({outputs}))
+                }})
+                .await;
+                {copy_back}
+                async_result
+            }}
+            "#
+        ));
+    }
+    methods
+}
+
+/// Emits `AsyncI<ident>`, the async flavor of the trait of `interface`, and
+/// its implementation of `I<ident>` polling the futures through the executor
+/// of the runtime.
+pub(super) fn emit_trait(interface: &Interface) -> String {
+    let ident = &interface.ident;
+    let mut trait_functions = String::new();
+    let mut blocking_functions = String::new();
+    for f in functions(interface) {
+        let name = &f.ident;
+        let signature = Signature::new(f);
+        let params = signature.params();
+        let idents = signature.param_idents().collect::<Vec<_>>().join(", ");
+        let returns = signature.return_types().collect::<Vec<_>>().join(", ");
+        let documentation = idlc_codegen::documentation::Documentation::new(
+            f,
+            idlc_codegen::documentation::DocumentationStyle::Rust,
+        );
+        let deprecated = crate::globals::deprecated(f.deprecation());
+        trait_functions.push_str(&format!(
+            r#"
+            {documentation}
+            {deprecated}fn r#{name}(&mut self, {params}) -> impl core::future::Future<Output = Result<({returns}), Error>>;
+            "#
+        ));
+        blocking_functions.push_str(&format!(
+            r#"
+            fn r#{name}(&mut self, {params}) -> Result<({returns}), Error> {{
+                {ASYNC}::block_on(self.0.r#{name}({idents}))
+            }}
+            "#
+        ));
+    }
+
+    let base_ident = interface
+        .base
+        .as_ref()
+        .map(|x| format!("AsyncI{} +", x.ident.as_ref()))
+        .unwrap_or_default();
+    let deprecated = crate::globals::deprecated(interface.deprecated.as_ref());
+
+    format!(
+        r#"
+    /// Async flavor of [`I{ident}`], implementing it once wrapped in
+    /// `Blocking` from the `asynchronous` module of the runtime.
+    {deprecated}pub trait AsyncI{ident}: {base_ident} 'static {{
+        {trait_functions}
+    }}
+
+    #[allow(deprecated)]
+    impl<T: AsyncI{ident}> I{ident} for {ASYNC}::Blocking<T> {{
+        {blocking_functions}
+    }}
+    "#
+    )
+}
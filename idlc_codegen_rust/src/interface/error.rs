@@ -3,7 +3,7 @@
 
 use convert_case::Casing;
 
-pub fn emit(errors: &[&idlc_mir::mir::Error]) -> String {
+pub fn emit(errors: &[&idlc_mir::mir::Error], no_std: bool) -> String {
     let prologue = r"
 #[repr(transparent)]
 #[derive(Clone, Copy, PartialEq, Eq)]
@@ -22,20 +22,26 @@ impl From<crate::object::Error> for Error {
     }
 }
 
-impl std::fmt::Display for Error {
+impl core::fmt::Display for Error {
     #[inline]
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        std::fmt::Debug::fmt(&self, f)
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(&self, f)
     }
 }
-impl std::error::Error for Error {}
 ";
+    // `no_std` crates only have `std` with their `std` feature.
+    let error_impl = if no_std {
+        "#[cfg(feature = \"std\")]\nimpl std::error::Error for Error {}\n"
+    } else {
+        "impl std::error::Error for Error {}\n"
+    };
     let mut match_arms = String::new();
     let mut defines = String::new();
     for error in errors {
         let ident = error.ident.to_uppercase();
         let value = error.value;
         let upper_camel_case_ident = ident.to_case(convert_case::Case::UpperCamel);
+        defines += &crate::globals::doc(error.doc.as_deref());
         defines += &format!(
             "pub const {ident}: Error = Error(unsafe {{ crate::object::Error::new_unchecked({value}) }});\n"
         );
@@ -44,9 +50,9 @@ impl std::error::Error for Error {}
 
     let debug_impl = format!(
         r#"
-    impl std::fmt::Debug for Error {{
+    impl core::fmt::Debug for Error {{
         #[inline]
-        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {{
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {{
             match *self {{
                 {match_arms}
                 _ => write!(f, "{{}}", self.0)
@@ -56,5 +62,5 @@ impl std::error::Error for Error {}
     "#
     );
 
-    prologue.to_owned() + &defines + &debug_impl
+    prologue.to_owned() + error_impl + &defines + &debug_impl
 }
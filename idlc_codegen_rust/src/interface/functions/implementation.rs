@@ -3,14 +3,14 @@
 
 use idlc_mir::Ident;
 
-use crate::interface::mink_primitives::{OK, PACK_COUNTS};
+use crate::interface::mink_primitives::{GENERIC_ERROR, OK, PACK_COUNTS};
 use crate::interface::variable_names::invoke::{ARGS, BI_NAME, BI_STRUCT, BO_NAME, BO_STRUCT};
 use crate::types::change_primitive;
 
 use crate::interface::mink_primitives::ARG;
 
 use crate::ident::EscapedIdent;
-use crate::types::namespaced_struct;
+use crate::types::{namespaced_enum, namespaced_struct};
 
 use super::serialization::TransportBuffer;
 const INPUT_BUFFER: &str = crate::interface::mink_primitives::namespace!("BufIn");
@@ -21,6 +21,7 @@ pub struct Implementation {
     initializations: Vec<String>,
     post_call: Vec<String>,
     args: Vec<String>,
+    checks_bools: bool,
 }
 
 impl Implementation {
@@ -41,13 +42,21 @@ impl Implementation {
     }
 
     pub fn post_call_assignments(&self) -> String {
-        self.post_call.concat()
+        let mut post_call = self.post_call.concat();
+        if self.checks_bools {
+            post_call.push_str(&format!(
+                r#"if invalid_bools {{
+                return Err({GENERIC_ERROR}::INVALID.into());
+            }}"#
+            ));
+        }
+        post_call
     }
 
     fn add_output_object(&mut self) {
         self.args.push(format!(
             r#"{ARG} {{
-                o: std::mem::ManuallyDrop::new(None)
+                o: core::mem::ManuallyDrop::new(None)
             }}"#
         ));
     }
@@ -60,23 +69,88 @@ impl Implementation {
             r#"{ARG} {{
                 bi: {INPUT_BUFFER} {{
                     ptr: {ident}.as_ptr().cast(),
-                    size: std::mem::size_of_val({ident})
+                    size: core::mem::size_of_val({ident})
                 }}
             }}"#
         ));
     }
 
+    /// Rejects the outputs unless the primitive `ident` is a variant of `ty`,
+    /// shadowing it with the variant.
+    fn output_enum(&mut self, ident: &Ident, ty: &idlc_mir::Enum) {
+        let r#enum = namespaced_enum(ty);
+        let ident = EscapedIdent::new(ident);
+        self.post_call.push(format!(
+            r#"let Ok({ident}) = {enum}::try_from({ident}) else {{
+                return Err({GENERIC_ERROR}::INVALID.into());
+            }};"#
+        ));
+    }
+
+    /// Zeroes the `bool`s at `ranges` of the elements at `ptr` which don't
+    /// hold `0` or `1`, `elements` being their count and size if it's an
+    /// array. The outputs are rejected once all of them are read, the caller's
+    /// buffers being left valid.
+    fn check_output_bools(
+        &mut self,
+        ptr: &str,
+        elements: Option<(&str, &str)>,
+        ranges: &[std::ops::Range<usize>],
+    ) {
+        if ranges.is_empty() {
+            return;
+        }
+        if !self.checks_bools {
+            self.checks_bools = true;
+            self.post_call
+                .push("let mut invalid_bools = false;\n".to_string());
+        }
+        let bytes = ranges
+            .iter()
+            .map(|range| {
+                format!(
+                    "core::slice::from_raw_parts_mut(element.add({}), {})",
+                    range.start,
+                    range.len()
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        let check = format!(
+            r#"for bytes in [{bytes}] {{
+                for b in bytes.iter_mut().filter(|b| **b > 1) {{
+                    *b = 0;
+                    invalid_bools = true;
+                }}
+            }}"#
+        );
+        self.post_call.push(match elements {
+            None => format!(
+                r#"unsafe {{
+                let element = {ptr}.cast::<u8>();
+                {check}
+            }}"#
+            ),
+            Some((count, size)) => format!(
+                r#"for i in 0..{count} {{
+                let element = unsafe {{ {ptr}.cast::<u8>().add(i * {size}) }};
+                unsafe {{ {check} }}
+            }}"#
+            ),
+        });
+    }
+
     fn generate_output_buffer(&mut self, ident: &Ident, ty: &str) {
         let ident = EscapedIdent::new(ident);
         self.post_call.push(format!(
-            "*{ident}_lenout = unsafe {{ {ARGS}[{idx}].b.size }} / std::mem::size_of::<{ty}>();",
+            "*{ident}_lenout = unsafe {{ {ARGS}[{idx}].b.size }} / core::mem::size_of::<{ty}>();",
             idx = self.args.len()
         ));
         self.args.push(format!(
             r#"{ARG} {{
                 b: {OUTPUT_BUFFER} {{
                     ptr: {ident}.as_mut_ptr().cast(),
-                    size: std::mem::size_of_val({ident})
+                    size: core::mem::size_of_val({ident})
                 }}
             }}"#
         ));
@@ -92,14 +166,23 @@ impl idlc_codegen::functions::ParameterVisitor for Implementation {
         self.generate_input_buffer(ident);
     }
 
+    fn visit_input_max_len(&mut self, ident: &Ident, _: usize, max_len: idlc_mir::Count) {
+        let ident = EscapedIdent::new(ident);
+        self.initializations.push(format!(
+            r#"if {ident}.len() > {max_len} {{
+                return Err({GENERIC_ERROR}::INVALID.into());
+            }}"#
+        ));
+    }
+
     fn visit_input_primitive(&mut self, ident: &Ident, ty: idlc_mir::Primitive) {
         let ty: &str = change_primitive(ty);
         let ident = EscapedIdent::new(ident);
         self.args.push(format!(
             r#"{ARG} {{
                 bi: {INPUT_BUFFER} {{
-                    ptr: std::ptr::addr_of!({ident}).cast(),
-                    size: std::mem::size_of::<{ty}>(),
+                    ptr: core::ptr::addr_of!({ident}).cast(),
+                    size: core::mem::size_of::<{ty}>(),
                 }}
             }}"#
         ));
@@ -126,7 +209,7 @@ impl idlc_codegen::functions::ParameterVisitor for Implementation {
         self.args.push(format!(
             r#"{ARG} {{
                 bi: {INPUT_BUFFER} {{
-                    ptr: std::ptr::addr_of!({BI_NAME}).cast(),
+                    ptr: core::ptr::addr_of!({BI_NAME}).cast(),
                     size: {size},
                 }}
             }}
@@ -144,21 +227,21 @@ impl idlc_codegen::functions::ParameterVisitor for Implementation {
                 r#"{ARG} {{
                 bi: {INPUT_BUFFER} {{
                     ptr: ({escaped_ident} as *const {ty}).cast(),
-                    size: std::mem::size_of::<{ty}>(),
+                    size: core::mem::size_of::<{ty}>(),
                 }}
             }}"#
             ));
         } else {
             self.initializations.push(format!(
                 r#"
-                let {escaped_ident} = std::mem::ManuallyDrop::new(std::cell::UnsafeCell::new(unsafe {{ std::ptr::read({escaped_ident}) }}));
+                let {escaped_ident} = core::mem::ManuallyDrop::new(core::cell::UnsafeCell::new(unsafe {{ core::ptr::read({escaped_ident}) }}));
             "#
             ));
             self.args.push(format!(
                 r#"{ARG} {{
                 bi: {INPUT_BUFFER} {{
                     ptr: {escaped_ident}.get().cast(),
-                    size: std::mem::size_of::<{ty}>(),
+                    size: core::mem::size_of::<{ty}>(),
                 }}
             }}"#
             ));
@@ -166,12 +249,12 @@ impl idlc_codegen::functions::ParameterVisitor for Implementation {
                 let path = super::signature::idents_to_struct_path(&object);
                 self.args.push(format!(
                     r#"{ARG} {{
-                o: std::mem::ManuallyDrop::new(unsafe {{
+                o: core::mem::ManuallyDrop::new(unsafe {{
                     let ptr = &mut (*{escaped_ident}.get()){path};
-                    let obj = std::mem::ManuallyDrop::new(std::ptr::read(ptr));
-                    std::ptr::write_volatile(ptr, std::mem::zeroed());
+                    let obj = core::mem::ManuallyDrop::new(core::ptr::read(ptr));
+                    core::ptr::write_volatile(ptr, core::mem::zeroed());
 
-                    std::mem::transmute_copy(&obj)
+                    core::mem::transmute_copy(&obj)
                 }})
             }}"#
                 ));
@@ -187,7 +270,7 @@ impl idlc_codegen::functions::ParameterVisitor for Implementation {
         let ident = EscapedIdent::new(ident);
         self.args.push(format!(
             r#"{ARG} {{
-                o: std::mem::ManuallyDrop::new({ident}.map(|o| unsafe {{ std::mem::transmute_copy(o) }} ))
+                o: core::mem::ManuallyDrop::new({ident}.map(|o| unsafe {{ core::mem::transmute_copy(o) }} ))
             }}"#
         ));
     }
@@ -203,30 +286,81 @@ impl idlc_codegen::functions::ParameterVisitor for Implementation {
 
     fn visit_output_primitive_buffer(&mut self, ident: &Ident, ty: idlc_mir::Primitive) {
         self.generate_output_buffer(ident, change_primitive(ty));
+        if ty == idlc_mir::Primitive::Bool {
+            let escaped = EscapedIdent::new(ident);
+            self.check_output_bools(
+                &format!("{escaped}.as_mut_ptr()"),
+                Some((&format!("(*{escaped}_lenout).min({escaped}.len())"), "1")),
+                std::slice::from_ref(&(0..1)),
+            );
+        }
+    }
+
+    fn visit_output_string(&mut self, ident: &Ident, max_len: idlc_mir::Count) {
+        let ident = EscapedIdent::new(ident);
+        self.initializations
+            .push(format!("let mut {ident} = vec![0u8; {max_len}];\n"));
+        self.post_call.push(format!(
+            r#"{ident}.truncate(unsafe {{ {ARGS}[{idx}].b.size }});
+            let Ok({ident}) = String::from_utf8({ident}) else {{
+                return Err({GENERIC_ERROR}::INVALID.into());
+            }};"#,
+            idx = self.args.len()
+        ));
+        self.args.push(format!(
+            r#"{ARG} {{
+                b: {OUTPUT_BUFFER} {{
+                    ptr: {ident}.as_mut_ptr().cast(),
+                    size: {ident}.len()
+                }}
+            }}"#
+        ));
     }
 
     fn visit_output_struct_buffer(&mut self, ident: &Ident, ty: &idlc_mir::StructInner) {
-        self.generate_output_buffer(ident, &namespaced_struct(ty));
+        let r#struct = namespaced_struct(ty);
+        self.generate_output_buffer(ident, &r#struct);
+        let escaped = EscapedIdent::new(ident);
+        self.check_output_bools(
+            &format!("{escaped}.as_mut_ptr()"),
+            Some((
+                &format!("(*{escaped}_lenout).min({escaped}.len())"),
+                &format!("core::mem::size_of::<{struct}>()"),
+            )),
+            &ty.bools(),
+        );
     }
 
     fn visit_output_primitive(&mut self, ident: &Ident, ty: idlc_mir::Primitive) {
         let ty: &str = change_primitive(ty);
         let ident = EscapedIdent::new(ident);
         self.initializations.push(format!(
-            "let mut {ident} = std::mem::MaybeUninit::<{ty}>::uninit();\n"
+            "let mut {ident} = core::mem::MaybeUninit::<{ty}>::uninit();\n"
         ));
+        if ty == "bool" {
+            self.check_output_bools(
+                &format!("{ident}.as_mut_ptr()"),
+                None,
+                std::slice::from_ref(&(0..1)),
+            );
+        }
         self.post_call
             .push(format!("let {ident} = unsafe {{ {ident}.assume_init() }};"));
         self.args.push(format!(
             r#"{ARG} {{
                 bi: {INPUT_BUFFER} {{
-                    ptr: std::ptr::addr_of_mut!({ident}).cast(),
-                    size: std::mem::size_of::<{ty}>(),
+                    ptr: core::ptr::addr_of_mut!({ident}).cast(),
+                    size: core::mem::size_of::<{ty}>(),
                 }}
             }}"#
         ));
     }
 
+    fn visit_output_enum(&mut self, ident: &Ident, ty: &idlc_mir::Enum) {
+        self.visit_output_primitive(ident, ty.r#type);
+        self.output_enum(ident, ty);
+    }
+
     fn visit_output_bundled(
         &mut self,
         packed_primitives: &idlc_codegen::serialization::PackedPrimitives,
@@ -239,16 +373,24 @@ impl idlc_codegen::functions::ParameterVisitor for Implementation {
         self.initializations.push(format!(
             r#"
                 {definition}
-                let mut {BO_NAME} = std::mem::MaybeUninit::<{BO_STRUCT}>::uninit();
+                let mut {BO_NAME} = core::mem::MaybeUninit::<{BO_STRUCT}>::uninit();
                 "#
         ));
+        self.check_output_bools(
+            &format!("{BO_NAME}.as_mut_ptr()"),
+            None,
+            &packed_primitives.output_bools(),
+        );
         self.post_call.push(format!(
             "let {BO_STRUCT}({idents}) = unsafe {{ {BO_NAME}.assume_init() }};"
         ));
+        for (ident, ty) in packer.bo_enums() {
+            self.output_enum(ident, ty);
+        }
         self.args.push(format!(
             r#"{ARG} {{
                 b: {OUTPUT_BUFFER} {{
-                    ptr: std::ptr::addr_of_mut!({BO_NAME}).cast(),
+                    ptr: core::ptr::addr_of_mut!({BO_NAME}).cast(),
                     size: {size},
                 }}
             }}"#
@@ -259,13 +401,13 @@ impl idlc_codegen::functions::ParameterVisitor for Implementation {
         let ty: &str = &namespaced_struct(r#struct);
         let escaped_ident = EscapedIdent::new(ident);
         self.initializations.push(format!(
-            "let mut {ident} = std::mem::MaybeUninit::<{ty}>::uninit();\n"
+            "let mut {ident} = core::mem::MaybeUninit::<{ty}>::uninit();\n"
         ));
         self.args.push(format!(
             r#"{ARG} {{
                 b: {OUTPUT_BUFFER} {{
-                    ptr: std::ptr::addr_of_mut!({escaped_ident}).cast(),
-                    size: std::mem::size_of::<{ty}>(),
+                    ptr: core::ptr::addr_of_mut!({escaped_ident}).cast(),
+                    size: core::mem::size_of::<{ty}>(),
                 }}
             }}"#
         ));
@@ -275,15 +417,20 @@ impl idlc_codegen::functions::ParameterVisitor for Implementation {
             let path = super::signature::idents_to_struct_path(&object);
             let idx = self.args.len();
             self.post_call.push(format!(
-                r#"unsafe {{ std::ptr::write(
-                    std::ptr::addr_of_mut!((*{escaped_ident}.as_mut_ptr()){path}),
-                    std::mem::transmute(std::mem::ManuallyDrop::take(&mut {ARGS}[{idx}].o))
+                r#"unsafe {{ core::ptr::write(
+                    core::ptr::addr_of_mut!((*{escaped_ident}.as_mut_ptr()){path}),
+                    core::mem::transmute(core::mem::ManuallyDrop::take(&mut {ARGS}[{idx}].o))
                 ); }}
             "#
             ));
 
             self.add_output_object();
         }
+        self.check_output_bools(
+            &format!("{escaped_ident}.as_mut_ptr()"),
+            None,
+            &r#struct.bools(),
+        );
         self.post_call.push(format!(
             "let {escaped_ident} = unsafe {{ {escaped_ident}.assume_init() }};"
         ));
@@ -297,7 +444,7 @@ impl idlc_codegen::functions::ParameterVisitor for Implementation {
         let idx = self.args.len();
         let ident = EscapedIdent::new(ident);
         self.post_call.push(format!(
-            "let {ident} = unsafe {{ std::mem::transmute(std::mem::ManuallyDrop::take(&mut {ARGS}[{idx}].o)) }};"
+            "let {ident} = unsafe {{ core::mem::transmute(core::mem::ManuallyDrop::take(&mut {ARGS}[{idx}].o)) }};"
         ));
         self.add_output_object();
     }
@@ -311,19 +458,19 @@ impl idlc_codegen::functions::ParameterVisitor for Implementation {
         });
         let ident = EscapedIdent::new(ident);
         self.initializations.push(format!(
-            "let mut {ident}: [std::mem::MaybeUninit<Option<{ty}>>; {cnt}] = unsafe {{ std::mem::MaybeUninit::uninit().assume_init() }};"
+            "let mut {ident}: [core::mem::MaybeUninit<Option<{ty}>>; {cnt}] = unsafe {{ core::mem::MaybeUninit::uninit().assume_init() }};"
         ));
         for i in 0..cnt.get() {
             let idx = self.args.len();
             self.args.push(format!(
                 r#"{ARG} {{
-                o: std::mem::ManuallyDrop::new(None)
+                o: core::mem::ManuallyDrop::new(None)
             }}"#
             ));
-            self.post_call.push(format!("unsafe {{ {ident}[{i}].write(std::mem::transmute(std::mem::ManuallyDrop::take(&mut {ARGS}[{idx}].o))); }}"));
+            self.post_call.push(format!("unsafe {{ {ident}[{i}].write(core::mem::transmute(core::mem::ManuallyDrop::take(&mut {ARGS}[{idx}].o))); }}"));
         }
         self.post_call.push(format!(
-            "let {ident} = unsafe {{ std::mem::transmute({ident}) }};"
+            "let {ident} = unsafe {{ core::mem::transmute({ident}) }};"
         ));
     }
 }
@@ -345,6 +492,7 @@ pub fn emit(
     let return_idents = signature.return_idents().collect::<Vec<_>>().join(", ");
     let returns_types = signature.return_types().collect::<Vec<_>>().join(", ");
     let params = signature.params();
+    let deprecated = crate::globals::deprecated(function.deprecation());
 
     let counts = (
         counts.input_buffers,
@@ -357,7 +505,7 @@ pub fn emit(
         r#"
         #[inline]
         {documentation}
-        pub fn r#{ident}(&self, {params}) -> Result<({returns_types}), Error> {{
+        {deprecated}pub fn r#{ident}(&self, {params}) -> Result<({returns_types}), Error> {{
             {initializations}
             let mut {ARGS} = [{args}];
 
@@ -366,7 +514,7 @@ pub fn emit(
                     {post_call_assignments}
                     Ok(({return_idents}))
                 }},
-                err => Err(unsafe {{ std::mem::transmute(err) }})
+                err => Err(unsafe {{ core::mem::transmute(err) }})
             }}
         }}
     "#
@@ -9,7 +9,10 @@ use crate::{
     types::change_primitive,
 };
 
-use crate::{ident::EscapedIdent, types::namespaced_struct};
+use crate::{
+    ident::EscapedIdent,
+    types::{namespaced_enum, namespaced_struct},
+};
 
 use super::serialization::TransportBuffer;
 
@@ -47,13 +50,58 @@ impl Invoke {
         idx
     }
 
+    /// Rejects the invocation unless the primitive `ident` is a variant of
+    /// `ty`, shadowing it with the variant.
+    fn input_enum(&mut self, ident: &Ident, ty: &idlc_mir::Enum) {
+        let r#enum = namespaced_enum(ty);
+        let ident = EscapedIdent::new(ident);
+        self.pre.push(format!(
+            r#"let Ok({ident}) = {enum}::try_from({ident}) else {{
+                return core::mem::transmute({GENERIC_ERROR}::INVALID);
+            }};"#
+        ));
+    }
+
+    /// Rejects the invocation unless the `bool`s at `ranges` of argument `idx`
+    /// hold `0` or `1`, its size has to be checked first.
+    fn check_bools(&mut self, idx: usize, ranges: &[std::ops::Range<usize>]) {
+        let Some(invalid) = invalid_bools("bytes", ranges) else {
+            return;
+        };
+        self.pre.push(format!(
+            r#"if {{
+            let bytes = core::slice::from_raw_parts({ARGS}[{idx}].bi.ptr.cast::<u8>(), {ARGS}[{idx}].bi.size);
+            {invalid}
+        }} {{
+            return core::mem::transmute({GENERIC_ERROR}::INVALID);
+        }}"#
+        ));
+    }
+
+    /// Rejects the invocation unless every `element_size` bytes long element
+    /// of buffer `idx` holds `0` or `1` at `ranges`.
+    fn check_buffer_bools(&mut self, element_size: &str, ranges: &[std::ops::Range<usize>]) {
+        let Some(invalid) = invalid_bools("bytes", ranges) else {
+            return;
+        };
+        let idx = self.idx;
+        self.pre.push(format!(
+            r#"if {ARGS}[{idx}].bi.size != 0 && core::slice::from_raw_parts({ARGS}[{idx}].bi.ptr.cast::<u8>(), {ARGS}[{idx}].bi.size)
+            .chunks_exact({element_size})
+            .any(|bytes| {invalid})
+        {{
+            return core::mem::transmute({GENERIC_ERROR}::INVALID);
+        }}"#
+        ));
+    }
+
     fn generate_for_input_buffer(&mut self, ident: EscapedIdent, ty: &str) {
         let idx = self.idx();
         self.pre.push(format!(
         r#"let {ident} = if {ARGS}[{idx}].bi.size == 0 {{
             &[]
         }} else {{
-            std::slice::from_raw_parts({ARGS}[{idx}].bi.ptr.cast::<{ty}>(), {ARGS}[{idx}].bi.size / std::mem::size_of::<{ty}>())
+            core::slice::from_raw_parts({ARGS}[{idx}].bi.ptr.cast::<{ty}>(), {ARGS}[{idx}].bi.size / core::mem::size_of::<{ty}>())
         }};"#));
     }
 
@@ -61,17 +109,17 @@ impl Invoke {
         let idx = self.idx();
         self.pre.push(format!(
             r#"let {ident}_orig = {ARGS}[{idx}].b.size;
-            let {ident}_lenout = &mut *std::ptr::addr_of_mut!({ARGS}[{idx}].b.size);"#
+            let {ident}_lenout = &mut *core::ptr::addr_of_mut!({ARGS}[{idx}].b.size);"#
         ));
         self.pre.push(format!(
         r#"let {ident} = if {ident}_orig == 0 {{
             &mut []
         }} else {{
-            std::slice::from_raw_parts_mut({ARGS}[{idx}].b.ptr.cast::<{ty}>(), {ident}_orig / std::mem::size_of::<{ty}>())
+            core::slice::from_raw_parts_mut({ARGS}[{idx}].b.ptr.cast::<{ty}>(), {ident}_orig / core::mem::size_of::<{ty}>())
         }};"#));
         self.post.push(format!(
             r#"
-            *{ident}_lenout = {ident}_lenout.saturating_mul(std::mem::size_of::<{ty}>());
+            *{ident}_lenout = {ident}_lenout.saturating_mul(core::mem::size_of::<{ty}>());
             assert!(*{ident}_lenout <= {ident}_orig);
             "#
         ));
@@ -80,28 +128,64 @@ impl Invoke {
 
 impl idlc_codegen::functions::ParameterVisitor for Invoke {
     fn visit_input_primitive_buffer(&mut self, ident: &Ident, ty: idlc_mir::Primitive) {
+        if ty == idlc_mir::Primitive::Bool {
+            self.check_buffer_bools("1", std::slice::from_ref(&(0..1)));
+        }
         self.generate_for_input_buffer(EscapedIdent::new(ident), change_primitive(ty));
     }
 
     fn visit_input_struct_buffer(&mut self, ident: &Ident, ty: &StructInner) {
-        self.generate_for_input_buffer(EscapedIdent::new(ident), &namespaced_struct(ty));
+        let r#struct = namespaced_struct(ty);
+        self.check_buffer_bools(&format!("core::mem::size_of::<{struct}>()"), &ty.bools());
+        self.generate_for_input_buffer(EscapedIdent::new(ident), &r#struct);
+    }
+
+    fn visit_input_string(&mut self, ident: &Ident, max_len: Option<idlc_mir::Count>) {
+        if let Some(max_len) = max_len {
+            self.visit_input_max_len(ident, 1, max_len);
+        }
+        let ident = EscapedIdent::new(ident);
+        self.generate_for_input_buffer(ident, "u8");
+        self.pre.push(format!(
+            r#"let Ok({ident}) = core::str::from_utf8({ident}) else {{
+            return core::mem::transmute({GENERIC_ERROR}::INVALID);
+        }};"#
+        ));
+    }
+
+    fn visit_input_max_len(&mut self, _: &Ident, element_size: usize, max_len: idlc_mir::Count) {
+        let idx = self.idx;
+        let size = usize::from(max_len.get()) * element_size;
+        self.pre.push(format!(
+            r#"if {ARGS}[{idx}].bi.size > {size} {{
+            return core::mem::transmute({GENERIC_ERROR}::INVALID);
+        }}"#
+        ));
     }
 
-    fn visit_input_primitive(&mut self, ident: &Ident, ty: idlc_mir::Primitive) {
-        let ty: &str = change_primitive(ty);
+    fn visit_input_primitive(&mut self, ident: &Ident, primitive: idlc_mir::Primitive) {
+        let ty: &str = change_primitive(primitive);
         let ident = EscapedIdent::new(ident);
         let idx = self.idx();
         self.pre.push(format!(
-            r#"if {ARGS}[{idx}].bi.size != std::mem::size_of::<{ty}>() {{
-            return std::mem::transmute({GENERIC_ERROR}::INVALID);
+            r#"if {ARGS}[{idx}].bi.size != core::mem::size_of::<{ty}>() {{
+            return core::mem::transmute({GENERIC_ERROR}::INVALID);
         }}"#
         ));
+        if primitive == idlc_mir::Primitive::Bool {
+            self.check_bools(idx, std::slice::from_ref(&(0..1)));
+        }
 
         self.pre.push(format!(
             "let {ident} = *{ARGS}[{idx}].bi.ptr.cast::<{ty}>();"
         ));
     }
 
+    fn visit_input_enum(&mut self, ident: &Ident, ty: &idlc_mir::Enum) {
+        self.visit_input_primitive(ident, ty.r#type);
+        self.input_enum(ident, ty);
+    }
+
     fn visit_input_bundled(
         &mut self,
         packed_primitives: &idlc_codegen::serialization::PackedPrimitives,
@@ -116,14 +200,18 @@ impl idlc_codegen::functions::ParameterVisitor for Invoke {
         self.pre.push(format!(
             r#"
             if {ARGS}[{idx}].bi.size != {size} {{
-                return std::mem::transmute({GENERIC_ERROR}::INVALID);
+                return core::mem::transmute({GENERIC_ERROR}::INVALID);
             }}
             "#,
         ));
+        self.check_bools(idx, &packed_primitives.input_bools());
         self.pre.push(format!(
-            "let {BI_STRUCT}({idents}) = std::ptr::read(args[0].bi.ptr.cast::<{BI_STRUCT}>());"
+            "let {BI_STRUCT}({idents}) = core::ptr::read(args[0].bi.ptr.cast::<{BI_STRUCT}>());"
         ));
         self.pre.push(packer.post_bi_assignments());
+        for (ident, ty) in packer.bi_enums() {
+            self.input_enum(ident, ty);
+        }
     }
 
     fn visit_input_big_struct(&mut self, ident: &Ident, r#struct: &StructInner) {
@@ -131,10 +219,11 @@ impl idlc_codegen::functions::ParameterVisitor for Invoke {
         let ident = EscapedIdent::new(ident);
         let idx = self.idx();
         self.pre.push(format!(
-            r#"if {ARGS}[{idx}].bi.size != std::mem::size_of::<{ty}>() {{
-            return std::mem::transmute({GENERIC_ERROR}::INVALID);
+            r#"if {ARGS}[{idx}].bi.size != core::mem::size_of::<{ty}>() {{
+            return core::mem::transmute({GENERIC_ERROR}::INVALID);
         }}"#
         ));
+        self.check_bools(idx, &r#struct.bools());
 
         let objects = r#struct.objects();
         if objects.is_empty() {
@@ -143,7 +232,7 @@ impl idlc_codegen::functions::ParameterVisitor for Invoke {
             ));
         } else {
             self.pre.push(format!(
-                "let mut {ident} = std::mem::ManuallyDrop::new(std::ptr::read({ARGS}[{idx}].bi.ptr.cast::<{ty}>()));"
+                "let mut {ident} = core::mem::ManuallyDrop::new(core::ptr::read({ARGS}[{idx}].bi.ptr.cast::<{ty}>()));"
             ));
             for (object, _) in objects {
                 dbg!(&object);
@@ -154,7 +243,7 @@ impl idlc_codegen::functions::ParameterVisitor for Invoke {
                     .join(".");
                 let idx = self.idx();
                 self.pre.push(format!(
-                    "std::ptr::write(&mut {ident}.{path}, std::mem::transmute_copy(&{ARGS}[{idx}].o));"
+                    "core::ptr::write(&mut {ident}.{path}, core::mem::transmute_copy(&{ARGS}[{idx}].o));"
                 ));
             }
             self.pre.push(format!("let {ident} = &{ident};"))
@@ -168,16 +257,16 @@ impl idlc_codegen::functions::ParameterVisitor for Invoke {
         let idx = self.idx();
         let ident = EscapedIdent::new(ident);
         self.pre.push(format!(
-            "let {ident} = std::mem::transmute({ARGS}[{idx}].o.as_ref());"
+            "let {ident} = core::mem::transmute({ARGS}[{idx}].o.as_ref());"
         ));
     }
 
     fn visit_input_object_array(&mut self, ident: &Ident, _: Option<&str>, cnt: idlc_mir::Count) {
         let ident = EscapedIdent::new(ident);
-        let mut definition = format!("let {ident} = &std::mem::ManuallyDrop::new([");
+        let mut definition = format!("let {ident} = &core::mem::ManuallyDrop::new([");
         for _ in 0..cnt.get() {
             let idx = self.idx();
-            definition += &format!("std::mem::transmute_copy(&{ARGS}[{idx}].o),",);
+            definition += &format!("core::mem::transmute_copy(&{ARGS}[{idx}].o),",);
         }
         definition.push_str("]);");
         self.pre.push(definition);
@@ -187,6 +276,25 @@ impl idlc_codegen::functions::ParameterVisitor for Invoke {
         self.generate_for_output_buffer(EscapedIdent::new(ident), change_primitive(ty));
     }
 
+    fn visit_output_string(&mut self, ident: &Ident, max_len: idlc_mir::Count) {
+        let ident = EscapedIdent::new(ident);
+        let idx = self.idx();
+        self.post.push(format!(
+            r#"
+            if {ident}.len() > {max_len} {{
+                return core::mem::transmute({GENERIC_ERROR}::INVALID);
+            }}
+            if {ident}.len() > {ARGS}[{idx}].b.size {{
+                return core::mem::transmute({GENERIC_ERROR}::SIZE_OUT);
+            }}
+            if !{ident}.is_empty() {{
+                core::ptr::copy_nonoverlapping({ident}.as_ptr(), {ARGS}[{idx}].b.ptr.cast::<u8>(), {ident}.len());
+            }}
+            {ARGS}[{idx}].b.size = {ident}.len();
+            "#
+        ));
+    }
+
     fn visit_output_struct_buffer(&mut self, ident: &Ident, ty: &StructInner) {
         self.generate_for_output_buffer(EscapedIdent::new(ident), &namespaced_struct(ty));
     }
@@ -196,8 +304,8 @@ impl idlc_codegen::functions::ParameterVisitor for Invoke {
         let ty = &namespaced_struct(r#struct);
         let idx = self.idx();
         self.pre.push(format!(
-            r#"if {ARGS}[{idx}].b.size != std::mem::size_of::<{ty}>() {{
-            return std::mem::transmute({GENERIC_ERROR}::SIZE_OUT);
+            r#"if {ARGS}[{idx}].b.size != core::mem::size_of::<{ty}>() {{
+            return core::mem::transmute({GENERIC_ERROR}::SIZE_OUT);
         }}"#
         ));
 
@@ -208,18 +316,18 @@ impl idlc_codegen::functions::ParameterVisitor for Invoke {
         } else {
             let struct_idx = idx;
             self.post.push(format!(
-                "let mut {ident} = std::mem::ManuallyDrop::new({ident});"
+                "let mut {ident} = core::mem::ManuallyDrop::new({ident});"
             ));
             for (object, _) in objects {
                 let idx = self.idx();
                 let path = super::signature::idents_to_struct_path(&object);
-                self.post.push(format!("{ARGS}[{idx}].o = std::mem::ManuallyDrop::new(std::mem::transmute({ident}{path}.take()));"));
+                self.post.push(format!("{ARGS}[{idx}].o = core::mem::ManuallyDrop::new(core::mem::transmute({ident}{path}.take()));"));
                 self.post.push(format!(
-                    "std::ptr::write(std::ptr::addr_of_mut!({ident}{path}), std::mem::zeroed());"
+                    "core::ptr::write(core::ptr::addr_of_mut!({ident}{path}), core::mem::zeroed());"
                 ));
             }
             self.post.push(format!(
-                "std::ptr::write({ARGS}[{struct_idx}].b.ptr.cast::<{ty}>(), std::mem::transmute_copy(&{ident}));"
+                "core::ptr::write({ARGS}[{struct_idx}].b.ptr.cast::<{ty}>(), core::mem::transmute_copy(&{ident}));"
             ));
         }
     }
@@ -232,14 +340,28 @@ impl idlc_codegen::functions::ParameterVisitor for Invoke {
         let ident = EscapedIdent::new(ident);
         let idx = self.idx();
         self.pre.push(format!(
-            r#"if {ARGS}[{idx}].b.size != std::mem::size_of::<{ty}>() {{
-            return std::mem::transmute({GENERIC_ERROR}::SIZE_OUT);
+            r#"if {ARGS}[{idx}].b.size != core::mem::size_of::<{ty}>() {{
+            return core::mem::transmute({GENERIC_ERROR}::SIZE_OUT);
         }}"#
         ));
         self.post
             .push(format!("*{ARGS}[{idx}].b.ptr.cast::<{ty}>() = {ident};\n"));
     }
 
+    fn visit_output_enum(&mut self, ident: &Ident, ty: &idlc_mir::Enum) {
+        let ty = change_primitive(ty.r#type);
+        let ident = EscapedIdent::new(ident);
+        let idx = self.idx();
+        self.pre.push(format!(
+            r#"if {ARGS}[{idx}].b.size != core::mem::size_of::<{ty}>() {{
+            return core::mem::transmute({GENERIC_ERROR}::SIZE_OUT);
+        }}"#
+        ));
+        self.post.push(format!(
+            "*{ARGS}[{idx}].b.ptr.cast::<{ty}>() = {ty}::from({ident});\n"
+        ));
+    }
+
     fn visit_output_bundled(
         &mut self,
         packed_primitives: &idlc_codegen::serialization::PackedPrimitives,
@@ -254,14 +376,17 @@ impl idlc_codegen::functions::ParameterVisitor for Invoke {
         self.pre.push(format!(
             r#"
             if {ARGS}[{idx}].b.size != {size} {{
-                return std::mem::transmute({GENERIC_ERROR}::INVALID);
+                return core::mem::transmute({GENERIC_ERROR}::INVALID);
             }}
             "#,
         ));
-        let idents = packer.bo_idents().collect::<Vec<String>>().join(", ");
+        let idents = packer
+            .bo_assignment_idents()
+            .collect::<Vec<String>>()
+            .join(", ");
         self.post.push(format!(
             r#"
-            std::ptr::write({ARGS}[{idx}].b.ptr.cast::<{BO_STRUCT}>(), {BO_STRUCT}({idents}));
+            core::ptr::write({ARGS}[{idx}].b.ptr.cast::<{BO_STRUCT}>(), {BO_STRUCT}({idents}));
             "#
         ));
     }
@@ -270,24 +395,35 @@ impl idlc_codegen::functions::ParameterVisitor for Invoke {
         let idx = self.idx();
         let ident = EscapedIdent::new(ident);
         self.post.push(format!(
-            "{ARGS}[{idx}].o = std::mem::ManuallyDrop::new(std::mem::transmute({ident}));\n"
+            "{ARGS}[{idx}].o = core::mem::ManuallyDrop::new(core::mem::transmute({ident}));\n"
         ));
     }
 
     fn visit_output_object_array(&mut self, ident: &Ident, _: Option<&str>, cnt: idlc_mir::Count) {
         let ident = EscapedIdent::new(ident);
         self.post.push(format!(
-            "let {ident} = std::mem::ManuallyDrop::new({ident});"
+            "let {ident} = core::mem::ManuallyDrop::new({ident});"
         ));
         for i in 0..cnt.get() {
             let idx = self.idx();
             self.post.push(format!(
-            "{ARGS}[{idx}].o = std::mem::ManuallyDrop::new(std::mem::transmute_copy(&{ident}[{i}]));"
+            "{ARGS}[{idx}].o = core::mem::ManuallyDrop::new(core::mem::transmute_copy(&{ident}[{i}]));"
         ));
         }
     }
 }
 
+/// Whether any of the `bool`s at `ranges` of the byte slice `bytes` doesn't
+/// hold `0` or `1`.
+fn invalid_bools(bytes: &str, ranges: &[std::ops::Range<usize>]) -> Option<String> {
+    let (first, rest) = ranges.split_first()?;
+    let mut chain = format!("{bytes}[{}..{}].iter()", first.start, first.end);
+    for range in rest {
+        chain += &format!(".chain(&{bytes}[{}..{}])", range.start, range.end);
+    }
+    Some(format!("{chain}.any(|b| *b > 1)"))
+}
+
 pub fn emit(
     function: &idlc_mir::Function,
     signature: &super::signature::Signature,
@@ -316,13 +452,13 @@ pub fn emit(
         r#"
     {op_id} => {{
         if {COUNTS} != {PACK_COUNTS}{counts:?} {{
-            return std::mem::transmute({GENERIC_ERROR}::GENERIC)
+            return core::mem::transmute({GENERIC_ERROR}::GENERIC)
         }}
-        let args = std::slice::from_raw_parts_mut({ARGS}, {n_args});
+        let args = core::slice::from_raw_parts_mut({ARGS}, {n_args});
 
         {pre}
 
-        match (*{CONTEXT}).inner.lock().map_err(|_| std::mem::transmute({GENERIC_ERROR}::INVALID)).and_then(|mut cx| cx.r#{ident}({params})) {{
+        match (*{CONTEXT}).inner.lock().map_err(|_| core::mem::transmute({GENERIC_ERROR}::INVALID)).and_then(|mut cx| cx.r#{ident}({params})) {{
             Ok(({returns})) => {{
                 {post}
                 {OK}
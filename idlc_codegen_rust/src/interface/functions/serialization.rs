@@ -40,11 +40,17 @@ impl<'a> PackedPrimitives<'a> {
             let ident = EscapedIdent::new(ident);
             match ty {
                 Type::Primitive(_) => ident.to_string(),
+                Type::Enum(e) => format!("{}::from({ident})", change_primitive(e.r#type)),
                 Type::SmallStruct(_) => format!("*{ident}"),
             }
         })
     }
 
+    /// Bundled input enums, sent as their primitive.
+    pub fn bi_enums(&self) -> impl Iterator<Item = (&'a idlc_mir::Ident, &'a idlc_mir::Enum)> {
+        Self::enums(self.0.inputs_by_idents())
+    }
+
     pub fn post_bi_assignments(&self) -> String {
         let mut assignments = String::new();
         self.0.inputs_by_idents().for_each(|(ident, ty)| {
@@ -67,6 +73,30 @@ impl<'a> PackedPrimitives<'a> {
             .map(|(ident, _)| EscapedIdent::new(ident).to_string())
     }
 
+    pub fn bo_assignment_idents(&self) -> impl ExactSizeIterator<Item = String> + '_ {
+        self.0.outputs_by_idents().map(|(ident, ty)| {
+            let ident = EscapedIdent::new(ident);
+            match ty {
+                Type::Enum(e) => format!("{}::from({ident})", change_primitive(e.r#type)),
+                Type::Primitive(_) | Type::SmallStruct(_) => ident.to_string(),
+            }
+        })
+    }
+
+    /// Bundled output enums, sent as their primitive.
+    pub fn bo_enums(&self) -> impl Iterator<Item = (&'a idlc_mir::Ident, &'a idlc_mir::Enum)> {
+        Self::enums(self.0.outputs_by_idents())
+    }
+
+    fn enums(
+        pairs: impl Iterator<Item = (&'a idlc_mir::Ident, &'a Type)>,
+    ) -> impl Iterator<Item = (&'a idlc_mir::Ident, &'a idlc_mir::Enum)> {
+        pairs.filter_map(|(ident, ty)| match ty {
+            Type::Enum(e) => Some((ident, e)),
+            Type::Primitive(_) | Type::SmallStruct(_) => None,
+        })
+    }
+
     #[inline]
     fn generate_struct(
         types: impl Iterator<Item = &'a Type>,
@@ -80,6 +110,8 @@ impl<'a> PackedPrimitives<'a> {
         let fields = types
             .map(|ty| match ty {
                 &Type::Primitive(p) => Cow::Borrowed(change_primitive(p)),
+                // Unknown values can be received, so they're checked once unbundled.
+                Type::Enum(e) => Cow::Borrowed(change_primitive(e.r#type)),
                 Type::SmallStruct(s) => Cow::Owned(namespaced_struct(s)),
             })
             .collect::<Vec<_>>()
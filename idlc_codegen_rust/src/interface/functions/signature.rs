@@ -1,17 +1,21 @@
 // Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
 // SPDX-License-Identifier: BSD-3-Clause
 
-use idlc_mir::{Ident, Primitive, StructInner};
+use std::collections::HashMap;
+
+use idlc_mir::{Enum, Ident, Primitive, StructInner, Type};
 
 use crate::{
     ident::EscapedIdent,
-    types::{change_primitive, namespaced_struct},
+    types::{change_primitive, namespaced_alias, namespaced_enum, namespaced_struct},
 };
 
 #[derive(Debug, Clone, Default)]
 pub struct Signature {
     inputs: Vec<(String, String)>,
     outputs: Vec<(String, String)>,
+    /// Typedefs naming the types of the parameters, by parameter.
+    aliases: HashMap<String, String>,
 }
 
 pub fn idents_to_struct_path(objects: &[&Ident]) -> String {
@@ -22,7 +26,19 @@ pub fn idents_to_struct_path(objects: &[&Ident]) -> String {
 
 impl Signature {
     pub fn new(function: &idlc_mir::Function) -> Self {
-        let mut me = Self::default();
+        let mut me = Self {
+            aliases: function
+                .params
+                .iter()
+                // Arrays of enums are slices of their primitive.
+                .filter(|param| !(param.is_array() && matches!(param.r#type(), Type::Enum(_))))
+                .filter_map(|param| {
+                    let alias = param.alias()?;
+                    Some((param.ident().to_string(), namespaced_alias(alias)))
+                })
+                .collect(),
+            ..Self::default()
+        };
         idlc_codegen::functions::visit_params(function, &mut me);
 
         me
@@ -33,6 +49,11 @@ impl Signature {
         self.inputs.iter().map(|(ident, _)| ident.as_str())
     }
 
+    #[inline]
+    pub fn param_types(&self) -> impl Iterator<Item = &str> {
+        self.inputs.iter().map(|(_, ty)| ty.as_str())
+    }
+
     pub fn params(&self) -> String {
         self.param_iter().collect::<Vec<String>>().join(", ")
     }
@@ -53,6 +74,13 @@ impl Signature {
         self.outputs.iter().map(|(ident, _)| ident.as_str())
     }
 
+    /// `ty` of the parameter `ident`, or the typedef naming it.
+    fn aliased(&self, ident: &Ident, ty: &str) -> String {
+        self.aliases
+            .get(ident.as_ref())
+            .map_or_else(|| ty.to_string(), String::clone)
+    }
+
     #[inline]
     fn push_inputs(&mut self, ident: &Ident, ty: impl Into<String>) {
         let ident = EscapedIdent::new(ident);
@@ -68,22 +96,34 @@ impl Signature {
 
 impl idlc_codegen::functions::ParameterVisitor for Signature {
     fn visit_input_primitive_buffer(&mut self, ident: &Ident, ty: Primitive) {
-        self.push_inputs(ident, format!("&[{}]", change_primitive(ty)));
+        let ty = self.aliased(ident, change_primitive(ty));
+        self.push_inputs(ident, format!("&[{ty}]"));
+    }
+
+    fn visit_input_string(&mut self, ident: &Ident, _: Option<idlc_mir::Count>) {
+        self.push_inputs(ident, "&str");
     }
 
     fn visit_input_struct_buffer(&mut self, ident: &Ident, ty: &StructInner) {
-        self.push_inputs(ident, format!("&[{}]", namespaced_struct(ty)));
+        let ty = self.aliased(ident, &namespaced_struct(ty));
+        self.push_inputs(ident, format!("&[{ty}]"));
     }
 
     fn visit_input_primitive(&mut self, ident: &Ident, ty: Primitive) {
-        self.push_inputs(ident, change_primitive(ty));
+        self.push_inputs(ident, self.aliased(ident, change_primitive(ty)));
+    }
+
+    fn visit_input_enum(&mut self, ident: &Ident, ty: &Enum) {
+        self.push_inputs(ident, self.aliased(ident, &namespaced_enum(ty)));
     }
 
     fn visit_input_big_struct(&mut self, ident: &Ident, ty: &StructInner) {
-        self.push_inputs(ident, format!("&{}", namespaced_struct(ty)));
+        let ty = self.aliased(ident, &namespaced_struct(ty));
+        self.push_inputs(ident, format!("&{ty}"));
     }
     fn visit_input_small_struct(&mut self, ident: &Ident, ty: &StructInner) {
-        self.push_inputs(ident, format!("&{}", namespaced_struct(ty)));
+        let ty = self.aliased(ident, &namespaced_struct(ty));
+        self.push_inputs(ident, format!("&{ty}"));
     }
 
     fn visit_input_object(&mut self, ident: &Ident, ty: Option<&str>) {
@@ -109,15 +149,21 @@ impl idlc_codegen::functions::ParameterVisitor for Signature {
     }
 
     fn visit_output_primitive_buffer(&mut self, ident: &Ident, ty: Primitive) {
-        self.push_inputs(ident, format!("&mut [{}]", change_primitive(ty)));
+        let ty = self.aliased(ident, change_primitive(ty));
+        self.push_inputs(ident, format!("&mut [{ty}]"));
         self.push_inputs(
             &idlc_mir::Ident::new_without_span(format!("{ident}_lenout")),
             "&mut usize",
         );
     }
 
+    fn visit_output_string(&mut self, ident: &Ident, _: idlc_mir::Count) {
+        self.push_outputs(ident, "String");
+    }
+
     fn visit_output_struct_buffer(&mut self, ident: &Ident, ty: &StructInner) {
-        self.push_inputs(ident, format!("&mut [{}]", namespaced_struct(ty)));
+        let ty = self.aliased(ident, &namespaced_struct(ty));
+        self.push_inputs(ident, format!("&mut [{ty}]"));
         self.push_inputs(
             &idlc_mir::Ident::new_without_span(format!("{ident}_lenout")),
             "&mut usize",
@@ -125,14 +171,18 @@ impl idlc_codegen::functions::ParameterVisitor for Signature {
     }
 
     fn visit_output_primitive(&mut self, ident: &Ident, ty: Primitive) {
-        self.push_outputs(ident, change_primitive(ty));
+        self.push_outputs(ident, self.aliased(ident, change_primitive(ty)));
+    }
+
+    fn visit_output_enum(&mut self, ident: &Ident, ty: &Enum) {
+        self.push_outputs(ident, self.aliased(ident, &namespaced_enum(ty)));
     }
 
     fn visit_output_big_struct(&mut self, ident: &Ident, ty: &StructInner) {
-        self.push_outputs(ident, namespaced_struct(ty));
+        self.push_outputs(ident, self.aliased(ident, &namespaced_struct(ty)));
     }
     fn visit_output_small_struct(&mut self, ident: &Ident, ty: &StructInner) {
-        self.push_outputs(ident, namespaced_struct(ty));
+        self.push_outputs(ident, self.aliased(ident, &namespaced_struct(ty)));
     }
 
     fn visit_output_object(&mut self, ident: &Ident, ty: Option<&str>) {
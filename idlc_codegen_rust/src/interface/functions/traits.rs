@@ -9,10 +9,11 @@ pub fn emit(
     let ident = &function.ident;
     let returns = signature.return_types().collect::<Vec<_>>().join(", ");
     let params = signature.params();
+    let deprecated = crate::globals::deprecated(function.deprecation());
     format!(
         r#"
     {documentation}
-    fn r#{ident}(&mut self, {params}) -> Result<({returns}), Error>
+    {deprecated}fn r#{ident}(&mut self, {params}) -> Result<({returns}), Error>
     "#
     )
 }
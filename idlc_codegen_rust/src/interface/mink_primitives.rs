@@ -1,6 +1,10 @@
 // Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
 // SPDX-License-Identifier: BSD-3-Clause
 
+/// Path of the runtime the generated code refers to, unless
+/// [`Generator::new`](crate::Generator::new) is given another one.
+pub const DEFAULT_RUNTIME_PATH: &str = "crate::object";
+
 macro_rules! namespace {
     ($ident: literal) => {
         concat!("crate::object::", $ident)
@@ -23,8 +27,26 @@ pub(super) const OP_RETAIN: &str = namespace!("OP_RETAIN");
 pub(super) const OP_VERSION: &str = namespace!("OP_VERSION");
 
 pub(super) const WRAPPER: &str = namespace!("wrapper");
+pub(super) const ASYNC: &str = namespace!("asynchronous");
+pub(crate) const SERDE_ARRAY: &str = namespace!("serde_array");
 
 pub const INTERFACES_BASE: &str = "crate::interfaces";
 pub const OBJECT: &str = namespace!("Object");
 
 pub(super) use namespace;
+
+/// Refers to the runtime at `runtime_path` in `output`, which spells it
+/// `crate::object`.
+///
+/// This has to happen before formatting, as macro arguments don't keep the
+/// path together.
+pub(crate) fn with_runtime(output: String, runtime_path: &str) -> String {
+    if runtime_path == DEFAULT_RUNTIME_PATH {
+        output
+    } else {
+        output.replace(
+            &format!("{DEFAULT_RUNTIME_PATH}::"),
+            &format!("{runtime_path}::"),
+        )
+    }
+}
@@ -0,0 +1,161 @@
+// Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
+// SPDX-License-Identifier: BSD-3-Clause
+
+use idlc_mir::{Interface, InterfaceNode};
+
+use super::functions::signature::Signature;
+use super::mink_primitives::{GENERIC_ERROR, INTERFACES_BASE};
+
+/// Emits `Mock<ident>`, implementing the trait of `interface` and of its bases
+/// with a queue of expected calls per method.
+///
+/// The methods run inside the `invoke` of the object, where unwinding aborts:
+/// failures are recorded and reported by `checkpoint` instead of panicking.
+pub(super) fn emit(interface: &Interface, no_std: bool) -> String {
+    let ident = &interface.ident;
+    let collections = if no_std { "alloc" } else { "std" };
+
+    let mut fields = String::new();
+    let mut expectations = String::new();
+    let mut checks = String::new();
+    let mut impls = String::new();
+
+    for (depth, iface) in interface.iter().enumerate() {
+        // Bases are glob imported, but their `Error` is shadowed by ours.
+        let error = if depth == 0 {
+            "Error".to_string()
+        } else {
+            format!("{INTERFACES_BASE}::{}::Error", iface.ident.to_lowercase())
+        };
+        let mut functions = String::new();
+        for node in &iface.nodes {
+            let InterfaceNode::Function(f) = node else {
+                continue;
+            };
+            let name = &f.ident;
+            let signature = Signature::new(f);
+            let params = signature.params();
+            let types = signature.param_types().collect::<Vec<_>>().join(", ");
+            let idents = signature.param_idents().collect::<Vec<_>>().join(", ");
+            let returns = signature.return_types().collect::<Vec<_>>().join(", ");
+            let closure = format!("FnMut({types}) -> Result<({returns}), {error}> + Send");
+
+            fields.push_str(&format!(
+                "r#{name}: {collections}::collections::VecDeque<Box<dyn {closure}>>,\n"
+            ));
+            expectations.push_str(&format!(
+                r#"
+                /// Queues `f` to answer the next call of `{name}`.
+                pub fn expect_{name}(&mut self, f: impl {closure} + 'static) -> &mut Self {{
+                    self.r#{name}.push_back(Box::new(f));
+                    self
+                }}
+                "#
+            ));
+            checks.push_str(&format!(
+                r#"
+                if !self.r#{name}.is_empty() {{
+                    failures.push({collections}::format!(
+                        "{{}} expected call(s) of `{}::{name}` never happened",
+                        self.r#{name}.len()
+                    ));
+                }}
+                "#,
+                iface.ident
+            ));
+            // Without `std`, panics of the closures can't be caught.
+            let call = if no_std {
+                format!("f({idents})")
+            } else {
+                format!(
+                    r#"
+                    match std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| f({idents}))) {{
+                        Ok(result) => result,
+                        Err(panic) => {{
+                            self.fail("`{}::{name}` panicked", &*panic);
+                            Err({GENERIC_ERROR}::GENERIC.into())
+                        }}
+                    }}
+                    "#,
+                    iface.ident
+                )
+            };
+            functions.push_str(&format!(
+                r#"
+                fn r#{name}(&mut self, {params}) -> Result<({returns}), {error}> {{
+                    let Some(mut f) = self.r#{name}.pop_front() else {{
+                        self.failures.push({collections}::format!("unexpected call of `{}::{name}`"));
+                        return Err({GENERIC_ERROR}::GENERIC.into());
+                    }};
+                    {call}
+                }}
+                "#,
+                iface.ident
+            ));
+        }
+        impls.push_str(&format!(
+            r#"
+            impl I{} for Mock{ident} {{
+                {functions}
+            }}
+            "#,
+            iface.ident
+        ));
+    }
+
+    let fail = if no_std {
+        String::new()
+    } else {
+        r#"
+        fn fail(&mut self, what: &str, panic: &(dyn std::any::Any + Send)) {
+            let message = panic
+                .downcast_ref::<&str>()
+                .copied()
+                .or_else(|| panic.downcast_ref::<String>().map(String::as_str))
+                .unwrap_or("non-string payload");
+            self.failures.push(std::format!("{what}: {message}"));
+        }
+        "#
+        .to_string()
+    };
+    let caught = if no_std {
+        ""
+    } else {
+        " or which\n    /// panicked"
+    };
+
+    format!(
+        r#"
+    /// Mock of [`I{ident}`], answering each call with the closure queued by the
+    /// `expect_` method of its method. Calls which weren't expected{caught} fail
+    /// with [`{GENERIC_ERROR}::GENERIC`] and are reported by
+    /// [`Mock{ident}::checkpoint`].
+    ///
+    /// It becomes an object like any other implementation, with [`{ident}::from`].
+    #[derive(Default)]
+    pub struct Mock{ident} {{
+        {fields}
+        failures: {collections}::vec::Vec<{collections}::string::String>,
+    }}
+
+    impl Mock{ident} {{
+        pub fn new() -> Self {{
+            Self::default()
+        }}
+        {expectations}
+        /// The failed calls, and the queued calls which didn't happen.
+        pub fn checkpoint(&self) -> Result<(), {collections}::vec::Vec<{collections}::string::String>> {{
+            let mut failures = self.failures.clone();
+            {checks}
+            if failures.is_empty() {{
+                Ok(())
+            }} else {{
+                Err(failures)
+            }}
+        }}
+        {fail}
+    }}
+    {impls}
+    "#
+    )
+}
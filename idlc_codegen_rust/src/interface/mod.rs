@@ -1,16 +1,26 @@
 // Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
 // SPDX-License-Identifier: BSD-3-Clause
 
-use crate::globals::emit_const;
+use crate::globals::{emit_const, emit_enum};
 
 use idlc_mir::{APIVersion, Interface, InterfaceNode, VERSION_FUNC_NAME};
 
+mod asynchronous;
 mod error;
 mod functions;
 pub mod mink_primitives;
+mod mock;
 mod variable_names;
 
-pub fn emit(interface: &Interface) -> String {
+/// Emits `interface`, referring to the runtime at `runtime_path`, along with
+/// its mock with `mocks` and its async flavor with `asynchronous`.
+pub fn emit(
+    interface: &Interface,
+    runtime_path: &str,
+    no_std: bool,
+    mocks: bool,
+    asynchronous: bool,
+) -> String {
     use mink_primitives::{
         ARG, CONTEXT, COUNTS, GENERIC_ERROR, INVOKE_FN, OBJECT, OP_ID, OP_RELEASE, OP_RETAIN,
         OP_VERSION, TYPED_OBJECT_TRAIT, WRAPPER,
@@ -26,6 +36,7 @@ pub fn emit(interface: &Interface) -> String {
     for node in &interface.nodes {
         match node {
             InterfaceNode::Const(c) => constants.push_str(&emit_const(c)),
+            InterfaceNode::Enum(e) => constants.push_str(&emit_enum(e)),
             InterfaceNode::Error(e) => errors.push(e),
             InterfaceNode::Function(f) => {
                 let signature = functions::signature::Signature::new(f);
@@ -76,7 +87,7 @@ pub fn emit(interface: &Interface) -> String {
     let implementations = implementations.concat();
     let invoke_arms = invoke_arms.join(",");
 
-    let errors = error::emit(&errors);
+    let errors = error::emit(&errors, no_std);
     let base_ident = interface
         .base
         .as_ref()
@@ -89,19 +100,35 @@ pub fn emit(interface: &Interface) -> String {
 
     let interface_version = interface.get_version();
     let APIVersion { major, minor } = interface_version;
+    let documentation = crate::globals::doc(interface.doc.as_deref());
+    let deprecated = crate::globals::deprecated(interface.deprecated.as_ref());
+
+    let mock = if mocks {
+        mock::emit(interface, no_std)
+    } else {
+        String::new()
+    };
+    let (async_methods, async_trait) = if asynchronous {
+        (
+            asynchronous::emit_proxy_methods(interface),
+            asynchronous::emit_trait(interface),
+        )
+    } else {
+        (String::new(), String::new())
+    };
 
     let output = format!(
         r#"
     {errors}
     {constants}
-    #[repr(transparent)]
+    {documentation}{deprecated}#[repr(transparent)]
     #[derive(Debug, Clone, PartialEq)]
     pub struct {ident}({OBJECT});
     unsafe impl Sync for {ident} {{}}
     unsafe impl Send for {ident} {{}}
     unsafe impl {TYPED_OBJECT_TRAIT} for {ident} {{}}
 
-    pub trait I{ident}: {base_ident} 'static {{
+    {deprecated}pub trait I{ident}: {base_ident} 'static {{
         {trait_functions}
     }}
 
@@ -109,12 +136,12 @@ pub fn emit(interface: &Interface) -> String {
     impl {ident} {{
         #[inline]
         pub fn r#{VERSION_FUNC_NAME}(&self) -> Result<(u32), Error> {{
-            let mut r#version = std::mem::MaybeUninit::<u32>::uninit();
+            let mut r#version = core::mem::MaybeUninit::<u32>::uninit();
             let mut args = [
                 crate::object::Arg {{
                     bi: crate::object::BufIn {{
-                        ptr: std::ptr::addr_of_mut!(r#version).cast(),
-                        size: std::mem::size_of::<u32>(),
+                        ptr: core::ptr::addr_of_mut!(r#version).cast(),
+                        size: core::mem::size_of::<u32>(),
                     }},
                 }},
             ];
@@ -125,20 +152,21 @@ pub fn emit(interface: &Interface) -> String {
                     let r#version = unsafe {{ r#version.assume_init() }};
                     Ok((r#version))
                 }}
-                err => Err(unsafe {{ std::mem::transmute(err) }}),
+                err => Err(unsafe {{ core::mem::transmute(err) }}),
             }}
         }}
         {implementations}
+        {async_methods}
     }}
 
     impl AsRef<{upcast_target}> for {ident} {{
         #[inline]
         fn as_ref(&self) -> &{upcast_target} {{
-            unsafe {{ std::mem::transmute(self) }}
+            unsafe {{ core::mem::transmute(self) }}
         }}
     }}
 
-    impl std::ops::Deref for {ident} {{
+    impl core::ops::Deref for {ident} {{
         type Target = {upcast_target};
 
         #[inline]
@@ -149,7 +177,7 @@ pub fn emit(interface: &Interface) -> String {
 
     impl From<{ident}> for {upcast_target} {{
         fn from(value: {ident}) -> {upcast_target} {{
-            unsafe {{ std::mem::transmute(value) }}
+            unsafe {{ core::mem::transmute(value) }}
         }}
     }}
 
@@ -162,7 +190,7 @@ pub fn emit(interface: &Interface) -> String {
 
     static {MARKER}: {INVOKE_FN} = invoke;
     unsafe extern "C" fn invoke({h}: {CONTEXT}, {op}: {OP_ID}, {args}: *mut {ARG}, {counts}: {COUNTS}) -> i32 {{
-        debug_assert_eq!({h}.align_offset(std::mem::align_of::<{wrapper}>()), 0);
+        debug_assert_eq!({h}.align_offset(core::mem::align_of::<{wrapper}>()), 0);
         let {cx} = {h}.cast::<{wrapper}>();
         match op {{
             {invoke_arms}
@@ -174,21 +202,21 @@ pub fn emit(interface: &Interface) -> String {
             }},
             {OP_VERSION} => {{
                 if counts != crate::object::pack_counts(0, 1, 0, 0) {{
-                    return std::mem::transmute(crate::object::error::generic::GENERIC);
+                    return core::mem::transmute(crate::object::error::generic::GENERIC);
                 }}
-                let args = std::slice::from_raw_parts_mut(args, 1);
+                let args = core::slice::from_raw_parts_mut(args, 1);
                 let r#a_orig = args[0].b.size;
-                if r#a_orig < std::mem::size_of::<u32>() {{
+                if r#a_orig < core::mem::size_of::<u32>() {{
                     return {GENERIC_ERROR}::SIZE_OUT.into();
                 }}
-                let r#a_lenout = &mut *std::ptr::addr_of_mut!(args[0].b.size);
-                let r#a = std::slice::from_raw_parts_mut(
+                let r#a_lenout = &mut *core::ptr::addr_of_mut!(args[0].b.size);
+                let r#a = core::slice::from_raw_parts_mut(
                     args[0].b.ptr.cast::<u8>(),
-                    r#a_orig / std::mem::size_of::<u8>(),
+                    r#a_orig / core::mem::size_of::<u8>(),
                 );
                 let value: u32 = IDLVersion::new({major}, {minor}, 0).into();
                 r#a.copy_from_slice(&value.to_le_bytes());
-                *r#a_lenout = std::mem::size_of::<u32>();
+                *r#a_lenout = core::mem::size_of::<u32>();
                 0
             }},
             _ => {GENERIC_ERROR}::INVALID.into(),
@@ -296,6 +324,8 @@ pub fn emit(interface: &Interface) -> String {
     pub fn downcast_concrete<R, T: I{ident} + 'static>(obj: &{OBJECT}, f: impl FnMut(&T) -> R) -> Option<R> {{
         {WRAPPER}::downcast_concrete::<R, T, dyn I{ident}>(obj, {MARKER}, f)
     }}
+    {async_trait}
+    {mock}
     "#,
         h = variable_names::invoke::HANDLE,
         op = variable_names::invoke::OP_ID,
@@ -304,6 +334,7 @@ pub fn emit(interface: &Interface) -> String {
         cx = variable_names::invoke::CONTEXT,
         MARKER = variable_names::invoke::MARKER
     );
+    let output = mink_primitives::with_runtime(output, runtime_path);
     match syn::parse_file(&output) {
         Ok(file) => prettyplease::unparse(&file),
         Err(e) => {
@@ -8,3 +8,4 @@ mod interface;
 mod types;
 
 pub use generator::Generator;
+pub use interface::mink_primitives::DEFAULT_RUNTIME_PATH;
@@ -13,6 +13,8 @@ pub const fn change_primitive(primitive: idlc_mir::Primitive) -> &'static str {
         idlc_mir::Primitive::Int64 => "i64",
         idlc_mir::Primitive::Float32 => "f32",
         idlc_mir::Primitive::Float64 => "f64",
+        idlc_mir::Primitive::Bool => "bool",
+        idlc_mir::Primitive::Char => "core::ffi::c_char",
     }
 }
 
@@ -34,3 +36,41 @@ pub fn namespaced_struct(r#struct: &idlc_mir::StructInner) -> String {
     let ident = super::ident::EscapedIdent::new(&r#struct.ident);
     format!("{namespace}{ident}")
 }
+
+/// Path of `r#enum`, enums declared in an interface live in that interface's module.
+pub fn namespaced_enum(r#enum: &idlc_mir::Enum) -> String {
+    use crate::interface::mink_primitives::INTERFACES_BASE;
+    let module = r#enum
+        .scope
+        .as_ref()
+        .map(|scope| scope.to_lowercase())
+        .or_else(|| {
+            r#enum.origin.as_ref().map(|origin| {
+                origin
+                    .file_stem()
+                    .expect("Expected IDL file")
+                    .to_str()
+                    .unwrap()
+                    .to_lowercase()
+            })
+        });
+    let namespace = module
+        .map(|module| format!("{INTERFACES_BASE}::{module}::"))
+        .unwrap_or_default();
+    let ident = super::ident::EscapedIdent::new(&r#enum.ident);
+    format!("{namespace}{ident}")
+}
+
+/// Path of the typedef `alias`, typedefs live in the module of their file.
+pub fn namespaced_alias(alias: &idlc_mir::Alias) -> String {
+    use crate::interface::mink_primitives::INTERFACES_BASE;
+    let module = alias
+        .origin
+        .file_stem()
+        .expect("Expected IDL file")
+        .to_str()
+        .unwrap()
+        .to_lowercase();
+    let ident = super::ident::EscapedIdent::new(&alias.ident);
+    format!("{INTERFACES_BASE}::{module}::{ident}")
+}
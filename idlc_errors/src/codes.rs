@@ -0,0 +1,84 @@
+// Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Stable codes of the diagnostics emitted by idlc.
+//!
+//! Codes are never reused, retired diagnostics keep their number reserved.
+
+/// Included file couldn't be located in any of the include paths.
+pub const INCLUDE_NOT_FOUND: &str = "E0001";
+/// IDL file couldn't be parsed.
+pub const PARSE_FAILURE: &str = "E0002";
+/// Same symbol is defined more than once across the compilation unit.
+pub const DUPLICATE_SYMBOL: &str = "E0003";
+/// Identifier is defined more than once within an interface hierarchy.
+pub const IDENT_COLLISION: &str = "E0004";
+/// Identifier is reserved by Mink IDL.
+pub const RESERVED_IDENT: &str = "E0005";
+/// Array of interfaces must be bounded.
+pub const UNBOUNDED_OBJECT_ARRAY: &str = "E0006";
+/// Structs containing objects cannot be used as arrays.
+pub const OBJECT_STRUCT_ARRAY: &str = "E0007";
+/// Arrays of primitives and structs cannot be bounded.
+pub const BOUNDED_VALUE_ARRAY: &str = "E0008";
+/// Object arrays cannot be mixed with objects in the same direction.
+pub const MIXED_OBJECT_ARGS: &str = "E0009";
+/// Method has multiple parameters with the same name.
+pub const DUPLICATE_PARAM: &str = "E0010";
+/// Method has multiple `version` attributes.
+pub const DUPLICATE_VERSION: &str = "E0011";
+/// Method version is less than the one of the preceding method.
+pub const VERSION_REGRESSION: &str = "E0012";
+/// Symbol couldn't be resolved.
+pub const UNRESOLVED_SYMBOL: &str = "E0013";
+/// A compiler pass failed.
+pub const PASS_FAILURE: &str = "E0014";
+/// Output path doesn't match what the code generator expects.
+pub const INVALID_OUTPUT: &str = "E0015";
+/// Enum variant value isn't an integer representable by the underlying type.
+pub const ENUM_VALUE_OUT_OF_RANGE: &str = "E0016";
+/// Enum has multiple variants with the same name or value.
+pub const DUPLICATE_ENUM_VARIANT: &str = "E0017";
+/// Code generator plugin couldn't be run or reported a failure.
+pub const PLUGIN_FAILURE: &str = "E0018";
+/// Method has multiple `opcode` attributes.
+pub const DUPLICATE_OP_CODE: &str = "E0019";
+/// Method op-code is above the Mink limit of `0x3FFF`.
+pub const OP_CODE_OUT_OF_RANGE: &str = "E0020";
+/// Op-code is assigned to several methods of an interface hierarchy.
+pub const OP_CODE_COLLISION: &str = "E0021";
+/// Error value is one of the generic Mink error codes below 10.
+pub const ERROR_VALUE_RESERVED: &str = "E0022";
+/// Error value is assigned to several errors of an interface hierarchy.
+pub const ERROR_VALUE_COLLISION: &str = "E0023";
+/// Typedef aliases an interface, refers back to itself or is too large.
+pub const INVALID_TYPEDEF: &str = "E0024";
+/// Array typedef is used where its elements can't be laid out.
+pub const INVALID_TYPEDEF_USE: &str = "E0025";
+/// Constant expression overflows its type, divides by zero or refers back to
+/// itself.
+pub const INVALID_CONST_EXPR: &str = "E0026";
+/// Array size names a constant which isn't an integer between 1 and 65535.
+pub const INVALID_ARRAY_SIZE: &str = "E0027";
+/// Maximum length is given twice, or to a parameter other than an input buffer
+/// or unbounded array.
+pub const INVALID_MAX_LEN: &str = "E0028";
+/// Output string has no maximum length for its caller to allocate.
+pub const UNBOUNDED_OUTPUT_STRING: &str = "E0029";
+/// Pinned error value doesn't fit in an `int32`.
+pub const ERROR_VALUE_OUT_OF_RANGE: &str = "E0030";
+/// Struct has multiple fields with the same name.
+pub const DUPLICATE_FIELD: &str = "E0031";
+/// Struct field is an array of objects.
+pub const OBJECT_ARRAY_FIELD: &str = "E0032";
+/// Struct field isn't at an offset multiple of its alignment.
+pub const UNALIGNED_FIELD: &str = "E0033";
+/// Struct size isn't a multiple of its alignment.
+pub const UNALIGNED_STRUCT: &str = "E0034";
+/// Input path doesn't name a file.
+pub const INVALID_INPUT: &str = "E0035";
+
+/// Interface array of size 1 is better expressed as a single interface.
+pub const SINGLE_OBJECT_ARRAY: &str = "W0001";
+/// Deprecated struct or interface is used by a declaration which isn't.
+pub const DEPRECATED_USE: &str = "W0002";
@@ -0,0 +1,210 @@
+// Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Structured diagnostics emitted by the compiler passes.
+
+use std::fmt::Write;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    Note,
+    Warning,
+    Error,
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Note => "note",
+            Self::Warning => "warning",
+            Self::Error => "error",
+        })
+    }
+}
+
+/// A byte range in the source file with an optional message attached to it.
+///
+/// Primary labels point at the cause of the diagnostic, secondary labels at
+/// related locations (e.g. the initial definition of a duplicated symbol).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Label {
+    pub range: Range<usize>,
+    pub message: String,
+    pub primary: bool,
+}
+
+impl Label {
+    #[must_use]
+    pub fn primary(range: impl Into<Range<usize>>, message: impl Into<String>) -> Self {
+        Self {
+            range: range.into(),
+            message: message.into(),
+            primary: true,
+        }
+    }
+
+    #[must_use]
+    pub fn secondary(range: impl Into<Range<usize>>, message: impl Into<String>) -> Self {
+        Self {
+            range: range.into(),
+            message: message.into(),
+            primary: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// Stable identifier of the diagnostic, see [`crate::codes`].
+    pub code: &'static str,
+    pub message: String,
+    /// File the labels point into.
+    pub file: Option<PathBuf>,
+    pub labels: Vec<Label>,
+    pub notes: Vec<String>,
+}
+
+impl Diagnostic {
+    #[must_use]
+    pub fn new(severity: Severity, code: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            severity,
+            code,
+            message: message.into(),
+            file: None,
+            labels: Vec::new(),
+            notes: Vec::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn error(code: &'static str, message: impl Into<String>) -> Self {
+        Self::new(Severity::Error, code, message)
+    }
+
+    #[must_use]
+    pub fn warning(code: &'static str, message: impl Into<String>) -> Self {
+        Self::new(Severity::Warning, code, message)
+    }
+
+    #[must_use]
+    pub fn with_file(mut self, file: impl AsRef<Path>) -> Self {
+        self.file = Some(file.as_ref().to_path_buf());
+        self
+    }
+
+    #[must_use]
+    pub fn with_label(mut self, label: Label) -> Self {
+        self.labels.push(label);
+        self
+    }
+
+    #[must_use]
+    pub fn with_note(mut self, note: impl Into<String>) -> Self {
+        self.notes.push(note.into());
+        self
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+
+    /// Renders the diagnostic along with snippets of `source` for each label.
+    ///
+    /// When `source` is unavailable only the header, the location and the
+    /// notes are rendered.
+    #[must_use]
+    pub fn render(&self, source: Option<&str>) -> String {
+        let mut out = format!("{self}\n");
+        let file = self
+            .file
+            .as_ref()
+            .map_or_else(|| "<unknown>".to_string(), |f| f.display().to_string());
+
+        let mut labels: Vec<&Label> = self.labels.iter().collect();
+        labels.sort_by_key(|l| !l.primary);
+
+        let gutter = labels
+            .iter()
+            .filter_map(|l| source.map(|s| line_col(s, l.range.start).0))
+            .max()
+            .unwrap_or(0)
+            .to_string()
+            .len();
+        let pad = " ".repeat(gutter);
+
+        if labels.is_empty() && self.file.is_some() {
+            let _ = writeln!(out, "{pad}--> {file}");
+        }
+        for label in labels {
+            let Some(source) = source else {
+                let _ = writeln!(out, "{pad}--> {file}");
+                if !label.message.is_empty() {
+                    let _ = writeln!(out, "{pad} = {}", label.message);
+                }
+                continue;
+            };
+            let (line, col) = line_col(source, label.range.start);
+            let text = source.lines().nth(line - 1).unwrap_or_default();
+            let prefix = &text[..text.len().min(col_to_byte(text, col))];
+            let width = source
+                .get(label.range.clone())
+                .and_then(|s| s.lines().next())
+                .map_or(1, |s| s.chars().count().max(1));
+            let marker = if label.primary { "^" } else { "-" };
+
+            let _ = writeln!(out, "{pad}--> {file}:{line}:{col}");
+            let _ = writeln!(out, "{pad} |");
+            let _ = writeln!(out, "{line:>gutter$} | {text}");
+            let indent: String = prefix
+                .chars()
+                .map(|c| if c == '\t' { '\t' } else { ' ' })
+                .collect();
+            let underline = format!("{pad} | {indent}{} {}", marker.repeat(width), label.message);
+            let _ = writeln!(out, "{}", underline.trim_end());
+        }
+        for note in &self.notes {
+            let _ = writeln!(out, "{pad} = note: {note}");
+        }
+        out
+    }
+}
+
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}[{}]: {}", self.severity, self.code, self.message)
+    }
+}
+
+/// Returns the 1-based line and column of the byte `offset` in `source`.
+#[must_use]
+pub fn line_col(source: &str, offset: usize) -> (usize, usize) {
+    let offset = offset.min(source.len());
+    let before = &source[..floor_char_boundary(source, offset)];
+    let line = before.matches('\n').count() + 1;
+    let col = before
+        .rfind('\n')
+        .map_or(before, |idx| &before[idx + 1..])
+        .chars()
+        .count()
+        + 1;
+    (line, col)
+}
+
+fn floor_char_boundary(s: &str, mut idx: usize) -> usize {
+    while !s.is_char_boundary(idx) {
+        idx -= 1;
+    }
+    idx
+}
+
+fn col_to_byte(line: &str, col: usize) -> usize {
+    line.char_indices()
+        .nth(col - 1)
+        .map_or(line.len(), |(idx, _)| idx)
+}
@@ -4,11 +4,24 @@
 use env_logger::Env;
 pub use log::{debug, error, info, trace, warn};
 
+pub mod codes;
+mod diagnostic;
+mod sink;
+
+pub use diagnostic::{line_col, Diagnostic, Label, Severity};
+pub use sink::{
+    assert_no_errors, emit, has_errors, register_source, render, report, source, take_diagnostics,
+    take_errors,
+};
+
+/// Sets up the logger, subsequent calls are no-ops.
 #[inline]
 pub fn init() {
-    env_logger::Builder::from_env(Env::default().default_filter_or("warn")).init();
+    let _ = env_logger::Builder::from_env(Env::default().default_filter_or("warn")).try_init();
 }
 
+/// Aborts on internal compiler errors, user facing errors are emitted as
+/// [`Diagnostic`]s instead.
 #[macro_export]
 macro_rules! unrecoverable {
     ($($arg:tt)+) => {
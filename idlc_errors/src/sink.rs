@@ -0,0 +1,100 @@
+// Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Per-thread sink collecting diagnostics from all the compiler passes.
+//!
+//! Passes push diagnostics with [`emit`] and carry on, the driver decides
+//! when to [`report`] them and whether to stop.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use crate::Diagnostic;
+
+thread_local! {
+    static DIAGNOSTICS: RefCell<Vec<Diagnostic>> = const { RefCell::new(Vec::new()) };
+    static SOURCES: RefCell<HashMap<PathBuf, Rc<str>>> = RefCell::new(HashMap::new());
+}
+
+/// Pushes `diagnostic` into the sink.
+pub fn emit(diagnostic: Diagnostic) {
+    DIAGNOSTICS.with_borrow_mut(|d| d.push(diagnostic));
+}
+
+/// Returns `true` if any error was emitted since the last [`take_diagnostics`].
+#[must_use]
+pub fn has_errors() -> bool {
+    DIAGNOSTICS.with_borrow(|d| d.iter().any(Diagnostic::is_error))
+}
+
+/// Drains all the diagnostics collected so far.
+#[must_use]
+pub fn take_diagnostics() -> Vec<Diagnostic> {
+    DIAGNOSTICS.with_borrow_mut(std::mem::take)
+}
+
+/// Drains the sink and returns the errors among the diagnostics it held.
+#[must_use]
+pub fn take_errors() -> Vec<Diagnostic> {
+    let mut diagnostics = take_diagnostics();
+    diagnostics.retain(Diagnostic::is_error);
+    diagnostics
+}
+
+/// Drains the sink and panics with the errors it held, if any.
+#[track_caller]
+pub fn assert_no_errors() {
+    let errors = take_errors();
+    assert!(
+        errors.is_empty(),
+        "{}",
+        errors
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("\n")
+    );
+}
+
+/// Remembers the contents of `path` so that snippets can be rendered for
+/// sources that don't live on the disk.
+pub fn register_source(path: &Path, contents: &str) {
+    SOURCES.with_borrow_mut(|s| {
+        s.insert(path.to_path_buf(), contents.into());
+    });
+}
+
+/// Returns the contents of `path`, either previously registered or read from
+/// the disk.
+#[must_use]
+pub fn source(path: &Path) -> Option<Rc<str>> {
+    SOURCES
+        .with_borrow(|s| s.get(path).cloned())
+        .or_else(|| std::fs::read_to_string(path).ok().map(Into::into))
+}
+
+/// Renders `diagnostic` with the snippet of the file it points into.
+#[must_use]
+pub fn render(diagnostic: &Diagnostic) -> String {
+    let source = diagnostic.file.as_deref().and_then(source);
+    diagnostic.render(source.as_deref())
+}
+
+/// Drains the sink, prints every diagnostic to stderr and returns the number
+/// of errors among them.
+pub fn report() -> usize {
+    let diagnostics = take_diagnostics();
+    for diagnostic in &diagnostics {
+        eprintln!("{}", render(diagnostic));
+    }
+    let errors = diagnostics.iter().filter(|d| d.is_error()).count();
+    if errors > 0 {
+        eprintln!(
+            "error: aborting due to {errors} previous error{}",
+            if errors == 1 { "" } else { "s" }
+        );
+    }
+    errors
+}
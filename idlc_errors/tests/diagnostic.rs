@@ -0,0 +1,62 @@
+// Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
+// SPDX-License-Identifier: BSD-3-Clause
+
+use idlc_errors::{Diagnostic, Label};
+
+#[test]
+fn line_col_is_one_based() {
+    let source = "interface IFoo {\n  method bar();\n};\n";
+    assert_eq!(idlc_errors::line_col(source, 0), (1, 1));
+    assert_eq!(idlc_errors::line_col(source, 26), (2, 10));
+    assert_eq!(idlc_errors::line_col(source, source.len()), (4, 1));
+}
+
+#[test]
+fn renders_snippet_for_each_label() {
+    let source = "struct S {\n  uint8 a;\n  uint8 a;\n};\n";
+    let rendered = Diagnostic::error("E0000", "duplicate field")
+        .with_file("s.idl")
+        .with_label(Label::primary(30..31, "redefined here"))
+        .with_label(Label::secondary(19..20, "first defined here"))
+        .with_note("fields must be unique")
+        .render(Some(source));
+
+    assert_eq!(
+        rendered,
+        r"error[E0000]: duplicate field
+ --> s.idl:3:9
+  |
+3 |   uint8 a;
+  |         ^ redefined here
+ --> s.idl:2:9
+  |
+2 |   uint8 a;
+  |         - first defined here
+  = note: fields must be unique
+"
+    );
+}
+
+#[test]
+fn sink_collects_until_drained() {
+    idlc_errors::emit(Diagnostic::warning("W0000", "first"));
+    assert!(!idlc_errors::has_errors());
+    idlc_errors::emit(Diagnostic::error("E0000", "second"));
+    assert!(idlc_errors::has_errors());
+
+    let diagnostics = idlc_errors::take_diagnostics();
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(diagnostics[1].to_string(), "error[E0000]: second");
+    assert!(!idlc_errors::has_errors());
+}
+
+#[test]
+fn take_errors_drops_warnings() {
+    idlc_errors::emit(Diagnostic::warning("W0000", "first"));
+    idlc_errors::emit(Diagnostic::error("E0000", "second"));
+
+    let errors = idlc_errors::take_errors();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].to_string(), "error[E0000]: second");
+    assert!(idlc_errors::take_diagnostics().is_empty());
+}
@@ -0,0 +1,463 @@
+// Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Answers to the requests of the client, computed from the open documents
+//! overlaid on the disk.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use idlc::{Compiler, Language};
+use idlc_ast::{InterfaceNode, Node, Span};
+use idlc_ast_passes::idl_store::{IDLStore, Sources};
+use idlc_ast_passes::CompilerPass;
+use idlc_codegen::counts::Counter;
+use idlc_errors::{Diagnostic, Severity};
+use idlc_mir::Mir;
+use lsp_types::{
+    CompletionItem, CompletionItemKind, DiagnosticRelatedInformation, DiagnosticSeverity,
+    DocumentSymbol, Location, NumberOrString, SymbolKind, Url,
+};
+
+use crate::position;
+use crate::Server;
+
+/// Types which aren't declared in any IDL file.
+const BUILTIN_TYPES: [&str; 15] = [
+    "uint8",
+    "uint16",
+    "uint32",
+    "uint64",
+    "int8",
+    "int16",
+    "int32",
+    "int64",
+    "float32",
+    "float64",
+    "bool",
+    "char",
+    "buffer",
+    "string",
+    "interface",
+];
+
+/// A named declaration of an IDL file.
+struct Definition {
+    name: String,
+    kind: SymbolKind,
+    file: PathBuf,
+    span: Span,
+}
+
+impl Server {
+    /// Returns the contents of `path`, from the editor if it's open.
+    pub(crate) fn text(&self, path: &Path) -> Option<String> {
+        self.documents
+            .get(path)
+            .cloned()
+            .or_else(|| std::fs::read_to_string(path).ok())
+    }
+
+    fn compiler(&self, path: &Path) -> Compiler {
+        let mut includes = self.config.includes.clone();
+        includes.extend(path.parent().map(Path::to_path_buf));
+        Compiler::new(
+            path.to_path_buf(),
+            PathBuf::new(),
+            includes,
+            Language::C,
+            self.config.allow_undefined_behavior,
+        )
+        .with_overlay(self.documents.clone())
+    }
+
+    /// Returns the MIR of `path`, if it compiles.
+    fn mir(&self, path: &Path) -> Option<Mir> {
+        let mir = self.compiler(path).mir().ok();
+        let _ = idlc_errors::take_diagnostics();
+        mir
+    }
+
+    /// Parses `path` and every file it includes, errors are ignored.
+    fn store(&self, path: &Path) -> IDLStore {
+        let mut includes = self.config.includes.clone();
+        includes.extend(path.parent().map(Path::to_path_buf));
+        let mut store = IDLStore::with_includes(&includes, self.config.allow_undefined_behavior)
+            .with_sources(Sources::Overlay(std::rc::Rc::new(self.documents.clone())));
+        let ast = store.get_or_insert(path);
+        let _ = store.run_pass(&ast);
+        let _ = idlc_errors::take_diagnostics();
+        store
+    }
+
+    /// Compiles `path` and returns its diagnostics grouped by the file they
+    /// point into.
+    pub(crate) fn diagnostics(&self, path: &Path) -> HashMap<PathBuf, Vec<lsp_types::Diagnostic>> {
+        let mut diagnostics = self.compiler(path).mir().err().unwrap_or_default();
+        diagnostics.extend(idlc_errors::take_diagnostics());
+
+        let mut files: HashMap<PathBuf, Vec<_>> = HashMap::new();
+        files.insert(path.to_path_buf(), Vec::new());
+        for diagnostic in diagnostics {
+            let file = diagnostic
+                .file
+                .clone()
+                .unwrap_or_else(|| path.to_path_buf());
+            let text = self.text(&file).unwrap_or_default();
+            let converted = convert(&diagnostic, &file, &text);
+            files.entry(file).or_default().push(converted);
+        }
+        files
+    }
+
+    pub(crate) fn definition(&self, path: &Path, offset: usize) -> Option<Location> {
+        let text = self.text(path)?;
+        let (word, _) = position::word_at(&text, offset)?;
+        let definition = lookup(definitions(&self.store(path)), word)?;
+        let target = self.text(&definition.file)?;
+        Some(Location::new(
+            Url::from_file_path(&definition.file).ok()?,
+            position::range(&target, definition.span.into()),
+        ))
+    }
+
+    /// Describes the layout of structs and the op-code and arguments of
+    /// methods, in markdown.
+    pub(crate) fn hover(&self, path: &Path, offset: usize) -> Option<(String, Span)> {
+        let text = self.text(path)?;
+        let (word, range) = position::word_at(&text, offset)?;
+        let span = Span {
+            start: range.start,
+            end: range.end,
+        };
+
+        // Methods are only described where they're declared.
+        if let Some(mir) = self.mir(path) {
+            let mut methods: Vec<_> = interfaces(&mir)
+                .flat_map(|iface| iface.nodes.iter().map(move |node| (iface, node)))
+                .filter_map(|(iface, node)| match node {
+                    idlc_mir::InterfaceNode::Function(f) if f.ident == *word => Some((iface, f)),
+                    _ => None,
+                })
+                .collect();
+            methods
+                .sort_by_key(|(_, f)| !(f.ident.span.start..=f.ident.span.end).contains(&offset));
+            if let Some((iface, function)) = methods.first() {
+                return Some((describe_method(iface, function), span));
+            }
+        }
+
+        let definition = lookup(definitions(&self.store(path)), word)?;
+        let mir = self.mir(&definition.file);
+        let nodes = mir.iter().flat_map(|mir| &mir.nodes);
+        let description = match definition.kind {
+            SymbolKind::STRUCT => nodes
+                .filter_map(|node| match node {
+                    idlc_mir::Node::Struct(s) => Some(s.as_ref()),
+                    _ => None,
+                })
+                .find(|s| s.ident == *word)
+                .map_or_else(
+                    || format!("```idl\nstruct {word}\n```"),
+                    |s| {
+                        format!(
+                            "```idl\nstruct {word}\n```\nsize: {} bytes, alignment: {} bytes",
+                            s.size(),
+                            s.align()
+                        )
+                    },
+                ),
+            SymbolKind::INTERFACE => nodes
+                .filter_map(|node| match node {
+                    idlc_mir::Node::Interface(i) => Some(i),
+                    _ => None,
+                })
+                .find(|i| i.ident == *word)
+                .map_or_else(
+                    || format!("```idl\ninterface {word}\n```"),
+                    |i| {
+                        let mut header = format!("interface {word}");
+                        if let Some(base) = &i.base {
+                            header.push_str(&format!(" : {}", base.ident));
+                        }
+                        let methods = i
+                            .nodes
+                            .iter()
+                            .filter(|node| matches!(node, idlc_mir::InterfaceNode::Function(_)))
+                            .count();
+                        format!(
+                            "```idl\n{header}\n```\n{methods} method(s), version {}",
+                            i.get_version()
+                        )
+                    },
+                ),
+            SymbolKind::ENUM => format!("```idl\nenum {word}\n```"),
+            SymbolKind::TYPE_PARAMETER => nodes
+                .filter_map(|node| match node {
+                    idlc_mir::Node::Typedef(t) => Some(t),
+                    _ => None,
+                })
+                .find(|t| t.ident == *word)
+                .map_or_else(
+                    || format!("```idl\ntypedef {word}\n```"),
+                    |t| {
+                        let count = match t.count.get() {
+                            1 => String::new(),
+                            count => format!("[{count}]"),
+                        };
+                        format!(
+                            "```idl\nusing {word} = {}{count}\n```",
+                            type_name(&t.r#type)
+                        )
+                    },
+                ),
+            _ => format!("```idl\nconst {word}\n```"),
+        };
+        Some((description, span))
+    }
+
+    /// Completes type names: builtins and the declarations visible from `path`.
+    pub(crate) fn completion(&self, path: &Path) -> Vec<CompletionItem> {
+        let mut items: Vec<_> = BUILTIN_TYPES
+            .iter()
+            .map(|name| CompletionItem {
+                label: (*name).to_string(),
+                kind: Some(CompletionItemKind::KEYWORD),
+                ..CompletionItem::default()
+            })
+            .collect();
+        for definition in definitions(&self.store(path)) {
+            let kind = match definition.kind {
+                SymbolKind::STRUCT => CompletionItemKind::STRUCT,
+                SymbolKind::ENUM => CompletionItemKind::ENUM,
+                SymbolKind::INTERFACE => CompletionItemKind::INTERFACE,
+                SymbolKind::TYPE_PARAMETER => CompletionItemKind::TYPE_PARAMETER,
+                _ => continue,
+            };
+            if items.iter().any(|item| item.label == definition.name) {
+                continue;
+            }
+            items.push(CompletionItem {
+                label: definition.name,
+                kind: Some(kind),
+                detail: definition
+                    .file
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned()),
+                ..CompletionItem::default()
+            });
+        }
+        items
+    }
+
+    /// Outlines the declarations of `path`, empty if it doesn't parse.
+    pub(crate) fn symbols(&self, path: &Path) -> Vec<DocumentSymbol> {
+        let Some(text) = self.text(path) else {
+            return Vec::new();
+        };
+        let ast = idlc_ast::from_string(
+            path.to_path_buf(),
+            &text,
+            self.config.allow_undefined_behavior,
+        );
+        let _ = idlc_errors::take_diagnostics();
+        let Ok(ast) = ast else {
+            return Vec::new();
+        };
+
+        let symbol = |ident: &idlc_ast::Ident, kind, children: Vec<DocumentSymbol>| {
+            let range = position::range(&text, ident.span.into());
+            #[allow(deprecated)]
+            DocumentSymbol {
+                name: ident.to_string(),
+                detail: None,
+                kind,
+                tags: None,
+                deprecated: None,
+                range,
+                selection_range: range,
+                children: (!children.is_empty()).then_some(children),
+            }
+        };
+        let enumeration = |e: &idlc_ast::Enum| {
+            let variants = e
+                .variants
+                .iter()
+                .map(|v| symbol(&v.ident, SymbolKind::ENUM_MEMBER, Vec::new()))
+                .collect();
+            symbol(&e.ident, SymbolKind::ENUM, variants)
+        };
+
+        ast.nodes
+            .iter()
+            .filter_map(|node| match node.as_ref() {
+                Node::Include(_) => None,
+                Node::Const(c) => Some(symbol(&c.ident, SymbolKind::CONSTANT, Vec::new())),
+                Node::Struct(s) => {
+                    let fields = s
+                        .fields
+                        .iter()
+                        .map(|f| symbol(&f.ident, SymbolKind::FIELD, Vec::new()))
+                        .collect();
+                    Some(symbol(&s.ident, SymbolKind::STRUCT, fields))
+                }
+                Node::Enum(e) => Some(enumeration(e)),
+                Node::Typedef(t) => Some(symbol(&t.ident, SymbolKind::TYPE_PARAMETER, Vec::new())),
+                Node::Interface(i) => {
+                    let members = i
+                        .nodes
+                        .iter()
+                        .map(|node| match node {
+                            InterfaceNode::Const(c) => {
+                                symbol(&c.ident, SymbolKind::CONSTANT, Vec::new())
+                            }
+                            InterfaceNode::Function(f) => {
+                                symbol(&f.ident, SymbolKind::METHOD, Vec::new())
+                            }
+                            InterfaceNode::Error(e) => {
+                                symbol(&e.ident, SymbolKind::CONSTANT, Vec::new())
+                            }
+                            InterfaceNode::Enum(e) => enumeration(e),
+                        })
+                        .collect();
+                    Some(symbol(&i.ident, SymbolKind::INTERFACE, members))
+                }
+            })
+            .collect()
+    }
+}
+
+/// Every declaration of the files in `store`, top-level ones first.
+fn definitions(store: &IDLStore) -> Vec<Definition> {
+    let mut top_level = Vec::new();
+    let mut scoped = Vec::new();
+    for ast in store.asts() {
+        let define = |definitions: &mut Vec<Definition>, ident: &idlc_ast::Ident, kind| {
+            definitions.push(Definition {
+                name: ident.to_string(),
+                kind,
+                file: ast.tag.clone(),
+                span: ident.span,
+            });
+        };
+        for node in &ast.nodes {
+            match node.as_ref() {
+                Node::Include(_) => {}
+                Node::Const(c) => define(&mut top_level, &c.ident, SymbolKind::CONSTANT),
+                Node::Struct(s) => define(&mut top_level, &s.ident, SymbolKind::STRUCT),
+                Node::Enum(e) => define(&mut top_level, &e.ident, SymbolKind::ENUM),
+                Node::Typedef(t) => define(&mut top_level, &t.ident, SymbolKind::TYPE_PARAMETER),
+                Node::Interface(i) => {
+                    define(&mut top_level, &i.ident, SymbolKind::INTERFACE);
+                    for node in &i.nodes {
+                        match node {
+                            InterfaceNode::Const(c) => {
+                                define(&mut scoped, &c.ident, SymbolKind::CONSTANT);
+                            }
+                            InterfaceNode::Enum(e) => {
+                                define(&mut scoped, &e.ident, SymbolKind::ENUM);
+                            }
+                            InterfaceNode::Function(_) | InterfaceNode::Error(_) => {}
+                        }
+                    }
+                }
+            }
+        }
+    }
+    top_level.append(&mut scoped);
+    top_level
+}
+
+/// IDL spelling of a resolved type.
+fn type_name(ty: &idlc_mir::Type) -> String {
+    match ty {
+        idlc_mir::Type::UntypedBuffer => "buffer".to_string(),
+        idlc_mir::Type::String(None) => "string".to_string(),
+        idlc_mir::Type::String(Some(bound)) => format!("string<{bound}>"),
+        idlc_mir::Type::Primitive(p) => p.to_string(),
+        idlc_mir::Type::Struct(s) => s.as_ref().ident.to_string(),
+        idlc_mir::Type::Enum(e) => e.ident.to_string(),
+        idlc_mir::Type::Interface(i) => i.clone().unwrap_or_else(|| "interface".to_string()),
+    }
+}
+
+fn lookup(definitions: Vec<Definition>, name: &str) -> Option<Definition> {
+    definitions.into_iter().find(|d| d.name == name)
+}
+
+fn interfaces(mir: &Mir) -> impl Iterator<Item = &idlc_mir::Interface> {
+    mir.nodes.iter().filter_map(|node| match node {
+        idlc_mir::Node::Interface(i) => Some(i),
+        _ => None,
+    })
+}
+
+fn describe_method(iface: &idlc_mir::Interface, function: &idlc_mir::Function) -> String {
+    let counts = Counter::new(function);
+    let mut description = format!(
+        "```idl\nmethod {}::{}\n```\nop-code: {}\n\n\
+         input buffers: {}, output buffers: {}, input objects: {}, output objects: {}",
+        iface.ident,
+        function.ident,
+        function.id,
+        counts.input_buffers,
+        counts.output_buffers,
+        counts.input_objects,
+        counts.output_objects,
+    );
+    if let Some(doc) = &function.doc {
+        description.push_str("\n\n---\n");
+        description.push_str(doc);
+    }
+    description
+}
+
+fn convert(diagnostic: &Diagnostic, file: &Path, text: &str) -> lsp_types::Diagnostic {
+    let primary = diagnostic
+        .labels
+        .iter()
+        .find(|label| label.primary)
+        .or_else(|| diagnostic.labels.first());
+    let range = primary.map_or_else(lsp_types::Range::default, |label| {
+        position::range(text, label.range.clone())
+    });
+
+    let mut message = diagnostic.message.clone();
+    if let Some(label) = primary.filter(|label| !label.message.is_empty()) {
+        message.push_str(&format!(": {}", label.message));
+    }
+    for note in &diagnostic.notes {
+        message.push_str(&format!("\nnote: {note}"));
+    }
+    let related: Vec<_> = Url::from_file_path(file)
+        .ok()
+        .map(|uri| {
+            diagnostic
+                .labels
+                .iter()
+                .filter(|label| !label.primary)
+                .map(|label| DiagnosticRelatedInformation {
+                    location: Location::new(
+                        uri.clone(),
+                        position::range(text, label.range.clone()),
+                    ),
+                    message: label.message.clone(),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    lsp_types::Diagnostic {
+        range,
+        severity: Some(match diagnostic.severity {
+            Severity::Error => DiagnosticSeverity::ERROR,
+            Severity::Warning => DiagnosticSeverity::WARNING,
+            Severity::Note => DiagnosticSeverity::INFORMATION,
+        }),
+        code: Some(NumberOrString::String(diagnostic.code.to_string())),
+        source: Some("idlc".to_string()),
+        message,
+        related_information: (!related.is_empty()).then_some(related),
+        ..lsp_types::Diagnostic::default()
+    }
+}
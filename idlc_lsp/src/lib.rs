@@ -0,0 +1,278 @@
+// Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Language server for Mink IDL files.
+//!
+//! Diagnostics are published when a document is opened or saved, and the
+//! server answers go-to-definition, hover, completion and document symbol
+//! requests. Open documents are read from the editor, every other file from
+//! the disk.
+//!
+//! Include paths are passed on the command line (`-I <DIR>`) or as the
+//! `includePaths` initialization option, relative ones being resolved against
+//! the root of the workspace.
+
+mod analysis;
+mod position;
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use lsp_server::{Connection, Message, Notification, Request, Response};
+use lsp_types::notification::{
+    DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, DidSaveTextDocument,
+    Notification as _, PublishDiagnostics,
+};
+use lsp_types::request::{
+    Completion, DocumentSymbolRequest, GotoDefinition, HoverRequest, Request as _,
+};
+use lsp_types::{
+    CompletionOptions, DocumentSymbolResponse, GotoDefinitionResponse, Hover, HoverContents,
+    HoverProviderCapability, InitializeParams, MarkupContent, MarkupKind, OneOf,
+    PublishDiagnosticsParams, SaveOptions, ServerCapabilities, ServerInfo,
+    TextDocumentPositionParams, TextDocumentSyncCapability, TextDocumentSyncKind,
+    TextDocumentSyncOptions, TextDocumentSyncSaveOptions, Url,
+};
+
+pub type Error = Box<dyn std::error::Error + Send + Sync>;
+
+#[derive(Debug, Clone, Default)]
+pub struct Config {
+    /// Directories includes are looked up in, besides the directory of the
+    /// including file.
+    pub includes: Vec<PathBuf>,
+    /// Allow integer widths to overflow, see `idlc --help`.
+    pub allow_undefined_behavior: bool,
+}
+
+struct Server {
+    config: Config,
+    /// Contents of the open documents, by canonical path.
+    documents: HashMap<PathBuf, String>,
+    /// Files diagnostics were published for, by the document they came from.
+    published: HashMap<PathBuf, Vec<PathBuf>>,
+}
+
+/// Serves `connection` until the client shuts the server down.
+pub fn run(connection: &Connection, mut config: Config) -> Result<(), Error> {
+    let capabilities = ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Options(
+            TextDocumentSyncOptions {
+                open_close: Some(true),
+                change: Some(TextDocumentSyncKind::FULL),
+                save: Some(TextDocumentSyncSaveOptions::SaveOptions(SaveOptions {
+                    include_text: Some(false),
+                })),
+                ..TextDocumentSyncOptions::default()
+            },
+        )),
+        definition_provider: Some(OneOf::Left(true)),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        completion_provider: Some(CompletionOptions::default()),
+        document_symbol_provider: Some(OneOf::Left(true)),
+        ..ServerCapabilities::default()
+    };
+    let (id, params) = connection.initialize_start()?;
+    let params: InitializeParams = serde_json::from_value(params)?;
+    connection.initialize_finish(
+        id,
+        serde_json::json!({
+            "capabilities": capabilities,
+            "serverInfo": ServerInfo {
+                name: "idlc-lsp".to_string(),
+                version: Some(env!("CARGO_PKG_VERSION").to_string()),
+            },
+        }),
+    )?;
+
+    #[allow(deprecated)]
+    let root = params.root_uri.and_then(|uri| uri.to_file_path().ok());
+    let extra = params
+        .initialization_options
+        .as_ref()
+        .and_then(|options| options.get("includePaths"))
+        .and_then(|paths| serde_json::from_value::<Vec<PathBuf>>(paths.clone()).ok())
+        .unwrap_or_default();
+    for path in extra {
+        config.includes.push(match &root {
+            Some(root) if path.is_relative() => root.join(path),
+            _ => path,
+        });
+    }
+
+    let mut server = Server {
+        config,
+        documents: HashMap::new(),
+        published: HashMap::new(),
+    };
+    for message in &connection.receiver {
+        match message {
+            Message::Request(request) => {
+                if connection.handle_shutdown(&request)? {
+                    return Ok(());
+                }
+                let response = server.request(request);
+                connection.sender.send(Message::Response(response))?;
+            }
+            Message::Notification(notification) => {
+                for published in server.notification(notification) {
+                    connection.sender.send(Message::Notification(published))?;
+                }
+            }
+            Message::Response(_) => {}
+        }
+    }
+    Ok(())
+}
+
+/// Returns the canonical path of a `file://` URI.
+fn path(uri: &Url) -> Option<PathBuf> {
+    let path = uri.to_file_path().ok()?;
+    Some(path.canonicalize().unwrap_or(path))
+}
+
+impl Server {
+    fn request(&self, request: Request) -> Response {
+        let id = request.id.clone();
+        let result = match request.method.as_str() {
+            GotoDefinition::METHOD => self
+                .position::<GotoDefinition>(request, |p| &p.text_document_position_params)
+                .map(|(path, offset)| {
+                    self.definition(&path, offset)
+                        .map(GotoDefinitionResponse::Scalar)
+                })
+                .and_then(to_value),
+            HoverRequest::METHOD => self
+                .position::<HoverRequest>(request, |p| &p.text_document_position_params)
+                .map(|(path, offset)| {
+                    let text = self.text(&path).unwrap_or_default();
+                    self.hover(&path, offset).map(|(value, span)| Hover {
+                        contents: HoverContents::Markup(MarkupContent {
+                            kind: MarkupKind::Markdown,
+                            value,
+                        }),
+                        range: Some(position::range(&text, span.into())),
+                    })
+                })
+                .and_then(to_value),
+            Completion::METHOD => self
+                .position::<Completion>(request, |p| &p.text_document_position)
+                .map(|(path, _)| self.completion(&path))
+                .and_then(to_value),
+            DocumentSymbolRequest::METHOD => request
+                .extract::<lsp_types::DocumentSymbolParams>(DocumentSymbolRequest::METHOD)
+                .map_err(|e| e.to_string())
+                .and_then(|(_, params)| {
+                    path(&params.text_document.uri).ok_or_else(|| "Not a file URI".to_string())
+                })
+                .map(|path| DocumentSymbolResponse::Nested(self.symbols(&path)))
+                .and_then(to_value),
+            method => {
+                return Response::new_err(
+                    id,
+                    lsp_server::ErrorCode::MethodNotFound as i32,
+                    format!("Unsupported request `{method}`"),
+                )
+            }
+        };
+        match result {
+            Ok(value) => Response::new_ok(id, value),
+            Err(message) => {
+                Response::new_err(id, lsp_server::ErrorCode::InvalidParams as i32, message)
+            }
+        }
+    }
+
+    /// Extracts the document and byte offset targeted by a request.
+    fn position<R: lsp_types::request::Request>(
+        &self,
+        request: Request,
+        target: impl Fn(&R::Params) -> &TextDocumentPositionParams,
+    ) -> Result<(PathBuf, usize), String> {
+        let (_, params) = request
+            .extract::<R::Params>(R::METHOD)
+            .map_err(|e| e.to_string())?;
+        let params = target(&params);
+        let path = path(&params.text_document.uri).ok_or("Not a file URI")?;
+        let text = self.text(&path).unwrap_or_default();
+        Ok((path.clone(), position::offset(&text, params.position)))
+    }
+
+    /// Updates the open documents, returns the diagnostics to publish.
+    fn notification(&mut self, notification: Notification) -> Vec<Notification> {
+        let document = match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => notification
+                .extract::<lsp_types::DidOpenTextDocumentParams>(DidOpenTextDocument::METHOD)
+                .ok()
+                .and_then(|params| {
+                    let path = path(&params.text_document.uri)?;
+                    self.documents
+                        .insert(path.clone(), params.text_document.text);
+                    Some(path)
+                }),
+            DidChangeTextDocument::METHOD => {
+                if let Ok(params) = notification.extract::<lsp_types::DidChangeTextDocumentParams>(
+                    DidChangeTextDocument::METHOD,
+                ) {
+                    if let (Some(path), Some(change)) = (
+                        path(&params.text_document.uri),
+                        params.content_changes.into_iter().last(),
+                    ) {
+                        self.documents.insert(path, change.text);
+                    }
+                }
+                None
+            }
+            DidSaveTextDocument::METHOD => notification
+                .extract::<lsp_types::DidSaveTextDocumentParams>(DidSaveTextDocument::METHOD)
+                .ok()
+                .and_then(|params| path(&params.text_document.uri)),
+            DidCloseTextDocument::METHOD => {
+                let closed = notification
+                    .extract::<lsp_types::DidCloseTextDocumentParams>(DidCloseTextDocument::METHOD)
+                    .ok()
+                    .and_then(|params| path(&params.text_document.uri));
+                if let Some(path) = closed {
+                    self.documents.remove(&path);
+                    let files = self.published.remove(&path).unwrap_or_default();
+                    return files
+                        .iter()
+                        .filter_map(|file| publish(file, Vec::new()))
+                        .collect();
+                }
+                None
+            }
+            _ => None,
+        };
+        document.map_or_else(Vec::new, |document| self.publish(&document))
+    }
+
+    /// Compiles `document` and publishes its diagnostics, clearing the ones
+    /// of the files which don't have any anymore.
+    fn publish(&mut self, document: &Path) -> Vec<Notification> {
+        let mut diagnostics = self.diagnostics(document);
+        let files: Vec<_> = diagnostics.keys().cloned().collect();
+        for stale in self
+            .published
+            .insert(document.to_path_buf(), files)
+            .unwrap_or_default()
+        {
+            diagnostics.entry(stale).or_default();
+        }
+        diagnostics
+            .into_iter()
+            .filter_map(|(file, diagnostics)| publish(&file, diagnostics))
+            .collect()
+    }
+}
+
+fn publish(file: &Path, diagnostics: Vec<lsp_types::Diagnostic>) -> Option<Notification> {
+    Some(Notification::new(
+        PublishDiagnostics::METHOD.to_string(),
+        PublishDiagnosticsParams::new(Url::from_file_path(file).ok()?, diagnostics, None),
+    ))
+}
+
+fn to_value(value: impl serde::Serialize) -> Result<serde_json::Value, String> {
+    serde_json::to_value(value).map_err(|e| e.to_string())
+}
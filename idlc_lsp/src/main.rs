@@ -0,0 +1,32 @@
+// Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
+// SPDX-License-Identifier: BSD-3-Clause
+
+use clap::Parser;
+
+/// Language server for Mink IDL files, speaking LSP over stdio.
+#[derive(Parser)]
+#[command(version, about)]
+struct Cli {
+    #[arg(short = 'I', long = "include", value_name = "DIR")]
+    /// Add DIR to include path. Can be passed multiple times.
+    include_paths: Vec<std::path::PathBuf>,
+
+    #[arg(long, default_value_t = false)]
+    /// Allow integer widths to overflow, see `idlc --help`.
+    allow_undefined_behavior: bool,
+}
+
+fn main() -> Result<(), idlc_lsp::Error> {
+    let cli = Cli::parse();
+    let (connection, io_threads) = lsp_server::Connection::stdio();
+    idlc_lsp::run(
+        &connection,
+        idlc_lsp::Config {
+            includes: cli.include_paths,
+            allow_undefined_behavior: cli.allow_undefined_behavior,
+        },
+    )?;
+    drop(connection);
+    io_threads.join()?;
+    Ok(())
+}
@@ -0,0 +1,59 @@
+// Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Conversions between byte offsets and LSP positions, whose characters are
+//! counted in UTF-16 code units.
+
+use lsp_types::{Position, Range};
+
+#[must_use]
+pub fn position(text: &str, offset: usize) -> Position {
+    let offset = offset.min(text.len());
+    let line_start = text[..offset].rfind('\n').map_or(0, |i| i + 1);
+    let line = text[..line_start].matches('\n').count();
+    let character: usize = text[line_start..offset].chars().map(char::len_utf16).sum();
+    Position::new(
+        u32::try_from(line).unwrap_or(u32::MAX),
+        u32::try_from(character).unwrap_or(u32::MAX),
+    )
+}
+
+#[must_use]
+pub fn range(text: &str, range: std::ops::Range<usize>) -> Range {
+    Range::new(position(text, range.start), position(text, range.end))
+}
+
+/// Returns the byte offset of `position`, clamped to the end of its line.
+#[must_use]
+pub fn offset(text: &str, position: Position) -> usize {
+    let line_start = if position.line == 0 {
+        0
+    } else {
+        text.match_indices('\n')
+            .nth(position.line as usize - 1)
+            .map_or(text.len(), |(i, _)| i + 1)
+    };
+    let mut units = 0;
+    for (i, c) in text[line_start..].char_indices() {
+        if units >= position.character as usize || c == '\n' {
+            return line_start + i;
+        }
+        units += c.len_utf16();
+    }
+    text.len()
+}
+
+/// Returns the identifier around `offset` along with its byte range.
+#[must_use]
+pub fn word_at(text: &str, offset: usize) -> Option<(&str, std::ops::Range<usize>)> {
+    let is_ident = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    let start = text[..offset]
+        .char_indices()
+        .rev()
+        .find(|(_, c)| !is_ident(*c))
+        .map_or(0, |(i, c)| i + c.len_utf8());
+    let end = text[offset..]
+        .find(|c| !is_ident(c))
+        .map_or(text.len(), |i| offset + i);
+    (start < end).then(|| (&text[start..end], start..end))
+}
@@ -0,0 +1,242 @@
+// Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+
+use serde_json::{json, Value};
+
+const MAIN: &str = "include \"base.idl\"
+
+interface IMain {
+  method get(in S s, out uint32 v);
+  method set(in uint32 v);
+};
+";
+
+fn temp_dir() -> PathBuf {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .expect("clock should be monotonic since epoch")
+        .as_nanos();
+    let dir = std::env::temp_dir().join(format!("idlc-lsp-{}-{nanos}", std::process::id()));
+    std::fs::create_dir_all(dir.join("inc")).expect("temp dir should be creatable");
+    std::fs::write(
+        dir.join("inc/base.idl"),
+        "struct S {\n  uint64 a;\n  uint32 b;\n  uint32 c;\n};\n",
+    )
+    .expect("include should be writable");
+    std::fs::write(dir.join("main.idl"), MAIN).expect("input idl should be writable");
+    dir.canonicalize()
+        .expect("temp dir should be canonicalizable")
+}
+
+fn uri(path: &Path) -> String {
+    format!("file://{}", path.display())
+}
+
+/// Scripted LSP client driving `idlc-lsp` over its stdio.
+struct Client {
+    server: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+    id: u64,
+}
+
+impl Client {
+    fn start(dir: &Path) -> Self {
+        let mut server = Command::new(PathBuf::from(env!("CARGO_BIN_EXE_idlc-lsp")))
+            .args(["-I", "inc"])
+            .current_dir(dir)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .expect("idlc-lsp should execute");
+        let stdin = server.stdin.take().expect("stdin should be piped");
+        let stdout = BufReader::new(server.stdout.take().expect("stdout should be piped"));
+        let mut client = Self {
+            server,
+            stdin,
+            stdout,
+            id: 0,
+        };
+        client.request("initialize", json!({ "capabilities": {} }));
+        client.notify("initialized", json!({}));
+        client
+    }
+
+    fn send(&mut self, message: &Value) {
+        let body = message.to_string();
+        write!(self.stdin, "Content-Length: {}\r\n\r\n{body}", body.len())
+            .expect("server should read stdin");
+        self.stdin.flush().expect("server should read stdin");
+    }
+
+    fn receive(&mut self) -> Value {
+        let mut length = 0;
+        loop {
+            let mut line = String::new();
+            self.stdout
+                .read_line(&mut line)
+                .expect("server should write stdout");
+            let line = line.trim_end();
+            if line.is_empty() {
+                break;
+            }
+            if let Some(value) = line.strip_prefix("Content-Length: ") {
+                length = value.parse().expect("length should be a number");
+            }
+        }
+        let mut body = vec![0; length];
+        self.stdout
+            .read_exact(&mut body)
+            .expect("server should write the whole message");
+        serde_json::from_slice(&body).expect("message should be JSON")
+    }
+
+    fn notify(&mut self, method: &str, params: Value) {
+        self.send(&json!({ "jsonrpc": "2.0", "method": method, "params": params }));
+    }
+
+    /// Returns the result of the request, skipping notifications.
+    fn request(&mut self, method: &str, params: Value) -> Value {
+        self.id += 1;
+        let id = self.id;
+        self.send(&json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }));
+        loop {
+            let message = self.receive();
+            if message["id"] == id {
+                assert!(message.get("error").is_none(), "{message}");
+                return message["result"].clone();
+            }
+        }
+    }
+
+    /// Returns the next diagnostics published for `uri`.
+    fn diagnostics(&mut self, uri: &str) -> Vec<Value> {
+        loop {
+            let message = self.receive();
+            if message["method"] == "textDocument/publishDiagnostics"
+                && message["params"]["uri"] == uri
+            {
+                return message["params"]["diagnostics"]
+                    .as_array()
+                    .expect("diagnostics should be an array")
+                    .clone();
+            }
+        }
+    }
+
+    fn at(&mut self, method: &str, uri: &str, line: u32, character: u32) -> Value {
+        self.request(
+            method,
+            json!({
+                "textDocument": { "uri": uri },
+                "position": { "line": line, "character": character },
+            }),
+        )
+    }
+
+    fn shutdown(mut self) {
+        self.request("shutdown", Value::Null);
+        self.notify("exit", Value::Null);
+        let status = self.server.wait().expect("server should exit");
+        assert!(status.success());
+    }
+}
+
+#[test]
+fn diagnostics_on_save() {
+    let dir = temp_dir();
+    let main = uri(&dir.join("main.idl"));
+    let mut client = Client::start(&dir);
+
+    client.notify(
+        "textDocument/didOpen",
+        json!({ "textDocument": { "uri": main, "languageId": "idl", "version": 1, "text": MAIN } }),
+    );
+    assert_eq!(client.diagnostics(&main), Vec::<Value>::new());
+
+    let broken = MAIN.replace("in uint32 v", "in uint32 v, in uint32 v");
+    std::fs::write(dir.join("main.idl"), &broken).expect("input idl should be writable");
+    client.notify(
+        "textDocument/didChange",
+        json!({
+            "textDocument": { "uri": main, "version": 2 },
+            "contentChanges": [{ "text": broken }],
+        }),
+    );
+    client.notify(
+        "textDocument/didSave",
+        json!({ "textDocument": { "uri": main } }),
+    );
+    let diagnostics = client.diagnostics(&main);
+    assert_eq!(diagnostics.len(), 1, "{diagnostics:?}");
+    assert_eq!(diagnostics[0]["code"], idlc_errors::codes::DUPLICATE_PARAM);
+    assert_eq!(diagnostics[0]["range"]["start"]["line"], 4);
+
+    client.shutdown();
+}
+
+#[test]
+fn navigation() {
+    let dir = temp_dir();
+    let main = uri(&dir.join("main.idl"));
+    let mut client = Client::start(&dir);
+    client.notify(
+        "textDocument/didOpen",
+        json!({ "textDocument": { "uri": main, "languageId": "idl", "version": 1, "text": MAIN } }),
+    );
+
+    // `S` in `in S s`.
+    let definition = client.at("textDocument/definition", &main, 3, 16);
+    assert_eq!(definition["uri"], uri(&dir.join("inc/base.idl")));
+    assert_eq!(
+        definition["range"]["start"],
+        json!({ "line": 0, "character": 7 })
+    );
+
+    let hover = client.at("textDocument/hover", &main, 3, 16);
+    let hover = hover["contents"]["value"]
+        .as_str()
+        .expect("hover is markdown");
+    assert!(
+        hover.contains("size: 16 bytes, alignment: 8 bytes"),
+        "{hover}"
+    );
+
+    let hover = client.at("textDocument/hover", &main, 4, 10);
+    let hover = hover["contents"]["value"]
+        .as_str()
+        .expect("hover is markdown");
+    assert!(hover.contains("IMain::set"), "{hover}");
+    assert!(hover.contains("op-code: 1"), "{hover}");
+    assert!(hover.contains("input buffers: 1"), "{hover}");
+
+    let completion = client.at("textDocument/completion", &main, 4, 12);
+    let labels: Vec<_> = completion
+        .as_array()
+        .expect("completion should be a list")
+        .iter()
+        .map(|item| item["label"].as_str().expect("items have a label"))
+        .collect();
+    assert!(labels.contains(&"S"), "{labels:?}");
+    assert!(labels.contains(&"IMain"), "{labels:?}");
+    assert!(labels.contains(&"uint32"), "{labels:?}");
+
+    let symbols = client.request(
+        "textDocument/documentSymbol",
+        json!({ "textDocument": { "uri": main } }),
+    );
+    assert_eq!(symbols[0]["name"], "IMain");
+    let methods: Vec<_> = symbols[0]["children"]
+        .as_array()
+        .expect("interfaces have children")
+        .iter()
+        .map(|method| method["name"].clone())
+        .collect();
+    assert_eq!(methods, [json!("get"), json!("set")]);
+
+    client.shutdown();
+}
@@ -0,0 +1,511 @@
+// Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! ABI compatibility between two revisions of the same IDL.
+//!
+//! Both revisions are expected to be lowered into a [`Mir`], the changes are
+//! reported in the order of the old revision followed by the additions of the
+//! new one.
+
+use crate::{Count, Enum, Interface, InterfaceNode, Mir, Node, Param, ParamTypeIn, ParamTypeOut};
+use crate::{StructInner, Type};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Compatibility {
+    /// Existing clients and implementations keep working.
+    Compatible,
+    /// Existing clients or implementations have to be rebuilt.
+    Breaking,
+}
+
+impl std::fmt::Display for Compatibility {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Compatible => "compatible",
+            Self::Breaking => "breaking",
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+    pub compatibility: Compatibility,
+    pub message: String,
+}
+
+impl Change {
+    #[inline]
+    #[must_use]
+    pub const fn is_breaking(&self) -> bool {
+        matches!(self.compatibility, Compatibility::Breaking)
+    }
+}
+
+impl std::fmt::Display for Change {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.compatibility, self.message)
+    }
+}
+
+/// Reports every change from `old` to `new` that is visible on the wire.
+#[must_use]
+pub fn compare(old: &Mir, new: &Mir) -> Vec<Change> {
+    let old_ifaces = interfaces(old);
+    let new_ifaces = interfaces(new);
+    let mut changes = Changes {
+        changes: Vec::new(),
+        local: old_ifaces
+            .iter()
+            .filter(|i| new_ifaces.iter().any(|n| n.ident == i.ident))
+            .map(|i| i.ident.to_string())
+            .collect(),
+    };
+
+    let old_structs = structs(old);
+    let new_structs = structs(new);
+    for (ident, old_struct) in &old_structs {
+        match new_structs.iter().find(|(i, _)| i == ident) {
+            Some((_, new_struct)) => changes.compare_struct(old_struct, new_struct),
+            None if is_declared(old, ident) => {
+                changes.breaking(format!("struct `{ident}` was removed"));
+            }
+            None => (),
+        }
+    }
+    for (ident, _) in &new_structs {
+        if is_declared(new, ident) && !old_structs.iter().any(|(i, _)| i == ident) {
+            changes.compatible(format!("struct `{ident}` was added"));
+        }
+    }
+
+    changes.compare_enums(&enums(&old.nodes), &enums(&new.nodes), "");
+
+    for old_iface in &old_ifaces {
+        match new_ifaces.iter().find(|i| i.ident == old_iface.ident) {
+            Some(new_iface) => changes.compare_interface(old_iface, new_iface),
+            None => changes.breaking(format!("interface `{}` was removed", old_iface.ident)),
+        }
+    }
+    for new_iface in &new_ifaces {
+        if !old_ifaces.iter().any(|i| i.ident == new_iface.ident) {
+            changes.compatible(format!("interface `{}` was added", new_iface.ident));
+        }
+    }
+
+    changes.changes
+}
+
+struct Changes {
+    changes: Vec<Change>,
+    /// Interfaces declared in both revisions, they're compared on their own
+    /// rather than as the base of another interface.
+    local: Vec<String>,
+}
+
+impl Changes {
+    fn breaking(&mut self, message: String) {
+        self.changes.push(Change {
+            compatibility: Compatibility::Breaking,
+            message,
+        });
+    }
+
+    fn compatible(&mut self, message: String) {
+        self.changes.push(Change {
+            compatibility: Compatibility::Compatible,
+            message,
+        });
+    }
+
+    fn compare_struct(&mut self, old: &StructInner, new: &StructInner) {
+        let ident = &old.ident;
+        if old.size() != new.size() {
+            self.breaking(format!(
+                "struct `{ident}` size changed from {} to {} bytes",
+                old.size(),
+                new.size()
+            ));
+        }
+        if old.align() != new.align() {
+            self.breaking(format!(
+                "struct `{ident}` alignment changed from {} to {} bytes",
+                old.align(),
+                new.align()
+            ));
+        }
+
+        let old_fields = fields(old);
+        let new_fields = fields(new);
+        for (name, (old_offset, old_ty)) in &old_fields {
+            let Some((_, (new_offset, new_ty))) = new_fields.iter().find(|(n, _)| n == name) else {
+                self.breaking(format!("field `{ident}::{name}` was removed"));
+                continue;
+            };
+            if old_offset != new_offset {
+                self.breaking(format!(
+                    "field `{ident}::{name}` offset changed from {old_offset} to {new_offset}"
+                ));
+            }
+            if old_ty != new_ty {
+                self.breaking(format!(
+                    "field `{ident}::{name}` type changed from `{old_ty}` to `{new_ty}`"
+                ));
+            }
+        }
+        for (name, _) in &new_fields {
+            if !old_fields.iter().any(|(n, _)| n == name) {
+                self.breaking(format!("field `{ident}::{name}` was added"));
+            }
+        }
+    }
+
+    fn compare_enums(&mut self, old: &[&Enum], new: &[&Enum], scope: &str) {
+        for old_enum in old {
+            match new.iter().find(|e| e.ident == old_enum.ident) {
+                Some(new_enum) => self.compare_enum(old_enum, new_enum, scope),
+                None => self.breaking(format!("enum `{scope}{}` was removed", old_enum.ident)),
+            }
+        }
+        for new_enum in new {
+            if !old.iter().any(|e| e.ident == new_enum.ident) {
+                self.compatible(format!("enum `{scope}{}` was added", new_enum.ident));
+            }
+        }
+    }
+
+    fn compare_enum(&mut self, old: &Enum, new: &Enum, scope: &str) {
+        let ident = &old.ident;
+        if old.r#type != new.r#type {
+            self.breaking(format!(
+                "enum `{scope}{ident}` type changed from `{}` to `{}`",
+                old.r#type, new.r#type
+            ));
+        }
+        for variant in &old.variants {
+            let name = &variant.ident;
+            match new.variants.iter().find(|v| v.ident == *name) {
+                Some(v) if v.value != variant.value => self.breaking(format!(
+                    "enum variant `{scope}{ident}::{name}` value changed from {} to {}",
+                    variant.value, v.value
+                )),
+                Some(_) => (),
+                None => self.breaking(format!("enum variant `{scope}{ident}::{name}` was removed")),
+            }
+        }
+        for variant in &new.variants {
+            if !old.variants.iter().any(|v| v.ident == variant.ident) {
+                self.compatible(format!(
+                    "enum variant `{scope}{ident}::{}` was added",
+                    variant.ident
+                ));
+            }
+        }
+    }
+
+    fn compare_interface(&mut self, old: &Interface, new: &Interface) {
+        let ident = &old.ident;
+        let old_base = old.base.as_ref().map(|b| b.ident.to_string());
+        let new_base = new.base.as_ref().map(|b| b.ident.to_string());
+        if old_base != new_base {
+            self.breaking(format!(
+                "interface `{ident}` base changed from `{}` to `{}`",
+                old_base.as_deref().unwrap_or("Object"),
+                new_base.as_deref().unwrap_or("Object")
+            ));
+        }
+
+        // Op-codes and error codes span the whole hierarchy, bases declared
+        // in other files are compared as part of the interface.
+        let local = self.local.clone();
+        let owned = |from: &String| *from == ident.ident || !local.contains(from);
+        let mut old_functions = functions(old);
+        let mut new_functions = functions(new);
+        old_functions.retain(|(from, _)| owned(from));
+        new_functions.retain(|(from, _)| owned(from));
+        for (from, old_fn) in &old_functions {
+            let name = &old_fn.ident;
+            let Some((_, new_fn)) = new_functions.iter().find(|(_, f)| f.ident == *name) else {
+                self.breaking(format!("method `{from}::{name}` was removed"));
+                continue;
+            };
+            if old_fn.id != new_fn.id {
+                self.breaking(format!(
+                    "method `{from}::{name}` op-code changed from {} to {}",
+                    old_fn.id, new_fn.id
+                ));
+            }
+            if let (Some(old_ver), Some(new_ver)) = (old_fn.get_version(), new_fn.get_version()) {
+                if new_ver < old_ver {
+                    self.breaking(format!(
+                        "method `{from}::{name}` version went back from {old_ver} to {new_ver}"
+                    ));
+                }
+            }
+            self.compare_params(&format!("{from}::{name}"), &old_fn.params, &new_fn.params);
+        }
+        for (from, new_fn) in &new_functions {
+            if !old_functions.iter().any(|(_, f)| f.ident == new_fn.ident) {
+                self.compatible(format!(
+                    "method `{from}::{}` was added with op-code {}",
+                    new_fn.ident, new_fn.id
+                ));
+            }
+        }
+
+        let mut old_errors = errors(old);
+        let mut new_errors = errors(new);
+        old_errors.retain(|(from, _, _)| owned(from));
+        new_errors.retain(|(from, _, _)| owned(from));
+        for (from, name, value) in &old_errors {
+            match new_errors.iter().find(|(_, n, _)| n == name) {
+                Some((_, _, v)) if v != value => self.breaking(format!(
+                    "error `{from}::{name}` value changed from {value} to {v}"
+                )),
+                Some(_) => (),
+                None => self.breaking(format!("error `{from}::{name}` was removed")),
+            }
+        }
+        for (from, name, value) in &new_errors {
+            if !old_errors.iter().any(|(_, n, _)| n == name) {
+                self.compatible(format!(
+                    "error `{from}::{name}` was added with value {value}"
+                ));
+            }
+        }
+
+        self.compare_enums(
+            &scoped_enums(&old.nodes),
+            &scoped_enums(&new.nodes),
+            &format!("{ident}::"),
+        );
+    }
+
+    fn compare_params(&mut self, function: &str, old: &[Param], new: &[Param]) {
+        if old.len() != new.len() {
+            self.breaking(format!(
+                "method `{function}` parameter count changed from {} to {}",
+                old.len(),
+                new.len()
+            ));
+            return;
+        }
+        for (old, new) in old.iter().zip(new) {
+            let name = old.ident();
+            if old.is_input() != new.is_input() {
+                self.breaking(format!(
+                    "parameter `{name}` of method `{function}` direction changed from `{}` to `{}`",
+                    direction(old),
+                    direction(new)
+                ));
+            }
+            let (old_ty, new_ty) = (param_type(old), param_type(new));
+            if old_ty != new_ty {
+                self.breaking(format!(
+                    "parameter `{name}` of method `{function}` type changed from `{old_ty}` to `{new_ty}`"
+                ));
+            }
+            match (old.max_len(), new.max_len()) {
+                (old_len, new_len) if old_len == new_len => {}
+                // Clients may allocate less than the new maximum.
+                (Some(old_len), Some(new_len)) if !old.is_input() && new_len > old_len => self
+                    .breaking(format!(
+                        "parameter `{name}` of method `{function}` maximum length changed from {old_len} to {new_len}"
+                    )),
+                (old_len, new_len) if !old.is_input() => self.compatible(format!(
+                    "parameter `{name}` of method `{function}` maximum length changed from {} to {}",
+                    max_len(old_len),
+                    max_len(new_len)
+                )),
+                // Clients may already send more than the new maximum.
+                (None, Some(new_len)) => self.breaking(format!(
+                    "parameter `{name}` of method `{function}` maximum length changed from unbounded to {new_len}"
+                )),
+                (Some(old_len), Some(new_len)) if new_len < old_len => self.breaking(format!(
+                    "parameter `{name}` of method `{function}` maximum length changed from {old_len} to {new_len}"
+                )),
+                (old_len, new_len) => self.compatible(format!(
+                    "parameter `{name}` of method `{function}` maximum length changed from {} to {}",
+                    max_len(old_len),
+                    max_len(new_len)
+                )),
+            }
+            if name != new.ident() {
+                self.compatible(format!(
+                    "parameter `{name}` of method `{function}` was renamed to `{}`",
+                    new.ident()
+                ));
+            }
+        }
+    }
+}
+
+fn enums(nodes: &[Node]) -> Vec<&Enum> {
+    nodes
+        .iter()
+        .filter_map(|node| match node {
+            Node::Enum(e) => Some(e),
+            _ => None,
+        })
+        .collect()
+}
+
+fn scoped_enums(nodes: &[InterfaceNode]) -> Vec<&Enum> {
+    nodes
+        .iter()
+        .filter_map(|node| match node {
+            InterfaceNode::Enum(e) => Some(e),
+            _ => None,
+        })
+        .collect()
+}
+
+fn interfaces(mir: &Mir) -> Vec<&Interface> {
+    mir.nodes
+        .iter()
+        .filter_map(|node| match node {
+            Node::Interface(i) => Some(i),
+            _ => None,
+        })
+        .collect()
+}
+
+fn is_declared(mir: &Mir, ident: &str) -> bool {
+    mir.nodes
+        .iter()
+        .any(|node| matches!(node, Node::Struct(s) if s.as_ref().ident == *ident))
+}
+
+/// Every struct reachable from `mir`, including the ones declared in includes
+/// and used as parameters or fields.
+fn structs(mir: &Mir) -> Vec<(String, &StructInner)> {
+    fn visit<'a>(s: &'a StructInner, out: &mut Vec<(String, &'a StructInner)>) {
+        if out.iter().any(|(ident, _)| *ident == s.ident.ident) {
+            return;
+        }
+        out.push((s.ident.to_string(), s));
+        for field in &s.fields {
+            if let Type::Struct(s) = &field.val.0 {
+                visit(s.as_ref(), out);
+            }
+        }
+    }
+
+    let mut out = Vec::new();
+    for node in &mir.nodes {
+        match node {
+            Node::Struct(s) => visit(s.as_ref(), &mut out),
+            Node::Interface(i) => {
+                for (_, f) in functions(i) {
+                    for param in &f.params {
+                        if let Type::Struct(s) = param.r#type() {
+                            visit(s.as_ref(), &mut out);
+                        }
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+    out
+}
+
+/// Fields of `s` along with their offset and type.
+fn fields(s: &StructInner) -> Vec<(String, (usize, String))> {
+    let mut offset = 0;
+    s.fields
+        .iter()
+        .map(|field| {
+            let entry = (
+                field.ident.to_string(),
+                (offset, array(&field.val.0, Some(field.val.1))),
+            );
+            offset += field.size();
+            entry
+        })
+        .collect()
+}
+
+fn functions(iface: &Interface) -> Vec<(String, &crate::Function)> {
+    iface
+        .iter()
+        .flat_map(|i| {
+            i.nodes.iter().filter_map(move |node| match node {
+                InterfaceNode::Function(f) => Some((i.ident.to_string(), f)),
+                _ => None,
+            })
+        })
+        .collect()
+}
+
+fn errors(iface: &Interface) -> Vec<(String, String, i32)> {
+    iface
+        .iter()
+        .flat_map(|i| {
+            i.nodes.iter().filter_map(move |node| match node {
+                InterfaceNode::Error(e) => {
+                    Some((i.ident.to_string(), e.ident.to_string(), e.value))
+                }
+                _ => None,
+            })
+        })
+        .collect()
+}
+
+const fn direction(param: &Param) -> &'static str {
+    if param.is_input() {
+        "in"
+    } else {
+        "out"
+    }
+}
+
+fn param_type(param: &Param) -> String {
+    match param {
+        Param::In {
+            r#type: ParamTypeIn::Array(ty, cnt),
+            ..
+        }
+        | Param::Out {
+            r#type: ParamTypeOut::Array(ty, cnt),
+            ..
+        } => match cnt {
+            Some(cnt) => format!("{}[{cnt}]", r#type(ty)),
+            None => format!("{}[]", r#type(ty)),
+        },
+        Param::In {
+            r#type: ParamTypeIn::Value(ty),
+            ..
+        }
+        | Param::Out {
+            r#type: ParamTypeOut::Reference(ty),
+            ..
+        } => r#type(ty),
+    }
+}
+
+fn max_len(max_len: Option<Count>) -> String {
+    max_len.map_or_else(|| "unbounded".to_string(), |max_len| max_len.to_string())
+}
+
+fn array(ty: &Type, cnt: Option<Count>) -> String {
+    match cnt {
+        Some(cnt) if cnt.get() != 1 => format!("{}[{cnt}]", r#type(ty)),
+        _ => r#type(ty),
+    }
+}
+
+fn r#type(ty: &Type) -> String {
+    match ty {
+        Type::UntypedBuffer => "buffer".to_string(),
+        // Bounds are compared as maximum lengths.
+        Type::String(_) => "string".to_string(),
+        Type::Primitive(p) => p.to_string(),
+        Type::Struct(s) => s.as_ref().ident.to_string(),
+        Type::Enum(e) => match &e.scope {
+            Some(scope) => format!("{scope}::{}", e.ident),
+            None => e.ident.to_string(),
+        },
+        Type::Interface(Some(i)) => i.clone(),
+        Type::Interface(None) => "interface".to_string(),
+    }
+}
@@ -4,6 +4,7 @@
 // Name of function which gets added to all auto-generated outputs
 pub const VERSION_FUNC_NAME: &str = "api_version";
 
+pub mod compat;
 pub mod mir;
 pub mod named_version;
 
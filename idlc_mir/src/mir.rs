@@ -26,19 +26,21 @@
 //! changes should not require codegen changes
 use crate::named_version::NamedVersion;
 use idlc_ast::Ast;
-pub use idlc_ast::{APIVersion, Ident, DEFAULT_VERSION};
+pub use idlc_ast::{APIVersion, Deprecation, Ident, DEFAULT_VERSION};
 use idlc_ast_passes::idl_store::IDLStore;
 
 use std::collections::{HashMap, VecDeque};
 use std::path::{Path, PathBuf};
 use std::rc::Rc;
 
+use serde::{Deserialize, Serialize};
+
 /// Code from 0 to 9 are reserved for generic IDL-generated code, so starting from 10.
-const ERROR_CODE_START: i32 = 10;
+pub const ERROR_CODE_START: i32 = 10;
 /// User defined method op-codes can range from 0 - 0x3FFF (inclusive) as defined by the Mink specification.
-const MAX_OP_CODE: u32 = 0x3fff;
+pub const MAX_OP_CODE: u32 = 0x3fff;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 /// Represents the Mink specifications over the source AST.
 pub struct Mir {
     /// Tag denoting the AST name.
@@ -49,15 +51,17 @@ pub struct Mir {
     pub nodes: Vec<Node>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Node {
     Include(PathBuf),
     Const(Const),
     Struct(Struct),
+    Enum(Enum),
+    Typedef(Typedef),
     Interface(Interface),
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Primitive {
     Uint8,
     Uint16,
@@ -69,6 +73,8 @@ pub enum Primitive {
     Int64,
     Float32,
     Float64,
+    Bool,
+    Char,
 }
 
 impl Primitive {
@@ -76,7 +82,7 @@ impl Primitive {
     #[must_use]
     pub const fn size(self) -> usize {
         match self {
-            Self::Uint8 | Self::Int8 => 1,
+            Self::Uint8 | Self::Int8 | Self::Bool | Self::Char => 1,
             Self::Uint16 | Self::Int16 => 2,
             Self::Uint32 | Self::Int32 | Self::Float32 => 4,
             Self::Uint64 | Self::Int64 | Self::Float64 => 8,
@@ -89,6 +95,25 @@ impl Primitive {
     }
 }
 
+impl std::fmt::Display for Primitive {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Uint8 => "uint8",
+            Self::Uint16 => "uint16",
+            Self::Uint32 => "uint32",
+            Self::Uint64 => "uint64",
+            Self::Int8 => "int8",
+            Self::Int16 => "int16",
+            Self::Int32 => "int32",
+            Self::Int64 => "int64",
+            Self::Float32 => "float32",
+            Self::Float64 => "float64",
+            Self::Bool => "bool",
+            Self::Char => "char",
+        })
+    }
+}
+
 impl Ord for Primitive {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
         self.size().cmp(&other.size())
@@ -100,33 +125,137 @@ impl PartialOrd for Primitive {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Const {
+    pub doc: Option<String>,
     pub ident: Ident,
     pub r#type: Primitive,
     pub value: String,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Enum {
+    pub doc: Option<String>,
+    pub ident: Ident,
+    /// Underlying integer type, which is also the wire format of the enum.
+    pub r#type: Primitive,
+    pub variants: Vec<EnumVariant>,
+    /// Interface the enum is declared in, if it's not declared at the top-level.
+    pub scope: Option<String>,
+    pub origin: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct EnumVariant {
+    pub ident: Ident,
+    pub value: i128,
+}
+
+impl Enum {
+    fn new(r#enum: &idlc_ast::Enum, scope: Option<String>, origin: Option<PathBuf>) -> Self {
+        let values = r#enum
+            .values()
+            .unwrap_or_else(|v| panic!("ICE: `{}` wasn't verified", v.ident));
+        Self {
+            doc: doc(&r#enum.doc),
+            ident: r#enum.ident.clone(),
+            r#type: Primitive::from(&r#enum.r#type),
+            variants: r#enum
+                .variants
+                .iter()
+                .zip(values)
+                .map(|(variant, value)| EnumVariant {
+                    ident: variant.ident.clone(),
+                    value,
+                })
+                .collect(),
+            scope,
+            origin,
+        }
+    }
+}
+
+/// Alias of a type, which is sent over the wire as the aliased type.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Typedef {
+    pub doc: Option<String>,
+    pub ident: Ident,
+    /// Aliased type, with the typedefs it names resolved.
+    pub r#type: Type,
+    /// Number of elements of the alias, `1` unless it aliases an array.
+    pub count: Count,
+    /// Typedef named by the declaration, e.g. `Sha256` of
+    /// `typedef Sha256[4] Hashes;`.
+    pub alias: Option<Alias>,
+}
+
+/// Typedef naming the type of a field, parameter or typedef, which the
+/// backends print in place of the resolved type it's carried along with.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Alias {
+    pub ident: Ident,
+    /// Elements of the resolved type the typedef stands for, the count of an
+    /// use is a multiple of it.
+    pub count: Count,
+    /// File declaring the typedef.
+    pub origin: PathBuf,
+}
+
+impl Alias {
+    /// Typedef named by `ty`, if any. `scopes` are the interfaces whose enums
+    /// shadow the top-level declarations.
+    fn new(ty: &idlc_ast::Type, idl_store: &IDLStore, scopes: &[String]) -> Option<Self> {
+        let idlc_ast::Type::Custom(custom) = ty else {
+            return None;
+        };
+        if scopes.iter().any(|scope| {
+            idl_store
+                .enum_lookup(&format!("{scope}::{custom}"))
+                .is_some()
+        }) {
+            return None;
+        }
+        let (typedef, origin) = idl_store.typedef_lookup(custom)?;
+        Some(Self {
+            ident: typedef.ident.clone(),
+            count: idl_store.resolve_typedef(ty).1,
+            origin,
+        })
+    }
+
+    /// Elements of the alias making up `count` elements of the resolved type.
+    #[must_use]
+    pub fn elements(&self, count: Count) -> u16 {
+        count.get() / self.count.get()
+    }
+}
+
 pub type Count = std::num::NonZeroU16;
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Type {
     UntypedBuffer,
+    /// UTF-8 string of at most the given number of bytes, sent as a buffer.
+    String(Option<Count>),
     Primitive(Primitive),
     Struct(Struct),
+    Enum(Enum),
     Interface(Option<String>),
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct StructField {
+    pub doc: Option<String>,
     pub ident: Ident,
     pub val: (Type, Count),
+    /// Typedef naming the type of the field, `val` is what it resolves to.
+    pub alias: Option<Alias>,
 }
 
 impl StructField {
     pub fn size(&self) -> usize {
         let count = self.val.1;
         let size = match &self.val.0 {
-            Type::Primitive(p) => p.size(),
+            Type::Primitive(p) | Type::Enum(Enum { r#type: p, .. }) => p.size(),
             Type::Struct(s) => s.as_ref().size(),
             Type::Interface(_) => Primitive::Uint64.size() * 2,
             _ => unreachable!(),
@@ -136,7 +265,7 @@ impl StructField {
     }
     pub fn alignment(&self) -> usize {
         match &self.val.0 {
-            Type::Primitive(p) => p.align(),
+            Type::Primitive(p) | Type::Enum(Enum { r#type: p, .. }) => p.align(),
             Type::Struct(s) => s.as_ref().align(),
             Type::Interface(_) => Primitive::Uint64.align(),
             _ => unreachable!(),
@@ -144,7 +273,7 @@ impl StructField {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Struct {
     /// This variant of struct can be bundled with other primitives as it's
     /// guaranteed to be less than [`Struct::BUNDLED_SIZE_MAX`] bytes long.
@@ -187,8 +316,10 @@ impl AsRef<StructInner> for Struct {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct StructInner {
+    pub doc: Option<String>,
+    pub deprecated: Option<Deprecation>,
     pub ident: Ident,
     pub fields: Vec<StructField>,
     pub origin: Option<PathBuf>,
@@ -234,6 +365,32 @@ impl StructInner {
         objects
     }
 
+    /// Byte ranges of the `bool`s of the struct, nested structs included,
+    /// which skeletons check to hold `0` or `1`.
+    pub fn bools(&self) -> Vec<std::ops::Range<usize>> {
+        let mut bools = Vec::new();
+        let mut offset = 0;
+        for field in &self.fields {
+            let count = usize::from(field.val.1.get());
+            match &field.val.0 {
+                Type::Primitive(Primitive::Bool) => push_range(&mut bools, offset..offset + count),
+                Type::Struct(s) => {
+                    let size = s.as_ref().size();
+                    let nested = s.as_ref().bools();
+                    for i in 0..count {
+                        for range in &nested {
+                            let start = offset + i * size;
+                            push_range(&mut bools, start + range.start..start + range.end);
+                        }
+                    }
+                }
+                _ => {}
+            }
+            offset += field.size();
+        }
+        bools
+    }
+
     pub fn is_primitive_struct(&self) -> bool {
         self.objects().is_empty()
     }
@@ -243,8 +400,18 @@ impl StructInner {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+/// Appends `range` to `ranges`, merging it with the last one if they touch.
+pub fn push_range(ranges: &mut Vec<std::ops::Range<usize>>, range: std::ops::Range<usize>) {
+    match ranges.last_mut() {
+        Some(last) if last.end == range.start => last.end = range.end,
+        _ => ranges.push(range),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Interface {
+    pub doc: Option<String>,
+    pub deprecated: Option<Deprecation>,
     pub ident: Ident,
     pub base: Option<Rc<Interface>>,
     pub nodes: Vec<InterfaceNode>,
@@ -265,39 +432,56 @@ impl Interface {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum InterfaceNode {
     Const(Const),
     Function(Function),
     Error(Error),
+    Enum(Enum),
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ParamTypeIn {
     Array(Type, Option<Count>),
     Value(Type),
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ParamTypeOut {
     Array(Type, Option<Count>),
     Reference(Type),
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Param {
-    In { r#type: ParamTypeIn, ident: Ident },
-    Out { r#type: ParamTypeOut, ident: Ident },
+    In {
+        r#type: ParamTypeIn,
+        ident: Ident,
+        /// Maximum number of elements of a buffer or unbounded array.
+        max_len: Option<Count>,
+        /// Typedef naming the type of the parameter or of its elements.
+        alias: Option<Alias>,
+    },
+    Out {
+        r#type: ParamTypeOut,
+        ident: Ident,
+        alias: Option<Alias>,
+    },
 }
 impl Param {
     #[inline]
     #[must_use]
     pub const fn r#type(&self) -> &Type {
         match self {
-            Self::In { r#type, ident: _ } => match r#type {
+            Self::In {
+                r#type,
+                ident: _,
+                max_len: _,
+                alias: _,
+            } => match r#type {
                 ParamTypeIn::Array(t, _) | ParamTypeIn::Value(t) => t,
             },
-            Self::Out { r#type, ident: _ } => match r#type {
+            Self::Out { r#type, .. } => match r#type {
                 ParamTypeOut::Array(t, _) | ParamTypeOut::Reference(t) => t,
             },
         }
@@ -307,7 +491,48 @@ impl Param {
     #[must_use]
     pub const fn ident(&self) -> &Ident {
         match self {
-            Self::In { r#type: _, ident } | Self::Out { r#type: _, ident } => ident,
+            Self::In {
+                r#type: _,
+                ident,
+                max_len: _,
+                alias: _,
+            }
+            | Self::Out { ident, .. } => ident,
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn alias(&self) -> Option<&Alias> {
+        match self {
+            Self::In { alias, .. } | Self::Out { alias, .. } => alias.as_ref(),
+        }
+    }
+
+    /// Maximum number of elements of input buffers and unbounded arrays, or
+    /// of bytes of strings.
+    #[inline]
+    #[must_use]
+    pub const fn max_len(&self) -> Option<Count> {
+        match self {
+            Self::In {
+                r#type: ParamTypeIn::Value(Type::String(bound)),
+                ident: _,
+                max_len: _,
+                alias: _,
+            }
+            | Self::Out {
+                r#type: ParamTypeOut::Reference(Type::String(bound)),
+                ident: _,
+                alias: _,
+            } => *bound,
+            Self::In {
+                r#type: _,
+                ident: _,
+                max_len,
+                alias: _,
+            } => *max_len,
+            Self::Out { .. } => None,
         }
     }
 
@@ -318,7 +543,9 @@ impl Param {
             self,
             Self::In {
                 r#type: _,
-                ident: _
+                ident: _,
+                max_len: _,
+                alias: _,
             }
         )
     }
@@ -330,7 +557,8 @@ impl Param {
             self,
             Self::Out {
                 r#type: _,
-                ident: _
+                ident: _,
+                alias: _,
             }
         )
     }
@@ -342,9 +570,12 @@ impl Param {
             Self::In {
                 r#type: ParamTypeIn::Array(_, _),
                 ident: _,
+                max_len: _,
+                alias: _,
             } | Self::Out {
                 r#type: ParamTypeOut::Array(_, _),
                 ident: _,
+                alias: _,
             }
         )
     }
@@ -368,6 +599,18 @@ impl Param {
     pub const fn is_small_struct_value(&self) -> bool {
         !self.is_array() && self.is_small_struct()
     }
+
+    #[must_use]
+    pub const fn is_enum_value(&self) -> bool {
+        !self.is_array() && matches!(self.r#type(), Type::Enum(_))
+    }
+
+    /// Whether the parameter is a candidate for the bundled buffer of its
+    /// direction, enums being bundled like their underlying primitive.
+    #[must_use]
+    pub const fn is_bundled_value(&self) -> bool {
+        self.is_primitive_value() || self.is_enum_value() || self.is_small_struct_value()
+    }
 }
 
 impl PartialOrd for Param {
@@ -384,10 +627,13 @@ impl Ord for Param {
                 Self::In {
                     r#type: _,
                     ident: _,
+                    max_len: _,
+                    alias: _,
                 },
                 Self::Out {
                     r#type: _,
                     ident: _,
+                    alias: _,
                 },
             ) => match (self.r#type(), other.r#type()) {
                 (Type::Interface(_), Type::Interface(_)) => {
@@ -397,22 +643,34 @@ impl Ord for Param {
                         std::cmp::Ordering::Less
                     }
                 }
-                (Type::UntypedBuffer | Type::Primitive(_) | Type::Struct(_), _) => {
-                    std::cmp::Ordering::Less
-                }
+                (
+                    Type::UntypedBuffer
+                    | Type::String(_)
+                    | Type::Primitive(_)
+                    | Type::Struct(_)
+                    | Type::Enum(_),
+                    _,
+                ) => std::cmp::Ordering::Less,
                 (
                     Type::Interface(_),
-                    Type::UntypedBuffer | Type::Primitive(_) | Type::Struct(_),
+                    Type::UntypedBuffer
+                    | Type::String(_)
+                    | Type::Primitive(_)
+                    | Type::Struct(_)
+                    | Type::Enum(_),
                 ) => std::cmp::Ordering::Greater,
             },
             (
                 Self::Out {
                     r#type: _,
                     ident: _,
+                    alias: _,
                 },
                 Self::In {
                     r#type: _,
                     ident: _,
+                    max_len: _,
+                    alias: _,
                 },
             ) => match (self.r#type(), other.r#type()) {
                 (Type::Interface(_), Type::Interface(_)) => {
@@ -423,14 +681,22 @@ impl Ord for Param {
                     }
                 }
                 (
-                    Type::UntypedBuffer | Type::Primitive(_) | Type::Struct(_),
+                    Type::UntypedBuffer
+                    | Type::String(_)
+                    | Type::Primitive(_)
+                    | Type::Struct(_)
+                    | Type::Enum(_),
                     Type::Interface(_),
                 ) => std::cmp::Ordering::Less,
                 _ => std::cmp::Ordering::Greater,
             },
             _ => match (self.r#type(), other.r#type()) {
                 (
-                    Type::UntypedBuffer | Type::Primitive(_) | Type::Struct(_),
+                    Type::UntypedBuffer
+                    | Type::String(_)
+                    | Type::Primitive(_)
+                    | Type::Struct(_)
+                    | Type::Enum(_),
                     Type::Interface(_),
                 ) => std::cmp::Ordering::Less,
                 (Type::Interface(_), Type::Interface(_)) => {
@@ -449,7 +715,7 @@ impl Ord for Param {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Function {
     pub doc: Option<String>,
     pub ident: Ident,
@@ -460,27 +726,73 @@ pub struct Function {
 
 impl Function {
     pub fn get_version(&self) -> Option<&APIVersion> {
-        self.attributes
-            .iter()
-            .map(|e| match e {
-                idlc_ast::FunctionAttribute::Version(a) => a,
-            })
-            .next()
+        self.attributes.iter().find_map(|e| match e {
+            idlc_ast::FunctionAttribute::Version(a) => Some(a),
+            _ => None,
+        })
+    }
+
+    pub fn deprecation(&self) -> Option<&Deprecation> {
+        self.attributes.iter().find_map(|e| match e {
+            idlc_ast::FunctionAttribute::Deprecated(d) => Some(d),
+            _ => None,
+        })
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Error {
+    pub doc: Option<String>,
     pub ident: Ident,
     pub value: i32,
 }
 
+/// Strips the documentation of its AST wrapper.
+fn doc(doc: &Option<idlc_ast::Documentation>) -> Option<String> {
+    doc.as_ref().map(|idlc_ast::Documentation(s)| s.to_string())
+}
+
 fn parse_include(path: &Path) -> Node {
     Node::Include(path.to_path_buf())
 }
 
-fn parse_const(const_: &idlc_ast::Const) -> Node {
-    Node::Const(Const::from(const_))
+fn parse_const(const_: &idlc_ast::Const, idl_store: &IDLStore) -> Node {
+    Node::Const(Const::new(const_, idl_store, None))
+}
+
+/// Elements of a bounded array, constants naming its size are looked up from
+/// interface `scope` if any.
+fn array_size(size: &idlc_ast::ArraySize, idl_store: &IDLStore, scope: Option<&str>) -> Count {
+    idlc_ast_passes::consts::array_size(idl_store, size, scope)
+        .unwrap_or_else(|_| panic!("ICE: Array size `{size}` wasn't verified"))
+}
+
+/// Array typedefs are flattened into the field, their elements count along
+/// with the ones of the field.
+fn parse_field(field: &idlc_ast::StructField, idl_store: &IDLStore) -> StructField {
+    let (ty, count) = idl_store.resolve_typedef(&field.val.0);
+    let count = count
+        .checked_mul(array_size(&field.val.1, idl_store, None))
+        .unwrap_or_else(|| panic!("ICE: `{}` wasn't verified", field.ident));
+    StructField {
+        doc: doc(&field.doc),
+        ident: field.ident.clone(),
+        val: (Type::new(&ty, idl_store), count),
+        alias: Alias::new(&field.val.0, idl_store, &[]),
+    }
+}
+
+fn parse_typedef(typedef: &idlc_ast::Typedef, idl_store: &IDLStore) -> Node {
+    let (ty, count) = idl_store.resolve_typedef(&typedef.val.0);
+    Node::Typedef(Typedef {
+        doc: doc(&typedef.doc),
+        ident: typedef.ident.clone(),
+        r#type: Type::new(&ty, idl_store),
+        count: count
+            .checked_mul(array_size(&typedef.val.1, idl_store, None))
+            .unwrap_or_else(|| panic!("ICE: `{}` wasn't verified", typedef.ident)),
+        alias: Alias::new(&typedef.val.0, idl_store, &[]),
+    })
 }
 
 fn parse_struct(struct_: &idlc_ast::Struct, idl_store: &IDLStore) -> Node {
@@ -488,17 +800,15 @@ fn parse_struct(struct_: &idlc_ast::Struct, idl_store: &IDLStore) -> Node {
     let mut fields = Vec::<StructField>::new();
     let mut size = 0;
     for field in &struct_.fields {
-        let val = (Type::new(&field.val.0, idl_store), field.val.1);
-        let field = StructField {
-            ident: field.ident.clone(),
-            val,
-        };
+        let field = parse_field(field, idl_store);
         size += field.size();
         fields.push(field);
     }
 
     Node::Struct(Struct::new(
         StructInner {
+            doc: doc(&struct_.doc),
+            deprecated: struct_.deprecated.clone(),
             ident,
             fields,
             origin: None,
@@ -519,6 +829,16 @@ fn parse_interface(
         .as_ref()
         .map(std::string::ToString::to_string);
 
+    // Enums are looked up in the interface hierarchy before the top-level.
+    let mut scopes = vec![class.to_string()];
+    let mut current = interface_.base.clone();
+    while let Some(base) = current {
+        current = idl_store
+            .iface_lookup(&base)
+            .and_then(|iface| iface.base.clone());
+        scopes.push(base.to_string());
+    }
+
     let mut iface_nodes = Vec::new();
     let base_node = base.map(|x| {
         parse_interface(
@@ -532,44 +852,60 @@ fn parse_interface(
     for node in &interface_.nodes {
         match node {
             idlc_ast::InterfaceNode::Const(const_) => {
-                iface_nodes.push(InterfaceNode::Const(Const::from(const_)));
+                iface_nodes.push(InterfaceNode::Const(Const::new(
+                    const_,
+                    idl_store,
+                    Some(&class),
+                )));
+            }
+            idlc_ast::InterfaceNode::Enum(enum_) => {
+                iface_nodes.push(InterfaceNode::Enum(Enum::new(
+                    enum_,
+                    Some(class.to_string()),
+                    None,
+                )));
             }
             idlc_ast::InterfaceNode::Error(error) => {
+                // Pinned values move the implicit ones which follow.
+                let value = error.value.unwrap_or(*error_code);
                 iface_nodes.push(InterfaceNode::Error(Error {
-                    ident: error.clone(),
-                    value: *error_code,
+                    doc: doc(&error.doc),
+                    ident: error.ident.clone(),
+                    value,
                 }));
-                *error_code = error_code
-                    .checked_add(1)
-                    .expect("Error should be under i32::MAX");
+                *error_code = value.saturating_add(1);
             }
             idlc_ast::InterfaceNode::Function(function) => {
-                let doc = function
-                    .doc
-                    .as_ref()
-                    .map(|idlc_ast::Documentation(s)| s.to_string());
+                let doc = doc(&function.doc);
                 let ident = function.ident.clone();
                 let mut params = Vec::new();
                 for param in &function.params {
-                    params.push(Param::new(param, idl_store));
+                    params.push(Param::new(param, idl_store, &scopes));
                 }
+                let id = function
+                    .attributes
+                    .iter()
+                    .find_map(|attr| match attr {
+                        idlc_ast::FunctionAttribute::OpCode(id) => Some(*id),
+                        _ => None,
+                    })
+                    .unwrap_or(*op_code);
                 iface_nodes.push(InterfaceNode::Function(Function {
                     doc,
                     ident,
                     params,
-                    id: *op_code,
+                    id,
                     attributes: function.attributes.clone(),
                 }));
-                if *op_code > MAX_OP_CODE {
-                    panic!("Numbers of functions should be lesser than 0x3fff");
-                } else {
-                    *op_code += 1;
-                }
+                // Out of range op-codes are reported by the interface verifier.
+                *op_code = id.saturating_add(1);
             }
         }
     }
 
     Interface {
+        doc: doc(&interface_.doc),
+        deprecated: interface_.deprecated.clone(),
         ident: class,
         base: base_node.map(Rc::new),
         nodes: iface_nodes,
@@ -581,8 +917,10 @@ pub fn parse_to_mir(ast: &Ast, idl_store: &mut IDLStore) -> Mir {
     for node in &ast.nodes {
         match &**node {
             idlc_ast::Node::Include(path) => nodes.push(parse_include(path)),
-            idlc_ast::Node::Const(const_) => nodes.push(parse_const(const_)),
+            idlc_ast::Node::Const(const_) => nodes.push(parse_const(const_, idl_store)),
             idlc_ast::Node::Struct(struct_) => nodes.push(parse_struct(struct_, idl_store)),
+            idlc_ast::Node::Enum(enum_) => nodes.push(Node::Enum(Enum::new(enum_, None, None))),
+            idlc_ast::Node::Typedef(typedef) => nodes.push(parse_typedef(typedef, idl_store)),
             idlc_ast::Node::Interface(interface) => {
                 let mut err_code = ERROR_CODE_START;
                 let mut op_code = 0;
@@ -627,33 +965,54 @@ impl Mir {
 }
 
 impl ParamTypeIn {
-    fn new(src: &idlc_ast::ParamTypeIn, idl_store: &IDLStore) -> Self {
+    fn new(src: &idlc_ast::ParamTypeIn, idl_store: &IDLStore, scopes: &[String]) -> Self {
         match src {
-            idlc_ast::ParamTypeIn::Array(ty, cnt) => Self::Array(Type::new(ty, idl_store), *cnt),
-            idlc_ast::ParamTypeIn::Value(ty) => Self::Value(Type::new(ty, idl_store)),
+            idlc_ast::ParamTypeIn::Array(ty, cnt) => Self::Array(
+                Type::new_scoped(ty, idl_store, scopes),
+                cnt.as_ref()
+                    .map(|cnt| array_size(cnt, idl_store, scopes.first().map(String::as_str))),
+            ),
+            idlc_ast::ParamTypeIn::Value(ty) => {
+                Self::Value(Type::new_scoped(ty, idl_store, scopes))
+            }
         }
     }
 }
 
 impl ParamTypeOut {
-    fn new(src: &idlc_ast::ParamTypeOut, idl_store: &IDLStore) -> Self {
+    fn new(src: &idlc_ast::ParamTypeOut, idl_store: &IDLStore, scopes: &[String]) -> Self {
         match src {
-            idlc_ast::ParamTypeOut::Array(ty, cnt) => Self::Array(Type::new(ty, idl_store), *cnt),
-            idlc_ast::ParamTypeOut::Reference(ty) => Self::Reference(Type::new(ty, idl_store)),
+            idlc_ast::ParamTypeOut::Array(ty, cnt) => Self::Array(
+                Type::new_scoped(ty, idl_store, scopes),
+                cnt.as_ref()
+                    .map(|cnt| array_size(cnt, idl_store, scopes.first().map(String::as_str))),
+            ),
+            idlc_ast::ParamTypeOut::Reference(ty) => {
+                Self::Reference(Type::new_scoped(ty, idl_store, scopes))
+            }
         }
     }
 }
 
 impl Param {
-    fn new(src: &idlc_ast::Param, idl_store: &IDLStore) -> Self {
+    fn new(src: &idlc_ast::Param, idl_store: &IDLStore, scopes: &[String]) -> Self {
         match src {
-            idlc_ast::Param::In { r#type, ident } => Self::In {
-                r#type: ParamTypeIn::new(r#type, idl_store),
+            idlc_ast::Param::In {
+                r#type,
+                ident,
+                max_len,
+            } => Self::In {
+                r#type: ParamTypeIn::new(r#type, idl_store, scopes),
                 ident: ident.clone(),
+                max_len: max_len.as_ref().map(|max_len| {
+                    array_size(max_len, idl_store, scopes.first().map(String::as_str))
+                }),
+                alias: Alias::new(r#type.as_ref(), idl_store, scopes),
             },
-            idlc_ast::Param::Out { r#type, ident } => Self::Out {
-                r#type: ParamTypeOut::new(r#type, idl_store),
+            idlc_ast::Param::Out { r#type, ident, .. } => Self::Out {
+                r#type: ParamTypeOut::new(r#type, idl_store, scopes),
                 ident: ident.clone(),
+                alias: Alias::new(r#type.as_ref(), idl_store, scopes),
             },
         }
     }
@@ -672,25 +1031,61 @@ impl From<&idlc_ast::Primitive> for Primitive {
             idlc_ast::Primitive::Int64 => Self::Int64,
             idlc_ast::Primitive::Float32 => Self::Float32,
             idlc_ast::Primitive::Float64 => Self::Float64,
+            idlc_ast::Primitive::Bool => Self::Bool,
+            idlc_ast::Primitive::Char => Self::Char,
         }
     }
 }
 
-impl From<&idlc_ast::Const> for Const {
-    fn from(const_: &idlc_ast::Const) -> Self {
+impl Const {
+    /// Literals are kept as written, expressions are replaced by their value.
+    fn new(const_: &idlc_ast::Const, idl_store: &IDLStore, scope: Option<&str>) -> Self {
+        let value = match &const_.value {
+            idlc_ast::Expr::Literal(literal) => literal.clone(),
+            _ => idlc_ast_passes::consts::evaluate(idl_store, const_, scope)
+                .unwrap_or_else(|_| panic!("ICE: `{}` wasn't verified", const_.ident))
+                .to_string(),
+        };
         Self {
+            doc: doc(&const_.doc),
             ident: const_.ident.clone(),
             r#type: Primitive::from(&const_.r#type),
-            value: const_.value.to_string(),
+            value,
         }
     }
 }
 
 impl Type {
     fn new(ty: &idlc_ast::Type, idl_store: &IDLStore) -> Self {
+        Self::new_scoped(ty, idl_store, &[])
+    }
+
+    /// `scopes` are the interfaces whose enums are visible, innermost first.
+    fn new_scoped(ty: &idlc_ast::Type, idl_store: &IDLStore, scopes: &[String]) -> Self {
+        if let idlc_ast::Type::Custom(custom) = ty {
+            for scope in scopes {
+                if let Some((r#enum, path)) = idl_store.enum_lookup(&format!("{scope}::{custom}")) {
+                    return Self::Enum(Enum::new(&r#enum, Some(scope.clone()), Some(path)));
+                }
+            }
+            if idl_store.typedef_lookup(custom).is_some() {
+                // Typedefs are top-level, so is what they alias.
+                let (ty, _) = idl_store.resolve_typedef(ty);
+                return Self::new(&ty, idl_store);
+            }
+            if let Some((r#enum, path)) = idl_store.enum_lookup(custom) {
+                return Self::Enum(Enum::new(&r#enum, None, Some(path)));
+            }
+        }
+
         match ty {
             idlc_ast::Type::Primitive(primitive) => Self::Primitive(Primitive::from(primitive)),
             idlc_ast::Type::UntypedBuffer => Self::UntypedBuffer,
+            idlc_ast::Type::String(bound) => Self::String(
+                bound
+                    .as_ref()
+                    .map(|bound| array_size(bound, idl_store, scopes.first().map(String::as_str))),
+            ),
             idlc_ast::Type::Interface => Self::Interface(None),
             idlc_ast::Type::Custom(custom) => {
                 let ident = &custom.ident;
@@ -700,16 +1095,14 @@ impl Type {
                             let mut fields = Vec::new();
                             let mut size = 0;
                             for field in &r#struct.fields {
-                                let (ty, count) = &field.val;
-                                let field = StructField {
-                                    ident: field.ident.clone(),
-                                    val: (Self::new(ty, idl_store), *count),
-                                };
+                                let field = parse_field(field, idl_store);
                                 size += field.size();
                                 fields.push(field);
                             }
                             Self::Struct(Struct::new(
                                 StructInner {
+                                    doc: doc(&r#struct.doc),
+                                    deprecated: r#struct.deprecated.clone(),
                                     ident: r#struct.ident.clone(),
                                     fields,
                                     origin: Some(path),
@@ -774,18 +1167,26 @@ mod tests {
     #[test]
     fn collect_errors_only_of_base() {
         let iface = Interface {
+            doc: None,
+            deprecated: None,
             ident: Ident::new_without_span("A".to_string()),
             base: Some(Rc::new(Interface {
+                doc: None,
+                deprecated: None,
                 ident: Ident::new_without_span("B".to_string()),
                 base: Some(Rc::new(Interface {
+                    doc: None,
+                    deprecated: None,
                     ident: Ident::new_without_span("C".to_string()),
                     base: None,
                     nodes: vec![
                         InterfaceNode::Error(Error {
+                            doc: None,
                             ident: Ident::new_without_span("ERROR_1".to_string()),
                             value: 10,
                         }),
                         InterfaceNode::Const(Const {
+                            doc: None,
                             ident: Ident::new_without_span("CONST_1".to_string()),
                             r#type: Primitive::Uint8,
                             value: "10".to_string(),
@@ -793,11 +1194,13 @@ mod tests {
                     ],
                 })),
                 nodes: vec![InterfaceNode::Error(Error {
+                    doc: None,
                     ident: Ident::new_without_span("ERROR_SOMETHING_ELSE".to_string()),
                     value: 10,
                 })],
             })),
             nodes: vec![InterfaceNode::Error(Error {
+                doc: None,
                 ident: Ident::new_without_span("THIS_SHOULDNT_SHOW_UP".to_string()),
                 value: 10,
             })],
@@ -817,6 +1220,7 @@ mod tests {
                 (
                     "B",
                     &Error {
+                        doc: None,
                         ident: Ident::new_without_span("ERROR_SOMETHING_ELSE".to_string()),
                         value: 10
                     }
@@ -824,6 +1228,7 @@ mod tests {
                 (
                     "C",
                     &Error {
+                        doc: None,
                         ident: Ident::new_without_span("ERROR_1".to_string()),
                         value: 10
                     }
@@ -838,27 +1243,34 @@ mod tests {
             Param::Out {
                 r#type: ParamTypeOut::Reference(Type::Interface(None)),
                 ident: Ident::new_without_span("interface3".to_string()),
+                alias: None,
             },
             Param::Out {
                 r#type: ParamTypeOut::Array(Type::Primitive(Primitive::Uint16), None),
                 ident: Ident::new_without_span("primitive4".to_string()),
+                alias: None,
             },
             Param::Out {
                 r#type: ParamTypeOut::Array(Type::Interface(None), None),
                 ident: Ident::new_without_span("interface6".to_string()),
+                alias: None,
             },
             Param::Out {
                 r#type: ParamTypeOut::Reference(Type::Interface(None)),
                 ident: Ident::new_without_span("interface4".to_string()),
+                alias: None,
             },
             Param::Out {
                 r#type: ParamTypeOut::Array(Type::Primitive(Primitive::Uint16), None),
                 ident: Ident::new_without_span("primitive5".to_string()),
+                alias: None,
             },
             Param::Out {
                 r#type: ParamTypeOut::Array(
                     Type::Struct(
                         StructInner {
+                            doc: None,
+                            deprecated: None,
                             ident: Ident::new_without_span(String::new()),
                             fields: Vec::new(),
                             origin: None,
@@ -868,11 +1280,14 @@ mod tests {
                     None,
                 ),
                 ident: Ident::new_without_span("struct3".to_string()),
+                alias: None,
             },
             Param::Out {
                 r#type: ParamTypeOut::Array(
                     Type::Struct(
                         StructInner {
+                            doc: None,
+                            deprecated: None,
                             ident: Ident::new_without_span(String::new()),
                             fields: Vec::new(),
                             origin: None,
@@ -882,31 +1297,44 @@ mod tests {
                     None,
                 ),
                 ident: Ident::new_without_span("struct4".to_string()),
+                alias: None,
             },
             Param::In {
                 r#type: ParamTypeIn::Array(Type::Interface(None), None),
                 ident: Ident::new_without_span("interface5".to_string()),
+                max_len: None,
+                alias: None,
             },
             Param::In {
                 r#type: ParamTypeIn::Value(Type::Interface(None)),
                 ident: Ident::new_without_span("interface1".to_string()),
+                max_len: None,
+                alias: None,
             },
             Param::In {
                 r#type: ParamTypeIn::Array(Type::Primitive(Primitive::Uint16), None),
                 ident: Ident::new_without_span("primitive1".to_string()),
+                max_len: None,
+                alias: None,
             },
             Param::In {
                 r#type: ParamTypeIn::Value(Type::Interface(None)),
                 ident: Ident::new_without_span("interface2".to_string()),
+                max_len: None,
+                alias: None,
             },
             Param::In {
                 r#type: ParamTypeIn::Value(Type::Primitive(Primitive::Uint16)),
                 ident: Ident::new_without_span("primitive2".to_string()),
+                max_len: None,
+                alias: None,
             },
             Param::In {
                 r#type: ParamTypeIn::Array(
                     Type::Struct(
                         StructInner {
+                            doc: None,
+                            deprecated: None,
                             ident: Ident::new_without_span(String::new()),
                             fields: Vec::new(),
                             origin: None,
@@ -916,10 +1344,14 @@ mod tests {
                     None,
                 ),
                 ident: Ident::new_without_span("struct1".to_string()),
+                max_len: None,
+                alias: None,
             },
             Param::In {
                 r#type: ParamTypeIn::Value(Type::Primitive(Primitive::Float32)),
                 ident: Ident::new_without_span("primitive3".to_string()),
+                max_len: None,
+                alias: None,
             },
         ];
         params.sort();
@@ -951,51 +1383,73 @@ mod tests {
     fn search_interfaces() {
         const ONE: NonZeroU16 = NonZeroU16::new(1).unwrap();
         let leaf = Struct::Big(StructInner {
+            doc: None,
+            deprecated: None,
             ident: Ident::new_without_span("leaf".to_owned()),
             fields: vec![StructField {
+                doc: None,
                 ident: Ident::new_without_span("leaf_foo".to_string()),
                 val: (Type::Interface(Some("level3".to_string())), ONE),
+                alias: None,
             }],
             origin: None,
         });
 
         let middle = Struct::Big(StructInner {
+            doc: None,
+            deprecated: None,
             ident: Ident::new_without_span("middle".to_owned()),
             fields: vec![
                 StructField {
+                    doc: None,
                     ident: Ident::new_without_span("middle_foo".to_string()),
                     val: (Type::Interface(Some("level2".to_string())), ONE),
+                    alias: None,
                 },
                 StructField {
+                    doc: None,
                     ident: Ident::new_without_span("leaf_struct".to_string()),
                     val: (Type::Struct(leaf.clone()), ONE),
+                    alias: None,
                 },
             ],
             origin: None,
         });
 
         let outer = Struct::Big(StructInner {
+            doc: None,
+            deprecated: None,
             ident: Ident::new_without_span("outer".to_owned()),
             fields: vec![
                 StructField {
+                    doc: None,
                     ident: Ident::new_without_span("a_float".to_owned()),
                     val: (Type::Primitive(Primitive::Float32), ONE),
+                    alias: None,
                 },
                 StructField {
+                    doc: None,
                     ident: Ident::new_without_span("a_foo".to_owned()),
                     val: (Type::Interface(Some("level1".to_string())), ONE),
+                    alias: None,
                 },
                 StructField {
+                    doc: None,
                     ident: Ident::new_without_span("a_foo2".to_owned()),
                     val: (Type::Interface(Some("level1".to_string())), ONE),
+                    alias: None,
                 },
                 StructField {
+                    doc: None,
                     ident: Ident::new_without_span("leaf_struct".to_string()),
                     val: (Type::Struct(leaf), ONE),
+                    alias: None,
                 },
                 StructField {
+                    doc: None,
                     ident: Ident::new_without_span("middle_struct".to_string()),
                     val: (Type::Struct(middle), ONE),
+                    alias: None,
                 },
             ],
             origin: None,
@@ -0,0 +1,146 @@
+// Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
+// SPDX-License-Identifier: BSD-3-Clause
+
+use idlc_ast_passes::cycles::Cycles;
+use idlc_ast_passes::idl_store::IDLStore;
+use idlc_ast_passes::struct_verifier::StructVerifier;
+use idlc_ast_passes::CompilerPass;
+use idlc_mir::compat::{compare, Change, Compatibility};
+use idlc_mir::mir;
+
+fn create_mir(a_idl: &str) -> mir::Mir {
+    let mut store = IDLStore::new();
+    let name = std::path::PathBuf::from("mir.idl");
+    let node = idlc_ast::from_string(name.clone(), a_idl, true).unwrap();
+    store.insert_canonical(&name, &node);
+
+    let ast = store.get_ast(&name).unwrap();
+    let struct_ordering = Cycles::new(&store).run_pass(&ast).unwrap();
+    StructVerifier::run_pass(&store, &struct_ordering);
+    idlc_mir::parse_to_mir(&ast, &mut store)
+}
+
+fn changes(old: &str, new: &str) -> Vec<String> {
+    compare(&create_mir(old), &create_mir(new))
+        .iter()
+        .map(Change::to_string)
+        .collect()
+}
+
+#[test]
+fn identical() {
+    let idl = r"
+        struct S { uint32 a; };
+        interface IBase { error E; method a(in S s); };
+        interface I : IBase { method b(out uint32[] c); };
+    ";
+    assert!(changes(idl, idl).is_empty());
+}
+
+#[test]
+fn appended_method() {
+    assert_eq!(
+        changes(
+            "interface I { method a(); };",
+            "interface I { method a(); method b(in uint32 x); };"
+        ),
+        ["compatible: method `I::b` was added with op-code 1"]
+    );
+}
+
+#[test]
+fn reordered_and_removed_methods() {
+    assert_eq!(
+        changes(
+            "interface I { method a(); method b(); method c(); };",
+            "interface I { method b(); method a(); };"
+        ),
+        [
+            "breaking: method `I::a` op-code changed from 0 to 1",
+            "breaking: method `I::b` op-code changed from 1 to 0",
+            "breaking: method `I::c` was removed",
+        ]
+    );
+}
+
+#[test]
+fn params() {
+    assert_eq!(
+        changes(
+            "interface I { method a(in uint32 x, out uint8[] y, in buffer z); };",
+            "interface I { method a(out uint32 x, out uint16[] y, in buffer w); };"
+        ),
+        [
+            "breaking: parameter `x` of method `I::a` direction changed from `in` to `out`",
+            "breaking: parameter `y` of method `I::a` type changed from `uint8[]` to `uint16[]`",
+            "compatible: parameter `z` of method `I::a` was renamed to `w`",
+        ]
+    );
+    assert_eq!(
+        changes(
+            "interface I { method a(in uint32 x); };",
+            "interface I { method a(in uint32 x, in uint32 y); };"
+        ),
+        ["breaking: method `I::a` parameter count changed from 1 to 2"]
+    );
+}
+
+#[test]
+fn struct_layout() {
+    assert_eq!(
+        changes(
+            "struct S { uint32 a; uint32 b; };",
+            "struct S { uint32 b; uint32 a; uint64 c; };"
+        ),
+        [
+            "breaking: struct `S` size changed from 8 to 16 bytes",
+            "breaking: struct `S` alignment changed from 4 to 8 bytes",
+            "breaking: field `S::a` offset changed from 0 to 4",
+            "breaking: field `S::b` offset changed from 4 to 0",
+            "breaking: field `S::c` was added",
+        ]
+    );
+}
+
+#[test]
+fn renumbered_errors() {
+    assert_eq!(
+        changes(
+            "interface IBase { error A; }; interface I : IBase { error B; };",
+            "interface IBase { error A; error C; }; interface I : IBase { error B; };"
+        ),
+        [
+            "compatible: error `IBase::C` was added with value 11",
+            "breaking: error `I::B` value changed from 11 to 12",
+        ]
+    );
+}
+
+#[test]
+fn version_regression() {
+    let changes = compare(
+        &create_mir("interface I { #[version=1.2] method a(); };"),
+        &create_mir("interface I { #[version=1.1] method a(); };"),
+    );
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].compatibility, Compatibility::Breaking);
+    assert_eq!(
+        changes[0].message,
+        "method `I::a` version went back from 1.2 to 1.1"
+    );
+}
+
+#[test]
+fn enums() {
+    assert_eq!(
+        changes(
+            "enum E : uint8 { A, B }; interface I { enum F : int32 { X }; };",
+            "enum E : uint8 { A, C, B }; interface I { enum F : int64 { X }; };"
+        ),
+        [
+            "breaking: enum variant `E::B` value changed from 1 to 2",
+            "compatible: enum variant `E::C` was added",
+            "breaking: enum `I::F` type changed from `int32` to `int64`",
+        ]
+    );
+}
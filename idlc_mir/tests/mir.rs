@@ -15,7 +15,7 @@ fn create_mir(a_idl: &str) -> mir::Mir {
 
     let ast = store.get_ast(&name).unwrap();
     let struct_ordering = Cycles::new(&store).run_pass(&ast).unwrap();
-    let _ = StructVerifier::run_pass(&store, &struct_ordering);
+    StructVerifier::run_pass(&store, &struct_ordering);
     idlc_mir::parse_to_mir(&ast, &mut store)
 }
 
@@ -139,3 +139,53 @@ fn err_code_test() {
         ]
     );
 }
+
+#[test]
+fn enum_resolution_test() {
+    let mir = create_mir(
+        r"
+            enum Mode : uint32 { OFF, ON = 5, AUTO };
+
+            struct s1 {
+                Mode mode;
+            };
+
+            interface iface {
+                enum Mode : int8 { LOW = -1, HIGH };
+                method set(in Mode m);
+            };
+
+            interface iface2 : iface {
+                method set2(in Mode m, in s1 s);
+            };
+        ",
+    );
+    let idlc_mir::Node::Enum(global) = &mir.nodes[0] else {
+        panic!("Expected an enum");
+    };
+    let values: Vec<_> = global.variants.iter().map(|v| v.value).collect();
+    assert_eq!(values, [0, 5, 6]);
+
+    let idlc_mir::Node::Interface(iface2) = mir.nodes.last().unwrap() else {
+        panic!("Expected an interface");
+    };
+    let idlc_mir::InterfaceNode::Function(f) = &iface2.nodes[0] else {
+        panic!("Expected a function");
+    };
+    // Enums of the base interface shadow the top-level ones.
+    let mir::Type::Enum(e) = f.params[0].r#type() else {
+        panic!("Expected an enum parameter");
+    };
+    assert_eq!(e.scope.as_deref(), Some("iface"));
+    assert_eq!(e.r#type, mir::Primitive::Int8);
+
+    let mir::Type::Struct(mir::Struct::Small(s) | mir::Struct::Big(s)) = f.params[1].r#type()
+    else {
+        panic!("Expected a struct parameter");
+    };
+    let mir::Type::Enum(e) = &s.fields[0].val.0 else {
+        panic!("Expected an enum field");
+    };
+    assert_eq!(e.scope, None);
+    assert_eq!(e.r#type, mir::Primitive::Uint32);
+}
@@ -1,20 +1,212 @@
 // Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
 // SPDX-License-Identifier: BSD-3-Clause
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use crate::MirCompilerPass;
 
-use idlc_mir::{InterfaceNode, Node, ParamTypeIn, ParamTypeOut, Struct, Type, VERSION_FUNC_NAME};
+use idlc_errors::{codes, Diagnostic, Label};
+use idlc_mir::{
+    Count, Function, Interface, InterfaceNode, Node, Param, ParamTypeIn, ParamTypeOut, Struct,
+    Type, ERROR_CODE_START, MAX_OP_CODE, VERSION_FUNC_NAME,
+};
 
 pub struct InterfaceVerifier<'mir> {
     mir: &'mir idlc_mir::Mir,
+    /// Interfaces defined in the file being verified, spans of everything else
+    /// point into other files.
+    local: HashSet<&'mir str>,
 }
 
 impl<'mir> InterfaceVerifier<'mir> {
     #[must_use]
-    pub const fn new(mir: &'mir idlc_mir::Mir) -> Self {
-        Self { mir }
+    pub fn new(mir: &'mir idlc_mir::Mir) -> Self {
+        let local = mir
+            .nodes
+            .iter()
+            .filter_map(|node| match node {
+                Node::Interface(i) => Some(i.ident.as_ref()),
+                _ => None,
+            })
+            .collect();
+        Self { mir, local }
+    }
+
+    #[inline]
+    fn is_local(&self, iface: &Interface) -> bool {
+        self.local.contains(iface.ident.as_ref())
+    }
+
+    fn emit(&self, diagnostic: Diagnostic) {
+        idlc_errors::emit(diagnostic.with_file(&self.mir.tag));
+    }
+
+    fn verify_function(&self, iface: &Interface, function: &Function) {
+        let label = |diagnostic: Diagnostic, span, message: &str| {
+            if self.is_local(iface) {
+                diagnostic.with_label(Label::primary(span, message))
+            } else {
+                diagnostic
+            }
+        };
+
+        if VERSION_FUNC_NAME == function.ident.as_ref() {
+            self.emit(label(
+                Diagnostic::error(
+                    codes::RESERVED_IDENT,
+                    format!(
+                        "Method `{}::{}` conflicts with Mink IDL reserved word",
+                        iface.ident, function.ident
+                    ),
+                ),
+                function.ident.span,
+                "reserved word",
+            ));
+        }
+
+        if function.id > MAX_OP_CODE {
+            self.emit(label(
+                Diagnostic::error(
+                    codes::OP_CODE_OUT_OF_RANGE,
+                    format!(
+                        "Method `{}::{}` has op-code `{:#x}`, above the maximum of `{MAX_OP_CODE:#x}`",
+                        iface.ident, function.ident, function.id
+                    ),
+                ),
+                function.ident.span,
+                "op-code out of range",
+            ));
+        }
+
+        let mut args_array_in = false;
+        let mut args_value_in = false;
+        let mut args_array_out = false;
+        let mut args_value_out = false;
+        for param in &function.params {
+            let (is_array, cnt) = match param {
+                Param::In {
+                    r#type: ParamTypeIn::Array(_, cnt),
+                    ..
+                }
+                | Param::Out {
+                    r#type: ParamTypeOut::Array(_, cnt),
+                    ..
+                } => (true, *cnt),
+                _ => (false, None),
+            };
+            let ident = param.ident();
+            match param.r#type() {
+                Type::Interface(i) if is_array => {
+                    let iface_name = i.as_deref().unwrap_or("interface");
+                    self.verify_object_array(iface_name, function, ident, cnt, &label);
+                    if param.is_input() {
+                        args_array_in = true;
+                    } else {
+                        args_array_out = true;
+                    }
+                }
+                Type::Interface(_) => {
+                    if param.is_input() {
+                        args_value_in = true;
+                    } else {
+                        args_value_out = true;
+                    }
+                }
+                t @ (Type::Struct(_) | Type::Primitive(_)) if is_array => {
+                    if let Type::Struct(Struct::Big(s) | Struct::Small(s)) = t {
+                        if s.contains_interfaces() {
+                            self.emit(label(
+                                Diagnostic::error(
+                                    codes::OBJECT_STRUCT_ARRAY,
+                                    "Struct with Object inside cannot be used as an array",
+                                ),
+                                ident.span,
+                                &format!("`{}` contains objects", s.ident),
+                            ));
+                        }
+                    }
+                    if cnt.is_some() {
+                        self.emit(label(
+                            Diagnostic::error(
+                                codes::BOUNDED_VALUE_ARRAY,
+                                format!(
+                                    "Interface `{}`, method `{}` should not have bounded array of primitive/struct",
+                                    iface.ident, function.ident
+                                ),
+                            ),
+                            ident.span,
+                            "bounded array",
+                        ));
+                    }
+                }
+                _ => (),
+            }
+        }
+        if (args_array_in && args_value_in) || (args_array_out && args_value_out) {
+            self.emit(label(
+                Diagnostic::error(
+                    codes::MIXED_OBJECT_ARGS,
+                    format!(
+                        "Interface `{}`, method `{}` has both object array and non-array object arguments",
+                        iface.ident, function.ident,
+                    ),
+                ),
+                function.ident.span,
+                "",
+            ));
+        }
+    }
+
+    fn verify_error(&self, iface: &Interface, error: &idlc_mir::mir::Error) {
+        if error.value >= ERROR_CODE_START {
+            return;
+        }
+        let mut diagnostic = Diagnostic::error(
+            codes::ERROR_VALUE_RESERVED,
+            format!(
+                "Error `{}::{}` has value `{}`, values below {ERROR_CODE_START} are reserved for generic errors",
+                iface.ident, error.ident, error.value
+            ),
+        );
+        if self.is_local(iface) {
+            diagnostic = diagnostic.with_label(Label::primary(error.ident.span, "reserved value"));
+        }
+        self.emit(diagnostic);
+    }
+
+    fn verify_object_array(
+        &self,
+        iface_name: &str,
+        function: &Function,
+        ident: &idlc_mir::Ident,
+        cnt: Option<Count>,
+        label: &impl Fn(Diagnostic, idlc_ast::Span, &str) -> Diagnostic,
+    ) {
+        match cnt {
+            None => self.emit(label(
+                Diagnostic::error(
+                    codes::UNBOUNDED_OBJECT_ARRAY,
+                    format!(
+                        "Interface `{iface_name}`, method `{}` should not have unbounded array of interface",
+                        function.ident
+                    ),
+                ),
+                ident.span,
+                "unbounded array",
+            )),
+            Some(cnt) if cnt.get() == 1 => self.emit(label(
+                Diagnostic::warning(
+                    codes::SINGLE_OBJECT_ARRAY,
+                    format!(
+                        "Interface `{iface_name}`, method `{}` has the array size of 1. It is better to use non-array interface instead",
+                        function.ident
+                    ),
+                ),
+                ident.span,
+                "array of size 1",
+            )),
+            Some(_) => (),
+        }
     }
 }
 
@@ -29,128 +221,32 @@ impl MirCompilerPass<'_> for InterfaceVerifier<'_> {
                 None
             }
         }) {
-            let mut consts = CollisionDetector::new(src);
-            let mut functions = CollisionDetector::new(src);
+            let mut consts = CollisionDetector::new();
+            let mut functions = CollisionDetector::new();
+            let mut op_codes = ValueCollisions::new(codes::OP_CODE_COLLISION, "Op-code", "method");
+            let mut error_values =
+                ValueCollisions::new(codes::ERROR_VALUE_COLLISION, "Value", "error");
 
-            for from in src {
+            // Values are assigned from the root of the hierarchy down.
+            for from in src.iter().collect::<Vec<_>>().into_iter().rev() {
+                // Bases defined in this file are verified on their own.
+                let owned = std::ptr::eq(from, src) || !self.is_local(from);
                 for node in &from.nodes {
                     match node {
-                        InterfaceNode::Const(c) => consts.add_ident(&c.ident, from),
-                        InterfaceNode::Error(e) => consts.add_ident(&e.ident, from),
-                        InterfaceNode::Function(f) => {
-                            functions.add_ident(&f.ident, from);
-
-                            if VERSION_FUNC_NAME == f.ident.as_ref() {
-                                idlc_errors::unrecoverable!(
-                                    "Method `{}::{}` conflicts with Mink IDL reserved word",
-                                    src.ident,
-                                    f.ident
-                                );
+                        InterfaceNode::Const(c) => consts.add_ident(self, &c.ident, from, owned),
+                        InterfaceNode::Error(e) => {
+                            consts.add_ident(self, &e.ident, from, owned);
+                            error_values.add(self, i128::from(e.value), &e.ident, from, owned);
+                            if owned {
+                                self.verify_error(from, e);
                             }
-
-                            let mut args_array_in = false;
-                            let mut args_value_in = false;
-                            let mut args_array_out = false;
-                            let mut args_value_out = false;
-                            for param in &f.params {
-                                match param {
-                                    idlc_mir::Param::In { r#type, ident: _ } => match r#type {
-                                        ParamTypeIn::Array(t, cnt) => {
-                                            if let idlc_mir::Type::Interface(i) = t {
-                                                let iface_name =
-                                                    i.as_deref().unwrap_or("interface").to_string();
-                                                if !cnt.is_some() {
-                                                    idlc_errors::unrecoverable!(
-                                                        "Interface `{}`, method `{}` should not have unbounded array of interface",
-                                                        iface_name, f.ident
-                                                    );
-                                                } else if cnt.unwrap()
-                                                    == std::num::NonZeroU16::new(1).unwrap()
-                                                {
-                                                    idlc_errors::warn!(
-                                                        "Interface `{}`, method `{}` has the array size of 1. It is better to use non-array interface instead222",
-                                                        iface_name, f.ident
-                                                    );
-                                                }
-
-                                                args_array_in = true;
-                                            };
-                                            if let Type::Struct(_) | Type::Primitive(_) = t {
-                                                if let Type::Struct(Struct::Big(s)) = t {
-                                                    if s.contains_interfaces() {
-                                                        idlc_errors::unrecoverable!(
-                                                            "Struct with Object inside cannot be used as an array",
-                                                        );
-                                                    }
-                                                }
-                                                if cnt.is_some() {
-                                                    idlc_errors::unrecoverable!(
-                                                        "Interface `{}`, method `{}` should not have bounded array of primitive/struct",
-                                                        src.ident, f.ident
-                                                    );
-                                                }
-                                            }
-                                        }
-                                        ParamTypeIn::Value(t) => {
-                                            if let Type::Interface(_) = t {
-                                                args_value_in = true;
-                                            };
-                                        }
-                                    },
-                                    idlc_mir::Param::Out { r#type, ident: _ } => match r#type {
-                                        ParamTypeOut::Array(t, cnt) => {
-                                            if let Type::Interface(i) = t {
-                                                let iface_name =
-                                                    i.as_deref().unwrap_or("interface").to_string();
-                                                if !cnt.is_some() {
-                                                    idlc_errors::unrecoverable!(
-                                                        "Interface `{}`, method `{}` should not have unbounded array of interface",
-                                                        iface_name, f.ident
-                                                    );
-                                                } else if cnt.unwrap()
-                                                    == std::num::NonZeroU16::new(1).unwrap()
-                                                {
-                                                    idlc_errors::warn!(
-                                                        "Interface `{}`, method `{}` has the array size of 1. It is better to use non-array interface instead111",
-                                                        iface_name, f.ident
-                                                    );
-                                                }
-                                                args_array_out = true;
-                                            };
-                                            if let Type::Struct(_) | Type::Primitive(_) = t {
-                                                if let Type::Struct(
-                                                    Struct::Big(s) | Struct::Small(s),
-                                                ) = t
-                                                {
-                                                    if s.contains_interfaces() {
-                                                        idlc_errors::unrecoverable!(
-                                                            "Struct with Object inside cannot be used as an array",
-                                                        );
-                                                    }
-                                                }
-                                                if cnt.is_some() {
-                                                    idlc_errors::unrecoverable!(
-                                                        "Interface `{}`, method `{}` should not have bounded array of primitive/struct",
-                                                        src.ident, f.ident
-                                                    );
-                                                }
-                                            }
-                                        }
-                                        ParamTypeOut::Reference(t) => {
-                                            if let Type::Interface(_) = t {
-                                                args_value_out = true;
-                                            };
-                                        }
-                                    },
-                                }
-                            }
-                            if (args_array_in && args_value_in)
-                                || (args_array_out && args_value_out)
-                            {
-                                idlc_errors::unrecoverable!(
-                                    "Interface `{}`, method `{}` has both object array and non-array object arguments",
-                                    src.ident, f.ident,
-                                );
+                        }
+                        InterfaceNode::Enum(e) => consts.add_ident(self, &e.ident, from, owned),
+                        InterfaceNode::Function(f) => {
+                            functions.add_ident(self, &f.ident, from, owned);
+                            op_codes.add(self, i128::from(f.id), &f.ident, from, owned);
+                            if owned {
+                                self.verify_function(from, f);
                             }
                         }
                     }
@@ -161,26 +257,110 @@ impl MirCompilerPass<'_> for InterfaceVerifier<'_> {
 }
 
 struct CollisionDetector<'a> {
-    src: &'a idlc_mir::Interface,
-    inner: HashSet<&'a idlc_ast::Ident>,
+    inner: HashMap<&'a idlc_ast::Ident, (&'a Interface, bool)>,
 }
 
 impl<'a> CollisionDetector<'a> {
-    pub fn new(src: &'a idlc_mir::Interface) -> Self {
+    pub fn new() -> Self {
+        Self {
+            inner: HashMap::new(),
+        }
+    }
+
+    pub fn add_ident(
+        &mut self,
+        verifier: &InterfaceVerifier<'_>,
+        ident: &'a idlc_ast::Ident,
+        from: &'a Interface,
+        owned: bool,
+    ) {
+        let Some(&(orig_from, orig_owned)) = self.inner.get(ident) else {
+            self.inner.insert(ident, (from, owned));
+            return;
+        };
+        // Collisions within a base are reported when verifying that base.
+        if !owned && !orig_owned {
+            return;
+        }
+
+        let orig = self.inner.get_key_value(ident).unwrap().0;
+        let mut diagnostic = Diagnostic::error(
+            codes::IDENT_COLLISION,
+            format!(
+                "Collision detected for identifier `{ident}`. Initially defined in interface `{}`, later defined again in `{}`",
+                orig_from.ident, from.ident,
+            ),
+        );
+        if verifier.is_local(from) {
+            diagnostic = diagnostic.with_label(Label::primary(ident.span, "defined again here"));
+        }
+        if verifier.is_local(orig_from) {
+            diagnostic = diagnostic.with_label(Label::secondary(
+                orig.span,
+                format!("`{ident}` initially defined here"),
+            ));
+        }
+        verifier.emit(diagnostic);
+    }
+}
+
+/// Detects op-codes or error values assigned twice within a hierarchy.
+struct ValueCollisions<'a> {
+    code: &'static str,
+    /// Name of the value, capitalized.
+    value: &'static str,
+    /// Kind of the declarations holding the values.
+    kind: &'static str,
+    inner: HashMap<i128, (&'a idlc_ast::Ident, &'a Interface, bool)>,
+}
+
+impl<'a> ValueCollisions<'a> {
+    pub fn new(code: &'static str, value: &'static str, kind: &'static str) -> Self {
         Self {
-            src,
-            inner: HashSet::new(),
+            code,
+            value,
+            kind,
+            inner: HashMap::new(),
         }
     }
 
-    pub fn add_ident(&mut self, ident: &'a idlc_ast::Ident, from: &'a idlc_mir::Interface) {
-        if !self.inner.insert(ident) {
-            let orig = self.inner.get(ident).unwrap();
-            idlc_errors::unrecoverable!(
-                "Collision deteced for identifier `{ident}`. Initially defined in interface `{}:{}`, later defined again at `{}:{}`",
-                self.src.ident, orig.span.start,
-                from.ident, ident.span.start,
-            );
+    pub fn add(
+        &mut self,
+        verifier: &InterfaceVerifier<'_>,
+        value: i128,
+        ident: &'a idlc_ast::Ident,
+        from: &'a Interface,
+        owned: bool,
+    ) {
+        let Some(&(orig, orig_from, orig_owned)) = self.inner.get(&value) else {
+            self.inner.insert(value, (ident, from, owned));
+            return;
+        };
+        // Collisions within a base are reported when verifying that base.
+        if !owned && !orig_owned {
+            return;
+        }
+
+        let mut diagnostic = Diagnostic::error(
+            self.code,
+            format!(
+                "{} `{value}` of {} `{}::{ident}` is already assigned to `{}::{orig}`",
+                self.value, self.kind, from.ident, orig_from.ident,
+            ),
+        )
+        .with_note(format!(
+            "{}s without a pinned value take the one following the previous {}",
+            self.kind, self.kind
+        ));
+        if verifier.is_local(from) {
+            diagnostic = diagnostic.with_label(Label::primary(ident.span, "assigned again here"));
+        }
+        if verifier.is_local(orig_from) {
+            diagnostic = diagnostic.with_label(Label::secondary(
+                orig.span,
+                format!("`{value}` first assigned here"),
+            ));
         }
+        verifier.emit(diagnostic);
     }
 }
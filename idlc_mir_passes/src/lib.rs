@@ -12,6 +12,8 @@
 //!    has the object inside
 //! 5. Cannot have input Object array + any type of input objects.
 //!    Same for Output
+//! 6. Op-codes and error values, pinned or not, should be in range and not be
+//!    duplicated
 
 pub trait MirCompilerPass<'mir> {
     type Output;
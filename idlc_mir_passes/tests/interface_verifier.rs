@@ -15,9 +15,10 @@ fn verify(a_idl: &str) {
 
     let ast = store.get_ast(&name).unwrap();
     let struct_ordering = Cycles::new(&store).run_pass(&ast).unwrap();
-    let _ = StructVerifier::run_pass(&store, &struct_ordering);
+    StructVerifier::run_pass(&store, &struct_ordering);
     let mir = idlc_mir::parse_to_mir(&ast, &mut store);
     idlc_mir_passes::interface_verifier::InterfaceVerifier::new(&mir).run_pass();
+    idlc_errors::assert_no_errors();
 }
 
 #[should_panic]
@@ -0,0 +1,179 @@
+// Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Protocol between idlc and out-of-process code generators.
+//!
+//! `idlc --plugin <PATH> -o <DIR>` runs the plugin with a JSON encoded
+//! [`Request`] on its stdin, containing the verified [`Mir`] of the input IDL.
+//! The plugin answers with a JSON encoded [`Response`] on its stdout, whose
+//! files are written relative to the output directory. Anything the plugin
+//! prints on stderr is forwarded to the user.
+//!
+//! A plugin written in Rust only needs to implement [`Generator`]:
+//! ```no_run
+//! struct Foo;
+//!
+//! impl idlc_codegen::Generator for Foo {
+//!     fn generate(mir: &idlc_mir::Mir) -> idlc_codegen::Descriptor {
+//!         vec![("foo.txt".into(), format!("{} nodes\n", mir.nodes.len()))]
+//!     }
+//! }
+//!
+//! fn main() {
+//!     idlc_plugin::run::<Foo>();
+//! }
+//! ```
+
+use std::io::{Read, Write};
+use std::path::{Component, Path, PathBuf};
+use std::process::{Command, ExitStatus, Stdio};
+
+use idlc_codegen::{Descriptor, Generator};
+use idlc_mir::Mir;
+use serde::{Deserialize, Serialize};
+
+/// Version of the [`Request`] and [`Response`] layout, plugins reject requests
+/// of other versions.
+pub const PROTOCOL_VERSION: u32 = 2;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Request {
+    pub protocol: u32,
+    pub mir: Mir,
+    /// Values of `--plugin-opt`, in the order they were passed.
+    pub parameters: Vec<String>,
+    /// Whether bundled parameters are ordered by size rather than by
+    /// alignment (`--bundle-params-by-size`). [`serve`] applies it while the
+    /// generator runs.
+    pub bundle_params_by_size: bool,
+}
+
+impl Request {
+    #[must_use]
+    pub const fn new(mir: Mir, parameters: Vec<String>) -> Self {
+        Self {
+            protocol: PROTOCOL_VERSION,
+            mir,
+            parameters,
+            bundle_params_by_size: false,
+        }
+    }
+
+    #[must_use]
+    pub const fn with_bundle_params_by_size(mut self, bundle_params_by_size: bool) -> Self {
+        self.bundle_params_by_size = bundle_params_by_size;
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Response {
+    /// Generated files, relative to the output directory.
+    Files(Descriptor),
+    /// Reason the plugin couldn't generate the files.
+    Error(String),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("Couldn't communicate with the plugin: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Malformed plugin message: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Plugin exited with {0}")]
+    Exit(ExitStatus),
+    #[error("Plugin failed: {0}")]
+    Plugin(String),
+    #[error("Plugin output `{}` isn't relative to the output directory", .0.display())]
+    OutputPath(PathBuf),
+}
+
+/// Runs `plugin` with `request` and returns the files it generated.
+pub fn invoke(plugin: &Path, request: &Request) -> Result<Descriptor, Error> {
+    let mut child = Command::new(plugin)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn()?;
+
+    let request = serde_json::to_vec(request)?;
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    // Written from another thread so a plugin replying before it consumed
+    // the whole request can't deadlock us.
+    let writer = std::thread::spawn(move || stdin.write_all(&request));
+    let output = child.wait_with_output()?;
+    if let Err(e) = writer.join().expect("writer thread panicked") {
+        // Plugins are free to stop reading once they've failed.
+        if output.status.success() {
+            return Err(e.into());
+        }
+    }
+
+    let response = match serde_json::from_slice(&output.stdout) {
+        Ok(response) => response,
+        Err(_) if !output.status.success() => return Err(Error::Exit(output.status)),
+        Err(e) => return Err(e.into()),
+    };
+    match response {
+        Response::Files(files) => {
+            if let Some((path, _)) = files.iter().find(|(path, _)| !is_relative(path)) {
+                return Err(Error::OutputPath(path.clone()));
+            }
+            Ok(files)
+        }
+        Response::Error(e) => Err(Error::Plugin(e)),
+    }
+}
+
+fn is_relative(path: &Path) -> bool {
+    path.components().next().is_some()
+        && path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+}
+
+/// Reads a [`Request`] from `input` and writes the [`Response`] of `generate`
+/// to `output`.
+///
+/// `generate` runs with the bundling order of the request, so the
+/// [`idlc_codegen::serialization::PackedPrimitives`] it builds match the
+/// bundles of the other generators.
+pub fn serve(
+    input: impl Read,
+    mut output: impl Write,
+    generate: impl FnOnce(&Request) -> Result<Descriptor, String>,
+) -> Result<(), Error> {
+    let request: Request = serde_json::from_reader(input)?;
+    let response = if request.protocol == PROTOCOL_VERSION {
+        let bundling = request.bundle_params_by_size;
+        match idlc_codegen::serialization::with_bundling(bundling, || generate(&request)) {
+            Ok(files) => Response::Files(files),
+            Err(e) => Response::Error(e),
+        }
+    } else {
+        Response::Error(format!(
+            "Unsupported protocol version {}, expected {PROTOCOL_VERSION}",
+            request.protocol
+        ))
+    };
+    serde_json::to_writer(&mut output, &response)?;
+    output.flush()?;
+    Ok(())
+}
+
+/// Entry point of plugins which only need the [`Mir`].
+pub fn run<G: Generator>() -> ! {
+    run_with(|request| Ok(G::generate(&request.mir)))
+}
+
+/// Entry point of plugins which also need the parameters of the [`Request`].
+pub fn run_with(generate: impl FnOnce(&Request) -> Result<Descriptor, String>) -> ! {
+    match serve(std::io::stdin().lock(), std::io::stdout().lock(), generate) {
+        Ok(()) => std::process::exit(0),
+        Err(e) => {
+            eprintln!("{e}");
+            std::process::exit(1)
+        }
+    }
+}
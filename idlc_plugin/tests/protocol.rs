@@ -0,0 +1,111 @@
+// Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
+// SPDX-License-Identifier: BSD-3-Clause
+
+use idlc_ast_passes::idl_store::IDLStore;
+use idlc_plugin::{serve, Request, Response, PROTOCOL_VERSION};
+
+fn create_mir(a_idl: &str) -> idlc_mir::Mir {
+    let mut store = IDLStore::new();
+    let name = std::path::PathBuf::from("plugin.idl");
+    let node = idlc_ast::from_string(name.clone(), a_idl, true).unwrap();
+    store.insert_canonical(&name, &node);
+
+    let ast = store.get_ast(&name).unwrap();
+    idlc_mir::parse_to_mir(&ast, &mut store)
+}
+
+fn respond(request: &Request) -> Response {
+    let input = serde_json::to_vec(request).unwrap();
+    let mut output = Vec::new();
+    serve(input.as_slice(), &mut output, |request| {
+        if request.parameters.iter().any(|p| p == "fail") {
+            return Err("asked to fail".to_string());
+        }
+        Ok(vec![(
+            "out.txt".into(),
+            format!("{} nodes", request.mir.nodes.len()),
+        )])
+    })
+    .unwrap();
+    serde_json::from_slice(&output).unwrap()
+}
+
+#[test]
+fn mir_round_trip() {
+    let mir = create_mir(
+        r"
+        struct S { uint32 a; ITest b; };
+        enum E : int8 { A = -1, B };
+        interface ITest {
+            error E_ONE;
+            #[version=1.2]
+            method a(in S s, out E e, in uint8[] arr, out ITest[2] objs);
+        };
+        ",
+    );
+    let request = Request::new(mir, vec!["opt".to_string()]);
+    let json = serde_json::to_string(&request).unwrap();
+    assert_eq!(serde_json::from_str::<Request>(&json).unwrap(), request);
+}
+
+#[test]
+fn generated_files() {
+    let request = Request::new(create_mir("const uint8 A = 1;"), Vec::new());
+    assert_eq!(
+        respond(&request),
+        Response::Files(vec![("out.txt".into(), "1 nodes".to_string())])
+    );
+}
+
+#[test]
+fn generator_failure() {
+    let request = Request::new(create_mir("const uint8 A = 1;"), vec!["fail".to_string()]);
+    assert_eq!(
+        respond(&request),
+        Response::Error("asked to fail".to_string())
+    );
+}
+
+#[test]
+fn protocol_mismatch() {
+    let mut request = Request::new(create_mir("const uint8 A = 1;"), Vec::new());
+    request.protocol = PROTOCOL_VERSION + 1;
+    assert!(matches!(respond(&request), Response::Error(e) if e.contains("protocol")));
+}
+
+#[test]
+fn applies_bundling() {
+    let mir = create_mir(
+        r"
+        struct Halves { uint16 a; uint16 b; uint16 c; uint16 d; };
+        interface ITest { method m(in uint32 x, in Halves h); };
+        ",
+    );
+    let bundled = |request: &Request| {
+        let input = serde_json::to_vec(request).unwrap();
+        let mut output = Vec::new();
+        serve(input.as_slice(), &mut output, |request| {
+            let Some(idlc_mir::Node::Interface(iface)) = request.mir.nodes.last() else {
+                return Err("expected an interface".to_string());
+            };
+            let Some(idlc_mir::InterfaceNode::Function(function)) = iface.nodes.first() else {
+                return Err("expected a method".to_string());
+            };
+            let packed = idlc_codegen::serialization::PackedPrimitives::new(function);
+            let idents: Vec<_> = packed.input_idents().map(ToString::to_string).collect();
+            Ok(vec![("out.txt".into(), idents.join(","))])
+        })
+        .unwrap();
+        serde_json::from_slice::<Response>(&output).unwrap()
+    };
+
+    let request = Request::new(mir, Vec::new());
+    assert_eq!(
+        bundled(&request),
+        Response::Files(vec![("out.txt".into(), "x,h".to_string())])
+    );
+    assert_eq!(
+        bundled(&request.with_bundle_params_by_size(true)),
+        Response::Files(vec![("out.txt".into(), "h,x".to_string())])
+    );
+}
@@ -0,0 +1,197 @@
+// Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Hooks of the async flavor of the bindings, generated with `--rust-async`.
+//!
+//! Invocations block until the object replies. The `_async` methods of the
+//! proxies hand them to the [`Executor`] set with [`set_executor`], which runs
+//! them off the thread polling the future, and also drives the futures of the
+//! async implementations wrapped in [`Blocking`].
+//!
+//! With tokio for instance:
+//! ```ignore
+//! struct Tokio(tokio::runtime::Handle);
+//!
+//! impl mink_object::asynchronous::Executor for Tokio {
+//!     fn blocking(
+//!         &self,
+//!         invocation: Box<dyn FnOnce() + Send>,
+//!     ) -> Pin<Box<dyn Future<Output = ()> + Send>> {
+//!         let task = self.0.spawn_blocking(invocation);
+//!         Box::pin(async move { task.await.expect("invocation panicked") })
+//!     }
+//!
+//!     // Called from the threads invoking the objects, which aren't running
+//!     // async tasks.
+//!     fn block_on(&self, task: Pin<&mut (dyn Future<Output = ()> + '_)>) {
+//!         self.0.block_on(task)
+//!     }
+//! }
+//! ```
+
+use alloc::boxed::Box;
+use alloc::sync::Arc;
+use core::future::Future;
+use core::ops::DerefMut;
+use core::pin::{pin, Pin};
+use core::task::{Context, Poll};
+
+use crate::wrapper::Mutex;
+
+/// Runs the invocations of the async proxies and the futures of the async
+/// implementations.
+pub trait Executor: Sync {
+    /// Runs `invocation`, which blocks until the object replies, returning a
+    /// future resolving once it has run.
+    ///
+    /// Runs it in place when the future is first polled by default, blocking
+    /// the thread polling it.
+    fn blocking(
+        &self,
+        invocation: Box<dyn FnOnce() + Send>,
+    ) -> Pin<Box<dyn Future<Output = ()> + Send>> {
+        Box::pin(async move { invocation() })
+    }
+
+    /// Polls `task` to completion from the thread invoking an async
+    /// implementation.
+    ///
+    /// Parks the thread between polls by default, or spins without `std`.
+    fn block_on(&self, task: Pin<&mut (dyn Future<Output = ()> + '_)>) {
+        poll_until_ready(task);
+    }
+}
+
+/// The [`Executor`] used until [`set_executor`] is called.
+struct InPlace;
+
+impl Executor for InPlace {}
+
+static EXECUTOR: Mutex<Option<&'static dyn Executor>> = Mutex::new(None);
+
+/// Locks `mutex` even if a panic poisoned it, the critical sections of this
+/// module only move values in and out so they can't leave one inconsistent.
+fn lock<T>(mutex: &Mutex<T>) -> impl DerefMut<Target = T> + '_ {
+    #[cfg(feature = "std")]
+    let guard = mutex
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    #[cfg(not(feature = "std"))]
+    let guard = match mutex.lock() {
+        Ok(guard) => guard,
+        Err(never) => match never {},
+    };
+    guard
+}
+
+/// Replaces the [`Executor`] of every object.
+pub fn set_executor(executor: &'static dyn Executor) {
+    *lock(&EXECUTOR) = Some(executor);
+}
+
+fn executor() -> &'static dyn Executor {
+    lock(&EXECUTOR).unwrap_or(&InPlace)
+}
+
+/// Runs `invocation` through [`Executor::blocking`], resolving to its result.
+///
+/// # Do not use in application code, this is a minkidl intrinsic.
+pub fn blocking<R: Send + 'static>(
+    invocation: impl FnOnce() -> R + Send + 'static,
+) -> impl Future<Output = R> + Send {
+    let output = Arc::new(Mutex::new(None));
+    let slot = Arc::clone(&output);
+    let task = executor().blocking(Box::new(move || {
+        let result = invocation();
+        *lock(&slot) = Some(result);
+    }));
+    async move {
+        task.await;
+        lock(&output)
+            .take()
+            .expect("`Executor::blocking` should run the invocation")
+    }
+}
+
+/// Polls `future` to completion through [`Executor::block_on`].
+///
+/// # Do not use in application code, this is a minkidl intrinsic.
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    let mut output = None;
+    {
+        let task = pin!(async {
+            output = Some(future.await);
+        });
+        executor().block_on(task);
+    }
+    output.expect("`Executor::block_on` should complete the task")
+}
+
+/// Implements the traits of the interfaces for their async implementation `T`,
+/// e.g. `IFoo::from(Blocking(foo))` for `foo` implementing `AsyncIIFoo`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Blocking<T>(pub T);
+
+#[cfg(feature = "std")]
+fn poll_until_ready(mut task: Pin<&mut (dyn Future<Output = ()> + '_)>) {
+    struct Unpark(std::thread::Thread);
+
+    impl alloc::task::Wake for Unpark {
+        fn wake(self: alloc::sync::Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    let waker = core::task::Waker::from(alloc::sync::Arc::new(Unpark(std::thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    while task.as_mut().poll(&mut cx) == Poll::Pending {
+        std::thread::park();
+    }
+}
+
+#[cfg(not(feature = "std"))]
+fn poll_until_ready(mut task: Pin<&mut (dyn Future<Output = ()> + '_)>) {
+    use core::task::{RawWaker, RawWakerVTable, Waker};
+
+    const VTABLE: RawWakerVTable = RawWakerVTable::new(
+        |_| RawWaker::new(core::ptr::null(), &VTABLE),
+        |_| (),
+        |_| (),
+        |_| (),
+    );
+    // SAFETY: The functions of the vtable don't use the data pointer.
+    let waker = unsafe { Waker::from_raw(RawWaker::new(core::ptr::null(), &VTABLE)) };
+    let mut cx = Context::from_waker(&waker);
+    while task.as_mut().poll(&mut cx) == Poll::Pending {
+        core::hint::spin_loop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Yield(bool);
+
+    impl Future for Yield {
+        type Output = u32;
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<u32> {
+            if self.0 {
+                Poll::Ready(7)
+            } else {
+                self.0 = true;
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        }
+    }
+
+    #[test]
+    fn runs_in_place() {
+        let mut value = 1;
+        assert_eq!(block_on(blocking(move || value + 1)), 2);
+        block_on(async { value += Yield(false).await });
+        assert_eq!(value, 8);
+    }
+}
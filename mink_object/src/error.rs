@@ -71,7 +71,7 @@ macro_rules! define_err {
         ($(#[$comment:meta] $var: ident = $val: expr;)+) => {
             $(
                 #[$comment]
-                pub const $var: crate::object::error::Error = unsafe { crate::object::Error::new_unchecked($val) };
+                pub const $var: crate::error::Error = unsafe { crate::Error::new_unchecked($val) };
             )+
         };
     }
@@ -3,12 +3,25 @@
 
 //! Provides Mink objects
 //!
-//! Most of the functionality in this file is intended only for internal use by
+//! Most of the functionality in this crate is intended only for internal use by
 //! code generated from minkidl. Applications should use objects primarily via
 //! the generated stub wrappers and invoke macros.
+//!
+//! Bindings generated with `idlc --rust --rust-runtime-path ::mink_object`
+//! refer to this crate, by default they expect a copy of it in the
+//! `crate::object` module.
+//!
+//! Without its default `std` feature, the crate only depends on `core` and
+//! `alloc`, as the bindings generated with `--rust-no-std` do.
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 
+extern crate alloc;
+
+pub mod asynchronous;
 pub mod error;
-#[cfg(feature = "std")]
+#[cfg(feature = "serde")]
+pub mod serde_array;
 pub mod wrapper;
 pub use error::Error;
 
@@ -21,6 +34,8 @@ pub type Ctx = *mut core::ffi::c_void;
 pub type Invoke = unsafe extern "C" fn(h: Ctx, op: Op, args: *mut Arg, counts: Counts) -> i32;
 
 #[repr(C)]
+// `PartialEq` compares the same fields, only without comparing function pointers directly.
+#[allow(clippy::derived_hash_with_manual_eq)]
 #[derive(Debug, Hash)]
 /// Mink [`Object`] is an opaque type that allows you to IPC between services.
 pub struct Object {
@@ -78,6 +93,11 @@ pub const fn pack_counts(
     n_buffers_in | (n_buffers_out << 4) | (n_objects_in << 8) | (n_objects_out << 12)
 }
 
+// Like the proxies of the interfaces wrapping it, an object can be invoked from
+// any thread.
+unsafe impl Send for Object {}
+unsafe impl Sync for Object {}
+
 impl Drop for Object {
     /// This calls the object's [`OP_RELEASE`] function.
     ///
@@ -127,7 +147,7 @@ impl Clone for Object {
 impl PartialEq for Object {
     #[inline]
     fn eq(&self, other: &Self) -> bool {
-        std::ptr::fn_addr_eq(self.invoke, other.invoke) && self.context == other.context
+        core::ptr::fn_addr_eq(self.invoke, other.invoke) && self.context == other.context
     }
 }
 
@@ -235,13 +255,12 @@ impl Object {
     }
 
     #[inline]
-    #[cfg(feature = "std")]
     /// Obtain the pointer to context if this object's invoke matches given
     /// value.
     ///
     /// # Do not use in application code, this is a minkidl intrinsic.
     fn get_raw_context(&self, invoke: Invoke) -> Option<Ctx> {
-        if !std::ptr::fn_addr_eq(self.invoke, invoke) || self.context.is_null() {
+        if !core::ptr::fn_addr_eq(self.invoke, invoke) || self.context.is_null() {
             None
         } else {
             Some(self.context)
@@ -0,0 +1,58 @@
+// Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Serde support of the array fields of the structs generated with `--serde`,
+//! as `#[serde(with = "serde_array")]`.
+//!
+//! Serde only implements its traits for arrays of up to 32 elements, this
+//! module handles any length with the same format: a tuple of the elements.
+
+use alloc::vec::Vec;
+use core::{fmt, marker::PhantomData};
+
+use serde::de::{Error, SeqAccess, Visitor};
+use serde::ser::SerializeTuple;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+pub fn serialize<S, T, const N: usize>(array: &[T; N], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    T: Serialize,
+{
+    let mut tuple = serializer.serialize_tuple(N)?;
+    for element in array {
+        tuple.serialize_element(element)?;
+    }
+    tuple.end()
+}
+
+pub fn deserialize<'de, D, T, const N: usize>(deserializer: D) -> Result<[T; N], D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    struct ArrayVisitor<T, const N: usize>(PhantomData<T>);
+
+    impl<'de, T: Deserialize<'de>, const N: usize> Visitor<'de> for ArrayVisitor<T, N> {
+        type Value = [T; N];
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "an array of {N} elements")
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            let mut elements = Vec::with_capacity(N);
+            while let Some(element) = seq.next_element()? {
+                if elements.len() == N {
+                    return Err(A::Error::invalid_length(N + 1, &self));
+                }
+                elements.push(element);
+            }
+            elements
+                .try_into()
+                .map_err(|elements: Vec<T>| A::Error::invalid_length(elements.len(), &self))
+        }
+    }
+
+    deserializer.deserialize_tuple(N, ArrayVisitor::<T, N>(PhantomData))
+}
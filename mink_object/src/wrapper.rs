@@ -1,10 +1,13 @@
 // Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
 // SPDX-License-Identifier: BSD-3-Clause
 
-#![cfg(feature = "std")]
-
+use alloc::boxed::Box;
 use core::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::Mutex;
+
+#[cfg(not(feature = "std"))]
+pub use spin::Mutex;
+#[cfg(feature = "std")]
+pub use std::sync::Mutex;
 
 /// Wrapper over an interface `T` which is an interface, and the the concrete implementation of
 /// some type `I` must be `dyn T`.
@@ -13,7 +16,7 @@ use std::sync::Mutex;
 /// because this is read only and never retagged to write into after creation.
 pub struct Wrapper<T: ?Sized + 'static> {
     pub refs: AtomicUsize,
-    pub type_id: std::any::TypeId,
+    pub type_id: core::any::TypeId,
     pub inner: Mutex<Box<T>>,
 }
 
@@ -31,7 +34,7 @@ impl<T: ?Sized + 'static> Wrapper<T> {
     pub unsafe fn new<I: 'static>(inner: Box<T>) -> Self {
         Self {
             refs: AtomicUsize::new(1),
-            type_id: std::any::TypeId::of::<I>(),
+            type_id: core::any::TypeId::of::<I>(),
             inner: Mutex::new(inner),
         }
     }
@@ -71,7 +74,7 @@ pub unsafe fn retain<T: ?Sized + 'static>(wrapper: *mut Wrapper<T>) -> i32 {
 /// double-frees
 pub unsafe fn release<T: ?Sized + 'static>(wrapper: *mut Wrapper<T>) -> i32 {
     match (*wrapper).refs.fetch_sub(1, Ordering::SeqCst) {
-        1 => std::mem::drop(Box::from_raw(wrapper)),
+        1 => core::mem::drop(Box::from_raw(wrapper)),
         0 => {
             unreachable!()
         }
@@ -82,12 +85,12 @@ pub unsafe fn release<T: ?Sized + 'static>(wrapper: *mut Wrapper<T>) -> i32 {
 }
 
 #[inline]
-/// Downcasts to a concrete type `I` if the `Wrapper<T>` contains the [`std::any::TypeId`] of `I`.
+/// Downcasts to a concrete type `I` if the `Wrapper<T>` contains the [`core::any::TypeId`] of `I`.
 ///
 /// This function also ensures that the calling function does match the [`super::Object::invoke`] field
 /// using the `marker` variable
 ///
-/// Returns None if [`super::Object`] doesn't match `marker` or if [`std::mem::TypeId`] doesn't
+/// Returns None if [`super::Object`] doesn't match `marker` or if [`core::any::TypeId`] doesn't
 /// match.
 pub fn downcast_concrete<R, I: 'static, T: ?Sized + 'static>(
     object: &super::Object,
@@ -97,7 +100,7 @@ pub fn downcast_concrete<R, I: 'static, T: ?Sized + 'static>(
     let wrapper = object
         .get_raw_context(marker)
         .map(<super::Ctx>::cast::<Wrapper<T>>)?;
-    if unsafe { (*wrapper).type_id } == std::any::TypeId::of::<I>() {
+    if unsafe { (*wrapper).type_id } == core::any::TypeId::of::<I>() {
         let locked = unsafe { (*wrapper).inner.lock().unwrap() };
         let ptr_t = Box::as_ref(&locked) as *const T;
         // Safety: Address is actually an `I` and this retag is exclusive
@@ -110,3 +113,75 @@ pub fn downcast_concrete<R, I: 'static, T: ?Sized + 'static>(
         None
     }
 }
+
+#[cfg(not(feature = "std"))]
+mod spin {
+    //! Spin lock standing in for `std::sync::Mutex` without `std`.
+
+    use core::cell::UnsafeCell;
+    use core::ops::{Deref, DerefMut};
+    use core::sync::atomic::{AtomicBool, Ordering};
+
+    pub struct Mutex<T: ?Sized> {
+        locked: AtomicBool,
+        value: UnsafeCell<T>,
+    }
+
+    unsafe impl<T: ?Sized + Send> Send for Mutex<T> {}
+    unsafe impl<T: ?Sized + Send> Sync for Mutex<T> {}
+
+    impl<T> Mutex<T> {
+        #[inline]
+        pub const fn new(value: T) -> Self {
+            Self {
+                locked: AtomicBool::new(false),
+                value: UnsafeCell::new(value),
+            }
+        }
+    }
+
+    impl<T: ?Sized> Mutex<T> {
+        #[inline]
+        /// Spins until the lock is acquired, this never fails but returns a
+        /// [`Result`] like `std::sync::Mutex::lock`.
+        pub fn lock(&self) -> Result<MutexGuard<'_, T>, core::convert::Infallible> {
+            while self
+                .locked
+                .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+                .is_err()
+            {
+                core::hint::spin_loop();
+            }
+            Ok(MutexGuard { mutex: self })
+        }
+    }
+
+    pub struct MutexGuard<'a, T: ?Sized> {
+        mutex: &'a Mutex<T>,
+    }
+
+    impl<T: ?Sized> Deref for MutexGuard<'_, T> {
+        type Target = T;
+
+        #[inline]
+        fn deref(&self) -> &T {
+            // Safety: the lock is held for the lifetime of the guard.
+            unsafe { &*self.mutex.value.get() }
+        }
+    }
+
+    impl<T: ?Sized> DerefMut for MutexGuard<'_, T> {
+        #[inline]
+        fn deref_mut(&mut self) -> &mut T {
+            // Safety: the lock is held for the lifetime of the guard.
+            unsafe { &mut *self.mutex.value.get() }
+        }
+    }
+
+    impl<T: ?Sized> Drop for MutexGuard<'_, T> {
+        #[inline]
+        fn drop(&mut self) {
+            self.mutex.locked.store(false, Ordering::Release);
+        }
+    }
+}
@@ -32,13 +32,21 @@ enum Language {
     Rust,
     C { is_skel: bool },
     Cpp { is_skel: bool },
+    CppMock,
 }
 
 fn build_interface(interface: &Path, output: &Path, lang: Language) {
     let mut args = vec![interface.to_str().unwrap()];
     match lang {
         Language::Rust => {
-            args.push("--rust");
+            args.extend_from_slice(&[
+                "--rust",
+                "--mock",
+                "--rust-async",
+                "--serde",
+                "--rust-runtime-path",
+                "::mink_object",
+            ]);
         }
         Language::C { is_skel } => {
             if is_skel {
@@ -49,8 +57,13 @@ fn build_interface(interface: &Path, output: &Path, lang: Language) {
             args.push("--cpp");
             if is_skel {
                 args.push("--skel");
+            } else {
+                args.push("--serde");
             }
         }
+        Language::CppMock => {
+            args.extend_from_slice(&["--cpp", "--mock"]);
+        }
     };
     args.extend_from_slice(&["-o", output.to_str().unwrap()]);
     let mut command = Command::new(idlc())
@@ -82,7 +95,12 @@ fn main() {
     println!("cargo:rerun-if-changed={}", idlc().display());
     println!("cargo:rerun-if-changed=idl/");
 
-    let interfaces = [Path::new("idl/ITest.idl"), Path::new("idl/ITest3.idl")];
+    let interfaces = [
+        Path::new("idl/ITest.idl"),
+        Path::new("idl/ITest3.idl"),
+        Path::new("idl/Flags.idl"),
+        Path::new("idl/Modes.idl"),
+    ];
 
     for interface in interfaces {
         let stem = interface.file_stem().unwrap().to_str().unwrap();
@@ -107,6 +125,11 @@ fn main() {
             &cpp_generated(Some(&PathBuf::from(format!("{stem}_invoke.hpp")))),
             Language::Cpp { is_skel: true },
         );
+        build_interface(
+            interface,
+            &cpp_generated(Some(&PathBuf::from(format!("{stem}_mock.hpp")))),
+            Language::CppMock,
+        );
         build_interface(interface, &rust_generated(), Language::Rust);
     }
 
@@ -132,4 +155,20 @@ fn main() {
     cpp_ffi.flag("-Wno-missing-field-initializers");
     cpp_ffi.flag("-Werror");
     cpp_ffi.compile("cpp-ffi");
+
+    // The GoogleMock headers are only compiled, against the stand-in of
+    // `cpp/gmock_stub` unless `GMOCK_INCLUDE` points to GoogleMock's.
+    println!("cargo:rerun-if-env-changed=GMOCK_INCLUDE");
+    let mut cpp_mock = cc::Build::new();
+    cpp_mock.file("cpp/mock.cpp");
+    cpp_mock.cpp(true);
+    cpp_mock.std("c++17");
+    cpp_mock.include("c");
+    cpp_mock.include("cpp");
+    cpp_mock.include(std::env::var_os("GMOCK_INCLUDE").unwrap_or_else(|| "cpp/gmock_stub".into()));
+    cpp_mock.include(c_generated(None));
+    cpp_mock.include(cpp_generated(None));
+    cpp_mock.flag("-Wno-unused-parameter");
+    cpp_mock.flag("-Werror");
+    cpp_mock.compile("cpp-mock");
 }
@@ -0,0 +1,12 @@
+// Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
+// SPDX-License-Identifier: BSD-3-Clause
+
+// Stand-in for GoogleMock when `GMOCK_INCLUDE` isn't set: each `MOCK_METHOD`
+// becomes a method with the same specifiers, which is enough to check that the
+// generated mocks override every method of the skeletons.
+
+#pragma once
+
+#define IDLC_MOCK_SPECS(...) __VA_ARGS__
+#define MOCK_METHOD(ret, name, args, specs)                                   \
+    ret name args IDLC_MOCK_SPECS specs { return ret(); }
@@ -7,6 +7,8 @@
 #include <stdio.h>
 #include <stdlib.h>
 
+#include <sstream>
+
 #include "ITest.hpp"
 #include "ITest_invoke.hpp"
 
@@ -524,3 +526,13 @@ Object create_cpp_itest3() {
   return (Object){ImplBase::invoke, me};
 }
 }
+
+extern "C" {
+size_t cpp_digest_json(const Digest *digest, char *json, size_t size) {
+  std::ostringstream os;
+  os << *digest;
+  const std::string printed = os.str();
+  snprintf(json, size, "%s", printed.c_str());
+  return printed.size();
+}
+}
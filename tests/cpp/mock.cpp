@@ -0,0 +1,16 @@
+// Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
+// SPDX-License-Identifier: BSD-3-Clause
+
+#include "ITest_mock.hpp"
+#include "ITest3_mock.hpp"
+
+// Only compiled: instantiating the mocks fails if they miss a method of the
+// skeletons they derive from.
+void instantiate_mocks() {
+    MockITest1 itest1;
+    MockITest2 itest2;
+    MockITest3 itest3;
+    (void)itest1;
+    (void)itest2;
+    (void)itest3;
+}
@@ -0,0 +1,43 @@
+// Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::{path::PathBuf, process::Command};
+
+fn main() {
+    let idlc =
+        PathBuf::from(std::env::var_os("IDLC").unwrap_or_else(|| "../../target/debug/idlc".into()));
+    assert!(
+        idlc.exists(),
+        "`idlc` not found @ {:?} nor was it set using the `IDLC` environment variable",
+        idlc,
+    );
+    let mut output = PathBuf::from(std::env::var_os("OUT_DIR").expect("OUT_DIR not set"));
+    output.push("rust");
+    _ = std::fs::create_dir(&output);
+
+    println!("cargo:rerun-if-changed={}", idlc.display());
+    println!("cargo:rerun-if-changed=idl/");
+    println!("cargo:rerun-if-changed=../idl/");
+
+    let interfaces = ["../idl/ITest.idl", "../idl/ITest3.idl", "idl/IEcho.idl"];
+    for interface in interfaces {
+        let mut command = Command::new(&idlc)
+            .args([
+                interface,
+                "--rust",
+                "--rust-no-std",
+                "--mock",
+                "--rust-async",
+                "--rust-runtime-path",
+                "::mink_object",
+                "-o",
+                output.to_str().unwrap(),
+            ])
+            .spawn()
+            .expect("`IDLC` should've been able to spawn");
+        assert!(
+            command.wait().unwrap().success(),
+            "`IDLC` didn't complete successfully"
+        );
+    }
+}
@@ -0,0 +1,28 @@
+// Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
+// SPDX-License-Identifier: BSD-3-Clause
+
+use alloc::string::{String, ToString};
+
+use crate::interfaces::iecho::{self, IIEcho};
+
+pub struct Echo;
+
+impl IIEcho for Echo {
+    fn r#echo(&mut self, text: &str) -> Result<String, iecho::Error> {
+        if text.is_empty() {
+            return Err(iecho::ERROR_EMPTY);
+        }
+        Ok(text.to_string())
+    }
+
+    fn r#sum(&mut self, values: &[u32]) -> Result<u64, iecho::Error> {
+        Ok(values.iter().copied().map(u64::from).sum())
+    }
+
+    fn r#swap(
+        &mut self,
+        other: Option<&iecho::IEcho>,
+    ) -> Result<Option<iecho::IEcho>, iecho::Error> {
+        Ok(other.cloned())
+    }
+}
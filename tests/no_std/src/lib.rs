@@ -0,0 +1,38 @@
+// Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Builds the bindings generated with `--rust-no-std` without `std`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[allow(
+    unused,
+    nonstandard_style,
+    clippy::all,
+    clippy::pedantic,
+    clippy::nursery
+)]
+pub mod interfaces {
+    pub mod itest {
+        include!(concat!(env!("OUT_DIR"), "/rust/itest.rs"));
+    }
+    pub mod itest1 {
+        include!(concat!(env!("OUT_DIR"), "/rust/itest1.rs"));
+    }
+    pub mod itest2 {
+        include!(concat!(env!("OUT_DIR"), "/rust/itest2.rs"));
+    }
+    pub mod itest3 {
+        include!(concat!(env!("OUT_DIR"), "/rust/itest3.rs"));
+    }
+    pub mod itest4 {
+        include!(concat!(env!("OUT_DIR"), "/rust/itest4.rs"));
+    }
+    pub mod iecho {
+        include!(concat!(env!("OUT_DIR"), "/rust/iecho.rs"));
+    }
+}
+
+pub mod implementation;
@@ -0,0 +1,50 @@
+// Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
+// SPDX-License-Identifier: BSD-3-Clause
+
+use idlc_test_no_std::{implementation::Echo, interfaces::iecho};
+
+#[test]
+fn to_rust() {
+    let echo = iecho::IEcho::from(Echo);
+
+    assert_eq!(echo.echo("no_std").unwrap(), "no_std");
+    assert_eq!(echo.echo("").unwrap_err(), iecho::ERROR_EMPTY);
+    assert_eq!(
+        echo.echo(&"a".repeat(65)).unwrap_err(),
+        mink_object::error::generic::INVALID.into()
+    );
+    assert_eq!(
+        echo.sum(&[1, 2, u32::MAX]).unwrap(),
+        3 + u64::from(u32::MAX)
+    );
+
+    let other = iecho::IEcho::from(Echo);
+    let swapped = echo.swap(Some(&other)).unwrap().unwrap();
+    assert_eq!(swapped.echo("swapped").unwrap(), "swapped");
+    assert!(echo.swap(None).unwrap().is_none());
+
+    let echoed = mink_object::asynchronous::block_on(echo.echo_async("async"));
+    assert_eq!(echoed.unwrap(), "async");
+}
+
+#[test]
+fn to_mock() {
+    let mut mock = iecho::MockIEcho::new();
+    mock.expect_sum(|values| Ok(values.len() as u64))
+        .expect_echo(|_| Err(iecho::ERROR_EMPTY))
+        .expect_echo(|_| Ok("a".repeat(65)));
+    let echo = iecho::IEcho::from(mock);
+
+    assert_eq!(echo.sum(&[7, 7]).unwrap(), 2);
+    assert_eq!(echo.echo("mocked").unwrap_err(), iecho::ERROR_EMPTY);
+    // Longer than the `string<64>` of the IDL
+    assert_eq!(
+        echo.echo("mocked").unwrap_err(),
+        mink_object::error::generic::INVALID.into()
+    );
+    iecho::downcast_concrete(&echo, iecho::MockIEcho::checkpoint)
+        .expect("object should be a `MockIEcho`")
+        .expect("every call should have happened");
+    // An unexpected call
+    assert_eq!(echo.sum(&[7]), Err(mink_object::error::generic::GENERIC.into()));
+}
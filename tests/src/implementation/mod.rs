@@ -19,9 +19,9 @@ pub use test2::ITest2;
 
 fn test_singular_object(
     o: Option<&crate::interfaces::itest1::ITest1>,
-) -> Result<(), crate::object::Error> {
+) -> Result<(), mink_object::Error> {
     use crate::interfaces::itest::*;
-    use crate::object::error::transport::BADOBJ;
+    use mink_object::error::transport::BADOBJ;
 
     let Some(o) = o else {
         return Err(BADOBJ);
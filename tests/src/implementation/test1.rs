@@ -91,11 +91,11 @@ macro_rules! itest1_impl {
                 _: &mut [u8],
                 _: &mut usize,
                 r#input: u16,
-                _: Option<&crate::object::Object>,
+                _: Option<&mink_object::Object>,
                 r#input2: u32,
                 _: &mut [u8],
                 _: &mut usize,
-            ) -> Result<(u16, Option<crate::object::Object>, u64), itest1::Error> {
+            ) -> Result<(u16, Option<mink_object::Object>, u64), itest1::Error> {
                 if !(input == itest::SUCCESS_FLAG as u16 && input2 == itest::SUCCESS_FLAG) {
                     dbg!(input, input2);
                     Err(itest1::MISMATCH)
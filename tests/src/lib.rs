@@ -1,8 +1,6 @@
 // Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
 // SPDX-License-Identifier: BSD-3-Clause
 
-pub mod object;
-
 #[allow(
     unused,
     nonstandard_style,
@@ -26,6 +24,18 @@ pub mod interfaces {
     pub mod itest4 {
         include!(concat!(env!("OUT_DIR"), "/rust/itest4.rs"));
     }
+    pub mod flags {
+        include!(concat!(env!("OUT_DIR"), "/rust/flags.rs"));
+    }
+    pub mod iflags {
+        include!(concat!(env!("OUT_DIR"), "/rust/iflags.rs"));
+    }
+    pub mod modes {
+        include!(concat!(env!("OUT_DIR"), "/rust/modes.rs"));
+    }
+    pub mod imodes {
+        include!(concat!(env!("OUT_DIR"), "/rust/imodes.rs"));
+    }
 }
 
 pub mod implementation;
@@ -55,5 +65,14 @@ pub mod cpp {
 
         #[link_name = "create_cpp_itest3"]
         pub fn create_itest3() -> Option<crate::interfaces::itest3::ITest3>;
+
+        /// Prints `digest` as JSON with its `operator<<` into `json`, returns
+        /// the length of the JSON.
+        #[link_name = "cpp_digest_json"]
+        pub fn digest_json(
+            digest: &crate::interfaces::itest::Digest,
+            json: *mut core::ffi::c_char,
+            size: usize,
+        ) -> usize;
     }
 }
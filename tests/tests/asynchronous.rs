@@ -0,0 +1,86 @@
+// Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use idlc_test::{
+    implementation::ITest1,
+    interfaces::{
+        itest1,
+        itest2::{self, AsyncIITest2, ITest2},
+    },
+};
+use mink_object::asynchronous::{block_on, set_executor, Blocking, Executor};
+
+// The `_async` proxy methods and the `AsyncI` traits of `--rust-async` go
+// through the executor hook of `mink_object`.
+
+struct Counting {
+    blocking: AtomicUsize,
+    block_on: AtomicUsize,
+}
+
+impl Executor for Counting {
+    fn blocking(
+        &self,
+        invocation: Box<dyn FnOnce() + Send>,
+    ) -> std::pin::Pin<Box<dyn std::future::Future<Output = ()> + Send>> {
+        self.blocking.fetch_add(1, Ordering::Relaxed);
+        // Runs the invocation on its own thread.
+        let invocation = std::thread::spawn(invocation);
+        Box::pin(async move { invocation.join().unwrap() })
+    }
+
+    fn block_on(&self, task: std::pin::Pin<&mut (dyn std::future::Future<Output = ()> + '_)>) {
+        self.block_on.fetch_add(1, Ordering::Relaxed);
+        // Polls the task with the default of the trait.
+        struct InPlace;
+        impl Executor for InPlace {}
+        InPlace.block_on(task);
+    }
+}
+
+static EXECUTOR: Counting = Counting {
+    blocking: AtomicUsize::new(0),
+    block_on: AtomicUsize::new(0),
+};
+
+struct AsyncTest2;
+
+impl AsyncIITest2 for AsyncTest2 {
+    async fn entrypoint(&mut self, o: Option<&itest1::ITest1>) -> Result<(), itest2::Error> {
+        let o = o.ok_or(mink_object::error::generic::INVALID)?;
+        assert_eq!(o.add_1000_async(1).await, Ok(1001));
+        assert_eq!(o.single_in_async(0xdead).await, Ok(()));
+        Ok(())
+    }
+}
+
+fn assert_send<T: Send>(value: T) -> T {
+    value
+}
+
+#[test]
+fn to_rust() {
+    set_executor(&EXECUTOR);
+    let blocking = EXECUTOR.blocking.load(Ordering::Relaxed);
+    let polled = EXECUTOR.block_on.load(Ordering::Relaxed);
+
+    // ITest2 implemented asynchronously in Rust, called through its async proxy
+    let rust_itest2: ITest2 = Blocking(AsyncTest2).into();
+    let rust_itest1: itest1::ITest1 = ITest1::default().into();
+    let entrypoint = assert_send(rust_itest2.entrypoint_async(Some(&rust_itest1)));
+    assert_eq!(block_on(entrypoint), Ok(()));
+
+    // The synchronous API is unchanged
+    assert_eq!(rust_itest2.entrypoint(Some(&rust_itest1)), Ok(()));
+    assert_eq!(
+        rust_itest2.entrypoint(None),
+        Err(mink_object::error::generic::INVALID.into())
+    );
+
+    // One `entrypoint_async` and two calls per `entrypoint`, other tests only
+    // add to the counts.
+    assert!(EXECUTOR.blocking.load(Ordering::Relaxed) >= blocking + 3);
+    assert!(EXECUTOR.block_on.load(Ordering::Relaxed) >= polled + 3);
+}
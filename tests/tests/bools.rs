@@ -0,0 +1,51 @@
+// Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
+// SPDX-License-Identifier: BSD-3-Clause
+
+use idlc_test::interfaces::{flags::Flags, iflags::IFlags};
+use mink_object::error::generic::INVALID;
+use mink_object::{Arg, Counts, Ctx, Object, Op, TypedObject, OK};
+
+/// Object filling every output buffer with `2`, which isn't a valid `bool`.
+unsafe extern "C" fn invoke(_: Ctx, _: Op, args: *mut Arg, counts: Counts) -> i32 {
+    let inputs = (counts & 0xf) as usize;
+    let outputs = ((counts >> 4) & 0xf) as usize;
+    for i in inputs..inputs + outputs {
+        let b = &(*args.add(i)).b;
+        core::ptr::write_bytes(b.ptr.cast::<u8>(), 2, b.size);
+    }
+    OK
+}
+
+fn invalid_bools() -> IFlags {
+    unsafe {
+        IFlags::from_raw(Object::create(
+            invoke,
+            core::ptr::NonNull::dangling().as_ptr(),
+        ))
+    }
+}
+
+#[test]
+fn rejects_invalid_output_bools() {
+    let flags = invalid_bools();
+    assert_eq!(flags.single(), Err(INVALID.into()));
+    assert_eq!(flags.bundled(), Err(INVALID.into()));
+    assert_eq!(flags.flags(), Err(INVALID.into()));
+
+    // Both buffers are left holding valid `bool`s.
+    let mut bits = [true; 3];
+    let mut bits_len = 0;
+    let flag = Flags {
+        id: 0,
+        on: true,
+        reserved: [0; 3],
+    };
+    let mut structs = [flag; 2];
+    let mut structs_len = 0;
+    assert_eq!(
+        flags.buffers(&mut bits, &mut bits_len, &mut structs, &mut structs_len),
+        Err(INVALID.into())
+    );
+    assert_eq!(bits, [false; 3]);
+    assert!(structs.iter().all(|s| !s.on), "{structs:?}");
+}
@@ -0,0 +1,49 @@
+// Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
+// SPDX-License-Identifier: BSD-3-Clause
+
+use idlc_test::interfaces::imodes::{Error, IIModes, IModes};
+use idlc_test::interfaces::modes::Mode;
+use mink_object::error::generic::INVALID;
+use mink_object::{Arg, Counts, Ctx, Object, Op, TypedObject, OK};
+
+struct Toggle;
+
+impl IIModes for Toggle {
+    fn r#toggle(&mut self, mode: Mode, times: u32) -> Result<(Mode, u8), Error> {
+        let toggled = match (mode, times % 2) {
+            (mode, 0) => mode,
+            (Mode::OFF, _) => Mode::ON,
+            (Mode::ON, _) => Mode::OFF,
+        };
+        Ok((toggled, times as u8))
+    }
+}
+
+/// Object filling every output buffer with `0xff`, which isn't a `Mode`.
+unsafe extern "C" fn invoke(_: Ctx, _: Op, args: *mut Arg, counts: Counts) -> i32 {
+    let inputs = (counts & 0xf) as usize;
+    let outputs = ((counts >> 4) & 0xf) as usize;
+    for i in inputs..inputs + outputs {
+        let b = &(*args.add(i)).b;
+        core::ptr::write_bytes(b.ptr.cast::<u8>(), 0xff, b.size);
+    }
+    OK
+}
+
+#[test]
+fn bundles_enums() {
+    let modes = IModes::from(Toggle);
+    assert_eq!(modes.toggle(Mode::OFF, 3), Ok((Mode::ON, 3)));
+    assert_eq!(modes.toggle(Mode::ON, 2), Ok((Mode::ON, 2)));
+}
+
+#[test]
+fn rejects_unknown_bundled_enums() {
+    let modes = unsafe {
+        IModes::from_raw(Object::create(
+            invoke,
+            core::ptr::NonNull::dangling().as_ptr(),
+        ))
+    };
+    assert_eq!(modes.toggle(Mode::OFF, 1), Err(INVALID.into()));
+}
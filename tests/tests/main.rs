@@ -90,3 +90,75 @@ fn implementation_and_invoke_send() {
         threads.into_iter().for_each(|t| t.join().unwrap());
     })
 }
+
+#[test]
+fn to_mock() {
+    use idlc_test::interfaces::{itest2::MockITest2, itest3, itest3::MockITest3};
+
+    // ITest3 mocked in Rust, its ITest1 methods included
+    let mut mock = MockITest3::new();
+    mock.expect_add_1000(|a| Ok(a + 1000))
+        .expect_extra_test3(|| Err(itest3::Error::from(mink_object::error::generic::MEM)));
+    let rust_itest3: itest3::ITest3 = mock.into();
+    assert_eq!(rust_itest3.add_1000(5), Ok(1005));
+    assert_eq!(
+        rust_itest3.extra_test3(),
+        Err(mink_object::error::generic::MEM.into())
+    );
+
+    // ITest2 mocked in Rust, receiving an ITest1 implemented in Rust
+    let mut mock = MockITest2::new();
+    mock.expect_entrypoint(|o| {
+        assert_eq!(o.unwrap().add_1000(1), Ok(1001));
+        Ok(())
+    });
+    let rust_itest2: ITest2 = mock.into();
+    let rust_itest1 = ITest1::default().into();
+    assert_eq!(rust_itest2.entrypoint(Some(&rust_itest1)), Ok(()));
+}
+
+#[test]
+fn mock_checkpoint() {
+    use idlc_test::interfaces::itest1::{self, MockITest1};
+
+    let mut mock = MockITest1::new();
+    mock.expect_single_in(|_| Ok(()));
+    assert_eq!(
+        mock.checkpoint(),
+        Err(vec![
+            "1 expected call(s) of `ITest1::single_in` never happened".to_string()
+        ])
+    );
+
+    let rust_itest1: itest1::ITest1 = mock.into();
+    assert_eq!(rust_itest1.single_in(1), Ok(()));
+    itest1::downcast_concrete(&rust_itest1, MockITest1::checkpoint)
+        .expect("object should be a `MockITest1`")
+        .expect("every call should have happened");
+}
+
+#[test]
+fn mock_failures() {
+    use idlc_test::interfaces::itest1::{self, MockITest1};
+    use mink_object::error::generic::GENERIC;
+
+    // Failing inside the invoke layer mustn't abort the tests
+    let mut mock = MockITest1::new();
+    mock.expect_single_in(|input| {
+        assert_eq!(input, 1);
+        Ok(())
+    });
+    let rust_itest1: itest1::ITest1 = mock.into();
+    assert_eq!(rust_itest1.single_in(2), Err(GENERIC.into()));
+    assert_eq!(rust_itest1.no_args(), Err(GENERIC.into()));
+
+    let failures = itest1::downcast_concrete(&rust_itest1, MockITest1::checkpoint)
+        .expect("object should be a `MockITest1`")
+        .unwrap_err();
+    assert_eq!(failures.len(), 2, "{failures:?}");
+    assert!(
+        failures[0].starts_with("`ITest1::single_in` panicked: assertion `left == right` failed"),
+        "{failures:?}"
+    );
+    assert_eq!(failures[1], "unexpected call of `ITest1::no_args`");
+}
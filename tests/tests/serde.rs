@@ -0,0 +1,58 @@
+// Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
+// SPDX-License-Identifier: BSD-3-Clause
+
+use idlc_test::interfaces::itest::{Digest, F2};
+
+fn digest() -> Digest {
+    Digest {
+        bytes: core::array::from_fn(|i| i as u8 * 6),
+        halves: [F2 { a: 1, b: 2 }, F2 { a: 254, b: 255 }],
+        ratio: 0.1,
+        len: 40,
+    }
+}
+
+fn cpp_json(digest: &Digest) -> String {
+    let mut json = vec![0u8; 512];
+    let len = unsafe { idlc_test::cpp::digest_json(digest, json.as_mut_ptr().cast(), json.len()) };
+    assert!(len < json.len());
+    json.truncate(len);
+    String::from_utf8(json).unwrap()
+}
+
+#[test]
+fn round_trips() {
+    let digest = digest();
+    let json = serde_json::to_string(&digest).unwrap();
+    assert_eq!(serde_json::from_str::<Digest>(&json).unwrap(), digest);
+}
+
+#[test]
+fn to_cpp() {
+    let digest = digest();
+    let json = cpp_json(&digest);
+    assert_eq!(
+        serde_json::from_str::<Digest>(&json).unwrap(),
+        digest,
+        "{json}"
+    );
+
+    // Exactly representable floats print the same JSON values.
+    let half = Digest {
+        ratio: 0.5,
+        ..digest
+    };
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&cpp_json(&half)).unwrap(),
+        serde_json::to_value(half).unwrap()
+    );
+
+    let nan = Digest {
+        ratio: f32::NAN,
+        ..digest
+    };
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&cpp_json(&nan)).unwrap(),
+        serde_json::to_value(nan).unwrap()
+    );
+}
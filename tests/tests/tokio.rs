@@ -0,0 +1,85 @@
+// Copyright (c) Qualcomm Technologies, Inc. and/or its subsidiaries.
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::mpsc;
+use std::time::Duration;
+
+use idlc_test::interfaces::{
+    itest1,
+    itest2::{self, AsyncIITest2, IITest2, ITest2},
+};
+use mink_object::asynchronous::{set_executor, Blocking, Executor};
+use mink_object::error::generic::INVALID;
+
+// The executor of the `asynchronous` module documentation, the invocations of
+// the `_async` methods must not block the thread of a current-thread runtime.
+
+struct Tokio(tokio::runtime::Handle);
+
+impl Executor for Tokio {
+    fn blocking(
+        &self,
+        invocation: Box<dyn FnOnce() + Send>,
+    ) -> Pin<Box<dyn Future<Output = ()> + Send>> {
+        let task = self.0.spawn_blocking(invocation);
+        Box::pin(async move { task.await.expect("invocation panicked") })
+    }
+
+    fn block_on(&self, task: Pin<&mut (dyn Future<Output = ()> + '_)>) {
+        self.0.block_on(task)
+    }
+}
+
+/// Replies once the caller signals it, which it can only do if the invocation
+/// doesn't block its thread.
+struct Waiting(mpsc::Receiver<()>);
+
+impl IITest2 for Waiting {
+    fn entrypoint(&mut self, _: Option<&itest1::ITest1>) -> Result<(), itest2::Error> {
+        self.0
+            .recv_timeout(Duration::from_secs(5))
+            .map_err(|_| INVALID.into())
+    }
+}
+
+/// The async flavor of [`Waiting`].
+struct AsyncWaiting(Option<tokio::sync::oneshot::Receiver<()>>);
+
+impl AsyncIITest2 for AsyncWaiting {
+    async fn entrypoint(&mut self, _: Option<&itest1::ITest1>) -> Result<(), itest2::Error> {
+        let signal = self.0.take().ok_or(INVALID)?;
+        tokio::time::timeout(Duration::from_secs(5), signal)
+            .await
+            .map_err(|_| INVALID)?
+            .map_err(|_| INVALID.into())
+    }
+}
+
+#[test]
+fn current_thread() {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_time()
+        .build()
+        .unwrap();
+    set_executor(Box::leak(Box::new(Tokio(runtime.handle().clone()))));
+
+    let (signal, waiting) = mpsc::channel();
+    let waiting: ITest2 = Waiting(waiting).into();
+    let (async_signal, async_waiting) = tokio::sync::oneshot::channel();
+    let async_waiting: ITest2 = Blocking(AsyncWaiting(Some(async_waiting))).into();
+
+    runtime.block_on(async {
+        let call = tokio::spawn(async move { waiting.entrypoint_async(None).await });
+        // Lets the call start before signaling it.
+        tokio::task::yield_now().await;
+        signal.send(()).unwrap();
+        assert_eq!(call.await.unwrap(), Ok(()));
+
+        let call = tokio::spawn(async move { async_waiting.entrypoint_async(None).await });
+        tokio::task::yield_now().await;
+        async_signal.send(()).unwrap();
+        assert_eq!(call.await.unwrap(), Ok(()));
+    });
+}